@@ -0,0 +1,53 @@
+// Measures the throughput of a service behind a `RateLimitLayer`, to check that it stays within
+// the configured rate.
+//
+// Run with `cargo bench --bench rate_limit`.
+
+use std::time::{Duration, Instant};
+use tower::{Layer, Service, ServiceExt};
+use vtubestudio::service::RateLimitLayer;
+
+const REQUESTS: u64 = 200;
+
+async fn measure(requests_per_sec: u64, burst: u64) {
+    let mut service = RateLimitLayer::new(requests_per_sec, Duration::from_secs(1))
+        .with_burst(burst)
+        .layer(tower::service_fn(|req: u64| async move {
+            Ok::<_, std::convert::Infallible>(req)
+        }));
+
+    let start = Instant::now();
+
+    for i in 0..REQUESTS {
+        std::hint::black_box(service.ready().await.unwrap().call(i).await.unwrap());
+    }
+
+    let elapsed = start.elapsed();
+    let actual = REQUESTS as f64 / elapsed.as_secs_f64();
+    let max_expected = requests_per_sec as f64 * (REQUESTS as f64 / (REQUESTS - burst) as f64);
+
+    println!(
+        "{:>5} req/s, burst {:>3}: {:>8.1} req/s over {:>8.1?}",
+        requests_per_sec, burst, actual, elapsed
+    );
+
+    assert!(
+        actual <= max_expected * 1.01,
+        "throughput {:.1} req/s exceeded limit of {:.1} req/s",
+        actual,
+        max_expected
+    );
+}
+
+fn main() {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_time()
+        .build()
+        .unwrap();
+
+    runtime.block_on(async {
+        measure(500, 1).await;
+        measure(500, 50).await;
+        measure(1000, 1).await;
+    });
+}
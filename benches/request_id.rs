@@ -0,0 +1,141 @@
+// Measures heap allocations when generating request IDs and sending requests.
+//
+// Run with `cargo bench --bench request_id`.
+
+use futures_util::{Sink, Stream};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::collections::VecDeque;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::task::{Context, Poll, Waker};
+use std::time::Instant;
+use tower::{Service, ServiceExt};
+use vtubestudio::data::{
+    RequestEnvelope, RequestId, ResponseEnvelope, StatisticsRequest, StatisticsResponse,
+};
+use vtubestudio::service::ApiService;
+
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+const ITERATIONS: usize = 100_000;
+
+fn report<F: FnMut(usize)>(name: &str, mut f: F) {
+    let allocations_before = ALLOCATIONS.load(Ordering::Relaxed);
+    let start = Instant::now();
+
+    for i in 0..ITERATIONS {
+        f(i);
+    }
+
+    print_result(name, allocations_before, start);
+}
+
+fn print_result(name: &str, allocations_before: usize, start: Instant) {
+    let elapsed = start.elapsed();
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed) - allocations_before;
+
+    println!(
+        "{:<32} {:>8.2} allocs/iter {:>10.1?}/iter",
+        name,
+        allocations as f64 / ITERATIONS as f64,
+        elapsed / ITERATIONS as u32
+    );
+}
+
+/// In-memory transport that immediately responds to every request.
+#[derive(Default)]
+struct EchoTransport {
+    responses: VecDeque<ResponseEnvelope>,
+    waker: Option<Waker>,
+}
+
+impl Sink<RequestEnvelope> for EchoTransport {
+    type Error = std::convert::Infallible;
+
+    fn poll_ready(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn start_send(mut self: Pin<&mut Self>, req: RequestEnvelope) -> Result<(), Self::Error> {
+        let mut resp = ResponseEnvelope::new(&StatisticsResponse::default()).unwrap();
+        resp.request_id = req.request_id.unwrap_or_default();
+        self.responses.push_back(resp);
+        if let Some(waker) = self.waker.take() {
+            waker.wake();
+        }
+        Ok(())
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl Stream for EchoTransport {
+    type Item = Result<ResponseEnvelope, std::convert::Infallible>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        match self.responses.pop_front() {
+            Some(resp) => Poll::Ready(Some(Ok(resp))),
+            None => {
+                self.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+fn main() {
+    report("RequestId::new(n.to_string())", |i| {
+        std::hint::black_box(RequestId::new(i.to_string()));
+    });
+
+    report("RequestId::from(n)", |i| {
+        std::hint::black_box(RequestId::from(i));
+    });
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+
+    runtime.block_on(async {
+        let (mut service, _events) = ApiService::new(EchoTransport::default(), 128);
+        let req = RequestEnvelope::new(&StatisticsRequest {}).unwrap();
+
+        let allocations_before = ALLOCATIONS.load(Ordering::Relaxed);
+        let start = Instant::now();
+
+        for _ in 0..ITERATIONS {
+            let resp = service
+                .ready()
+                .await
+                .unwrap()
+                .call(req.clone())
+                .await
+                .unwrap();
+            std::hint::black_box(resp);
+        }
+
+        print_result("ApiService::call", allocations_before, start);
+    });
+}
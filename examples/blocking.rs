@@ -0,0 +1,33 @@
+// This example uses the blocking client to send a request every time you press the Enter key,
+// without an async runtime.
+
+use vtubestudio::blocking::Client;
+use vtubestudio::data::StatisticsRequest;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let stored_token = std::env::var("VTS_AUTH_TOKEN").ok();
+    if stored_token.is_some() {
+        println!("Attempting to use stored auth token");
+    }
+
+    let mut client = Client::builder()
+        .auth_token(stored_token)
+        .authentication("vtubestudio-rs example", "Walfie", None)
+        .build()?;
+
+    let mut line = String::new();
+    loop {
+        println!("Press Enter to send a request");
+        line.clear();
+        std::io::stdin().read_line(&mut line)?;
+
+        match client.send(&StatisticsRequest {}) {
+            Ok(resp) => println!("Received response:\n{:#?}\n", resp),
+            Err(e) => println!("Received error:\n{}\n{:#?}", e, e),
+        }
+
+        while let Some(event) = client.try_next_event() {
+            println!("Received event: {:?}", event);
+        }
+    }
+}
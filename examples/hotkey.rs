@@ -22,7 +22,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             })
             .await?;
 
-        if resp.available_hotkeys.len() == 0 {
+        if resp.available_hotkeys.is_empty() {
             println!(
                 "No hotkeys detected! Please add some in the VTube Studio app, then press Enter."
             );
@@ -1,7 +1,7 @@
 // This example demonstrates using `MoveModelRequest` to rotate your
 // Live2D model until you exit the example.
 
-use vtubestudio::data::{MoveModelRequest, StatisticsRequest};
+use vtubestudio::data::{CurrentModelRequest, ModelPosition, MoveModelRequest};
 use vtubestudio::Client;
 
 #[tokio::main]
@@ -15,19 +15,21 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let mut line = String::new();
 
     println!("Please accept the permission pop-up in VTube Studio");
-    client.send(&StatisticsRequest {}).await?;
+    let original = ModelPosition::from(client.send(&CurrentModelRequest {}).await?);
 
-    println!("Press Enter to reset model position");
+    println!("Press Enter to reset model rotation");
     std::io::stdin().read_line(&mut line)?;
 
-    client
-        .send(&MoveModelRequest {
-            time_in_seconds: 0.0,
-            values_are_relative_to_model: false,
-            rotation: Some(0.0),
-            ..MoveModelRequest::default()
-        })
-        .await?;
+    let mut reset = MoveModelRequest {
+        time_in_seconds: 0.5,
+        ..MoveModelRequest::default()
+    };
+    ModelPosition {
+        rotation: 0.0,
+        ..original
+    }
+    .apply_to_move_request(&mut reset);
+    client.send(&reset).await?;
 
     println!("Press Enter to start spinning");
     std::io::stdin().read_line(&mut line)?;
@@ -0,0 +1,81 @@
+use std::time::Duration;
+use vtubestudio::data::{Event, EventSubscriptionRequest, TestEventConfig};
+use vtubestudio::{Client, ClientEvent, Error};
+use wasm_bindgen::prelude::*;
+use web_sys::Storage;
+
+// The `localStorage` key for the auth token, so the user only needs to accept the permission
+// pop-up once.
+const TOKEN_KEY: &str = "vtubestudio-auth-token";
+
+fn log(message: &str) {
+    web_sys::console::log_1(&message.into());
+}
+
+fn local_storage() -> Option<Storage> {
+    web_sys::window()?.local_storage().ok()?
+}
+
+#[wasm_bindgen(start)]
+pub fn start() {
+    wasm_bindgen_futures::spawn_local(async {
+        if let Err(e) = run().await {
+            log(&format!("Error: {e}"));
+        }
+    });
+}
+
+async fn run() -> Result<(), Error> {
+    let storage = local_storage();
+
+    // An auth token from a previous successful authentication request
+    let stored_token = storage
+        .as_ref()
+        .and_then(|storage| storage.get_item(TOKEN_KEY).ok().flatten());
+
+    let (mut client, mut events) = Client::builder()
+        .auth_token(stored_token)
+        .authentication("Browser plugin", "Developer name", None)
+        .build_web();
+
+    log("Please accept the permission pop-up in VTube Studio");
+
+    // Create the event subscription request, to be sent later.
+    let req = EventSubscriptionRequest::subscribe(&TestEventConfig {
+        test_message_for_event: "Hello from the browser!".to_owned(),
+    })?;
+
+    while let Some(client_event) = events.next().await {
+        match client_event {
+            // We receive a `Disconnected` client event whenever we are disconnected, including on
+            // startup. This can be used as a cue to refresh any event subscriptions.
+            ClientEvent::Disconnected => {
+                log("Connecting...");
+
+                while let Err(e) = client.send(&req).await {
+                    log(&format!("Failed to subscribe to test events: {e}"));
+                    log("Retrying in 2s...");
+                    gloo_timers::future::sleep(Duration::from_secs(2)).await;
+                }
+            }
+
+            // Store new auth tokens, to be used the next time the page is loaded.
+            ClientEvent::NewAuthToken(token) => {
+                if let Some(storage) = &storage {
+                    let _ = storage.set_item(TOKEN_KEY, &token);
+                }
+            }
+
+            ClientEvent::Api(Event::Test(event)) => {
+                log(&format!(
+                    "VTube Studio has been running for {} seconds.",
+                    event.counter
+                ));
+            }
+
+            other => log(&format!("Received event: {:?}", other)),
+        }
+    }
+
+    Ok(())
+}
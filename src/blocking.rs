@@ -0,0 +1,198 @@
+//! A synchronous wrapper around [`Client`](crate::Client), for use outside of async code.
+//!
+//! # Example
+//!
+//! ```no_run
+//! # fn run() -> Result<(), vtubestudio::Error> {
+//! use vtubestudio::blocking::Client;
+//! use vtubestudio::data::StatisticsRequest;
+//!
+//! let mut client = Client::builder()
+//!     .authentication("Plugin name", "Developer name", None)
+//!     .build()?;
+//!
+//! let resp = client.send(&StatisticsRequest {})?;
+//! println!("VTube Studio has been running for {}ms", resp.uptime);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::data::Request;
+use crate::error::{Error, ErrorKind};
+use crate::ClientEvent;
+
+use std::borrow::Cow;
+use std::marker::PhantomData;
+use tokio::runtime::Runtime;
+
+/// A blocking client for the VTube Studio API.
+///
+/// This owns a [`tokio`] runtime (with a single worker thread), which drives the underlying
+/// [`Client`](crate::Client) in the background. Methods on this type must not be called from
+/// within an async context, since they block the current thread.
+///
+/// This type is deliberately not [`Send`], so it stays on the thread that created it.
+#[derive(Debug)]
+pub struct Client {
+    client: crate::Client,
+    events: crate::ClientEventStream,
+    runtime: Runtime,
+    _not_send: PhantomData<*const ()>,
+}
+
+impl Client {
+    /// Creates a new [`ClientBuilder`].
+    pub fn builder() -> ClientBuilder {
+        ClientBuilder::new()
+    }
+
+    /// Sends a VTube Studio API request, blocking until a response is received.
+    ///
+    /// See [`Client::send`](crate::Client::send).
+    pub fn send<Req: Request>(&mut self, req: &Req) -> Result<Req::Response, Error> {
+        let client = &mut self.client;
+        self.runtime.block_on(client.send(req))
+    }
+
+    /// Connects now, blocking until the connection is established or fails.
+    ///
+    /// See [`Client::connect`](crate::Client::connect).
+    pub fn connect(&mut self) -> Result<(), Error> {
+        let client = &mut self.client;
+        self.runtime.block_on(client.connect())
+    }
+
+    /// Returns the next [`ClientEvent`], blocking until one is available.
+    ///
+    /// Returns `None` if the underlying client has been dropped.
+    pub fn next_event(&mut self) -> Option<ClientEvent> {
+        let events = &mut self.events;
+        self.runtime.block_on(events.next())
+    }
+
+    /// Returns the next [`ClientEvent`] if one is available, without blocking.
+    pub fn try_next_event(&mut self) -> Option<ClientEvent> {
+        self.events.receiver.try_recv().ok()
+    }
+
+    /// Returns the auth token currently stored by the client, if any.
+    ///
+    /// See [`Client::auth_token`](crate::Client::auth_token).
+    pub fn auth_token(&self) -> Option<String> {
+        self.client.auth_token()
+    }
+}
+
+/// Builder for a blocking [`Client`].
+///
+/// This wraps a [`crate::ClientBuilder`], and uses
+/// [`build_tungstenite`](crate::ClientBuilder::build_tungstenite) to create the client. Other
+/// options can be configured on a [`crate::ClientBuilder`] and converted with [`From`].
+///
+/// # Example
+///
+/// ```no_run
+/// # fn run() -> Result<(), vtubestudio::Error> {
+/// use std::time::Duration;
+/// use vtubestudio::blocking;
+///
+/// let builder = vtubestudio::Client::builder().keepalive_interval(Duration::from_secs(5));
+/// let client = blocking::ClientBuilder::from(builder).build()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default)]
+pub struct ClientBuilder {
+    inner: crate::ClientBuilder,
+}
+
+impl ClientBuilder {
+    /// Creates new builder with default values.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// See [`ClientBuilder::url`](crate::ClientBuilder::url).
+    pub fn url<S: Into<String>>(mut self, url: S) -> Self {
+        self.inner = self.inner.url(url);
+        self
+    }
+
+    /// See [`ClientBuilder::authentication`](crate::ClientBuilder::authentication).
+    pub fn authentication<S1, S2, S3>(mut self, name: S1, developer: S2, icon: S3) -> Self
+    where
+        S1: Into<Cow<'static, str>>,
+        S2: Into<Cow<'static, str>>,
+        S3: Into<Option<Cow<'static, str>>>,
+    {
+        self.inner = self.inner.authentication(name, developer, icon);
+        self
+    }
+
+    /// See [`ClientBuilder::auth_token`](crate::ClientBuilder::auth_token).
+    pub fn auth_token(mut self, token: Option<String>) -> Self {
+        self.inner = self.inner.auth_token(token);
+        self
+    }
+
+    /// Consumes the builder and initializes a blocking [`Client`].
+    ///
+    /// This returns an error if the [`tokio`] runtime could not be created.
+    pub fn build(self) -> Result<Client, Error> {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .thread_name("vtubestudio-blocking")
+            .enable_all()
+            .build()
+            .map_err(|e| Error::new(ErrorKind::Other).with_source(e))?;
+
+        let (client, events) = {
+            let _guard = runtime.enter();
+            self.inner.build_tungstenite()
+        };
+
+        Ok(Client {
+            client,
+            events,
+            runtime,
+            _not_send: PhantomData,
+        })
+    }
+}
+
+impl From<crate::ClientBuilder> for ClientBuilder {
+    fn from(inner: crate::ClientBuilder) -> Self {
+        Self { inner }
+    }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use super::*;
+    use crate::data::{ResponseEnvelope, StatisticsRequest, StatisticsResponse};
+    use crate::error::BoxError;
+    use crate::testing::{FakeVtsServer, RequestMatcher};
+
+    #[test]
+    fn send() -> Result<(), BoxError> {
+        // The server runs on its own runtime, since the blocking client can't be used in one
+        let server_runtime = Runtime::new()?;
+        let server = server_runtime.block_on(FakeVtsServer::start(vec![(
+            RequestMatcher::message_type::<StatisticsRequest>(),
+            ResponseEnvelope::new(&StatisticsResponse {
+                uptime: 1000,
+                ..Default::default()
+            })?,
+        )]))?;
+
+        let mut client = Client::builder().url(server.url()).build()?;
+
+        assert!(matches!(client.next_event(), Some(ClientEvent::Disconnected)));
+        assert_eq!(client.send(&StatisticsRequest {})?.uptime, 1000);
+        assert!(matches!(client.next_event(), Some(ClientEvent::Connected)));
+        assert!(client.try_next_event().is_none());
+        server.assert_all_matched();
+
+        Ok(())
+    }
+}
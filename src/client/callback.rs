@@ -0,0 +1,194 @@
+use crate::client::{Client, ClientState};
+use crate::data::{RequestEnvelope, ResponseEnvelope};
+use crate::error::Error;
+use crate::service::BoxCloneApiService;
+
+use futures_channel::mpsc;
+use futures_util::future::BoxFuture;
+use futures_util::{FutureExt, StreamExt};
+use std::fmt;
+use std::future::Future;
+use std::panic::AssertUnwindSafe;
+use std::sync::{Arc, Mutex, Weak};
+use std::task::{Context, Poll};
+use tower::util::BoxCloneService;
+use tower::Service;
+
+type CallbackFn = dyn Fn(Client) -> BoxFuture<'static, Result<(), Error>> + Send + Sync;
+
+/// An async callback invoked with a [`Client`] when a connection is established or lost. Set via
+/// [`ClientBuilder::on_connect`](crate::ClientBuilder::on_connect) and
+/// [`ClientBuilder::on_disconnect`](crate::ClientBuilder::on_disconnect).
+#[derive(Clone)]
+pub(crate) struct ConnectionCallback(Arc<CallbackFn>);
+
+impl ConnectionCallback {
+    pub(crate) fn new<F, Fut>(callback: F) -> Self
+    where
+        F: Fn(Client) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<(), Error>> + Send + 'static,
+    {
+        Self(Arc::new(move |client| Box::pin(callback(client))))
+    }
+}
+
+impl fmt::Debug for ConnectionCallback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ConnectionCallback").finish()
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum ConnectionEvent {
+    Connected,
+    Disconnected,
+}
+
+/// Runs connection callbacks in the order that connection events occur.
+///
+/// Callbacks need a [`Client`] to work with, but the connection is owned by the client's own
+/// service stack, so holding a [`Client`] there would keep the connection open forever. Instead,
+/// this only keeps a [`Weak`] reference to a [`ServiceTracker`], which is owned by the [`Client`]s
+/// returned from the builder.
+#[derive(Debug)]
+pub(crate) struct ConnectionCallbacks {
+    sender: mpsc::UnboundedSender<ConnectionEvent>,
+    runner: Mutex<Option<CallbackRunner>>,
+}
+
+#[derive(Debug)]
+struct CallbackRunner {
+    receiver: mpsc::UnboundedReceiver<ConnectionEvent>,
+    on_connect: Option<ConnectionCallback>,
+    on_disconnect: Option<ConnectionCallback>,
+    tracker: Weak<ServiceTracker>,
+}
+
+impl ConnectionCallbacks {
+    pub(crate) fn new(
+        on_connect: Option<ConnectionCallback>,
+        on_disconnect: Option<ConnectionCallback>,
+    ) -> Self {
+        let (sender, receiver) = mpsc::unbounded();
+
+        Self {
+            sender,
+            runner: Mutex::new(Some(CallbackRunner {
+                receiver,
+                on_connect,
+                on_disconnect,
+                tracker: Weak::new(),
+            })),
+        }
+    }
+
+    /// Wraps the service used by the [`Client`], so that callbacks can create new [`Client`]s from
+    /// it while any of the original [`Client`]s are still alive.
+    pub(crate) fn track(&self, service: BoxCloneApiService) -> BoxCloneApiService {
+        let tracker = Arc::new(ServiceTracker {
+            service: Mutex::new(service.clone()),
+        });
+
+        if let Some(runner) = self.lock_runner().as_mut() {
+            runner.tracker = Arc::downgrade(&tracker);
+        }
+
+        BoxCloneService::new(TrackedService {
+            service,
+            _tracker: tracker,
+        })
+    }
+
+    /// Queues the callback for a connection event.
+    pub(crate) fn notify(&self, event: ConnectionEvent, state: &Arc<ClientState>) {
+        // The runner is started on the first event, since the builder may be used outside of a
+        // runtime
+        if let Some(runner) = self.lock_runner().take() {
+            crate::runtime::spawn(runner.run(Arc::clone(state)));
+        }
+
+        let _ = self.sender.unbounded_send(event);
+    }
+
+    fn lock_runner(&self) -> std::sync::MutexGuard<'_, Option<CallbackRunner>> {
+        self.runner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl CallbackRunner {
+    async fn run(mut self, state: Arc<ClientState>) {
+        while let Some(event) = self.receiver.next().await {
+            let (callback, description) = match event {
+                ConnectionEvent::Connected => (&self.on_connect, "on_connect"),
+                ConnectionEvent::Disconnected => (&self.on_disconnect, "on_disconnect"),
+            };
+
+            let callback = match callback {
+                Some(callback) => callback,
+                None => continue,
+            };
+
+            // All clients have been dropped, so there's nothing left to do
+            let tracker = match self.tracker.upgrade() {
+                Some(tracker) => tracker,
+                None => break,
+            };
+
+            let client = Client::new_with_state(tracker.service(), Arc::clone(&state));
+
+            // Panics in the callback shouldn't stop the runner
+            match AssertUnwindSafe((callback.0)(client)).catch_unwind().await {
+                Ok(Ok(())) => {}
+                Ok(Err(error)) => tracing::warn!(%error, "Error in {} callback", description),
+                Err(_) => tracing::warn!("Panic in {} callback", description),
+            }
+        }
+    }
+}
+
+/// Shared by all [`TrackedService`]s created from the same builder.
+struct ServiceTracker {
+    service: Mutex<BoxCloneApiService>,
+}
+
+impl fmt::Debug for ServiceTracker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ServiceTracker").finish()
+    }
+}
+
+impl ServiceTracker {
+    fn service(self: Arc<Self>) -> BoxCloneApiService {
+        let service = self
+            .service
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone();
+
+        BoxCloneService::new(TrackedService {
+            service,
+            _tracker: self,
+        })
+    }
+}
+
+#[derive(Clone)]
+struct TrackedService {
+    service: BoxCloneApiService,
+    // Only held to keep the tracker alive
+    _tracker: Arc<ServiceTracker>,
+}
+
+impl Service<RequestEnvelope> for TrackedService {
+    type Response = ResponseEnvelope;
+    type Error = Error;
+    type Future = BoxFuture<'static, Result<ResponseEnvelope, Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: RequestEnvelope) -> Self::Future {
+        self.service.call(req)
+    }
+}
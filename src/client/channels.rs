@@ -0,0 +1,84 @@
+use crate::data::{Event, EventData};
+
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Mutex;
+use tokio::sync::broadcast;
+
+/// A type-erased [`broadcast::Sender`] for a single event type.
+trait EventSender: Send + Sync {
+    /// Sends the event if it matches this sender's event type.
+    fn send_any(&self, event: &dyn Any);
+    fn as_any(&self) -> &dyn Any;
+}
+
+impl<E> EventSender for broadcast::Sender<E>
+where
+    E: Clone + Send + 'static,
+{
+    fn send_any(&self, event: &dyn Any) {
+        if let Some(event) = event.downcast_ref::<E>() {
+            // This only fails if there are no receivers, which is fine
+            let _ = self.send(event.clone());
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// Broadcast channels for individual event types, keyed by [`TypeId`].
+pub(crate) struct EventChannels {
+    capacity: usize,
+    senders: Mutex<HashMap<TypeId, Box<dyn EventSender>>>,
+}
+
+impl fmt::Debug for EventChannels {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EventChannels")
+            .field("capacity", &self.capacity)
+            .field("channels", &self.lock().len())
+            .finish()
+    }
+}
+
+impl EventChannels {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            senders: Mutex::default(),
+        }
+    }
+
+    /// Returns a receiver for the given event type, creating the channel if it doesn't exist.
+    pub fn subscribe<E>(&self) -> broadcast::Receiver<E>
+    where
+        E: EventData + Clone + Send + 'static,
+    {
+        let mut senders = self.lock();
+        let sender = senders
+            .entry(TypeId::of::<E>())
+            .or_insert_with(|| Box::new(broadcast::channel::<E>(self.capacity).0));
+
+        sender
+            .as_any()
+            .downcast_ref::<broadcast::Sender<E>>()
+            .expect("event channel has mismatched type")
+            .subscribe()
+    }
+
+    /// Sends the event to the channel for its type, if one exists.
+    pub fn dispatch(&self, event: &Event) {
+        if let Some(event) = event.as_any() {
+            if let Some(sender) = self.lock().get(&event.type_id()) {
+                sender.send_any(event);
+            }
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<TypeId, Box<dyn EventSender>>> {
+        self.senders.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
@@ -0,0 +1,449 @@
+use crate::client::{Client, ClientEvent, ClientEventStream};
+use crate::data::{
+    EnumString, Event, EventConfig, EventSubscriptionRequest, ParameterCreationRequest,
+    ParameterDeletionRequest, Permission,
+};
+use crate::error::{Error, ErrorKind};
+
+use futures_util::future::{self, BoxFuture, Either};
+use std::fmt;
+use std::future::Future;
+use std::time::Duration;
+
+type HandlerFn =
+    dyn Fn(Client, &Event) -> Option<BoxFuture<'static, Result<(), Error>>> + Send + Sync;
+
+/// Drives the lifecycle of a typical plugin.
+///
+/// Most plugins follow the same pattern: connect, authenticate, request permissions, create
+/// custom parameters, subscribe to events, and then handle events in a loop. A
+/// [`PluginController`] takes care of everything except the event handling, which is done by
+/// callbacks registered with [`on_event`](Self::on_event).
+///
+/// Whenever the client (re)connects, [`run`](Self::run) requests any missing
+/// [permissions](Self::permission), creates the [custom parameters](Self::parameter), and
+/// resubscribes to all events. When [`run_until`](Self::run_until) is given a shutdown signal,
+/// the custom parameters are deleted and all events are unsubscribed before it returns.
+///
+/// Event handlers are awaited one at a time, in the order that events are received. Errors
+/// returned by handlers, and [`ApiError`](crate::data::ApiError)s returned while setting up the
+/// connection, are logged rather than stopping the controller.
+///
+/// # Example
+///
+#[cfg_attr(feature = "tokio-tungstenite", doc = "```no_run")]
+#[cfg_attr(not(feature = "tokio-tungstenite"), doc = "```ignore")]
+/// # async fn run() -> Result<(), vtubestudio::error::BoxError> {
+/// use vtubestudio::client::PluginController;
+/// use vtubestudio::data::{ParameterCreationRequest, TestEventConfig};
+/// use vtubestudio::Client;
+///
+/// let (client, events) = Client::builder()
+///     .authentication("Plugin name", "Developer name", None)
+///     .build_tungstenite();
+///
+/// PluginController::new(client, events)
+///     .parameter(ParameterCreationRequest {
+///         parameter_name: "MyPluginParam".to_owned(),
+///         explanation: None,
+///         min: 0.0,
+///         max: 1.0,
+///         default_value: 0.0,
+///     })
+///     .on_event(
+///         &TestEventConfig {
+///             test_message_for_event: "Hello".to_owned(),
+///         },
+///         |_client, event| async move {
+///             println!("VTube Studio has been running for {}s", event.counter);
+///             Ok(())
+///         },
+///     )
+///     .run()
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct PluginController {
+    client: Client,
+    events: ClientEventStream,
+    permissions: Vec<EnumString<Permission>>,
+    parameters: Vec<ParameterCreationRequest>,
+    subscriptions: Vec<EventSubscriptionRequest>,
+    handlers: Vec<Box<HandlerFn>>,
+    created_parameters: Vec<String>,
+    retry_delay: Duration,
+    config_error: Option<Error>,
+}
+
+impl fmt::Debug for PluginController {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PluginController")
+            .field("client", &self.client)
+            .field("permissions", &self.permissions)
+            .field("parameters", &self.parameters)
+            .field("subscriptions", &self.subscriptions)
+            .field("handlers", &self.handlers.len())
+            .field("retry_delay", &self.retry_delay)
+            .finish()
+    }
+}
+
+impl PluginController {
+    /// Creates a new [`PluginController`] from the [`Client`] and [`ClientEventStream`] returned
+    /// by a [`ClientBuilder`](crate::ClientBuilder).
+    pub fn new(client: Client, events: ClientEventStream) -> Self {
+        Self {
+            client,
+            events,
+            permissions: Vec::new(),
+            parameters: Vec::new(),
+            subscriptions: Vec::new(),
+            handlers: Vec::new(),
+            created_parameters: Vec::new(),
+            retry_delay: Duration::from_secs(2),
+            config_error: None,
+        }
+    }
+
+    /// Requests a permission on connect, if it hasn't already been granted.
+    ///
+    /// This shows a pop-up in VTube Studio. If the user denies the permission, a warning is
+    /// logged, and it's requested again after the next reconnect.
+    pub fn permission<P: Into<EnumString<Permission>>>(mut self, permission: P) -> Self {
+        self.permissions.push(permission.into());
+        self
+    }
+
+    /// Creates a custom parameter on connect, and deletes it when shutting down.
+    pub fn parameter(mut self, req: ParameterCreationRequest) -> Self {
+        self.parameters.push(req);
+        self
+    }
+
+    /// Subscribes to an event on connect, and calls `handler` for every event of that type.
+    ///
+    /// The handler is given a clone of the [`Client`], so it can send requests in response to
+    /// the event.
+    pub fn on_event<C, F, Fut>(mut self, config: &C, handler: F) -> Self
+    where
+        C: EventConfig,
+        C::Event: Clone + Send + 'static,
+        F: Fn(Client, C::Event) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<(), Error>> + Send + 'static,
+    {
+        match EventSubscriptionRequest::subscribe(config) {
+            Ok(req) => self.subscriptions.push(req),
+            Err(e) => {
+                self.config_error.get_or_insert_with(|| e.into());
+            }
+        }
+
+        self.handlers.push(Box::new(move |client, event| {
+            let event = event.as_any()?.downcast_ref::<C::Event>()?.clone();
+            Some(Box::pin(handler(client, event)))
+        }));
+
+        self
+    }
+
+    /// How long to wait before retrying after failing to set up the connection (e.g., if VTube
+    /// Studio isn't running). The default value is 2 seconds.
+    pub fn retry_delay(mut self, delay: Duration) -> Self {
+        self.retry_delay = delay;
+        self
+    }
+
+    /// Returns a reference to the underlying [`Client`].
+    pub fn client(&self) -> &Client {
+        &self.client
+    }
+
+    /// Runs the controller until the client's event stream ends.
+    ///
+    /// This is the same as [`run_until`](Self::run_until) with a shutdown signal that never
+    /// resolves, so the custom parameters are never explicitly deleted.
+    pub async fn run(self) -> Result<(), Error> {
+        self.run_until(future::pending()).await
+    }
+
+    /// Runs the controller until `shutdown` resolves (or the client's event stream ends), then
+    /// deletes the custom parameters and unsubscribes from all events.
+    ///
+    /// This returns an error if an event config couldn't be serialized, or with
+    /// [`ErrorKind::ConnectionRefused`] if the client gave up reconnecting (see
+    /// [`ClientBuilder::max_reconnect_attempts`](crate::ClientBuilder::max_reconnect_attempts)).
+    pub async fn run_until<F>(mut self, shutdown: F) -> Result<(), Error>
+    where
+        F: Future<Output = ()>,
+    {
+        if let Some(e) = self.config_error.take() {
+            return Err(e);
+        }
+
+        futures_util::pin_mut!(shutdown);
+
+        let result = loop {
+            let event = match future::select(Box::pin(self.events.next()), shutdown.as_mut()).await
+            {
+                Either::Left((event, _)) => event,
+                Either::Right(_) => break Ok(()),
+            };
+
+            match event {
+                Some(ClientEvent::Disconnected) => {
+                    self.created_parameters.clear();
+                    let setup = Box::pin(self.setup_until_success());
+                    if let Either::Right(_) = future::select(setup, shutdown.as_mut()).await {
+                        break Ok(());
+                    }
+                }
+                Some(ClientEvent::GaveUp) => {
+                    return Err(Error::new(ErrorKind::ConnectionRefused)
+                        .with_source("client gave up reconnecting"));
+                }
+                Some(ClientEvent::Api(event)) => self.dispatch(&event).await,
+                Some(_) => {}
+                None => return Ok(()),
+            }
+        };
+
+        self.cleanup().await;
+        result
+    }
+
+    async fn dispatch(&mut self, event: &Event) {
+        for handler in &self.handlers {
+            if let Some(fut) = handler(self.client.clone(), event) {
+                if let Err(error) = fut.await {
+                    tracing::warn!(%error, "Event handler failed");
+                }
+            }
+        }
+    }
+
+    async fn setup_until_success(&mut self) {
+        while let Err(error) = self.setup().await {
+            tracing::warn!(
+                %error,
+                retry_delay = ?self.retry_delay,
+                "Failed to set up plugin, retrying"
+            );
+            crate::runtime::sleep(self.retry_delay).await;
+        }
+    }
+
+    /// Requests permissions, creates parameters, and subscribes to events. API errors are logged,
+    /// while other errors (e.g., failing to connect) are returned so the setup can be retried.
+    async fn setup(&mut self) -> Result<(), Error> {
+        for permission in &self.permissions {
+            let granted = match self.client.has_permission(permission.clone()).await {
+                Ok(true) => true,
+                Ok(false) => self.client.request_permission(permission.clone()).await?,
+                Err(e) => return Err(e),
+            };
+
+            if !granted {
+                tracing::warn!(%permission, "Permission was not granted");
+            }
+        }
+
+        // `Client` isn't `Sync`, so each request gets its own clone to keep this future `Send`
+        let results = future::join_all(self.parameters.iter().cloned().map(|req| {
+            let mut client = self.client.clone();
+            async move { client.send(&req).await }
+        }))
+        .await;
+        for (req, result) in self.parameters.iter().zip(results) {
+            match result {
+                Ok(_) => {
+                    if !self.created_parameters.contains(&req.parameter_name) {
+                        self.created_parameters.push(req.parameter_name.clone());
+                    }
+                }
+                Err(e) if e.is_api_error() => {
+                    tracing::warn!(
+                        error = %e,
+                        parameter = %req.parameter_name,
+                        "Failed to create parameter"
+                    );
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        for req in &self.subscriptions {
+            match self.client.send(req).await {
+                Ok(_) => {}
+                Err(e) if e.is_api_error() => {
+                    tracing::warn!(error = %e, "Failed to subscribe to event");
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn cleanup(&mut self) {
+        let client = self.client.clone();
+        let deletions = self
+            .created_parameters
+            .drain(..)
+            .map(|parameter_name| {
+                let mut client = client.clone();
+                async move {
+                    client
+                        .send(&ParameterDeletionRequest { parameter_name })
+                        .await
+                }
+            })
+            .collect::<Vec<_>>();
+
+        for result in future::join_all(deletions).await {
+            if let Err(error) = result {
+                tracing::warn!(%error, "Failed to delete parameter");
+            }
+        }
+
+        if !self.subscriptions.is_empty() {
+            if let Err(error) = self
+                .client
+                .send(&EventSubscriptionRequest::unsubscribe_all())
+                .await
+            {
+                tracing::warn!(%error, "Failed to unsubscribe from events");
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use super::*;
+    use crate::data::{
+        EventSubscriptionResponse, ParameterCreationResponse, ParameterDeletionResponse, TestEvent,
+        TestEventConfig,
+    };
+    use crate::testing::MockServer;
+    use tokio::sync::{mpsc, oneshot};
+
+    fn server() -> Result<MockServer, Error> {
+        let server = MockServer::new();
+        server.respond::<ParameterCreationRequest>(&ParameterCreationResponse {
+            parameter_name: "MyParam".into(),
+        })?;
+        server.respond::<ParameterDeletionRequest>(&ParameterDeletionResponse {
+            parameter_name: "MyParam".into(),
+        })?;
+        server.respond::<EventSubscriptionRequest>(&EventSubscriptionResponse::default())?;
+        Ok(server)
+    }
+
+    fn count(server: &MockServer, message_type: &str) -> usize {
+        server
+            .received_message_types()
+            .iter()
+            .filter(|t| *t == message_type)
+            .count()
+    }
+
+    async fn wait_for(server: &MockServer, message_type: &str, n: usize) {
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while count(server, message_type) < n {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+        })
+        .await
+        .unwrap_or_else(|_| panic!("timed out waiting for {} {}", n, message_type));
+    }
+
+    fn controller(server: &MockServer) -> (PluginController, mpsc::UnboundedReceiver<TestEvent>) {
+        let (client, events) = Client::builder().build_connector(server.connector());
+        let (tx, rx) = mpsc::unbounded_channel();
+
+        let controller = PluginController::new(client, events)
+            .retry_delay(Duration::from_millis(10))
+            .parameter(ParameterCreationRequest {
+                parameter_name: "MyParam".into(),
+                explanation: None,
+                min: 0.0,
+                max: 1.0,
+                default_value: 0.0,
+            })
+            .on_event(
+                &TestEventConfig {
+                    test_message_for_event: "hello".into(),
+                },
+                move |_client, event| {
+                    let tx = tx.clone();
+                    async move {
+                        tx.send(event).ok();
+                        Ok(())
+                    }
+                },
+            );
+
+        (controller, rx)
+    }
+
+    #[tokio::test]
+    async fn lifecycle() -> Result<(), Error> {
+        let server = server()?;
+        let (controller, mut received) = controller(&server);
+        let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
+        let task = tokio::spawn(controller.run_until(async {
+            shutdown_rx.await.ok();
+        }));
+
+        wait_for(&server, "EventSubscriptionRequest", 1).await;
+        assert_eq!(
+            server.received_message_types(),
+            ["ParameterCreationRequest", "EventSubscriptionRequest"]
+        );
+
+        let event = TestEvent {
+            your_test_message: "hello".into(),
+            counter: 5,
+        };
+        server.send_event(&event)?;
+        assert_eq!(received.recv().await, Some(event));
+
+        // Parameters are recreated and events are resubscribed after reconnecting
+        server.disconnect_all();
+        wait_for(&server, "EventSubscriptionRequest", 2).await;
+        assert_eq!(count(&server, "ParameterCreationRequest"), 2);
+
+        shutdown_tx.send(()).ok();
+        task.await.unwrap()?;
+
+        assert_eq!(count(&server, "ParameterDeletionRequest"), 1);
+        assert_eq!(count(&server, "EventSubscriptionRequest"), 3);
+        assert!(server.unexpected().is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn api_errors_are_not_retried() -> Result<(), Error> {
+        // Parameter creation isn't handled, so it fails with an API error
+        let server = MockServer::new();
+        server.respond::<EventSubscriptionRequest>(&EventSubscriptionResponse::default())?;
+
+        let (controller, _received) = controller(&server);
+        let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
+        let task = tokio::spawn(controller.run_until(async {
+            shutdown_rx.await.ok();
+        }));
+
+        wait_for(&server, "EventSubscriptionRequest", 1).await;
+        shutdown_tx.send(()).ok();
+        task.await.unwrap()?;
+
+        // No parameters were created, so none are deleted
+        assert_eq!(count(&server, "ParameterCreationRequest"), 1);
+        assert_eq!(count(&server, "ParameterDeletionRequest"), 0);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,367 @@
+use crate::client::guard::spawn_cleanup;
+use crate::client::Client;
+use crate::data::{
+    ExpressionActivationRequest, ExpressionActivationResponse, ExpressionStateRequest,
+    RequestEnvelope, ResponseEnvelope,
+};
+use crate::error::Error;
+use crate::service::send_request;
+
+use std::ops::Deref;
+use tower::Service;
+
+/// Activates (or deactivates) an expression, and restores its previous state when dropped.
+///
+/// Expressions changed via [`ExpressionActivationRequest`] stay that way until they're explicitly
+/// changed again, even if the plugin disconnects. The guard checks the expression's state (using
+/// an [`ExpressionStateRequest`]) before changing it, and when it's dropped (including during a
+/// panic), an [`ExpressionActivationRequest`] is sent to restore that state. If the expression was
+/// already in the requested state, nothing is sent on drop.
+///
+/// Since the cleanup request can't be awaited in [`Drop`], it is sent from a spawned task. Use
+/// [`release`](Self::release) to wait for it instead.
+///
+/// This dereferences to the [`ExpressionActivationResponse`] of the initial request.
+///
+/// # Example
+///
+#[cfg_attr(feature = "tokio-tungstenite", doc = "```no_run")]
+#[cfg_attr(not(feature = "tokio-tungstenite"), doc = "```ignore")]
+/// # async fn run() -> Result<(), vtubestudio::error::BoxError> {
+/// use std::time::Duration;
+/// use vtubestudio::client::ExpressionGuard;
+/// use vtubestudio::Client;
+///
+/// let (client, _) = Client::builder()
+///     .authentication("Plugin name", "Developer name", None)
+///     .build_tungstenite();
+///
+/// let guard = ExpressionGuard::activate(client, "smile.exp3.json").await?;
+/// tokio::time::sleep(Duration::from_secs(5)).await;
+/// guard.release().await?; // Deactivates the expression, unless it was already active
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+#[must_use = "the expression is restored as soon as the guard is dropped"]
+pub struct ExpressionGuard {
+    client: Option<Client>,
+    expression_file: String,
+    active: bool,
+    was_active: bool,
+    response: ExpressionActivationResponse,
+}
+
+impl ExpressionGuard {
+    /// Activates the expression, returning a guard that restores its previous state when dropped.
+    ///
+    /// If the expression was already active, it stays active after the guard is dropped.
+    ///
+    /// If VTube Studio doesn't return the state of the expression, it's assumed to be inactive.
+    pub async fn activate<S: Into<String>>(
+        mut client: Client,
+        expression_file: S,
+    ) -> Result<Self, Error> {
+        let expression_file = expression_file.into();
+        let was_active = is_expression_active(&mut client.service, &expression_file).await?;
+        Self::set_active(client, expression_file, was_active, true).await
+    }
+
+    /// Checks whether the expression is currently active (using an [`ExpressionStateRequest`]),
+    /// and switches it to the opposite state. The returned guard switches it back when dropped.
+    ///
+    /// If VTube Studio doesn't return the state of the expression, it's assumed to be inactive.
+    pub async fn toggle<S: Into<String>>(
+        mut client: Client,
+        expression_file: S,
+    ) -> Result<Self, Error> {
+        let expression_file = expression_file.into();
+        let was_active = is_expression_active(&mut client.service, &expression_file).await?;
+        Self::set_active(client, expression_file, was_active, !was_active).await
+    }
+
+    async fn set_active(
+        mut client: Client,
+        expression_file: String,
+        was_active: bool,
+        active: bool,
+    ) -> Result<Self, Error> {
+        let response = client
+            .send(&ExpressionActivationRequest {
+                expression_file: expression_file.clone(),
+                active,
+            })
+            .await?;
+
+        Ok(Self {
+            // Nothing needs to be restored if the state didn't change
+            client: (was_active != active).then_some(client),
+            expression_file,
+            active,
+            was_active,
+            response,
+        })
+    }
+
+    /// The file name of the expression.
+    pub fn expression_file(&self) -> &str {
+        &self.expression_file
+    }
+
+    /// Whether the expression was activated (`true`) or deactivated (`false`) by this guard.
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    /// Whether the expression was active before this guard changed it. This is the state that's
+    /// restored when the guard is dropped.
+    pub fn was_active(&self) -> bool {
+        self.was_active
+    }
+
+    /// Restores the expression's previous state, waiting for VTube Studio to respond.
+    pub async fn release(mut self) -> Result<(), Error> {
+        match self.client.take() {
+            Some(mut client) => {
+                client.send(&self.restore_request()).await?;
+                Ok(())
+            }
+            None => Ok(()),
+        }
+    }
+
+    fn restore_request(&self) -> ExpressionActivationRequest {
+        ExpressionActivationRequest {
+            expression_file: self.expression_file.clone(),
+            active: self.was_active,
+        }
+    }
+}
+
+impl Deref for ExpressionGuard {
+    type Target = ExpressionActivationResponse;
+
+    fn deref(&self) -> &Self::Target {
+        &self.response
+    }
+}
+
+impl Drop for ExpressionGuard {
+    fn drop(&mut self) {
+        if let Some(mut client) = self.client.take() {
+            let req = self.restore_request();
+            spawn_cleanup("restore expression state", async move {
+                client.send(&req).await?;
+                Ok(())
+            });
+        }
+    }
+}
+
+/// Checks whether the expression is currently active using an [`ExpressionStateRequest`].
+///
+/// If VTube Studio doesn't return the state of the expression, it's assumed to be inactive.
+pub(crate) async fn is_expression_active<S>(
+    service: &mut S,
+    expression_file: &str,
+) -> Result<bool, Error>
+where
+    S: Service<RequestEnvelope, Response = ResponseEnvelope>,
+    Error: From<S::Error>,
+{
+    let state = send_request(
+        service,
+        &ExpressionStateRequest {
+            details: false,
+            expression_file: Some(expression_file.to_owned()),
+        },
+    )
+    .await?;
+
+    Ok(state
+        .find_by_file(expression_file)
+        .is_some_and(|expression| expression.active))
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use super::*;
+    use crate::data::{Expression, ExpressionStateResponse};
+    use crate::testing::MockServer;
+
+    fn activation_requests(server: &MockServer) -> Vec<ExpressionActivationRequest> {
+        server
+            .received()
+            .into_iter()
+            .filter_map(|req| req.data.deserialize().ok())
+            .collect()
+    }
+
+    fn respond_with_state(server: &MockServer, active: bool) -> Result<(), serde_json::Error> {
+        server.respond::<ExpressionStateRequest>(&ExpressionStateResponse {
+            model_loaded: true,
+            expressions: vec![Expression {
+                file: "smile.exp3.json".into(),
+                active,
+                ..Default::default()
+            }],
+            ..Default::default()
+        })
+    }
+
+    fn request(active: bool) -> ExpressionActivationRequest {
+        ExpressionActivationRequest {
+            expression_file: "smile.exp3.json".into(),
+            active,
+        }
+    }
+
+    #[tokio::test]
+    async fn deactivate_on_drop() -> Result<(), Error> {
+        let server = MockServer::new();
+        server.respond::<ExpressionActivationRequest>(&ExpressionActivationResponse {})?;
+        respond_with_state(&server, false)?;
+        let (client, _events) = Client::builder().build_connector(server.connector());
+
+        {
+            let guard = ExpressionGuard::activate(client, "smile.exp3.json").await?;
+            assert!(guard.is_active());
+            assert!(!guard.was_active());
+            assert_eq!(guard.expression_file(), "smile.exp3.json");
+            assert_eq!(*guard, ExpressionActivationResponse {});
+        }
+
+        server.wait_for_requests(3).await;
+        assert_eq!(
+            activation_requests(&server),
+            vec![request(true), request(false)]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn release_then_drop() -> Result<(), Error> {
+        let server = MockServer::new();
+        server.respond::<ExpressionActivationRequest>(&ExpressionActivationResponse {})?;
+        respond_with_state(&server, false)?;
+        let (client, _events) = Client::builder().build_connector(server.connector());
+
+        let guard = ExpressionGuard::activate(client, "smile.exp3.json").await?;
+        guard.release().await?;
+
+        // Dropping after releasing doesn't send another request
+        tokio::task::yield_now().await;
+        assert_eq!(
+            activation_requests(&server),
+            vec![request(true), request(false)]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn already_active() -> Result<(), Error> {
+        let server = MockServer::new();
+        server.respond::<ExpressionActivationRequest>(&ExpressionActivationResponse {})?;
+        respond_with_state(&server, true)?;
+        let (client, _events) = Client::builder().build_connector(server.connector());
+
+        {
+            let guard = ExpressionGuard::activate(client, "smile.exp3.json").await?;
+            assert!(guard.is_active());
+            assert!(guard.was_active());
+        }
+
+        // The expression was already active, so dropping the guard doesn't deactivate it
+        tokio::task::yield_now().await;
+        assert_eq!(
+            server.received_message_types(),
+            ["ExpressionStateRequest", "ExpressionActivationRequest"]
+        );
+        assert_eq!(activation_requests(&server), vec![request(true)]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn toggle() -> Result<(), Error> {
+        let server = MockServer::new();
+        server.respond::<ExpressionActivationRequest>(&ExpressionActivationResponse {})?;
+        respond_with_state(&server, true)?;
+        let (client, _events) = Client::builder().build_connector(server.connector());
+
+        let guard = ExpressionGuard::toggle(client, "smile.exp3.json").await?;
+        assert!(!guard.is_active());
+        guard.release().await?;
+
+        assert_eq!(
+            server.received_message_types(),
+            [
+                "ExpressionStateRequest",
+                "ExpressionActivationRequest",
+                "ExpressionActivationRequest"
+            ]
+        );
+        assert_eq!(
+            activation_requests(&server),
+            vec![request(false), request(true)]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn client_helpers() -> Result<(), Error> {
+        use crate::data::{ApiError, ErrorId, ExpressionStateResponse};
+        use crate::error::ErrorKind;
+        use crate::testing::RequestMatcher;
+
+        let server = MockServer::new();
+        server.respond_with(
+            RequestMatcher::message_type::<ExpressionActivationRequest>(),
+            |req| {
+                let req: ExpressionActivationRequest = req.data.deserialize().unwrap();
+                if req.expression_file == "smile.exp3.json" {
+                    ResponseEnvelope::new(&ExpressionActivationResponse {}).unwrap()
+                } else {
+                    ResponseEnvelope {
+                        data: Err(ApiError {
+                            error_id: ErrorId::EXPRESSION_ACTIVATION_REQUEST_FILE_NOT_FOUND,
+                            message: "Expression not found".into(),
+                        }),
+                        ..Default::default()
+                    }
+                }
+            },
+        );
+        server.respond::<ExpressionStateRequest>(&ExpressionStateResponse {
+            model_loaded: true,
+            ..Default::default()
+        })?;
+        let (mut client, _events) = Client::builder().build_connector(server.connector());
+
+        client.set_expression("smile.exp3.json", false).await?;
+        assert!(client.toggle_expression("smile.exp3.json").await?);
+
+        let err = client
+            .set_expression("missing.exp3.json", true)
+            .await
+            .unwrap_err();
+        assert!(err.has_kind(ErrorKind::NotFound), "{:?}", err);
+
+        assert_eq!(
+            activation_requests(&server),
+            vec![
+                request(false),
+                request(true),
+                ExpressionActivationRequest {
+                    expression_file: "missing.exp3.json".into(),
+                    active: true,
+                }
+            ]
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,23 @@
+use crate::error::Error;
+
+use std::future::Future;
+
+/// Runs a cleanup request from a [`Drop`] impl, where it can't be awaited.
+///
+/// The future is spawned on the current Tokio runtime, if any. Errors are logged, since there's no
+/// caller to return them to.
+pub(crate) fn spawn_cleanup<F>(description: &'static str, cleanup: F)
+where
+    F: Future<Output = Result<(), Error>> + Send + 'static,
+{
+    match tokio::runtime::Handle::try_current() {
+        Ok(handle) => {
+            handle.spawn(async move {
+                if let Err(error) = cleanup.await {
+                    tracing::warn!(%error, "Failed to {}", description);
+                }
+            });
+        }
+        Err(_) => tracing::warn!("Failed to {}: no runtime available", description),
+    }
+}
@@ -0,0 +1,312 @@
+use crate::client::guard::spawn_cleanup;
+use crate::client::Client;
+use crate::data::{ItemLoadRequest, ItemLoadResponse, ItemUnloadRequest, ItemUnloadResponse};
+use crate::error::Error;
+
+use futures_util::future;
+use std::future::Future;
+use std::ops::Deref;
+
+/// Unloads an item from the scene when dropped.
+///
+/// Items loaded via [`ItemLoadRequest`] stay in the scene after the plugin disconnects, unless
+/// [`unload_when_plugin_disconnects`](ItemLoadRequest::unload_when_plugin_disconnects) is set.
+/// When this guard is dropped (including during a panic), an [`ItemUnloadRequest`] is sent for the
+/// item's instance ID.
+///
+/// Since the unload request can't be awaited in [`Drop`], it is sent from a spawned task, and
+/// failures are logged rather than returned. Use [`release`](Self::release) to wait for it
+/// instead.
+///
+/// This dereferences to the [`ItemLoadResponse`] of the loaded item.
+///
+/// # Example
+///
+#[cfg_attr(feature = "tokio-tungstenite", doc = "```no_run")]
+#[cfg_attr(not(feature = "tokio-tungstenite"), doc = "```ignore")]
+/// # async fn run() -> Result<(), vtubestudio::error::BoxError> {
+/// use std::time::Duration;
+/// use vtubestudio::client::ItemUnloadGuard;
+/// use vtubestudio::data::ItemLoadRequest;
+/// use vtubestudio::Client;
+///
+/// let (client, _) = Client::builder()
+///     .authentication("Plugin name", "Developer name", None)
+///     .build_tungstenite();
+///
+/// let item = ItemUnloadGuard::load(
+///     client,
+///     &ItemLoadRequest {
+///         file_name: "akari_3.png".to_owned(),
+///         size: 0.32,
+///         ..Default::default()
+///     },
+/// )
+/// .await?;
+///
+/// println!("Loaded item {}", item.instance_id());
+/// tokio::time::sleep(Duration::from_secs(5)).await;
+/// drop(item); // Unloads the item
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+#[must_use = "the item is unloaded as soon as the guard is dropped"]
+pub struct ItemUnloadGuard {
+    client: Option<Client>,
+    response: ItemLoadResponse,
+}
+
+impl ItemUnloadGuard {
+    /// Loads an item, returning a guard that unloads it when dropped.
+    pub async fn load(mut client: Client, req: &ItemLoadRequest) -> Result<Self, Error> {
+        let response = client.send(req).await?;
+        Ok(Self::new(client, response))
+    }
+
+    /// Creates a guard for an item that has already been loaded.
+    pub fn new(client: Client, response: ItemLoadResponse) -> Self {
+        Self {
+            client: Some(client),
+            response,
+        }
+    }
+
+    /// The instance ID of the loaded item.
+    pub fn instance_id(&self) -> &str {
+        &self.response.instance_id
+    }
+
+    /// The file name of the loaded item.
+    pub fn file_name(&self) -> &str {
+        &self.response.file_name
+    }
+
+    /// Unloads the item, waiting for VTube Studio to respond.
+    pub async fn release(mut self) -> Result<ItemUnloadResponse, Error> {
+        match self.take_unload() {
+            Some(unload) => unload.await,
+            None => Ok(ItemUnloadResponse::default()),
+        }
+    }
+
+    /// Consumes the guard without unloading the item, returning the original response.
+    pub fn keep(mut self) -> ItemLoadResponse {
+        self.client = None;
+        std::mem::take(&mut self.response)
+    }
+
+    /// Returns a future that unloads the item, or `None` if it has already been released.
+    fn take_unload(
+        &mut self,
+    ) -> Option<impl Future<Output = Result<ItemUnloadResponse, Error>> + Send + 'static> {
+        let mut client = self.client.take()?;
+        let req = ItemUnloadRequest {
+            instance_ids: vec![self.response.instance_id.clone()],
+            ..Default::default()
+        };
+
+        Some(async move { client.send(&req).await })
+    }
+}
+
+impl Deref for ItemUnloadGuard {
+    type Target = ItemLoadResponse;
+
+    fn deref(&self) -> &Self::Target {
+        &self.response
+    }
+}
+
+impl Drop for ItemUnloadGuard {
+    fn drop(&mut self) {
+        if let Some(unload) = self.take_unload() {
+            spawn_cleanup("unload item", async move {
+                unload.await?;
+                Ok(())
+            });
+        }
+    }
+}
+
+/// A collection of [`ItemUnloadGuard`]s, which unloads all of its items in parallel when dropped.
+#[derive(Debug, Default)]
+#[must_use = "the items are unloaded as soon as the guard set is dropped"]
+pub struct ItemUnloadGuardSet {
+    guards: Vec<ItemUnloadGuard>,
+}
+
+impl ItemUnloadGuardSet {
+    /// Creates an empty [`ItemUnloadGuardSet`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a guard to the set.
+    pub fn push(&mut self, guard: ItemUnloadGuard) {
+        self.guards.push(guard);
+    }
+
+    /// The number of guards in the set.
+    pub fn len(&self) -> usize {
+        self.guards.len()
+    }
+
+    /// Returns `true` if the set contains no guards.
+    pub fn is_empty(&self) -> bool {
+        self.guards.is_empty()
+    }
+
+    /// Returns an iterator over the guards in the set.
+    pub fn iter(&self) -> impl Iterator<Item = &ItemUnloadGuard> {
+        self.guards.iter()
+    }
+
+    /// Unloads all items in parallel, waiting for VTube Studio to respond to each request.
+    ///
+    /// The results are returned in the same order as the guards were added. Since the set is
+    /// consumed, nothing else is sent when it's dropped.
+    pub async fn release_all(mut self) -> Vec<Result<ItemUnloadResponse, Error>> {
+        future::join_all(self.take_unloads()).await
+    }
+
+    fn take_unloads(
+        &mut self,
+    ) -> Vec<impl Future<Output = Result<ItemUnloadResponse, Error>> + Send + 'static> {
+        self.guards
+            .iter_mut()
+            .filter_map(ItemUnloadGuard::take_unload)
+            .collect()
+    }
+}
+
+impl Extend<ItemUnloadGuard> for ItemUnloadGuardSet {
+    fn extend<T: IntoIterator<Item = ItemUnloadGuard>>(&mut self, iter: T) {
+        self.guards.extend(iter);
+    }
+}
+
+impl std::iter::FromIterator<ItemUnloadGuard> for ItemUnloadGuardSet {
+    fn from_iter<T: IntoIterator<Item = ItemUnloadGuard>>(iter: T) -> Self {
+        Self {
+            guards: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl Drop for ItemUnloadGuardSet {
+    fn drop(&mut self) {
+        let unloads = self.take_unloads();
+        if !unloads.is_empty() {
+            spawn_cleanup("unload items", async move {
+                future::join_all(unloads)
+                    .await
+                    .into_iter()
+                    .collect::<Result<Vec<_>, Error>>()?;
+                Ok(())
+            });
+        }
+    }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use super::*;
+    use crate::data::ResponseEnvelope;
+    use crate::testing::{MockServer, RequestMatcher};
+
+    fn server() -> Result<MockServer, Error> {
+        let server = MockServer::new();
+        server.respond_with(RequestMatcher::message_type::<ItemLoadRequest>(), |req| {
+            let req = req.data.deserialize::<ItemLoadRequest>().unwrap();
+            ResponseEnvelope::new(&ItemLoadResponse {
+                instance_id: format!("id-{}", req.file_name),
+                file_name: req.file_name,
+            })
+            .unwrap()
+        });
+        server.respond::<ItemUnloadRequest>(&ItemUnloadResponse::default())?;
+        Ok(server)
+    }
+
+    fn load_request(file_name: &str) -> ItemLoadRequest {
+        ItemLoadRequest {
+            file_name: file_name.into(),
+            ..Default::default()
+        }
+    }
+
+    fn unloaded_ids(server: &MockServer) -> Vec<String> {
+        let mut ids = server
+            .received()
+            .into_iter()
+            .filter_map(|req| req.data.deserialize::<ItemUnloadRequest>().ok())
+            .flat_map(|req| req.instance_ids)
+            .collect::<Vec<_>>();
+        ids.sort();
+        ids
+    }
+
+    #[tokio::test]
+    async fn unload_on_drop() -> Result<(), Error> {
+        let server = server()?;
+        let (client, _events) = Client::builder().build_connector(server.connector());
+
+        {
+            let guard = ItemUnloadGuard::load(client.clone(), &load_request("a.png")).await?;
+            assert_eq!(guard.instance_id(), "id-a.png");
+            assert_eq!(guard.file_name(), "a.png");
+        }
+
+        server.wait_for_requests(2).await;
+        assert_eq!(unloaded_ids(&server), ["id-a.png"]);
+
+        // Kept items aren't unloaded
+        let guard = ItemUnloadGuard::load(client, &load_request("b.png")).await?;
+        assert_eq!(guard.keep().instance_id, "id-b.png");
+        tokio::task::yield_now().await;
+        assert_eq!(server.received().len(), 3);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn guard_set() -> Result<(), Error> {
+        let server = server()?;
+        let (client, _events) = Client::builder().build_connector(server.connector());
+
+        let mut set = ItemUnloadGuardSet::new();
+        for file_name in ["a.png", "b.png"].iter() {
+            set.push(ItemUnloadGuard::load(client.clone(), &load_request(file_name)).await?);
+        }
+        assert_eq!(set.len(), 2);
+        drop(set);
+
+        server.wait_for_requests(4).await;
+        assert_eq!(unloaded_ids(&server), ["id-a.png", "id-b.png"]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn release_all() -> Result<(), Error> {
+        let server = server()?;
+        let (client, _events) = Client::builder().build_connector(server.connector());
+
+        let mut set = ItemUnloadGuardSet::new();
+        for file_name in ["a.png", "b.png", "c.png"].iter() {
+            set.push(ItemUnloadGuard::load(client.clone(), &load_request(file_name)).await?);
+        }
+
+        let results = set.release_all().await;
+        assert_eq!(results.len(), 3);
+        assert!(results.iter().all(Result::is_ok));
+
+        // Dropping the released set doesn't send any more requests
+        tokio::task::yield_now().await;
+        assert_eq!(server.received().len(), 6);
+        assert_eq!(unloaded_ids(&server), ["id-a.png", "id-b.png", "id-c.png"]);
+
+        Ok(())
+    }
+}
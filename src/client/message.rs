@@ -0,0 +1,189 @@
+use crate::client::{ClientEvent, ClientEventStream};
+use crate::data::Event;
+
+use futures_channel::mpsc;
+use futures_core::Stream;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// A message yielded by a [`ClientMessageStream`], which is either a [`ClientEvent`] or the
+/// result of a request sent through the responses channel.
+#[non_exhaustive]
+#[derive(Debug)]
+pub enum ClientMessage<T> {
+    /// See [`ClientEvent::Connected`].
+    Connected,
+    /// See [`ClientEvent::Disconnected`].
+    Disconnected,
+    /// See [`ClientEvent::NewAuthToken`].
+    NewAuthToken(String),
+    /// See [`ClientEvent::Api`].
+    Event(Event),
+    /// A value received from the responses channel (typically the result of a request).
+    Response(T),
+    /// Any other [`ClientEvent`], such as [`ClientEvent::Reconnecting`] or [`ClientEvent::Error`].
+    Other(ClientEvent),
+}
+
+impl<T> From<ClientEvent> for ClientMessage<T> {
+    fn from(event: ClientEvent) -> Self {
+        match event {
+            ClientEvent::Connected => Self::Connected,
+            ClientEvent::Disconnected => Self::Disconnected,
+            ClientEvent::NewAuthToken(token) => Self::NewAuthToken(token),
+            ClientEvent::Api(event) => Self::Event(event),
+            other => Self::Other(other),
+        }
+    }
+}
+
+/// A [`Stream`](futures_core::Stream) that merges a [`ClientEventStream`] with a channel of
+/// request results, for plugins that handle everything in a single loop.
+///
+/// Created with [`ClientEventStream::with_responses`]. Requests are typically sent from spawned
+/// tasks holding a clone of the [`Client`](crate::Client), which send their results to the
+/// channel. Client events are yielded before responses if both are ready.
+///
+/// The stream ends once both the event stream and the responses channel are closed.
+///
+/// # Example
+///
+#[cfg_attr(feature = "tokio-tungstenite", doc = "```no_run")]
+#[cfg_attr(not(feature = "tokio-tungstenite"), doc = "```ignore")]
+/// # async fn run() -> Result<(), vtubestudio::error::BoxError> {
+/// use futures_channel::mpsc;
+/// use futures_util::SinkExt;
+/// use vtubestudio::client::ClientMessage;
+/// use vtubestudio::data::StatisticsRequest;
+/// use vtubestudio::Client;
+///
+/// let (client, events) = Client::builder()
+///     .authentication("Plugin name", "Developer name", None)
+///     .build_tungstenite();
+///
+/// let (tx, rx) = mpsc::channel(16);
+/// let mut messages = events.with_responses(rx);
+///
+/// while let Some(message) = messages.next().await {
+///     match message {
+///         ClientMessage::Disconnected => {
+///             let (mut client, mut tx) = (client.clone(), tx.clone());
+///             tokio::spawn(async move {
+///                 let _ = tx.send(client.send(&StatisticsRequest {}).await).await;
+///             });
+///         }
+///         ClientMessage::Response(Ok(stats)) => println!("Uptime: {}ms", stats.uptime),
+///         ClientMessage::Response(Err(e)) => eprintln!("Request failed: {e}"),
+///         other => println!("Received {:?}", other),
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ClientMessageStream<T> {
+    events: Option<Pin<Box<async_channel::Receiver<ClientEvent>>>>,
+    responses: Option<mpsc::Receiver<T>>,
+}
+
+impl ClientEventStream {
+    /// Merges this stream with a channel of request results, returning a [`ClientMessageStream`].
+    pub fn with_responses<T>(self, responses: mpsc::Receiver<T>) -> ClientMessageStream<T> {
+        ClientMessageStream {
+            events: Some(self.receiver),
+            responses: Some(responses),
+        }
+    }
+}
+
+impl<T> ClientMessageStream<T> {
+    /// Returns the next [`ClientMessage`], or `None` once both underlying channels are closed.
+    pub async fn next(&mut self) -> Option<ClientMessage<T>> {
+        futures_util::future::poll_fn(|cx| self.poll_message(cx)).await
+    }
+
+    fn poll_message(&mut self, cx: &mut Context<'_>) -> Poll<Option<ClientMessage<T>>> {
+        if let Some(events) = &mut self.events {
+            match events.as_mut().poll_next(cx) {
+                Poll::Ready(Some(event)) => return Poll::Ready(Some(event.into())),
+                Poll::Ready(None) => self.events = None,
+                Poll::Pending => {}
+            }
+        }
+
+        if let Some(responses) = &mut self.responses {
+            match Pin::new(responses).poll_next(cx) {
+                Poll::Ready(Some(resp)) => return Poll::Ready(Some(ClientMessage::Response(resp))),
+                Poll::Ready(None) => self.responses = None,
+                Poll::Pending => {}
+            }
+        }
+
+        if self.events.is_none() && self.responses.is_none() {
+            Poll::Ready(None)
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+impl<T> Stream for ClientMessageStream<T> {
+    type Item = ClientMessage<T>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.poll_message(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::{SinkExt, StreamExt};
+
+    #[tokio::test]
+    async fn merge_events_and_responses() {
+        let (event_tx, receiver) = async_channel::bounded(8);
+        let (mut resp_tx, resp_rx) = mpsc::channel(8);
+        let messages = ClientEventStream::new(receiver).with_responses(resp_rx);
+
+        resp_tx.send(1).await.unwrap();
+        event_tx.send(ClientEvent::Connected).await.unwrap();
+        event_tx
+            .send(ClientEvent::NewAuthToken("token".to_owned()))
+            .await
+            .unwrap();
+        event_tx.send(ClientEvent::GaveUp).await.unwrap();
+        drop(event_tx);
+        resp_tx.send(2).await.unwrap();
+        drop(resp_tx);
+
+        let messages = messages.collect::<Vec<_>>().await;
+        assert!(matches!(
+            messages.as_slice(),
+            [
+                ClientMessage::Connected,
+                ClientMessage::NewAuthToken(token),
+                ClientMessage::Other(ClientEvent::GaveUp),
+                ClientMessage::Response(1),
+                ClientMessage::Response(2),
+            ] if token == "token"
+        ));
+    }
+
+    #[tokio::test]
+    async fn ends_when_both_closed() {
+        let (event_tx, receiver) = async_channel::bounded(8);
+        let (resp_tx, resp_rx) = mpsc::channel::<()>(8);
+        let mut messages = ClientEventStream::new(receiver).with_responses(resp_rx);
+
+        drop(resp_tx);
+        event_tx.send(ClientEvent::Disconnected).await.unwrap();
+        assert!(matches!(
+            messages.next().await,
+            Some(ClientMessage::Disconnected)
+        ));
+
+        drop(event_tx);
+        assert!(messages.next().await.is_none());
+    }
+}
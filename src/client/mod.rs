@@ -0,0 +1,2400 @@
+mod callback;
+mod channels;
+mod controller;
+mod expression;
+mod guard;
+mod item;
+mod message;
+mod overflow;
+mod parameter;
+mod physics;
+mod reconnect;
+mod subscription;
+
+pub use crate::client::controller::PluginController;
+pub use crate::client::expression::ExpressionGuard;
+pub use crate::client::item::{ItemUnloadGuard, ItemUnloadGuardSet};
+pub use crate::client::message::{ClientMessage, ClientMessageStream};
+pub use crate::client::overflow::EventOverflowPolicy;
+pub use crate::client::parameter::ParameterSet;
+pub use crate::client::physics::{PhysicsOverrideGuard, PhysicsOverrideSession};
+pub use crate::client::subscription::EventSubscriptionManager;
+
+use crate::client::callback::{ConnectionCallback, ConnectionCallbacks, ConnectionEvent};
+use crate::client::channels::EventChannels;
+use crate::client::expression::is_expression_active;
+use crate::client::overflow::forward_events;
+use crate::client::reconnect::{ReconnectConfig, ReconnectGate};
+
+use crate::data::{
+    ApiError, ApiStateRequest, AuthenticationTokenRequest, EnumString, ErrorId, Event, EventConfig,
+    EventData, EventDataList, EventSubscriptionRequest, EventSubscriptionResponse,
+    ExpressionActivationRequest, ExpressionActivationResponse, HotkeyTriggerRequest,
+    HotkeyTriggerResponse, HotkeysInCurrentModelRequest, InjectParameterDataMode,
+    InjectParameterDataRequest, ParameterCreationRequest, ParameterCreationResponse,
+    ParameterDeletionRequest, ParameterDeletionResponse, ParameterValue, Permission,
+    PermissionRequest, PermissionResponse, Request, RequestEnvelope, RequestList, ResponseEnvelope,
+    ResponseMeta,
+};
+use crate::error::{BatchError, BoxError, Error, ErrorKind};
+use crate::service::auth::SharedToken;
+use crate::service::retry::RetryPredicate;
+use crate::service::BoxCloneApiService;
+use crate::service::{
+    client_metrics_layer, client_tracing_layer, send_ping, send_raw_request, send_request,
+    send_request_with_meta, AuthenticationLayer, MakeApiService, Reconnect, ResponseWithToken,
+    RetryPolicy,
+};
+use crate::transport::closeable::CloseableConnector;
+
+use futures_util::{future, StreamExt};
+use std::borrow::Cow;
+use std::error::Error as StdError;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{broadcast, watch};
+use tokio_tower::MakeTransport;
+use tower::buffer::Buffer;
+use tower::util::BoxCloneService;
+use tower::{Service, ServiceBuilder, ServiceExt};
+
+#[cfg(all(feature = "async-tungstenite", not(feature = "tokio-tungstenite")))]
+use async_tungstenite::tungstenite::protocol::WebSocketConfig;
+#[cfg(feature = "tokio-tungstenite")]
+use tokio_tungstenite::tungstenite::protocol::WebSocketConfig;
+
+const DEFAULT_EVENT_BUFFER_SIZE: usize = 128;
+
+/// A client for interacting with the VTube Studio API.
+///
+/// This is a wrapper on top of [`tower::Service`] that provides a convenient interface for
+/// [`send`](Self::send)ing API requests and receiving structured data.
+#[derive(Clone, Debug)]
+pub struct Client<S = BoxCloneApiService> {
+    service: S,
+    state: Arc<ClientState>,
+}
+
+/// State shared between clones of a [`Client`].
+#[derive(Debug)]
+struct ClientState {
+    /// Incremented to signal that current connections should be closed.
+    close: watch::Sender<u64>,
+    /// Number of connections whose events are still being forwarded.
+    connections: watch::Sender<usize>,
+    /// Shared with the [`Authentication`](crate::service::Authentication) service, if any.
+    authenticated: Arc<AtomicBool>,
+    /// The auth token stored by the [`Authentication`](crate::service::Authentication) service.
+    auth_token: SharedToken,
+    /// Typed event channels, as returned by [`Client::event_channel`].
+    event_channels: EventChannels,
+    /// Whether a connection has ever been attempted.
+    connection_attempted: AtomicBool,
+    /// Number of reconnection attempts since the last successful connection.
+    reconnect_attempts: AtomicU32,
+    /// Whether the maximum number of reconnection attempts has been exceeded.
+    gave_up: AtomicBool,
+}
+
+impl ClientState {
+    fn new(event_buffer_size: usize) -> Self {
+        Self {
+            close: watch::Sender::new(0),
+            connections: watch::Sender::new(0),
+            authenticated: Arc::default(),
+            auth_token: SharedToken::default(),
+            event_channels: EventChannels::new(event_buffer_size),
+            connection_attempted: AtomicBool::new(false),
+            reconnect_attempts: AtomicU32::new(0),
+            gave_up: AtomicBool::new(false),
+        }
+    }
+}
+
+impl Default for ClientState {
+    fn default() -> Self {
+        Self::new(DEFAULT_EVENT_BUFFER_SIZE)
+    }
+}
+
+/// The state of a [`Client`]'s connection, as returned by [`Client::connection_state`].
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ConnectionState {
+    /// Not connected. Note that by default, [`Client`] connects lazily on the next request.
+    Disconnected,
+    /// Connected, but not authenticated.
+    Connected,
+    /// Connected and authenticated.
+    Authenticated,
+}
+
+impl ConnectionState {
+    /// Returns `true` if the state is [`Connected`](Self::Connected) or
+    /// [`Authenticated`](Self::Authenticated).
+    pub fn is_connected(&self) -> bool {
+        !matches!(self, Self::Disconnected)
+    }
+
+    /// Returns `true` if the state is [`Authenticated`](Self::Authenticated).
+    pub fn is_authenticated(&self) -> bool {
+        matches!(self, Self::Authenticated)
+    }
+}
+
+/// A client event received outside of the typical request/response flow.
+///
+/// This includes [`Event`]s received from the API, as requested via
+/// [`EventSubscriptionRequest`](crate::data::EventSubscriptionRequest).
+#[non_exhaustive]
+#[derive(Debug)]
+pub enum ClientEvent {
+    /// The underlying event transport established a connection.
+    ///
+    /// Note that by default, [`Client`] connects lazily, so this event might not be received until
+    /// after making the first request.
+    Connected,
+    /// The underlying event transport disconnected.
+    ///
+    /// You can use this as a signal to resubscribe to events.
+    Disconnected,
+    /// A new connection attempt is starting after a previous connection (or connection attempt)
+    /// ended.
+    ///
+    /// This is only emitted by clients created with [`ClientBuilder::build_connector`] (or helpers
+    /// that use it, such as [`ClientBuilder::build_tungstenite`]).
+    Reconnecting {
+        /// The number of connection attempts since the last successful connection, starting at 1.
+        attempt: u32,
+    },
+    /// The client stopped trying to reconnect, after exceeding the number of attempts set by
+    /// [`ClientBuilder::max_reconnect_attempts`].
+    ///
+    /// This follows a [`ClientEvent::Disconnected`] event (or the failed attempts), and is only
+    /// emitted once. All requests sent after this will fail with
+    /// [`ErrorKind::ConnectionRefused`].
+    GaveUp,
+    /// Received new auth token.
+    NewAuthToken(String),
+    /// Event received from the API.
+    Api(Event),
+    /// Error received outside the request/response flow.
+    Error(Error),
+}
+
+impl Client<BoxCloneApiService> {
+    /// Creates a builder to configure a new client.
+    ///
+    /// # Example
+    ///
+    #[cfg_attr(feature = "tokio-tungstenite", doc = "```no_run")]
+    #[cfg_attr(not(feature = "tokio-tungstenite"), doc = "```ignore")]
+    /// # use vtubestudio::Client;
+    /// let (mut client, mut events) = Client::builder()
+    ///     .auth_token(Some("...".to_string()))
+    ///     .authentication("Plugin name", "Developer name", None)
+    ///     .build_tungstenite();
+    /// ```
+    pub fn builder() -> ClientBuilder {
+        ClientBuilder::new()
+    }
+}
+
+impl<S> Client<S>
+where
+    S: Service<RequestEnvelope, Response = ResponseEnvelope>,
+    Error: From<S::Error>,
+{
+    /// Creates a new client from a [`Service`], if you want to provide your own custom middleware
+    /// or transport. Most users will probably want to use the [`builder`](Client::builder) helper.
+    pub fn new_from_service(service: S) -> Self {
+        Self::new_with_state(service, Arc::default())
+    }
+
+    fn new_with_state(service: S, state: Arc<ClientState>) -> Self {
+        Self { service, state }
+    }
+
+    /// Consumes this client and returns the underlying [`Service`].
+    pub fn into_service(self) -> S {
+        self.service
+    }
+
+    /// Sends a VTube Studio API request.
+    ///
+    /// # Example
+    ///
+    #[cfg_attr(feature = "tokio-tungstenite", doc = "```no_run")]
+    #[cfg_attr(not(feature = "tokio-tungstenite"), doc = "```ignore")]
+    /// # async fn run() -> Result<(), vtubestudio::error::BoxError> {
+    /// # use vtubestudio::Client;
+    /// use vtubestudio::data::StatisticsRequest;
+    ///
+    /// # let (mut client, _) = Client::builder().build_tungstenite();
+    /// let resp = client.send(&StatisticsRequest {}).await?;
+    /// println!("VTube Studio has been running for {}ms", resp.uptime);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn send<Req: Request>(&mut self, data: &Req) -> Result<Req::Response, Error> {
+        send_request(&mut self.service, data).await
+    }
+
+    /// Sends a VTube Studio API request, returning the response along with its metadata (the
+    /// timestamp, API name, API version, and request ID).
+    ///
+    /// # Example
+    ///
+    #[cfg_attr(feature = "tokio-tungstenite", doc = "```no_run")]
+    #[cfg_attr(not(feature = "tokio-tungstenite"), doc = "```ignore")]
+    /// # async fn run() -> Result<(), vtubestudio::error::BoxError> {
+    /// # use vtubestudio::Client;
+    /// use std::time::{SystemTime, UNIX_EPOCH};
+    /// use vtubestudio::data::{StatisticsRequest, API_VERSION};
+    ///
+    /// # let (mut client, _) = Client::builder().build_tungstenite();
+    /// let (resp, meta) = client.send_with_meta(&StatisticsRequest {}).await?;
+    ///
+    /// if meta.api_version != API_VERSION {
+    ///     println!("Unexpected API version {}", meta.api_version);
+    /// }
+    ///
+    /// let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis() as i64;
+    /// println!("Response was sent {}ms ago", now - meta.timestamp);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn send_with_meta<Req: Request>(
+        &mut self,
+        data: &Req,
+    ) -> Result<(Req::Response, ResponseMeta), Error> {
+        send_request_with_meta(&mut self.service, data).await
+    }
+
+    /// Sends a request with an arbitrary message type and JSON data, returning the raw response.
+    ///
+    /// This is an escape hatch for calling API requests that don't have typed structs in this
+    /// library yet. Note that an [`ApiError`](crate::data::ApiError) returned by VTube Studio is
+    /// not treated as an error here, and is instead available in the response's
+    /// [`data`](ResponseEnvelope::data) field.
+    ///
+    /// # Example
+    ///
+    #[cfg_attr(feature = "tokio-tungstenite", doc = "```no_run")]
+    #[cfg_attr(not(feature = "tokio-tungstenite"), doc = "```ignore")]
+    /// # async fn run() -> Result<(), vtubestudio::error::BoxError> {
+    /// # use vtubestudio::Client;
+    /// use serde_json::json;
+    ///
+    /// # let (mut client, _) = Client::builder().build_tungstenite();
+    /// let resp = client
+    ///     .send_raw("SomeNewRequest", json!({ "someField": true }))
+    ///     .await?;
+    ///
+    /// match resp.data {
+    ///     Ok(data) => println!("Received {}: {}", data.message_type, data.data.get()),
+    ///     Err(e) => println!("Received API error: {}", e),
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn send_raw(
+        &mut self,
+        message_type: &str,
+        data: serde_json::Value,
+    ) -> Result<ResponseEnvelope, Error> {
+        send_raw_request(&mut self.service, message_type, data).await
+    }
+
+    /// Returns the current connection state, without sending any requests.
+    ///
+    /// Connections are only tracked for clients created with
+    /// [`build_connector`](ClientBuilder::build_connector) (or
+    /// [`build_tungstenite`](ClientBuilder::build_tungstenite)). Other clients will always return
+    /// [`ConnectionState::Disconnected`].
+    ///
+    /// # Example
+    ///
+    #[cfg_attr(feature = "tokio-tungstenite", doc = "```no_run")]
+    #[cfg_attr(not(feature = "tokio-tungstenite"), doc = "```ignore")]
+    /// # use vtubestudio::Client;
+    /// use vtubestudio::client::ConnectionState;
+    ///
+    /// # let (client, _) = Client::builder().build_tungstenite();
+    /// match client.connection_state() {
+    ///     ConnectionState::Disconnected => println!("Disconnected"),
+    ///     ConnectionState::Connected => println!("Connected (not authenticated)"),
+    ///     ConnectionState::Authenticated => println!("Authenticated"),
+    ///     _ => {}
+    /// }
+    /// ```
+    pub fn connection_state(&self) -> ConnectionState {
+        if *self.state.connections.borrow() == 0 {
+            ConnectionState::Disconnected
+        } else if self.state.authenticated.load(Ordering::Relaxed) {
+            ConnectionState::Authenticated
+        } else {
+            ConnectionState::Connected
+        }
+    }
+
+    /// Returns the auth token currently stored by the
+    /// [`Authentication`](crate::service::Authentication) middleware, if any.
+    ///
+    /// This is either the token set via [`ClientBuilder::auth_token`], or the most recent token
+    /// obtained from VTube Studio (which is also sent as a [`ClientEvent::NewAuthToken`]). This
+    /// can be used to persist the token when shutting down, without having to keep track of every
+    /// [`ClientEvent::NewAuthToken`] event.
+    ///
+    /// Returns `None` if the client was built without
+    /// [`authentication`](ClientBuilder::authentication) info.
+    ///
+    /// # Example
+    ///
+    #[cfg_attr(feature = "tokio-tungstenite", doc = "```no_run")]
+    #[cfg_attr(not(feature = "tokio-tungstenite"), doc = "```ignore")]
+    /// # async fn run() -> Result<(), vtubestudio::error::BoxError> {
+    /// # use vtubestudio::Client;
+    /// # fn save_token(token: &str) {}
+    /// let (mut client, _) = Client::builder()
+    ///     .authentication("Plugin name", "Developer name", None)
+    ///     .build_tungstenite();
+    ///
+    /// client.ensure_authenticated().await?;
+    ///
+    /// if let Some(token) = client.auth_token() {
+    ///     save_token(&token);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn auth_token(&self) -> Option<String> {
+        self.state.auth_token.get()
+    }
+
+    /// Returns a [`broadcast::Receiver`] that only receives events of type `E`.
+    ///
+    /// This is an alternative to matching on [`ClientEvent::Api`] variants from the
+    /// [`ClientEventStream`], which still receives all events. All callers requesting the same
+    /// event type share the same underlying channel, whose capacity is the
+    /// [`event_buffer_size`](ClientBuilder::event_buffer_size). A receiver that falls behind will
+    /// get [`RecvError::Lagged`](broadcast::error::RecvError::Lagged) without blocking other
+    /// receivers.
+    ///
+    /// Note that this only receives events for clients created with
+    /// [`build_connector`](ClientBuilder::build_connector) (or
+    /// [`build_tungstenite`](ClientBuilder::build_tungstenite)), and you still need to subscribe
+    /// to the event with an [`EventSubscriptionRequest`](crate::data::EventSubscriptionRequest).
+    ///
+    /// # Example
+    ///
+    #[cfg_attr(feature = "tokio-tungstenite", doc = "```no_run")]
+    #[cfg_attr(not(feature = "tokio-tungstenite"), doc = "```ignore")]
+    /// # async fn run() -> Result<(), vtubestudio::error::BoxError> {
+    /// # use vtubestudio::Client;
+    /// use vtubestudio::data::{EventSubscriptionRequest, ModelLoadedEvent, ModelLoadedEventConfig};
+    ///
+    /// # let (mut client, _) = Client::builder().build_tungstenite();
+    /// let mut model_loaded = client.event_channel::<ModelLoadedEvent>();
+    ///
+    /// let req = EventSubscriptionRequest::subscribe(&ModelLoadedEventConfig::default())?;
+    /// client.send(&req).await?;
+    ///
+    /// while let Ok(event) = model_loaded.recv().await {
+    ///     println!("Model loaded: {}", event.model_name);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn event_channel<E>(&self) -> broadcast::Receiver<E>
+    where
+        E: EventData + Clone + Send + 'static,
+    {
+        self.state.event_channels.subscribe::<E>()
+    }
+
+    /// Closes the current websocket connection (sending a close frame) and waits for the
+    /// underlying transport to finish. A final [`ClientEvent::Disconnected`] is emitted to the
+    /// [`ClientEventStream`].
+    ///
+    /// This affects all clones of this client. Requests sent after closing will cause the client
+    /// to reconnect.
+    ///
+    /// This only has an effect on clients created with
+    /// [`build_connector`](ClientBuilder::build_connector) (or
+    /// [`build_tungstenite`](ClientBuilder::build_tungstenite)), since other clients don't manage
+    /// their own transport.
+    ///
+    /// # Example
+    ///
+    #[cfg_attr(feature = "tokio-tungstenite", doc = "```no_run")]
+    #[cfg_attr(not(feature = "tokio-tungstenite"), doc = "```ignore")]
+    /// # async fn run() -> Result<(), vtubestudio::error::BoxError> {
+    /// # use vtubestudio::Client;
+    /// use vtubestudio::data::StatisticsRequest;
+    ///
+    /// # let (mut client, _) = Client::builder().build_tungstenite();
+    /// client.send(&StatisticsRequest {}).await?;
+    /// client.close().await;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn close(&mut self) {
+        let mut connections = self.state.connections.subscribe();
+        self.state.close.send_modify(|generation| *generation += 1);
+        let _ = connections.wait_for(|count| *count == 0).await;
+    }
+
+    /// Sends an [`ApiStateRequest`](crate::data::ApiStateRequest) and returns the round-trip time.
+    ///
+    /// This can be used as a health check, since it returns an error if the connection is dead.
+    ///
+    /// # Example
+    ///
+    #[cfg_attr(feature = "tokio-tungstenite", doc = "```no_run")]
+    #[cfg_attr(not(feature = "tokio-tungstenite"), doc = "```ignore")]
+    /// # async fn run() -> Result<(), vtubestudio::error::BoxError> {
+    /// # use vtubestudio::Client;
+    /// # let (mut client, _) = Client::builder().build_tungstenite();
+    /// let latency = client.ping().await?;
+    /// println!("Round-trip time: {:?}", latency);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn ping(&mut self) -> Result<Duration, Error> {
+        send_ping(&mut self.service).await
+    }
+
+    /// Connects now, instead of waiting for the first request to connect lazily.
+    ///
+    /// If the client isn't already connected, this sends an
+    /// [`ApiStateRequest`](crate::data::ApiStateRequest), which establishes the connection (and
+    /// authenticates, if the client was built with [`authentication`](ClientBuilder::authentication)
+    /// info). This is useful for reporting an unreachable VTube Studio instance at startup, rather
+    /// than on the first request.
+    ///
+    /// # Example
+    ///
+    #[cfg_attr(feature = "tokio-tungstenite", doc = "```no_run")]
+    #[cfg_attr(not(feature = "tokio-tungstenite"), doc = "```ignore")]
+    /// # async fn run() -> Result<(), vtubestudio::error::BoxError> {
+    /// # use vtubestudio::Client;
+    /// let (mut client, _) = Client::builder().build_tungstenite();
+    ///
+    /// if let Err(e) = client.connect().await {
+    ///     eprintln!("Couldn't connect to VTube Studio: {}", e);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn connect(&mut self) -> Result<(), Error> {
+        if self.connection_state().is_connected() {
+            return Ok(());
+        }
+
+        send_request(&mut self.service, &ApiStateRequest {}).await?;
+        Ok(())
+    }
+
+    /// Authenticates now, instead of waiting for the first request to authenticate lazily.
+    ///
+    /// If the session isn't already authenticated, this sends an
+    /// [`ApiStateRequest`](crate::data::ApiStateRequest), which goes through the usual
+    /// authentication flow of the [`Authentication`](crate::service::Authentication) middleware
+    /// (using the stored token, or requesting a new one if needed). Returns an error if the session
+    /// is still unauthenticated afterwards, e.g., if the user denied the token request or the
+    /// client was built without [`authentication`](ClientBuilder::authentication) info.
+    ///
+    /// # Example
+    ///
+    #[cfg_attr(feature = "tokio-tungstenite", doc = "```no_run")]
+    #[cfg_attr(not(feature = "tokio-tungstenite"), doc = "```ignore")]
+    /// # async fn run() -> Result<(), vtubestudio::error::BoxError> {
+    /// # use vtubestudio::Client;
+    /// let (mut client, _) = Client::builder()
+    ///     .authentication("Plugin name", "Developer name", None)
+    ///     .build_tungstenite();
+    ///
+    /// client.ensure_authenticated().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn ensure_authenticated(&mut self) -> Result<(), Error> {
+        if self.state.authenticated.load(Ordering::Relaxed) {
+            return Ok(());
+        }
+
+        let resp = send_request(&mut self.service, &ApiStateRequest {}).await?;
+
+        if resp.current_session_authenticated {
+            Ok(())
+        } else {
+            Err(ApiError {
+                error_id: ErrorId::REQUEST_REQUIRES_AUTHENTICATION,
+                message: "Session is not authenticated".to_owned(),
+            }
+            .into())
+        }
+    }
+
+    /// Injects parameter values, splitting them across multiple sequential
+    /// [`InjectParameterDataRequest`]s of at most [`InjectParameterDataRequest::CHUNK_SIZE`]
+    /// values each.
+    ///
+    /// # Errors
+    ///
+    /// Every chunk is sent, even if an earlier one fails. If any of them fail, an error with
+    /// [`ErrorKind::Batch`] is returned, with a [`BatchError`](crate::error::BatchError) source
+    /// listing the index and error of each failed chunk. The requests are not atomic: values in
+    /// the successful chunks will have been applied.
+    ///
+    /// # Example
+    ///
+    #[cfg_attr(feature = "tokio-tungstenite", doc = "```no_run")]
+    #[cfg_attr(not(feature = "tokio-tungstenite"), doc = "```ignore")]
+    /// # async fn run() -> Result<(), vtubestudio::error::BoxError> {
+    /// # use vtubestudio::Client;
+    /// use vtubestudio::data::{InjectParameterDataMode, ParameterValue};
+    ///
+    /// # let (mut client, _) = Client::builder().build_tungstenite();
+    /// let values = (0..500)
+    ///     .map(|i| ParameterValue {
+    ///         id: format!("MyCustomParam{}", i),
+    ///         value: 0.5,
+    ///         weight: None,
+    ///     })
+    ///     .collect::<Vec<_>>();
+    ///
+    /// client
+    ///     .inject_parameters(&values, Some(InjectParameterDataMode::Set.into()), true)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn inject_parameters(
+        &mut self,
+        values: &[ParameterValue],
+        mode: Option<EnumString<InjectParameterDataMode>>,
+        face_found: bool,
+    ) -> Result<(), Error> {
+        let mut total = 0;
+        let mut failed = Vec::new();
+
+        for (index, req) in
+            InjectParameterDataRequest::chunked(values, mode, face_found).enumerate()
+        {
+            total += 1;
+            if let Err(e) = send_request(&mut self.service, &req).await {
+                failed.push((index, e));
+            }
+        }
+
+        if failed.is_empty() {
+            Ok(())
+        } else {
+            Err(BatchError { total, failed }.into())
+        }
+    }
+
+    /// Requests a permission, and returns whether it was granted.
+    ///
+    /// If the permission hasn't been granted yet, this shows a pop-up in VTube Studio asking the
+    /// user to grant it, and waits for the user to respond.
+    ///
+    /// # Example
+    ///
+    #[cfg_attr(feature = "tokio-tungstenite", doc = "```no_run")]
+    #[cfg_attr(not(feature = "tokio-tungstenite"), doc = "```ignore")]
+    /// # async fn run() -> Result<(), vtubestudio::error::BoxError> {
+    /// # use vtubestudio::Client;
+    /// use vtubestudio::data::Permission;
+    ///
+    /// # let (mut client, _) = Client::builder().build_tungstenite();
+    /// while !client
+    ///     .request_permission(Permission::LoadCustomImagesAsItems)
+    ///     .await?
+    /// {
+    ///     println!("Please accept the permission pop-up in VTube Studio");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn request_permission<P>(&mut self, permission: P) -> Result<bool, Error>
+    where
+        P: Into<EnumString<Permission>>,
+    {
+        let permission = permission.into();
+
+        let resp = send_request(
+            &mut self.service,
+            &PermissionRequest {
+                requested_permission: Some(permission.clone()),
+            },
+        )
+        .await?;
+
+        Ok(is_granted(&resp, &permission))
+    }
+
+    /// Returns whether a permission has already been granted, without prompting the user.
+    pub async fn has_permission<P>(&mut self, permission: P) -> Result<bool, Error>
+    where
+        P: Into<EnumString<Permission>>,
+    {
+        let resp = send_request(
+            &mut self.service,
+            &PermissionRequest {
+                requested_permission: None,
+            },
+        )
+        .await?;
+
+        Ok(is_granted(&resp, &permission.into()))
+    }
+
+    /// Triggers a hotkey in the current model by name.
+    ///
+    /// This sends a [`HotkeysInCurrentModelRequest`] to find the ID of the first hotkey with the
+    /// given name, then sends a [`HotkeyTriggerRequest`] with that ID. If no hotkey has the given
+    /// name, this returns an error with [`ErrorKind::HotkeyNotFound`].
+    ///
+    /// # Example
+    ///
+    #[cfg_attr(feature = "tokio-tungstenite", doc = "```no_run")]
+    #[cfg_attr(not(feature = "tokio-tungstenite"), doc = "```ignore")]
+    /// # async fn run() -> Result<(), vtubestudio::error::BoxError> {
+    /// # use vtubestudio::Client;
+    /// use vtubestudio::ErrorKind;
+    ///
+    /// # let (mut client, _) = Client::builder().build_tungstenite();
+    /// match client.trigger_hotkey_by_name("Eyes Cry").await {
+    ///     Ok(resp) => println!("Triggered hotkey {}", resp.hotkey_id),
+    ///     Err(e) if e.has_kind(ErrorKind::HotkeyNotFound) => println!("No such hotkey"),
+    ///     Err(e) => return Err(e.into()),
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn trigger_hotkey_by_name(
+        &mut self,
+        name: &str,
+    ) -> Result<HotkeyTriggerResponse, Error> {
+        let hotkeys = send_request(
+            &mut self.service,
+            &HotkeysInCurrentModelRequest {
+                model_id: None,
+                live2d_item_file_name: None,
+            },
+        )
+        .await?;
+
+        let hotkey_id = hotkeys
+            .available_hotkeys
+            .into_iter()
+            .find(|hotkey| hotkey.name == name)
+            .map(|hotkey| hotkey.hotkey_id)
+            .ok_or_else(|| {
+                Error::new(ErrorKind::HotkeyNotFound)
+                    .with_source(format!("no hotkey named {:?} in current model", name))
+            })?;
+
+        send_request(
+            &mut self.service,
+            &HotkeyTriggerRequest {
+                hotkey_id,
+                item_instance_id: None,
+            },
+        )
+        .await
+    }
+
+    /// Activates or deactivates an expression, given its file name (e.g., `"cry.exp3.json"`).
+    ///
+    /// If the model has no expression with the given file name, this returns an error with
+    /// [`ErrorKind::NotFound`]. To restore the expression's state automatically afterwards, use
+    /// an [`ExpressionGuard`](crate::client::ExpressionGuard) instead.
+    ///
+    /// # Example
+    ///
+    #[cfg_attr(feature = "tokio-tungstenite", doc = "```no_run")]
+    #[cfg_attr(not(feature = "tokio-tungstenite"), doc = "```ignore")]
+    /// # async fn run() -> Result<(), vtubestudio::error::BoxError> {
+    /// # use vtubestudio::Client;
+    /// use vtubestudio::ErrorKind;
+    ///
+    /// # let (mut client, _) = Client::builder().build_tungstenite();
+    /// match client.set_expression("cry.exp3.json", true).await {
+    ///     Ok(_) => println!("Activated expression"),
+    ///     Err(e) if e.has_kind(ErrorKind::NotFound) => println!("No such expression"),
+    ///     Err(e) => return Err(e.into()),
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn set_expression(
+        &mut self,
+        expression_file: &str,
+        active: bool,
+    ) -> Result<ExpressionActivationResponse, Error> {
+        send_request(
+            &mut self.service,
+            &ExpressionActivationRequest {
+                expression_file: expression_file.to_owned(),
+                active,
+            },
+        )
+        .await
+    }
+
+    /// Toggles an expression, given its file name (e.g., `"cry.exp3.json"`), returning whether
+    /// it is now active.
+    ///
+    /// This sends an [`ExpressionStateRequest`] to check whether the expression is currently
+    /// active (assuming it's inactive if VTube Studio doesn't return its state), then an
+    /// [`ExpressionActivationRequest`] to switch it to the opposite state. Errors are the same as
+    /// [`set_expression`](Self::set_expression).
+    ///
+    /// [`ExpressionStateRequest`]: crate::data::ExpressionStateRequest
+    pub async fn toggle_expression(&mut self, expression_file: &str) -> Result<bool, Error> {
+        let active = !is_expression_active(&mut self.service, expression_file).await?;
+        self.set_expression(expression_file, active).await?;
+        Ok(active)
+    }
+}
+
+impl<S> Client<S>
+where
+    S: Service<RequestEnvelope, Response = ResponseEnvelope> + Clone,
+    Error: From<S::Error>,
+{
+    /// Sends multiple requests of different types concurrently, given as a tuple of up to 8
+    /// requests.
+    ///
+    /// Each request is sent using a separate clone of this client, so they're all in flight at the
+    /// same time. The results are returned as a tuple in the same order as the requests, and a
+    /// failed request doesn't affect the others.
+    ///
+    /// # Example
+    ///
+    #[cfg_attr(feature = "tokio-tungstenite", doc = "```no_run")]
+    #[cfg_attr(not(feature = "tokio-tungstenite"), doc = "```ignore")]
+    /// # async fn run() -> Result<(), vtubestudio::error::BoxError> {
+    /// # use vtubestudio::Client;
+    /// use vtubestudio::data::{CurrentModelRequest, ExpressionStateRequest, InputParameterListRequest};
+    ///
+    /// # let (client, _) = Client::builder().build_tungstenite();
+    /// let (model, params, expressions) = client
+    ///     .send_all((
+    ///         CurrentModelRequest {},
+    ///         InputParameterListRequest {},
+    ///         ExpressionStateRequest::default(),
+    ///     ))
+    ///     .await;
+    ///
+    /// println!("Current model: {}", model?.model_name);
+    /// println!("{} default parameters", params?.default_parameters.len());
+    /// println!("{} expressions", expressions?.expressions.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn send_all<T>(&self, requests: T) -> T::Output
+    where
+        T: RequestList,
+    {
+        let futures = requests.into_envelopes().into_iter().map(|req| {
+            let mut service = self.service.clone();
+            async move { Ok(service.ready().await?.call(req?).await?) }
+        });
+
+        T::parse_responses(future::join_all(futures).await)
+    }
+
+    /// Sends multiple requests of the same type concurrently.
+    ///
+    /// Like [`send_all`](Self::send_all), each request is sent using a separate clone of this
+    /// client. The results are returned in the same order as the requests, and a failed request
+    /// doesn't affect the others.
+    ///
+    /// # Example
+    ///
+    #[cfg_attr(feature = "tokio-tungstenite", doc = "```no_run")]
+    #[cfg_attr(not(feature = "tokio-tungstenite"), doc = "```ignore")]
+    /// # async fn run() -> Result<(), vtubestudio::error::BoxError> {
+    /// # use vtubestudio::Client;
+    /// use vtubestudio::data::ParameterValueRequest;
+    ///
+    /// # let (client, _) = Client::builder().build_tungstenite();
+    /// let requests = ["FaceAngleX", "FaceAngleY"].map(|name| ParameterValueRequest {
+    ///     name: name.to_owned(),
+    /// });
+    ///
+    /// for result in client.send_many(requests).await {
+    ///     let param = result?;
+    ///     println!("{} = {}", param.0.name, param.0.value);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn send_many<Req, I>(&self, requests: I) -> Vec<Result<Req::Response, Error>>
+    where
+        Req: Request,
+        I: IntoIterator<Item = Req>,
+    {
+        self.send_concurrently(requests.into_iter().map(Ok)).await
+    }
+
+    /// Creates multiple custom parameters concurrently.
+    ///
+    /// This is a shorthand for [`send_many`](Self::send_many) with
+    /// [`ParameterCreationRequest`]s. The results are returned in the same order as the requests,
+    /// and a parameter that fails to be created doesn't affect the others. To delete the
+    /// parameters automatically, see [`ParameterSet`](crate::client::ParameterSet).
+    ///
+    /// # Example
+    ///
+    #[cfg_attr(feature = "tokio-tungstenite", doc = "```no_run")]
+    #[cfg_attr(not(feature = "tokio-tungstenite"), doc = "```ignore")]
+    /// # async fn run() -> Result<(), vtubestudio::error::BoxError> {
+    /// # use vtubestudio::Client;
+    /// use vtubestudio::data::ParameterCreationRequest;
+    ///
+    /// # let (client, _) = Client::builder().build_tungstenite();
+    /// let requests = ["MyParamX", "MyParamY"].map(|name| ParameterCreationRequest {
+    ///     parameter_name: name.to_owned(),
+    ///     explanation: None,
+    ///     min: -1.0,
+    ///     max: 1.0,
+    ///     default_value: 0.0,
+    /// });
+    ///
+    /// for result in client.create_parameters(requests).await {
+    ///     println!("Created {}", result?.parameter_name);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn create_parameters<I>(
+        &self,
+        requests: I,
+    ) -> Vec<Result<ParameterCreationResponse, Error>>
+    where
+        I: IntoIterator<Item = ParameterCreationRequest>,
+    {
+        self.send_many(requests).await
+    }
+
+    /// Deletes multiple custom parameters concurrently, by name.
+    ///
+    /// Like [`create_parameters`](Self::create_parameters), the results are returned in the same
+    /// order as the names, and a failed deletion doesn't affect the others.
+    pub async fn delete_parameters<I>(
+        &self,
+        names: I,
+    ) -> Vec<Result<ParameterDeletionResponse, Error>>
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        let requests = names.into_iter().map(|name| ParameterDeletionRequest {
+            parameter_name: name.into(),
+        });
+
+        self.send_many(requests).await
+    }
+
+    /// Subscribes to multiple events concurrently.
+    ///
+    /// The VTube Studio API doesn't support subscribing to multiple events in a single request, so
+    /// this sends one [`EventSubscriptionRequest`] per config, using a separate clone of this
+    /// client for each. The results are returned in the same order as the input configs, and a
+    /// failed subscription doesn't affect the others.
+    ///
+    /// # Example
+    ///
+    #[cfg_attr(feature = "tokio-tungstenite", doc = "```no_run")]
+    #[cfg_attr(not(feature = "tokio-tungstenite"), doc = "```ignore")]
+    /// # async fn run() -> Result<(), vtubestudio::error::BoxError> {
+    /// # use vtubestudio::Client;
+    /// use vtubestudio::data::ItemEventConfig;
+    ///
+    /// # let (client, _) = Client::builder().build_tungstenite();
+    /// let configs = ["a.png", "b.png"].map(|file_name| ItemEventConfig {
+    ///     item_instance_ids: Vec::new(),
+    ///     item_file_names: vec![file_name.to_owned()],
+    /// });
+    ///
+    /// for result in client.subscribe_many(&configs).await {
+    ///     result?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn subscribe_many<C>(
+        &self,
+        configs: &[C],
+    ) -> Vec<Result<EventSubscriptionResponse, Error>>
+    where
+        C: EventConfig,
+    {
+        let requests = configs
+            .iter()
+            .map(|config| Ok(EventSubscriptionRequest::subscribe(config)?));
+
+        self.send_concurrently(requests).await
+    }
+
+    /// Unsubscribes from multiple event types concurrently, given as a tuple.
+    ///
+    /// Like [`subscribe_many`](Self::subscribe_many), this sends one request per event type, and
+    /// returns the results in the same order as the tuple.
+    ///
+    /// # Example
+    ///
+    #[cfg_attr(feature = "tokio-tungstenite", doc = "```no_run")]
+    #[cfg_attr(not(feature = "tokio-tungstenite"), doc = "```ignore")]
+    /// # async fn run() -> Result<(), vtubestudio::error::BoxError> {
+    /// # use vtubestudio::Client;
+    /// use vtubestudio::data::{ItemEvent, ModelLoadedEvent, TestEvent};
+    ///
+    /// # let (client, _) = Client::builder().build_tungstenite();
+    /// client
+    ///     .unsubscribe_many::<(TestEvent, ItemEvent, ModelLoadedEvent)>()
+    ///     .await;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn unsubscribe_many<T>(&self) -> Vec<Result<EventSubscriptionResponse, Error>>
+    where
+        T: EventDataList,
+    {
+        let requests = EventSubscriptionRequest::unsubscribe_many::<T>()
+            .into_iter()
+            .map(Ok);
+
+        self.send_concurrently(requests).await
+    }
+
+    async fn send_concurrently<Req, I>(&self, requests: I) -> Vec<Result<Req::Response, Error>>
+    where
+        Req: Request,
+        I: IntoIterator<Item = Result<Req, Error>>,
+    {
+        let futures = requests.into_iter().map(|req| {
+            let mut client = self.clone();
+            async move { client.send(&req?).await }
+        });
+
+        future::join_all(futures).await
+    }
+}
+
+fn is_granted(resp: &PermissionResponse, permission: &EnumString<Permission>) -> bool {
+    resp.permissions
+        .iter()
+        .any(|status| &status.name == permission && status.granted)
+}
+
+/// A builder to configure a new [`Client`] with a set of recommended [`tower`] middleware.
+///
+/// * retrying requests on disconnect (using [`Reconnect`] and [`RetryPolicy`])
+/// * if [`authentication`](Self::authentication) is provided, automatically reauthenticate and
+///   retry when it encounters an auth error (using [`AuthenticationLayer`]).
+///
+/// # Example
+///
+#[cfg_attr(feature = "tokio-tungstenite", doc = "```no_run")]
+#[cfg_attr(not(feature = "tokio-tungstenite"), doc = "```ignore")]
+/// # async fn run() -> Result<(), vtubestudio::error::BoxError> {
+/// use vtubestudio::{Client, ClientEvent};
+///
+/// // An auth token from a previous successful authentication request
+/// let stored_token = Some("...".to_string());
+///
+/// let (mut client, mut events) = Client::builder()
+///     .authentication("Plugin name", "Developer name", None)
+///     .auth_token(stored_token)
+///     .build_tungstenite();
+///
+/// tokio::spawn(async move {
+///     while let Some(event) = events.next().await {
+///         match event {
+///             ClientEvent::NewAuthToken(token) =>
+///                println!("Got new token: {token}"),
+///             _ =>
+///                println!("Received event {:?}", event),
+///         }
+///     }
+/// });
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ClientBuilder {
+    url: String,
+    retry_on_disconnect: bool,
+    reconnect: ReconnectConfig,
+    request_buffer_size: usize,
+    event_buffer_size: usize,
+    event_overflow_policy: EventOverflowPolicy,
+    max_message_size: Option<usize>,
+    max_frame_size: Option<usize>,
+    keepalive_interval: Option<Duration>,
+    keepalive_timeout: Duration,
+    auth_token: Option<String>,
+    token_request: Option<AuthenticationTokenRequest>,
+    retry_predicate: Option<RetryPredicate>,
+    on_connect: Option<ConnectionCallback>,
+    on_disconnect: Option<ConnectionCallback>,
+    #[cfg(feature = "proxy")]
+    proxy: Option<crate::service::HttpProxy>,
+    #[cfg(feature = "proxy")]
+    proxy_credentials: Option<(String, String)>,
+    #[cfg(feature = "__tls")]
+    tls_connector: Option<crate::service::maker::TlsConnector>,
+}
+
+impl Default for ClientBuilder {
+    fn default() -> Self {
+        Self {
+            url: "ws://localhost:8001".to_string(),
+            retry_on_disconnect: true,
+            reconnect: ReconnectConfig::default(),
+            request_buffer_size: 128,
+            event_buffer_size: DEFAULT_EVENT_BUFFER_SIZE,
+            event_overflow_policy: EventOverflowPolicy::default(),
+            max_message_size: Some(64 << 20),
+            max_frame_size: Some(16 << 20),
+            keepalive_interval: None,
+            keepalive_timeout: Duration::from_secs(10),
+            auth_token: None,
+            token_request: None,
+            retry_predicate: None,
+            on_connect: None,
+            on_disconnect: None,
+            #[cfg(feature = "proxy")]
+            proxy: None,
+            #[cfg(feature = "proxy")]
+            proxy_credentials: None,
+            #[cfg(feature = "__tls")]
+            tls_connector: None,
+        }
+    }
+}
+
+/// A wrapper for an [`async_channel::Receiver`] that yields client events.
+#[derive(Debug)]
+pub struct ClientEventStream {
+    // Boxed, since the receiver is `!Unpin`
+    pub(crate) receiver: std::pin::Pin<Box<async_channel::Receiver<ClientEvent>>>,
+}
+
+impl ClientEventStream {
+    pub(crate) fn new(receiver: async_channel::Receiver<ClientEvent>) -> Self {
+        Self {
+            receiver: Box::pin(receiver),
+        }
+    }
+
+    /// Returns [`ClientEvent`]s.
+    ///
+    /// # Example
+    ///
+    #[cfg_attr(feature = "tokio-tungstenite", doc = "```no_run")]
+    #[cfg_attr(not(feature = "tokio-tungstenite"), doc = "```ignore")]
+    /// # async fn run() -> Result<(), vtubestudio::error::BoxError> {
+    /// use vtubestudio::{Client, ClientEvent};
+    /// use vtubestudio::data::EventData;
+    ///
+    /// let (mut client, mut events) = Client::builder()
+    ///     .authentication("Plugin name", "Developer name", None)
+    ///     .build_tungstenite();
+    ///
+    /// tokio::spawn(async move {
+    ///     while let Some(event) = events.next().await {
+    ///         match event {
+    ///             ClientEvent::NewAuthToken(token) =>
+    ///                println!("Got new token: {token}"),
+    ///             _ =>
+    ///                println!("Received event {:?}", event),
+    ///         }
+    ///     }
+    /// });
+    /// # Ok(())
+    /// # }
+    pub async fn next(&mut self) -> Option<ClientEvent> {
+        self.receiver.recv().await.ok()
+    }
+
+    /// Consume this receiver and return the underlying [`async_channel::Receiver`].
+    pub fn into_inner(self) -> async_channel::Receiver<ClientEvent> {
+        // Receivers share the same channel, so this is equivalent to unboxing it
+        async_channel::Receiver::clone(&self.receiver)
+    }
+}
+
+/// Events can also be consumed as a [`Stream`](futures_core::Stream), which doesn't depend on any
+/// particular async runtime.
+impl futures_core::Stream for ClientEventStream {
+    type Item = ClientEvent;
+
+    fn poll_next(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Self::Item>> {
+        futures_core::Stream::poll_next(self.receiver.as_mut(), cx)
+    }
+}
+
+impl ClientBuilder {
+    /// Creates new builder with default values.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    crate::cfg_feature! {
+        #![feature = "tokio-tungstenite"]
+        /// Consumes the builder and initializes a [`Client`] and [`ClientEventStream`] using
+        /// [`tokio_tungstenite`] as the underlying websocket transport library.
+        pub fn build_tungstenite(self) -> (Client, ClientEventStream)
+        {
+            use crate::service::maker::TungsteniteConnector;
+            let mut connector = TungsteniteConnector::new().with_config(self.websocket_config());
+            if let Some(interval) = self.keepalive_interval {
+                connector = connector.with_keepalive(interval, self.keepalive_timeout);
+            }
+            #[cfg(feature = "proxy")]
+            if let Some(mut proxy) = self.proxy.clone() {
+                if let Some((username, password)) = self.proxy_credentials.clone() {
+                    proxy = proxy.with_credentials(username, password);
+                }
+                connector = connector.with_proxy(proxy);
+            }
+            #[cfg(feature = "__tls")]
+            if let Some(tls) = self.tls_connector.clone() {
+                connector = connector.with_tls_connector(tls.0);
+            }
+            self.build_connector(connector)
+        }
+    }
+
+    crate::cfg_feature! {
+        #![feature = "async-tungstenite"]
+        /// Consumes the builder and initializes a [`Client`] and [`ClientEventStream`] using
+        /// [`async_tungstenite`] as the underlying websocket transport library.
+        ///
+        /// The `connect` function is given a `host:port` address, and should open a connection
+        /// using the async runtime of your choice (see
+        /// [`AsyncTungsteniteConnector`](crate::service::AsyncTungsteniteConnector)).
+        ///
+        /// The [`Client`]'s background tasks and timers run on the current [`tokio`] runtime if
+        /// there is one. Without a Tokio runtime, enable the `async-std` feature, which runs them
+        /// on `async-std` instead (this also works alongside other runtimes such as `smol`).
+        /// Otherwise, this panics outside of a Tokio runtime.
+        ///
+        /// # Example
+        ///
+        #[cfg_attr(feature = "async-std", doc = "```no_run")]
+        #[cfg_attr(not(feature = "async-std"), doc = "```ignore")]
+        /// # use vtubestudio::Client;
+        /// let (mut client, mut events) = Client::builder()
+        ///     .authentication("Plugin name", "Developer name", None)
+        ///     .build_async_tungstenite(|addr| async_std::net::TcpStream::connect(addr));
+        /// ```
+        pub fn build_async_tungstenite<F, Fut, S>(self, connect: F) -> (Client, ClientEventStream)
+        where
+            F: Fn(String) -> Fut + Clone + Send + 'static,
+            Fut: std::future::Future<Output = std::io::Result<S>> + Send + 'static,
+            S: futures_util::io::AsyncRead + futures_util::io::AsyncWrite + Unpin + Send + 'static,
+        {
+            use crate::service::maker::AsyncTungsteniteConnector;
+            let mut connector = AsyncTungsteniteConnector::new(connect).with_config(self.websocket_config());
+            if let Some(interval) = self.keepalive_interval {
+                connector = connector.with_keepalive(interval, self.keepalive_timeout);
+            }
+            self.build_connector(connector)
+        }
+    }
+
+    crate::cfg_feature! {
+        #![feature = "async-std"]
+        /// Consumes the builder and initializes a [`Client`] and [`ClientEventStream`] using
+        /// [`async_tungstenite`] with an [`async_std::net::TcpStream`] connection.
+        ///
+        /// This is equivalent to calling [`build_async_tungstenite`](Self::build_async_tungstenite)
+        /// with [`async_std::net::TcpStream::connect`]. The [`Client`]'s background tasks and
+        /// timers run on `async-std`, so this doesn't need a [`tokio`] runtime.
+        pub fn build_async_std(self) -> (Client, ClientEventStream) {
+            self.build_async_tungstenite(|addr| async move {
+                async_std::net::TcpStream::connect(addr).await
+            })
+        }
+    }
+
+    #[cfg(any(feature = "tokio-tungstenite", feature = "async-tungstenite"))]
+    fn websocket_config(&self) -> WebSocketConfig {
+        WebSocketConfig {
+            max_message_size: self.max_message_size,
+            max_frame_size: self.max_frame_size,
+            ..WebSocketConfig::default()
+        }
+    }
+
+    crate::cfg_feature! {
+        #![feature = "wasm"]
+        /// Consumes the builder and initializes a [`Client`] and [`ClientEventStream`] using the
+        /// browser's `WebSocket` API (via [`gloo_net`]) as the underlying websocket transport.
+        ///
+        /// This is intended for `wasm32-unknown-unknown` targets, and mirrors
+        /// [`build_tungstenite`](Self::build_tungstenite).
+        pub fn build_web(self) -> (Client, ClientEventStream)
+        {
+            use crate::service::maker::WasmConnector;
+            self.build_connector(WasmConnector)
+        }
+    }
+
+    /// If this is provided, whenever the underlying service encounters an authentication error, it
+    /// will try to obtain a new auth token and retry the request.
+    pub fn authentication<S1, S2, S3>(mut self, name: S1, developer: S2, icon: S3) -> Self
+    where
+        S1: Into<Cow<'static, str>>,
+        S2: Into<Cow<'static, str>>,
+        S3: Into<Option<Cow<'static, str>>>,
+    {
+        self.token_request = Some(AuthenticationTokenRequest {
+            plugin_name: name.into(),
+            plugin_developer: developer.into(),
+            plugin_icon: icon.into(),
+        });
+        self
+    }
+
+    /// Sets the websocket URL. The default value is `ws://localhost:8001`.
+    ///
+    /// The URL must start with `ws://` or `wss://` (e.g., when VTube Studio is behind a TLS
+    /// tunnel). This is checked by [`build_connector`](Self::build_connector) (and helpers that
+    /// use it, such as [`build_tungstenite`](Self::build_tungstenite)). If the URL is invalid, the
+    /// [`ClientEventStream`] yields a [`ClientEvent::Error`], and every request fails with
+    /// [`ErrorKind::InvalidUrl`] without attempting to connect.
+    ///
+    /// To check the URL up-front, use [`validate_url`](Self::validate_url).
+    pub fn url<S: Into<String>>(mut self, url: S) -> Self {
+        self.url = url.into();
+        self
+    }
+
+    /// Sets the websocket URL to `ws://{host}:{port}`.
+    ///
+    /// IPv6 addresses are wrapped in brackets if needed.
+    ///
+    /// ```
+    /// use vtubestudio::ClientBuilder;
+    ///
+    /// let builder = ClientBuilder::new().host_port("192.168.0.10", 8001);
+    /// assert_eq!(builder.get_url(), "ws://192.168.0.10:8001");
+    ///
+    /// let builder = ClientBuilder::new().host_port("::1", 8001);
+    /// assert_eq!(builder.get_url(), "ws://[::1]:8001");
+    /// ```
+    pub fn host_port<S: AsRef<str>>(self, host: S, port: u16) -> Self {
+        let host = host.as_ref();
+        let url = if host.contains(':') && !host.starts_with('[') {
+            format!("ws://[{}]:{}", host, port)
+        } else {
+            format!("ws://{}:{}", host, port)
+        };
+        self.url(url)
+    }
+
+    /// The websocket URL that the client will connect to.
+    pub fn get_url(&self) -> &str {
+        &self.url
+    }
+
+    /// Checks that the websocket URL has a `ws://` or `wss://` scheme and a host.
+    ///
+    /// This returns an error with [`ErrorKind::InvalidUrl`] otherwise.
+    ///
+    /// ```
+    /// use vtubestudio::{ClientBuilder, ErrorKind};
+    ///
+    /// assert!(ClientBuilder::new().url("wss://vts.example.com").validate_url().is_ok());
+    ///
+    /// let err = ClientBuilder::new().url("http://localhost:8001").validate_url().unwrap_err();
+    /// assert!(err.has_kind(ErrorKind::InvalidUrl));
+    /// ```
+    pub fn validate_url(&self) -> Result<(), Error> {
+        check_url(&self.url)
+            .map_err(|message| Error::new(ErrorKind::InvalidUrl).with_source(message))
+    }
+
+    /// Initial token to use for reauthentication (if [`authentication`](Self::authentication) is
+    /// provided). This should be the result of a previous successful authentication attempt.
+    pub fn auth_token(mut self, token: Option<String>) -> Self {
+        self.auth_token = token;
+        self
+    }
+
+    /// Retry requests on disconnect. The default value is `true`.
+    pub fn retry_on_disconnect(mut self, retry: bool) -> Self {
+        self.retry_on_disconnect = retry;
+        self
+    }
+
+    /// The maximum number of consecutive failed reconnection attempts, after which the client gives
+    /// up and emits [`ClientEvent::GaveUp`]. By default, there is no limit.
+    ///
+    /// Once the client has given up, requests fail with [`ErrorKind::ConnectionRefused`] without
+    /// trying to connect again. The initial connection attempt isn't counted, and the count is
+    /// reset after each successful connection.
+    ///
+    /// This only applies to clients created with [`build_connector`](Self::build_connector) (or
+    /// helpers that use it, such as [`build_tungstenite`](Self::build_tungstenite)).
+    pub fn max_reconnect_attempts(mut self, attempts: usize) -> Self {
+        self.reconnect.max_attempts = Some(attempts);
+        self
+    }
+
+    /// The delay before each reconnection attempt. The default value is zero.
+    ///
+    /// This only applies to clients created with [`build_connector`](Self::build_connector) (or
+    /// helpers that use it, such as [`build_tungstenite`](Self::build_tungstenite)).
+    pub fn reconnect_delay(mut self, delay: Duration) -> Self {
+        self.reconnect.delay = delay;
+        self
+    }
+
+    /// Decides whether to retry errors other than disconnects and auth errors (which are handled
+    /// by [`retry_on_disconnect`](Self::retry_on_disconnect) and
+    /// [`authentication`](Self::authentication)).
+    ///
+    /// The predicate should return `Some(delay)` to retry the request after `delay`, or `None` to
+    /// return the error immediately. See [`RetryPolicy::with_predicate`] for details.
+    ///
+    /// # Example
+    ///
+    #[cfg_attr(feature = "tokio-tungstenite", doc = "```no_run")]
+    #[cfg_attr(not(feature = "tokio-tungstenite"), doc = "```ignore")]
+    /// use std::time::Duration;
+    /// use vtubestudio::data::ErrorId;
+    /// use vtubestudio::Client;
+    ///
+    /// let (client, _) = Client::builder()
+    ///     .retry_predicate(|error| match error.to_api_error() {
+    ///         Some(e) if e.error_id == ErrorId::HOTKEY_COOLDOWN_NOT_OVER => {
+    ///             Some(Duration::from_secs(1))
+    ///         }
+    ///         _ => None,
+    ///     })
+    ///     .build_tungstenite();
+    /// ```
+    pub fn retry_predicate<F>(mut self, predicate: F) -> Self
+    where
+        F: Fn(&Error) -> Option<Duration> + Send + Sync + 'static,
+    {
+        self.retry_predicate = Some(RetryPredicate::new(predicate));
+        self
+    }
+
+    /// Sets an async callback that is called with a [`Client`] each time a connection is
+    /// established, e.g., to resubscribe to events or recreate custom parameters.
+    ///
+    /// Callbacks are run one at a time, in the order that connections are established and lost.
+    /// Errors (and panics) are logged, and don't affect other requests.
+    ///
+    /// This only applies to [`build_connector`](Self::build_connector) (and helpers that use it,
+    /// such as [`build_tungstenite`](Self::build_tungstenite)). Callbacks stop being called once all
+    /// [`Client`]s created by the builder are dropped.
+    ///
+    /// # Example
+    ///
+    #[cfg_attr(feature = "tokio-tungstenite", doc = "```no_run")]
+    #[cfg_attr(not(feature = "tokio-tungstenite"), doc = "```ignore")]
+    /// # use vtubestudio::Client;
+    /// use vtubestudio::data::{EventSubscriptionRequest, ModelLoadedEventConfig};
+    ///
+    /// let (client, _events) = Client::builder()
+    ///     .on_connect(|mut client| async move {
+    ///         let req = EventSubscriptionRequest::subscribe(&ModelLoadedEventConfig::default())?;
+    ///         client.send(&req).await?;
+    ///         Ok(())
+    ///     })
+    ///     .on_disconnect(|_client| async move {
+    ///         println!("Disconnected from VTube Studio");
+    ///         Ok(())
+    ///     })
+    ///     .build_tungstenite();
+    /// ```
+    pub fn on_connect<F, Fut>(mut self, callback: F) -> Self
+    where
+        F: Fn(Client) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<(), Error>> + Send + 'static,
+    {
+        self.on_connect = Some(ConnectionCallback::new(callback));
+        self
+    }
+
+    /// Sets an async callback that is called with a [`Client`] each time a connection is lost.
+    ///
+    /// See [`on_connect`](Self::on_connect) for details.
+    pub fn on_disconnect<F, Fut>(mut self, callback: F) -> Self
+    where
+        F: Fn(Client) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<(), Error>> + Send + 'static,
+    {
+        self.on_disconnect = Some(ConnectionCallback::new(callback));
+        self
+    }
+
+    /// The max number of outstanding requests/responses.
+    ///
+    /// The default value is `128`.
+    pub fn request_buffer_size(mut self, size: usize) -> Self {
+        self.request_buffer_size = size;
+        self
+    }
+
+    /// The max capacity of the [`ClientEventStream`] buffer.
+    ///
+    /// This represents the max number of unacknowledged new events before the client stops sending
+    /// them.
+    ///
+    /// The default value is `128`.
+    pub fn event_buffer_size(mut self, size: usize) -> Self {
+        self.event_buffer_size = size;
+        self
+    }
+
+    /// What to do with incoming events when the [`ClientEventStream`] buffer is full.
+    ///
+    /// The default value is [`EventOverflowPolicy::Block`]. This only applies to
+    /// [`build_connector`](Self::build_connector) (and helpers that use it, such as
+    /// [`build_tungstenite`](Self::build_tungstenite)).
+    ///
+    /// ```
+    /// use vtubestudio::client::EventOverflowPolicy;
+    /// use vtubestudio::ClientBuilder;
+    ///
+    /// // Skip stale `ModelMovedEvent`s if the consumer falls behind
+    /// let builder = ClientBuilder::new()
+    ///     .event_buffer_size(16)
+    ///     .event_overflow_policy(EventOverflowPolicy::DropOldest);
+    /// ```
+    pub fn event_overflow_policy(mut self, policy: EventOverflowPolicy) -> Self {
+        self.event_overflow_policy = policy;
+        self
+    }
+
+    /// The max size (in bytes) of an incoming websocket message, or `None` for no limit.
+    ///
+    /// Receiving a larger message (e.g., a large
+    /// [`ItemListResponse`](crate::data::ItemListResponse)) closes the connection, failing any
+    /// in-flight requests and emitting a [`ClientEvent::Error`] with
+    /// [`ErrorKind::MessageTooLarge`].
+    ///
+    /// This only applies to [`build_tungstenite`](Self::build_tungstenite) and
+    /// `build_async_tungstenite`. The default value is 64 MiB.
+    pub fn max_message_size(mut self, size: Option<usize>) -> Self {
+        self.max_message_size = size;
+        self
+    }
+
+    /// The max size (in bytes) of a single incoming websocket frame, or `None` for no limit.
+    ///
+    /// This only applies to [`build_tungstenite`](Self::build_tungstenite) and
+    /// `build_async_tungstenite`. The default value is 16 MiB.
+    pub fn max_frame_size(mut self, size: Option<usize>) -> Self {
+        self.max_frame_size = size;
+        self
+    }
+
+    /// Sends a websocket ping at this interval, to detect connections that have silently died.
+    /// By default, no pings are sent.
+    ///
+    /// If no pong is received within the [`keepalive_timeout`](Self::keepalive_timeout), the
+    /// connection is closed (emitting [`ClientEvent::Disconnected`]), and a new connection is
+    /// established on the next request.
+    ///
+    /// This only applies to [`build_tungstenite`](Self::build_tungstenite) and
+    /// `build_async_tungstenite`.
+    pub fn keepalive_interval(mut self, interval: Duration) -> Self {
+        self.keepalive_interval = Some(interval);
+        self
+    }
+
+    /// Sends a websocket ping at this interval, closing the connection if no pong is received
+    /// within the [`keepalive_timeout`](Self::keepalive_timeout).
+    ///
+    /// This is shorthand for [`keepalive_interval`](Self::keepalive_interval). Pending requests on
+    /// a connection closed this way fail with [`ErrorKind::ConnectionDropped`], which is retried
+    /// on a new connection (unless disabled with
+    /// [`retry_on_disconnect`](Self::retry_on_disconnect)).
+    pub fn keepalive(self, interval: Duration) -> Self {
+        self.keepalive_interval(interval)
+    }
+
+    /// How long to wait for a pong after sending a ping, before closing the connection. This only
+    /// applies if [`keepalive_interval`](Self::keepalive_interval) is set. The default value is
+    /// 10 seconds.
+    pub fn keepalive_timeout(mut self, timeout: Duration) -> Self {
+        self.keepalive_timeout = timeout;
+        self
+    }
+
+    crate::cfg_feature! {
+        #![feature = "proxy"]
+        /// Connects through an HTTP proxy (using a `CONNECT` request), such as
+        /// `http://proxy.example.com:3128`. See [`HttpProxy::new`](crate::service::HttpProxy::new)
+        /// for the accepted URL formats.
+        ///
+        /// This only applies to [`build_tungstenite`](Self::build_tungstenite), and only `ws://`
+        /// URLs are supported through the proxy.
+        pub fn http_proxy(mut self, url: &str) -> Self {
+            self.proxy = Some(crate::service::HttpProxy::new(url));
+            self
+        }
+
+        /// Connects through the HTTP proxy set in the `HTTPS_PROXY` environment variable (if any),
+        /// excluding hosts listed in the `NO_PROXY` environment variable. See
+        /// [`HttpProxy::from_env`](crate::service::HttpProxy::from_env).
+        ///
+        /// If `HTTPS_PROXY` isn't set, any proxy set via [`http_proxy`](Self::http_proxy) is kept.
+        pub fn proxy_from_env(mut self) -> Self {
+            if let Some(proxy) = crate::service::HttpProxy::from_env() {
+                self.proxy = Some(proxy);
+            }
+            self
+        }
+
+        /// Sets the username and password for `Basic` authentication with the HTTP proxy.
+        pub fn proxy_credentials<U, P>(mut self, username: U, password: P) -> Self
+        where
+            U: Into<String>,
+            P: Into<String>,
+        {
+            self.proxy_credentials = Some((username.into(), password.into()));
+            self
+        }
+    }
+
+    /// Uses a custom TLS connector for `wss://` URLs, such as when VTube Studio is behind a
+    /// reverse proxy with a self-signed certificate or a custom CA.
+    ///
+    /// This only applies to [`build_tungstenite`](Self::build_tungstenite), and requires one of
+    /// the `native-tls`, `rustls-tls-native-roots`, or `rustls-tls-webpki-roots` features.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use vtubestudio::Client;
+    ///
+    /// let root_cert = native_tls::Certificate::from_pem(include_bytes!("ca.pem"))?;
+    /// let tls = native_tls::TlsConnector::builder()
+    ///     .add_root_certificate(root_cert)
+    ///     .build()?;
+    ///
+    /// let (mut client, mut events) = Client::builder()
+    ///     .url("wss://vts.example.com")
+    ///     .tls_connector(tokio_tungstenite::Connector::NativeTls(tls))
+    ///     .build_tungstenite();
+    /// ```
+    #[cfg(feature = "__tls")]
+    #[cfg_attr(
+        docsrs,
+        doc(cfg(any(
+            feature = "native-tls",
+            feature = "rustls-tls-native-roots",
+            feature = "rustls-tls-webpki-roots"
+        )))
+    )]
+    pub fn tls_connector(mut self, connector: tokio_tungstenite::Connector) -> Self {
+        self.tls_connector = Some(crate::service::maker::TlsConnector(connector));
+        self
+    }
+
+    /// Consumes the builder and initializes a [`Client`] and [`ClientEventStream`] using a custom
+    /// [`Service`].
+    ///
+    /// Note the [`ClientEventStream`] will only yield [`ClientEvent::NewAuthToken`] events. To
+    /// receive all events, use [`ClientBuilder::build_connector`].
+    pub fn build_service<S>(self, service: S) -> (Client, ClientEventStream)
+    where
+        S: Service<RequestEnvelope, Response = ResponseEnvelope> + Send + 'static,
+        S::Error: Into<BoxError> + Send + Sync,
+        S::Future: Send,
+    {
+        let (event_tx, event_rx) = async_channel::bounded(self.event_buffer_size);
+        let client = self.build_service_internal(service, event_tx, false, Arc::default());
+        let event_receiver = ClientEventStream::new(event_rx);
+        (client, event_receiver)
+    }
+
+    fn build_service_internal<S>(
+        self,
+        service: S,
+        event_tx: async_channel::Sender<ClientEvent>,
+        send_disconnect: bool,
+        state: Arc<ClientState>,
+    ) -> Client
+    where
+        S: Service<RequestEnvelope, Response = ResponseEnvelope> + Send + 'static,
+        S::Error: Into<BoxError> + Send + Sync,
+        S::Future: Send,
+    {
+        if send_disconnect && event_tx.try_send(ClientEvent::Disconnected).is_err() {
+            tracing::warn!("Failed to send Disconnected event to EventStream on startup");
+        }
+
+        let (service, worker) =
+            Buffer::<S, RequestEnvelope>::pair(service, self.request_buffer_size);
+        crate::runtime::spawn(worker);
+
+        let policy = RetryPolicy::new()
+            .on_disconnect(self.retry_on_disconnect)
+            .on_auth_error(self.token_request.is_some())
+            .with_retry_predicate(self.retry_predicate);
+
+        let service = if let Some(token_req) = self.token_request {
+            let auth_layer = AuthenticationLayer::new(token_req)
+                .with_token(self.auth_token)
+                .with_authentication_status(Arc::clone(&state.authenticated))
+                .with_token_storage(state.auth_token.clone());
+
+            BoxCloneService::new(
+                ServiceBuilder::new()
+                    .layer(client_tracing_layer())
+                    .retry(policy)
+                    .and_then(|resp: ResponseWithToken| async move {
+                        if let Some(token) = resp.new_token {
+                            // Ignore send errors (the consumer probably isn't reading the stream)
+                            let _ = event_tx.send(ClientEvent::NewAuthToken(token)).await;
+                        }
+                        Ok(resp.response)
+                    })
+                    .layer(auth_layer)
+                    .layer(client_metrics_layer())
+                    .map_err(Error::from_boxed)
+                    .service(service),
+            )
+        } else {
+            BoxCloneService::new(
+                ServiceBuilder::new()
+                    .layer(client_tracing_layer())
+                    .retry(policy)
+                    .layer(client_metrics_layer())
+                    .map_err(Error::from_boxed)
+                    .service(service),
+            )
+        };
+
+        Client::new_with_state(service, state)
+    }
+
+    /// Consumes the builder and initializes a [`Client`] and [`ClientEventStream`] with a
+    /// connector.
+    ///
+    /// The input connector should be a [`MakeTransport`] that meets the requirements of
+    /// [`Reconnect`].
+    pub fn build_connector<M>(mut self, connector: M) -> (Client, ClientEventStream)
+    where
+        M: MakeTransport<String, RequestEnvelope, Item = ResponseEnvelope> + Send + Clone + 'static,
+        M::Future: Send + 'static,
+        M::Transport: Send + 'static,
+        M::MakeError: StdError + Send + Sync + 'static,
+        M::Error: Send,
+        BoxError: From<M::Error> + From<M::SinkError>,
+    {
+        let (event_tx, event_rx) = async_channel::bounded(self.event_buffer_size);
+
+        if let Err(message) = check_url(&self.url) {
+            tracing::error!(%message, "Invalid websocket URL");
+            let error =
+                |message: &str| Error::new(ErrorKind::InvalidUrl).with_source(message.to_owned());
+            let _ = event_tx.try_send(ClientEvent::Error(error(&message)));
+
+            // Fail every request instead of repeatedly trying to connect
+            let service = tower::service_fn(move |_: RequestEnvelope| {
+                future::ready(Err::<ResponseEnvelope, _>(error(&message)))
+            });
+            let client = self.build_service_internal(service, event_tx, false, Arc::default());
+            return (client, ClientEventStream::new(event_rx));
+        }
+
+        let event_tx_cloned = event_tx.clone();
+
+        let log_err = |result: Result<(), async_channel::SendError<ClientEvent>>| {
+            if let Err(async_channel::SendError(event)) = result {
+                tracing::warn!(
+                    ?event,
+                    "Failed to send event to EventStream because buffer is full"
+                );
+            }
+        };
+
+        let state = Arc::new(ClientState::new(self.event_buffer_size));
+        let overflow_policy = self.event_overflow_policy;
+        let event_buffer_size = self.event_buffer_size;
+        let connector = CloseableConnector::new(connector, state.close.subscribe());
+
+        let callbacks = match (self.on_connect.take(), self.on_disconnect.take()) {
+            (None, None) => None,
+            (on_connect, on_disconnect) => Some(Arc::new(ConnectionCallbacks::new(
+                on_connect,
+                on_disconnect,
+            ))),
+        };
+
+        let service = MakeApiService::<_, String>::new(connector, self.request_buffer_size)
+            .with_error_handler({
+                let event_tx = event_tx.clone();
+                move |error| {
+                    // Disconnects are already reported as `ClientEvent::Disconnected`
+                    if !error.has_kind(ErrorKind::ConnectionDropped) {
+                        let result = event_tx.try_send(ClientEvent::Error(error));
+                        log_err(result.map_err(|e| async_channel::SendError(e.into_inner())));
+                    }
+                }
+            })
+            .map_response({
+                let state = Arc::clone(&state);
+                let callbacks = callbacks.clone();
+                move |(service, events)| {
+                    let event_tx = event_tx.clone();
+                    let state = Arc::clone(&state);
+                    let callbacks = callbacks.clone();
+                    state.reconnect_attempts.store(0, Ordering::Relaxed);
+                    state.connections.send_modify(|count| *count += 1);
+
+                    crate::runtime::spawn(async move {
+                        log_err(event_tx.send(ClientEvent::Connected).await);
+                        if let Some(callbacks) = &callbacks {
+                            callbacks.notify(ConnectionEvent::Connected, &state);
+                        }
+                        let events = events.map(|result| {
+                            if let Ok(event) = &result {
+                                state.event_channels.dispatch(event);
+                            }
+                            result.map_or_else(ClientEvent::Error, ClientEvent::Api)
+                        });
+                        forward_events(events, &event_tx, overflow_policy, event_buffer_size).await;
+                        // The next connection will need to reauthenticate
+                        state.authenticated.store(false, Ordering::Relaxed);
+                        state.connections.send_modify(|count| *count -= 1);
+                        log_err(event_tx.send(ClientEvent::Disconnected).await);
+                        if let Some(callbacks) = &callbacks {
+                            callbacks.notify(ConnectionEvent::Disconnected, &state);
+                        }
+                    });
+
+                    service
+                }
+            });
+
+        let service = ReconnectGate::new(
+            service,
+            self.reconnect,
+            Arc::clone(&state),
+            event_tx_cloned.clone(),
+        );
+
+        let mut client = self.build_reconnecting_service_internal(service, event_tx_cloned, state);
+        if let Some(callbacks) = callbacks {
+            client.service = callbacks.track(client.service);
+        }
+
+        let event_receiver = ClientEventStream::new(event_rx);
+        (client, event_receiver)
+    }
+
+    /// Consumes the builder and initializes a [`Client`] and [`ClientEventStream`] with a
+    /// reconnecting service.
+    ///
+    /// The input service should be a [`Service`] that returns [`Service`]s (such as
+    /// [`MakeApiService`]), which is used to connect and reconnect with [`Reconnect`].
+    ///
+    /// Note the [`ClientEventStream`] will only yield [`ClientEvent::NewAuthToken`] events. To
+    /// receive all events, use [`ClientBuilder::build_connector`].
+    pub fn build_reconnecting_service<S>(self, maker: S) -> (Client, ClientEventStream)
+    where
+        S: Service<String> + Send + 'static,
+        S::Error: StdError + Send + Sync,
+        S::Future: Send + Unpin,
+        S::Response: Service<RequestEnvelope, Response = ResponseEnvelope> + Send + 'static,
+        <S::Response as Service<RequestEnvelope>>::Error: StdError + Send + Sync,
+        <S::Response as Service<RequestEnvelope>>::Future: Send,
+    {
+        let service = Reconnect::new(maker, self.url.clone());
+
+        self.build_service(service)
+    }
+
+    fn build_reconnecting_service_internal<S>(
+        self,
+        maker: S,
+        event_tx: async_channel::Sender<ClientEvent>,
+        state: Arc<ClientState>,
+    ) -> Client
+    where
+        S: Service<String> + Send + 'static,
+        S::Error: StdError + Send + Sync,
+        S::Future: Send + Unpin,
+        S::Response: Service<RequestEnvelope, Response = ResponseEnvelope> + Send + 'static,
+        <S::Response as Service<RequestEnvelope>>::Error: StdError + Send + Sync,
+        <S::Response as Service<RequestEnvelope>>::Future: Send,
+    {
+        let service = Reconnect::new(maker, self.url.clone());
+
+        self.build_service_internal(service, event_tx, true, state)
+    }
+}
+
+// Returns a description of the problem if the URL isn't a `ws://` or `wss://` URL with a host
+fn check_url(url: &str) -> Result<(), String> {
+    let url = url.trim();
+    let rest = match url.split_once("://") {
+        Some((scheme, rest))
+            if scheme.eq_ignore_ascii_case("ws") || scheme.eq_ignore_ascii_case("wss") =>
+        {
+            rest
+        }
+        Some((scheme, _)) => {
+            return Err(format!(
+                "unsupported scheme {:?} in {:?} (expected \"ws\" or \"wss\")",
+                scheme, url
+            ))
+        }
+        None => {
+            return Err(format!(
+                "missing scheme in {:?} (expected \"ws://\" or \"wss://\")",
+                url
+            ))
+        }
+    };
+
+    let authority = rest.split(&['/', '?', '#'][..]).next().unwrap_or_default();
+    if authority.is_empty() {
+        return Err(format!("missing host in {:?}", url));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::{
+        ApiError, ErrorId, ItemEvent, ItemEventConfig, ModelLoadedEvent, Response, TestEvent,
+    };
+    use std::sync::Mutex;
+
+    #[tokio::test]
+    async fn send_with_meta() -> Result<(), Error> {
+        use crate::data::{StatisticsRequest, StatisticsResponse};
+
+        let service = tower::service_fn(|req: RequestEnvelope| async move {
+            let mut resp = ResponseEnvelope::new(&StatisticsResponse {
+                uptime: 1000,
+                ..Default::default()
+            })?
+            .with_id(req.request_id.unwrap_or_else(|| "id".into()));
+            resp.api_version = "1.1".into();
+            resp.timestamp = 1_625_405_710_728;
+            Ok::<_, Error>(resp)
+        });
+
+        let (mut client, _) = Client::builder().build_service(service);
+        let (resp, meta) = client.send_with_meta(&StatisticsRequest {}).await?;
+
+        assert_eq!(resp.uptime, 1000);
+        assert_eq!(meta.api_name, "VTubeStudioPublicAPI");
+        assert_eq!(meta.api_version, "1.1");
+        assert_eq!(meta.timestamp, 1_625_405_710_728);
+        assert_eq!(meta.request_id.as_str(), "id");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn send_raw() -> Result<(), Error> {
+        use crate::data::{OpaqueValue, ResponseData};
+        use serde_json::json;
+
+        // Echo the request back as the response
+        let service = tower::service_fn(|req: RequestEnvelope| async move {
+            let message_type = req.message_type.to_string().replace("Request", "Response");
+            Ok::<_, Error>(ResponseEnvelope {
+                data: Ok(ResponseData {
+                    message_type: EnumString::new_from_str(message_type),
+                    data: req.data,
+                }),
+                ..ResponseEnvelope::default()
+            })
+        });
+
+        let (mut client, _) = Client::builder().build_service(service);
+        let resp = client
+            .send_raw("SomeNewRequest", json!({ "someField": true }))
+            .await?;
+
+        let data = resp.data?;
+        assert_eq!(
+            data.message_type,
+            EnumString::new_from_str("SomeNewResponse")
+        );
+        assert_eq!(data.data, OpaqueValue::new(&json!({ "someField": true }))?);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn send_all_and_send_many() -> Result<(), Error> {
+        use crate::data::{
+            CurrentModelRequest, Parameter, ParameterValueRequest, ParameterValueResponse,
+            StatisticsRequest, StatisticsResponse,
+        };
+        use tokio::sync::Barrier;
+
+        // Each request waits until 3 requests are in flight, so this would never finish if the
+        // requests were sent one at a time
+        let barrier = Arc::new(Barrier::new(3));
+        let events = Arc::new(Mutex::new(Vec::new()));
+
+        let service = tower::service_fn({
+            let events = Arc::clone(&events);
+            move |req: RequestEnvelope| {
+                let barrier = Arc::clone(&barrier);
+                let events = Arc::clone(&events);
+                async move {
+                    let message_type = req.message_type.as_str().to_owned();
+                    events
+                        .lock()
+                        .unwrap()
+                        .push(format!("start {}", message_type));
+                    barrier.wait().await;
+                    events.lock().unwrap().push(format!("end {}", message_type));
+
+                    let resp = if req.message_type == StatisticsRequest::MESSAGE_TYPE {
+                        ResponseEnvelope::new(&StatisticsResponse::default())?
+                    } else if req.message_type == CurrentModelRequest::MESSAGE_TYPE {
+                        ResponseEnvelope {
+                            data: Err(ApiError {
+                                error_id: ErrorId::INTERNAL_SERVER_ERROR,
+                                message: String::new(),
+                            }),
+                            ..Default::default()
+                        }
+                    } else {
+                        let req = req.data.deserialize::<ParameterValueRequest>()?;
+                        ResponseEnvelope::new(&ParameterValueResponse(Parameter {
+                            name: req.name,
+                            ..Default::default()
+                        }))?
+                    };
+
+                    Ok::<_, Error>(resp.with_id(req.request_id.unwrap_or_default()))
+                }
+            }
+        });
+
+        let (client, _) = Client::builder().build_service(service);
+        let timeout = Duration::from_secs(5);
+
+        let (stats, model, param) = tokio::time::timeout(
+            timeout,
+            client.send_all((
+                StatisticsRequest {},
+                CurrentModelRequest {},
+                ParameterValueRequest { name: "A".into() },
+            )),
+        )
+        .await
+        .expect("requests were not sent concurrently");
+
+        assert!(stats.is_ok());
+        assert!(model.unwrap_err().is_api_error());
+        assert_eq!(param?.0.name, "A");
+
+        let events = std::mem::take(&mut *events.lock().unwrap());
+        assert!(events[..3].iter().all(|event| event.starts_with("start ")));
+        assert!(events[3..].iter().all(|event| event.starts_with("end ")));
+
+        let names = ["B", "C", "D"];
+        let results = tokio::time::timeout(
+            timeout,
+            client.send_many(names.iter().map(|name| ParameterValueRequest {
+                name: name.to_string(),
+            })),
+        )
+        .await
+        .expect("requests were not sent concurrently");
+
+        let results = results
+            .into_iter()
+            .map(|result| result.map(|resp| resp.0.name))
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(results, names);
+
+        Ok(())
+    }
+
+    #[cfg(feature = "testing")]
+    #[tokio::test]
+    async fn inject_parameters() -> Result<(), BoxError> {
+        use crate::data::InjectParameterDataResponse;
+        use crate::testing::{MockServer, RequestMatcher};
+
+        // Fail any chunk containing the parameter "Fail"
+        let server = MockServer::new();
+        server.respond_with(
+            RequestMatcher::message_type::<InjectParameterDataRequest>(),
+            |req| {
+                let req: InjectParameterDataRequest = req.data.deserialize().unwrap();
+                if req.parameter_values.iter().any(|value| value.id == "Fail") {
+                    ResponseEnvelope {
+                        data: Err(ApiError {
+                            error_id: ErrorId::INJECT_DATA_PARAM_NAME_NOT_FOUND,
+                            message: "Parameter not found".into(),
+                        }),
+                        ..Default::default()
+                    }
+                } else {
+                    ResponseEnvelope::new(&InjectParameterDataResponse {}).unwrap()
+                }
+            },
+        );
+        let (mut client, _events) = Client::builder().build_connector(server.connector());
+
+        let sent_requests = |server: &MockServer| {
+            server
+                .received()
+                .into_iter()
+                .map(|req| req.data.deserialize::<InjectParameterDataRequest>())
+                .collect::<Result<Vec<_>, _>>()
+        };
+
+        let chunk_size = InjectParameterDataRequest::CHUNK_SIZE;
+        let mut values = (0..chunk_size * 2 + 1)
+            .map(|i| ParameterValue {
+                id: format!("Param{}", i),
+                value: 0.5,
+                weight: None,
+            })
+            .collect::<Vec<_>>();
+
+        let mode = Some(InjectParameterDataMode::Add.into());
+        client
+            .inject_parameters(&values, mode.clone(), true)
+            .await?;
+
+        let requests = sent_requests(&server)?;
+        let sizes = requests
+            .iter()
+            .map(|req| req.parameter_values.len())
+            .collect::<Vec<_>>();
+        assert_eq!(sizes, [chunk_size, chunk_size, 1]);
+        assert!(requests
+            .iter()
+            .all(|req| req.mode == mode && req.face_found));
+        let sent_values = requests
+            .into_iter()
+            .flat_map(|req| req.parameter_values)
+            .collect::<Vec<_>>();
+        assert_eq!(sent_values, values);
+
+        // Sends the remaining chunks after a failure, and collects the errors
+        values[chunk_size].id = "Fail".into();
+        let err = client
+            .inject_parameters(&values, mode, true)
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::Batch);
+        assert!(err.has_kind(ErrorKind::NotFound), "{:?}", err);
+        assert_eq!(sent_requests(&server)?.len(), 6);
+
+        let batch = err.find_source::<BatchError>().unwrap();
+        assert_eq!(batch.total(), 3);
+        let failed = batch
+            .failed()
+            .iter()
+            .map(|(index, e)| (*index, e.is_not_found()))
+            .collect::<Vec<_>>();
+        assert_eq!(failed, [(1, true)]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn subscribe_and_unsubscribe_many() {
+        let received = Arc::new(Mutex::new(Vec::new()));
+
+        // Fails any subscription involving `bad.png`, and responds successfully to the rest
+        let service = tower::service_fn({
+            let received = Arc::clone(&received);
+            move |req: RequestEnvelope| {
+                let received = Arc::clone(&received);
+                async move {
+                    let data = req.data.deserialize::<EventSubscriptionRequest>()?;
+                    let is_bad = data.config.as_ref().is_some_and(|config| {
+                        let value = config.deserialize::<serde_json::Value>().unwrap();
+                        value.to_string().contains("bad.png")
+                    });
+                    received.lock().unwrap().push(data);
+
+                    let resp = if is_bad {
+                        ResponseEnvelope {
+                            data: Err(ApiError {
+                                error_id: ErrorId::EVENT_SUBSCRIPTION_REQUEST_EVENT_TYPE_UNKNOWN,
+                                message: String::new(),
+                            }),
+                            ..Default::default()
+                        }
+                    } else {
+                        ResponseEnvelope::new(&EventSubscriptionResponse::default())?
+                    };
+
+                    Ok::<_, Error>(resp)
+                }
+            }
+        });
+
+        let client = Client::new_from_service(service);
+
+        let configs = ["a.png", "bad.png", "c.png"].map(|file_name| ItemEventConfig {
+            item_instance_ids: Vec::new(),
+            item_file_names: vec![file_name.to_owned()],
+        });
+
+        let results = client.subscribe_many(&configs).await;
+        assert_eq!(results.len(), 3);
+        assert!(results[0].is_ok());
+        assert!(results[1].as_ref().unwrap_err().is_api_error());
+        assert!(results[2].is_ok());
+
+        let subscribed = received
+            .lock()
+            .unwrap()
+            .drain(..)
+            .filter(|req| req.subscribe && req.event_name == Some(ItemEvent::MESSAGE_TYPE))
+            .count();
+        assert_eq!(subscribed, 3);
+
+        let results = client
+            .unsubscribe_many::<(TestEvent, ItemEvent, ModelLoadedEvent)>()
+            .await;
+        assert!(results.iter().all(Result::is_ok));
+
+        let mut unsubscribed = received
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|req| {
+                assert!(!req.subscribe);
+                req.event_name.clone().unwrap()
+            })
+            .collect::<Vec<_>>();
+        unsubscribed.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+        assert_eq!(
+            unsubscribed,
+            vec![
+                ItemEvent::MESSAGE_TYPE,
+                ModelLoadedEvent::MESSAGE_TYPE,
+                TestEvent::MESSAGE_TYPE
+            ]
+        );
+    }
+
+    #[test]
+    fn check_urls() {
+        for url in [
+            "ws://localhost:8001",
+            "WSS://vts.example.com/path",
+            " ws://[::1]:8001 ",
+        ] {
+            assert_eq!(check_url(url), Ok(()), "{}", url);
+        }
+
+        for url in [
+            "http://localhost:8001",
+            "localhost:8001",
+            "ws://",
+            "ws:///path",
+            "",
+        ] {
+            assert!(check_url(url).is_err(), "{}", url);
+        }
+    }
+
+    #[cfg(feature = "testing")]
+    #[tokio::test]
+    async fn invalid_url() {
+        use crate::data::StatisticsRequest;
+        use crate::testing::MockServer;
+
+        let server = MockServer::new();
+        let (mut client, mut events) = Client::builder()
+            .url("http://localhost:8001")
+            .build_connector(server.connector());
+
+        match events.next().await {
+            Some(ClientEvent::Error(e)) => assert!(e.has_kind(ErrorKind::InvalidUrl)),
+            other => panic!("unexpected event {:?}", other),
+        }
+
+        let err = client.send(&StatisticsRequest {}).await.unwrap_err();
+        assert!(err.has_kind(ErrorKind::InvalidUrl), "{:?}", err);
+        assert_eq!(server.connection_count(), 0);
+    }
+
+    #[cfg(feature = "testing")]
+    #[tokio::test]
+    async fn connect() -> Result<(), BoxError> {
+        use crate::data::{ApiStateRequest, ApiStateResponse};
+        use crate::testing::{FakeVtsServer, RequestMatcher};
+
+        let server = FakeVtsServer::start(vec![(
+            RequestMatcher::message_type::<ApiStateRequest>(),
+            ResponseEnvelope::new(&ApiStateResponse::default())?,
+        )])
+        .await?;
+
+        let (mut client, _events) = Client::builder().url(server.url()).build_tungstenite();
+        assert!(!client.connection_state().is_connected());
+
+        client.connect().await?;
+        assert!(client.connection_state().is_connected());
+        server.assert_all_matched();
+
+        // Already connected, so this doesn't send another request
+        client.connect().await?;
+
+        // Nothing is listening on this port after the listener is dropped
+        let port = std::net::TcpListener::bind("127.0.0.1:0")?
+            .local_addr()?
+            .port();
+        let (mut client, _events) = Client::builder()
+            .host_port("127.0.0.1", port)
+            .build_tungstenite();
+        assert!(client.connect().await.is_err());
+
+        Ok(())
+    }
+
+    #[cfg(all(feature = "testing", feature = "__tls"))]
+    #[tokio::test]
+    async fn tls_connector() -> Result<(), BoxError> {
+        use crate::data::{StatisticsRequest, StatisticsResponse};
+        use crate::testing::{FakeVtsServer, RequestMatcher};
+
+        let server = FakeVtsServer::start(vec![(
+            RequestMatcher::message_type::<StatisticsRequest>(),
+            ResponseEnvelope::new(&StatisticsResponse {
+                uptime: 1000,
+                ..Default::default()
+            })?,
+        )])
+        .await?;
+
+        // The connector is passed through to the handshake (and isn't used for `ws://` URLs)
+        let (mut client, _events) = Client::builder()
+            .url(server.url())
+            .tls_connector(tokio_tungstenite::Connector::Plain)
+            .build_tungstenite();
+
+        assert_eq!(client.send(&StatisticsRequest {}).await?.uptime, 1000);
+        server.assert_all_matched();
+
+        Ok(())
+    }
+
+    #[cfg(all(feature = "testing", feature = "async-std"))]
+    #[tokio::test]
+    async fn async_std() -> Result<(), BoxError> {
+        use crate::data::{StatisticsRequest, StatisticsResponse};
+        use crate::testing::{FakeVtsServer, RequestMatcher};
+
+        let server = FakeVtsServer::start(vec![(
+            RequestMatcher::message_type::<StatisticsRequest>(),
+            ResponseEnvelope::new(&StatisticsResponse {
+                uptime: 1000,
+                ..Default::default()
+            })?,
+        )])
+        .await?;
+
+        let (mut client, mut events) = Client::builder().url(server.url()).build_async_std();
+
+        assert_eq!(client.send(&StatisticsRequest {}).await?.uptime, 1000);
+        server.assert_all_matched();
+
+        // The event stream can be polled as a `Stream`
+        assert!(matches!(
+            futures_util::StreamExt::next(&mut events).await,
+            Some(ClientEvent::Disconnected)
+        ));
+
+        Ok(())
+    }
+
+    #[cfg(feature = "async-std")]
+    #[async_std::test]
+    async fn async_std_without_tokio() -> Result<(), BoxError> {
+        use crate::data::{StatisticsRequest, StatisticsResponse};
+        use async_tungstenite::tungstenite::Message;
+        use futures_util::SinkExt;
+
+        assert!(tokio::runtime::Handle::try_current().is_err());
+
+        let listener = async_std::net::TcpListener::bind("127.0.0.1:0").await?;
+        let url = format!("ws://{}", listener.local_addr()?);
+
+        // Responds to one request per connection with an increasing uptime, then disconnects
+        async_std::task::spawn(async move {
+            for uptime in 1.. {
+                let (stream, _) = listener.accept().await.unwrap();
+                let mut ws = async_tungstenite::accept_async(stream).await.unwrap();
+
+                while let Some(Ok(msg)) = ws.next().await {
+                    if let Message::Text(text) = msg {
+                        let req: RequestEnvelope = serde_json::from_str(&text).unwrap();
+                        let resp = ResponseEnvelope::new(&StatisticsResponse {
+                            uptime,
+                            ..Default::default()
+                        })
+                        .unwrap()
+                        .with_id(req.request_id.unwrap());
+
+                        let text = serde_json::to_string(&resp).unwrap();
+                        ws.send(Message::Text(text)).await.unwrap();
+                        break;
+                    }
+                }
+
+                let _ = ws.close(None).await;
+            }
+        });
+
+        // Background tasks and timers (keepalive, reconnect delay) all run on async-std
+        let (mut client, mut events) = Client::builder()
+            .url(url)
+            .keepalive_interval(Duration::from_millis(10))
+            .reconnect_delay(Duration::from_millis(10))
+            .build_async_std();
+
+        assert_eq!(client.send(&StatisticsRequest {}).await?.uptime, 1);
+        assert_eq!(client.send(&StatisticsRequest {}).await?.uptime, 2);
+
+        let reconnected = async_std::future::timeout(Duration::from_secs(5), async {
+            while let Some(event) = events.next().await {
+                if let ClientEvent::Reconnecting { attempt: 1 } = event {
+                    return true;
+                }
+            }
+            false
+        });
+        assert!(reconnected.await?);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,169 @@
+use crate::client::ClientEvent;
+
+use async_channel::{SendError, Sender, TrySendError};
+use futures_core::Stream;
+use futures_util::future::{self, Either};
+use futures_util::StreamExt;
+use std::collections::VecDeque;
+
+/// What to do with incoming API events when the [`ClientEventStream`](crate::ClientEventStream)
+/// buffer is full. Configured with
+/// [`ClientBuilder::event_overflow_policy`](crate::ClientBuilder::event_overflow_policy).
+///
+/// With the default [`Block`](Self::Block) policy, a consumer that can't keep up with
+/// high-frequency events (such as [`ModelOutlineEvent`](crate::data::ModelOutlineEvent) or
+/// [`ModelMovedEvent`](crate::data::ModelMovedEvent)) stops the client from reading from the
+/// websocket, which also delays responses to requests. The other policies drop events instead.
+///
+/// This only applies to [`ClientEvent::Api`] and [`ClientEvent::Error`] events received from the
+/// connection. Connection lifecycle events (such as [`ClientEvent::Disconnected`]) are never
+/// dropped.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum EventOverflowPolicy {
+    /// Wait until there is space in the buffer, which stops reading from the connection in the
+    /// meantime.
+    #[default]
+    Block,
+    /// Drop the oldest events that haven't been added to the buffer yet, so the consumer always
+    /// receives the most recent ones.
+    ///
+    /// Up to [`event_buffer_size`](crate::ClientBuilder::event_buffer_size) additional events are
+    /// held in a queue while the buffer is full.
+    DropOldest,
+    /// Drop incoming events until there is space in the buffer.
+    DropNewest,
+}
+
+/// Forwards events from a single connection to the [`ClientEventStream`] sender, according to
+/// the overflow policy.
+///
+/// [`ClientEventStream`]: crate::ClientEventStream
+pub(crate) async fn forward_events<S>(
+    mut events: S,
+    event_tx: &Sender<ClientEvent>,
+    policy: EventOverflowPolicy,
+    capacity: usize,
+) where
+    S: Stream<Item = ClientEvent> + Unpin,
+{
+    match policy {
+        EventOverflowPolicy::Block => {
+            while let Some(event) = events.next().await {
+                if let Err(SendError(event)) = event_tx.send(event).await {
+                    tracing::warn!(
+                        ?event,
+                        "Failed to send event to EventStream because buffer is full"
+                    );
+                }
+            }
+        }
+
+        EventOverflowPolicy::DropNewest => {
+            while let Some(event) = events.next().await {
+                if let Err(TrySendError::Full(event)) = event_tx.try_send(event) {
+                    tracing::debug!(?event, "Dropping event because buffer is full");
+                }
+            }
+        }
+
+        EventOverflowPolicy::DropOldest => {
+            let mut queue = VecDeque::new();
+            // The oldest queued event, while it waits for space in the buffer. Dropping this
+            // future drops the event without sending it.
+            let mut sending = None;
+
+            loop {
+                if sending.is_none() {
+                    sending = queue
+                        .pop_front()
+                        .map(|event| Box::pin(event_tx.send(event)));
+                }
+
+                let event = match &mut sending {
+                    None => events.next().await,
+                    // Prefer sending queued events, but keep reading while the buffer is full
+                    Some(send) => match future::select(send.as_mut(), events.next()).await {
+                        Either::Left((result, _)) => {
+                            sending = None;
+                            if result.is_err() {
+                                // The receiver was dropped, so there's nowhere to send events
+                                queue.clear();
+                            }
+                            continue;
+                        }
+                        Either::Right((event, _)) => event,
+                    },
+                };
+
+                match event {
+                    Some(event) => {
+                        queue.push_back(event);
+                        if queue.len() + usize::from(sending.is_some()) > capacity.max(1) {
+                            if sending.take().is_some() {
+                                tracing::debug!("Dropping event because buffer is full");
+                            } else {
+                                let event = queue.pop_front();
+                                tracing::debug!(?event, "Dropping event because buffer is full");
+                            }
+                        }
+                    }
+                    None => break,
+                }
+            }
+
+            if let Some(send) = sending {
+                if send.await.is_err() {
+                    return;
+                }
+            }
+
+            for event in queue {
+                if event_tx.send(event).await.is_err() {
+                    break;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::stream;
+
+    fn events(n: u32) -> impl Stream<Item = ClientEvent> + Unpin {
+        stream::iter((1..=n).map(|attempt| ClientEvent::Reconnecting { attempt }))
+    }
+
+    async fn forward(policy: EventOverflowPolicy) -> Vec<u32> {
+        let (tx, rx) = async_channel::bounded(2);
+        let task = tokio::spawn(async move {
+            forward_events(events(6), &tx, policy, 2).await;
+        });
+
+        // Let the forwarder fill the buffer before reading anything
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+
+        let mut received = Vec::new();
+        while let Ok(event) = rx.recv().await {
+            match event {
+                ClientEvent::Reconnecting { attempt } => received.push(attempt),
+                other => panic!("unexpected event {:?}", other),
+            }
+        }
+
+        task.await.unwrap();
+        received
+    }
+
+    #[tokio::test]
+    async fn overflow_policies() {
+        assert_eq!(
+            forward(EventOverflowPolicy::Block).await,
+            [1, 2, 3, 4, 5, 6]
+        );
+        assert_eq!(forward(EventOverflowPolicy::DropNewest).await, [1, 2]);
+        assert_eq!(forward(EventOverflowPolicy::DropOldest).await, [1, 2, 5, 6]);
+    }
+}
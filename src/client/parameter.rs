@@ -0,0 +1,332 @@
+use crate::client::guard::spawn_cleanup;
+use crate::client::Client;
+use crate::data::{
+    ParameterCreationRequest, ParameterCreationResponse, ParameterDeletionRequest,
+    ParameterDeletionResponse,
+};
+use crate::error::Error;
+
+use futures_util::future;
+use std::future::Future;
+
+/// A named group of custom parameters, which are deleted when the set is dropped.
+///
+/// Custom parameters created via [`ParameterCreationRequest`] stay in VTube Studio after the plugin
+/// disconnects. A [`ParameterSet`] keeps track of which of its parameters were successfully
+/// created by [`create_all`](Self::create_all), and when it's dropped (including during a panic),
+/// a [`ParameterDeletionRequest`](crate::data::ParameterDeletionRequest) is sent for each of them.
+///
+/// Since the deletion requests can't be awaited in [`Drop`], they're sent from a spawned task, and
+/// failures are logged rather than returned. Use [`delete_all`](Self::delete_all) to wait for them
+/// instead.
+///
+/// # Example
+///
+#[cfg_attr(feature = "tokio-tungstenite", doc = "```no_run")]
+#[cfg_attr(not(feature = "tokio-tungstenite"), doc = "```ignore")]
+/// # async fn run() -> Result<(), vtubestudio::error::BoxError> {
+/// use vtubestudio::client::ParameterSet;
+/// use vtubestudio::data::ParameterCreationRequest;
+/// use vtubestudio::Client;
+///
+/// let (client, _) = Client::builder()
+///     .authentication("Plugin name", "Developer name", None)
+///     .build_tungstenite();
+///
+/// let mut params = ParameterSet::new("Eye tracking").with_parameter(ParameterCreationRequest {
+///     parameter_name: "MyEyeOpen".to_owned(),
+///     explanation: Some("How open the eyes are".to_owned()),
+///     min: 0.0,
+///     max: 1.0,
+///     default_value: 1.0,
+/// });
+///
+/// for result in params.create_all(&client).await {
+///     result?;
+/// }
+///
+/// // ...
+///
+/// params.delete_all().await; // Or drop `params`
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+#[must_use = "created parameters are deleted as soon as the set is dropped"]
+pub struct ParameterSet {
+    name: String,
+    parameters: Vec<ParameterCreationRequest>,
+    client: Option<Client>,
+    created: Vec<String>,
+}
+
+impl ParameterSet {
+    /// Creates an empty [`ParameterSet`] with the given name.
+    ///
+    /// The name is only used to identify the set (e.g., in logs), and isn't sent to VTube Studio.
+    pub fn new<S: Into<String>>(name: S) -> Self {
+        Self {
+            name: name.into(),
+            parameters: Vec::new(),
+            client: None,
+            created: Vec::new(),
+        }
+    }
+
+    /// Adds a parameter to the set.
+    pub fn with_parameter(mut self, req: ParameterCreationRequest) -> Self {
+        self.push(req);
+        self
+    }
+
+    /// Adds a parameter to the set. It isn't created until [`create_all`](Self::create_all) is
+    /// called.
+    pub fn push(&mut self, req: ParameterCreationRequest) {
+        self.parameters.push(req);
+    }
+
+    /// The name of the set.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The parameters in the set, whether or not they've been created.
+    pub fn parameters(&self) -> &[ParameterCreationRequest] {
+        &self.parameters
+    }
+
+    /// The names of the parameters that were successfully created, and will be deleted when the
+    /// set is dropped.
+    pub fn created(&self) -> &[String] {
+        &self.created
+    }
+
+    /// Creates all parameters in the set concurrently, using
+    /// [`Client::create_parameters`].
+    ///
+    /// The results are returned in the same order as the parameters were added. Parameters that
+    /// were created successfully are deleted when the set is dropped, using a clone of `client`.
+    pub async fn create_all(
+        &mut self,
+        client: &Client,
+    ) -> Vec<Result<ParameterCreationResponse, Error>> {
+        self.client = Some(client.clone());
+
+        let results = client.create_parameters(self.parameters.clone()).await;
+        for (req, result) in self.parameters.iter().zip(&results) {
+            if result.is_ok() && !self.created.contains(&req.parameter_name) {
+                self.created.push(req.parameter_name.clone());
+            }
+        }
+
+        results
+    }
+
+    /// Deletes all parameters created by this set concurrently, waiting for VTube Studio to
+    /// respond to each request.
+    ///
+    /// The results are returned in the same order as [`created`](Self::created). Afterwards, the
+    /// parameters are no longer tracked, so nothing else is sent when the set is dropped.
+    pub async fn delete_all(&mut self) -> Vec<Result<ParameterDeletionResponse, Error>> {
+        future::join_all(self.take_deletions()).await
+    }
+
+    /// Consumes the set without deleting any parameters, returning the names of the parameters
+    /// that were created.
+    pub fn keep(mut self) -> Vec<String> {
+        self.client = None;
+        std::mem::take(&mut self.created)
+    }
+
+    /// Returns a future per created parameter that deletes it, and stops tracking them.
+    fn take_deletions(
+        &mut self,
+    ) -> Vec<impl Future<Output = Result<ParameterDeletionResponse, Error>> + Send + 'static> {
+        let client = match &self.client {
+            Some(client) => client,
+            None => return Vec::new(),
+        };
+
+        self.created
+            .drain(..)
+            .map(|parameter_name| {
+                let mut client = client.clone();
+                async move {
+                    client
+                        .send(&ParameterDeletionRequest { parameter_name })
+                        .await
+                }
+            })
+            .collect()
+    }
+}
+
+impl Extend<ParameterCreationRequest> for ParameterSet {
+    fn extend<T: IntoIterator<Item = ParameterCreationRequest>>(&mut self, iter: T) {
+        self.parameters.extend(iter);
+    }
+}
+
+impl Drop for ParameterSet {
+    fn drop(&mut self) {
+        let deletions = self.take_deletions();
+        if !deletions.is_empty() {
+            spawn_cleanup("delete parameters", async move {
+                future::join_all(deletions)
+                    .await
+                    .into_iter()
+                    .collect::<Result<Vec<_>, Error>>()?;
+                Ok(())
+            });
+        }
+    }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use super::*;
+    use crate::data::{ApiError, ErrorId, Request, ResponseEnvelope};
+    use crate::testing::{MockServer, RequestMatcher};
+
+    /// Creates parameters successfully, unless the name starts with "Invalid".
+    fn server() -> Result<MockServer, Error> {
+        let server = MockServer::new();
+        server.respond_with(
+            RequestMatcher::message_type::<ParameterCreationRequest>(),
+            |req| {
+                let req = req.data.deserialize::<ParameterCreationRequest>().unwrap();
+                if req.parameter_name.starts_with("Invalid") {
+                    ResponseEnvelope {
+                        data: Err(ApiError {
+                            error_id: ErrorId::CUSTOM_PARAM_NAME_INVALID,
+                            message: "Invalid parameter name".into(),
+                        }),
+                        ..Default::default()
+                    }
+                } else {
+                    ResponseEnvelope::new(&ParameterCreationResponse {
+                        parameter_name: req.parameter_name,
+                    })
+                    .unwrap()
+                }
+            },
+        );
+        server.respond_with(
+            RequestMatcher::message_type::<ParameterDeletionRequest>(),
+            |req| {
+                let req = req.data.deserialize::<ParameterDeletionRequest>().unwrap();
+                ResponseEnvelope::new(&ParameterDeletionResponse {
+                    parameter_name: req.parameter_name,
+                })
+                .unwrap()
+            },
+        );
+        Ok(server)
+    }
+
+    fn param(name: &str) -> ParameterCreationRequest {
+        ParameterCreationRequest {
+            parameter_name: name.into(),
+            explanation: None,
+            min: 0.0,
+            max: 1.0,
+            default_value: 0.5,
+        }
+    }
+
+    fn deleted_names(server: &MockServer) -> Vec<String> {
+        let mut names = server
+            .received()
+            .into_iter()
+            .filter(|req| req.message_type == ParameterDeletionRequest::MESSAGE_TYPE)
+            .filter_map(|req| req.data.deserialize::<ParameterDeletionRequest>().ok())
+            .map(|req| req.parameter_name)
+            .collect::<Vec<_>>();
+        names.sort();
+        names
+    }
+
+    #[tokio::test]
+    async fn create_and_delete_parameters() -> Result<(), Error> {
+        let server = server()?;
+        let (client, _events) = Client::builder().build_connector(server.connector());
+
+        let created = client
+            .create_parameters(vec![param("A"), param("InvalidB"), param("C")])
+            .await;
+        assert_eq!(created.len(), 3);
+        assert_eq!(created[0].as_ref().unwrap().parameter_name, "A");
+        let error = created[1].as_ref().unwrap_err().find_source::<ApiError>();
+        assert_eq!(
+            error.map(|e| e.error_id),
+            Some(ErrorId::CUSTOM_PARAM_NAME_INVALID)
+        );
+        assert_eq!(created[2].as_ref().unwrap().parameter_name, "C");
+
+        let deleted = client.delete_parameters(vec!["A", "C"]).await;
+        let deleted = deleted
+            .into_iter()
+            .map(|result| result.map(|resp| resp.parameter_name))
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(deleted, ["A", "C"]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn delete_on_drop() -> Result<(), Error> {
+        let server = server()?;
+        let (client, _events) = Client::builder().build_connector(server.connector());
+
+        {
+            let mut set = ParameterSet::new("test")
+                .with_parameter(param("A"))
+                .with_parameter(param("InvalidB"));
+            set.push(param("C"));
+            assert_eq!(set.name(), "test");
+            assert_eq!(set.parameters().len(), 3);
+
+            let results = set.create_all(&client).await;
+            assert!(results[0].is_ok());
+            assert!(results[1].is_err());
+            assert!(results[2].is_ok());
+            assert_eq!(set.created(), ["A", "C"]);
+        }
+
+        // Only the successfully created parameters are deleted
+        server.wait_for_requests(5).await;
+        assert_eq!(deleted_names(&server), ["A", "C"]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn delete_all_then_drop() -> Result<(), Error> {
+        let server = server()?;
+        let (client, _events) = Client::builder().build_connector(server.connector());
+
+        let mut set = ParameterSet::new("test");
+        set.extend(vec![param("A"), param("B")]);
+        set.create_all(&client).await;
+
+        let results = set.delete_all().await;
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(Result::is_ok));
+        assert!(set.created().is_empty());
+        drop(set);
+
+        // Dropping after deleting doesn't send any more requests
+        tokio::task::yield_now().await;
+        assert_eq!(server.received().len(), 4);
+        assert_eq!(deleted_names(&server), ["A", "B"]);
+
+        // Kept parameters aren't deleted
+        let mut set = ParameterSet::new("test").with_parameter(param("C"));
+        set.create_all(&client).await;
+        assert_eq!(set.keep(), ["C"]);
+        tokio::task::yield_now().await;
+        assert_eq!(server.received().len(), 5);
+
+        Ok(())
+    }
+}
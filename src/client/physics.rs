@@ -0,0 +1,638 @@
+use crate::client::guard::spawn_cleanup;
+use crate::client::Client;
+use crate::data::{ErrorId, PhysicsOverride, SetCurrentModelPhysicsRequest};
+use crate::error::{Error, ErrorKind};
+
+use std::time::Duration;
+use tokio::task::{AbortHandle, JoinHandle};
+use tokio::time::Instant;
+
+/// Keeps physics overrides active until dropped, then lets them expire.
+///
+/// Physics overrides set via [`SetCurrentModelPhysicsRequest`] expire after their
+/// [`override_seconds`](crate::data::PhysicsOverride::override_seconds), so this guard spawns a
+/// background task that re-sends the overrides every `refresh_interval`. When the guard is dropped
+/// (including during a panic), the background task is stopped and the overrides are sent one last
+/// time with [`MIN_OVERRIDE_SECONDS`](crate::data::PhysicsOverride::MIN_OVERRIDE_SECONDS), so
+/// they expire shortly after.
+///
+/// The API has no way to clear overrides immediately (a request with empty override lists is
+/// rejected with [`ErrorId::SET_CURRENT_MODEL_PHYSICS_REQUEST_NO_OVERRIDES_PROVIDED`]).
+///
+/// Only one plugin can override physics at a time. If another plugin takes control, the
+/// background task stops (see [`is_active`](Self::is_active)), and [`release`](Self::release)
+/// returns the error without attempting to end the overrides.
+///
+/// Since the cleanup request can't be awaited in [`Drop`], it is sent from a spawned task. Use
+/// [`release`](Self::release) to wait for it instead.
+///
+/// # Example
+///
+#[cfg_attr(feature = "tokio-tungstenite", doc = "```no_run")]
+#[cfg_attr(not(feature = "tokio-tungstenite"), doc = "```ignore")]
+/// # async fn run() -> Result<(), vtubestudio::error::BoxError> {
+/// use std::time::Duration;
+/// use vtubestudio::client::PhysicsOverrideGuard;
+/// use vtubestudio::data::{PhysicsOverride, SetCurrentModelPhysicsRequest};
+/// use vtubestudio::Client;
+///
+/// let (client, _) = Client::builder()
+///     .authentication("Plugin name", "Developer name", None)
+///     .build_tungstenite();
+///
+/// let overrides = SetCurrentModelPhysicsRequest {
+///     strength_overrides: vec![PhysicsOverride {
+///         id: String::new(),
+///         value: 100.0,
+///         set_base_value: true,
+///         override_seconds: 2.0,
+///     }],
+///     wind_overrides: vec![],
+/// };
+///
+/// let guard = PhysicsOverrideGuard::new(client, overrides, Duration::from_secs(1));
+/// tokio::time::sleep(Duration::from_secs(10)).await;
+/// guard.release().await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+#[must_use = "physics overrides expire as soon as the guard is dropped"]
+pub struct PhysicsOverrideGuard {
+    client: Option<Client>,
+    expire_request: SetCurrentModelPhysicsRequest,
+    task: JoinHandle<Result<(), Error>>,
+}
+
+impl PhysicsOverrideGuard {
+    /// Sends the physics overrides immediately, and then again every `refresh_interval` until the
+    /// guard is dropped.
+    ///
+    /// The `refresh_interval` should be shorter than the overrides'
+    /// [`override_seconds`](crate::data::PhysicsOverride::override_seconds). Errors from the
+    /// background task are logged and otherwise ignored, unless physics are controlled by
+    /// another plugin.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a Tokio runtime, or if `refresh_interval` is zero.
+    pub fn new(
+        client: Client,
+        overrides: SetCurrentModelPhysicsRequest,
+        refresh_interval: Duration,
+    ) -> Self {
+        let expire_request = expire_request(&overrides);
+        let task = spawn_renewal(client.clone(), overrides, refresh_interval, None);
+
+        Self {
+            client: Some(client),
+            expire_request,
+            task,
+        }
+    }
+
+    /// Returns `false` if the overrides are no longer being refreshed, because another plugin
+    /// took control of the physics.
+    pub fn is_active(&self) -> bool {
+        !self.task.is_finished()
+    }
+
+    /// Stops refreshing the overrides and makes them expire after
+    /// [`MIN_OVERRIDE_SECONDS`](crate::data::PhysicsOverride::MIN_OVERRIDE_SECONDS), waiting for
+    /// VTube Studio to respond.
+    ///
+    /// If the overrides stopped being refreshed because another plugin took control of the
+    /// physics, this returns that error instead (with [`ErrorKind::Api`]).
+    pub async fn release(mut self) -> Result<(), Error> {
+        self.task.abort();
+
+        match (&mut self.task).await {
+            Ok(Err(error)) => {
+                self.client = None;
+                return Err(error);
+            }
+            Ok(Ok(())) => {}
+            Err(join_error) if join_error.is_cancelled() => {}
+            Err(join_error) => {
+                return Err(Error::new(ErrorKind::Other).with_source(join_error));
+            }
+        }
+
+        match self.client.take() {
+            Some(mut client) => send_expire_request(&mut client, &self.expire_request).await,
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for PhysicsOverrideGuard {
+    fn drop(&mut self) {
+        // The task only finishes on its own if another plugin controls the physics
+        if self.task.is_finished() {
+            self.client = None;
+        }
+        self.task.abort();
+
+        if let Some(mut client) = self.client.take() {
+            let req = std::mem::take(&mut self.expire_request);
+            spawn_cleanup("expire physics overrides", async move {
+                send_expire_request(&mut client, &req).await
+            });
+        }
+    }
+}
+
+/// Renews physics overrides in the background, either for a target duration or until stopped.
+///
+/// This is an alternative to [`PhysicsOverrideGuard`] for when the overrides shouldn't be tied to
+/// the lifetime of a value. [`start`](Self::start) spawns a task that sends the overrides
+/// immediately and then every `renewal_every`, and returns a [`JoinHandle`] for it. The task
+/// keeps running until [`stop`](Self::stop) is called, the target duration passed to
+/// [`start_for`](Self::start_for) has elapsed, or another plugin takes control of the physics (in
+/// which case the task returns that error, with [`ErrorKind::Api`]).
+///
+/// Like [`PhysicsOverrideGuard`], ending the session re-sends the overrides with
+/// [`MIN_OVERRIDE_SECONDS`](crate::data::PhysicsOverride::MIN_OVERRIDE_SECONDS), since the API has
+/// no way to clear them immediately.
+///
+/// # Example
+///
+#[cfg_attr(feature = "tokio-tungstenite", doc = "```no_run")]
+#[cfg_attr(not(feature = "tokio-tungstenite"), doc = "```ignore")]
+/// # async fn run() -> Result<(), vtubestudio::error::BoxError> {
+/// use std::time::Duration;
+/// use vtubestudio::client::PhysicsOverrideSession;
+/// use vtubestudio::data::PhysicsOverride;
+/// use vtubestudio::Client;
+///
+/// let (client, _) = Client::builder()
+///     .authentication("Plugin name", "Developer name", None)
+///     .build_tungstenite();
+///
+/// // Override the wind for 30 seconds
+/// let (_session, handle) = PhysicsOverrideSession::start_for(
+///     client.clone(),
+///     vec![],
+///     vec![PhysicsOverride::base_wind(100.0, 2.0)],
+///     Duration::from_secs(1),
+///     Duration::from_secs(30),
+/// );
+/// handle.await??;
+///
+/// // Override the strength until stopped
+/// let (session, _handle) = PhysicsOverrideSession::start(
+///     client,
+///     vec![PhysicsOverride::base_strength(0.0, 2.0)],
+///     vec![],
+///     Duration::from_secs(1),
+/// );
+/// tokio::time::sleep(Duration::from_secs(10)).await;
+/// session.stop().await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct PhysicsOverrideSession {
+    client: Client,
+    expire_request: SetCurrentModelPhysicsRequest,
+    abort_handle: AbortHandle,
+}
+
+impl PhysicsOverrideSession {
+    /// Starts renewing the overrides every `renewal_every` until [`stop`](Self::stop) is called.
+    ///
+    /// The `renewal_every` interval should be shorter than the overrides'
+    /// [`override_seconds`](crate::data::PhysicsOverride::override_seconds).
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a Tokio runtime, or if `renewal_every` is zero.
+    pub fn start(
+        client: Client,
+        strength_overrides: Vec<PhysicsOverride>,
+        wind_overrides: Vec<PhysicsOverride>,
+        renewal_every: Duration,
+    ) -> (Self, JoinHandle<Result<(), Error>>) {
+        Self::start_inner(
+            client,
+            strength_overrides,
+            wind_overrides,
+            renewal_every,
+            None,
+        )
+    }
+
+    /// Like [`start`](Self::start), but stops renewing the overrides once `duration` has elapsed,
+    /// and then lets them expire.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a Tokio runtime, or if `renewal_every` is zero.
+    pub fn start_for(
+        client: Client,
+        strength_overrides: Vec<PhysicsOverride>,
+        wind_overrides: Vec<PhysicsOverride>,
+        renewal_every: Duration,
+        duration: Duration,
+    ) -> (Self, JoinHandle<Result<(), Error>>) {
+        Self::start_inner(
+            client,
+            strength_overrides,
+            wind_overrides,
+            renewal_every,
+            Some(Instant::now() + duration),
+        )
+    }
+
+    fn start_inner(
+        client: Client,
+        strength_overrides: Vec<PhysicsOverride>,
+        wind_overrides: Vec<PhysicsOverride>,
+        renewal_every: Duration,
+        deadline: Option<Instant>,
+    ) -> (Self, JoinHandle<Result<(), Error>>) {
+        let overrides =
+            SetCurrentModelPhysicsRequest::from_overrides(strength_overrides, wind_overrides);
+        let expire_request = expire_request(&overrides);
+        let task = spawn_renewal(client.clone(), overrides, renewal_every, deadline);
+
+        let session = Self {
+            client,
+            expire_request,
+            abort_handle: task.abort_handle(),
+        };
+
+        (session, task)
+    }
+
+    /// Returns `false` if the overrides are no longer being renewed, because the target duration
+    /// has elapsed or another plugin took control of the physics.
+    pub fn is_active(&self) -> bool {
+        !self.abort_handle.is_finished()
+    }
+
+    /// Stops renewing the overrides and makes them expire after
+    /// [`MIN_OVERRIDE_SECONDS`](crate::data::PhysicsOverride::MIN_OVERRIDE_SECONDS), waiting for
+    /// VTube Studio to respond.
+    ///
+    /// If the session already ended on its own (see [`is_active`](Self::is_active)), nothing is
+    /// sent. The reason it ended is returned by the [`JoinHandle`].
+    pub async fn stop(mut self) -> Result<(), Error> {
+        if self.abort_handle.is_finished() {
+            return Ok(());
+        }
+
+        self.abort_handle.abort();
+        send_expire_request(&mut self.client, &self.expire_request).await
+    }
+}
+
+/// Spawns a task that sends the overrides every `interval`, until the optional `deadline` (after
+/// which the overrides are expired) or until another plugin takes control of the physics.
+fn spawn_renewal(
+    mut client: Client,
+    overrides: SetCurrentModelPhysicsRequest,
+    interval: Duration,
+    deadline: Option<Instant>,
+) -> JoinHandle<Result<(), Error>> {
+    let mut interval = tokio::time::interval(interval);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    tokio::spawn(async move {
+        loop {
+            match deadline {
+                Some(deadline) => {
+                    let ticked = tokio::time::timeout_at(deadline, interval.tick()).await;
+                    if ticked.is_err() || Instant::now() >= deadline {
+                        break;
+                    }
+                }
+                None => {
+                    interval.tick().await;
+                }
+            }
+
+            match client.send(&overrides).await {
+                Ok(_) => {}
+                Err(error) if is_controlled_by_other_plugin(&error) => {
+                    tracing::warn!(%error, "Physics are controlled by another plugin");
+                    return Err(error);
+                }
+                Err(error) => tracing::warn!(%error, "Failed to refresh physics overrides"),
+            }
+        }
+
+        send_expire_request(&mut client, &expire_request(&overrides)).await
+    })
+}
+
+fn is_controlled_by_other_plugin(error: &Error) -> bool {
+    matches!(
+        error.to_api_error(),
+        Some(e) if e.error_id == ErrorId::SET_CURRENT_MODEL_PHYSICS_REQUEST_PHYSICS_CONTROLLED_BY_OTHER_PLUGIN
+    )
+}
+
+/// The same overrides, but expiring as soon as possible.
+fn expire_request(overrides: &SetCurrentModelPhysicsRequest) -> SetCurrentModelPhysicsRequest {
+    let expire = |overrides: &[PhysicsOverride]| {
+        overrides
+            .iter()
+            .map(|o| PhysicsOverride {
+                override_seconds: PhysicsOverride::MIN_OVERRIDE_SECONDS,
+                ..o.clone()
+            })
+            .collect()
+    };
+
+    SetCurrentModelPhysicsRequest {
+        strength_overrides: expire(&overrides.strength_overrides),
+        wind_overrides: expire(&overrides.wind_overrides),
+    }
+}
+
+async fn send_expire_request(
+    client: &mut Client,
+    req: &SetCurrentModelPhysicsRequest,
+) -> Result<(), Error> {
+    // There's nothing to expire, and VTube Studio rejects requests without overrides
+    if req.strength_overrides.is_empty() && req.wind_overrides.is_empty() {
+        return Ok(());
+    }
+
+    client.send(req).await?;
+    Ok(())
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use super::*;
+    use crate::data::{PhysicsOverride, SetCurrentModelPhysicsResponse};
+    use crate::testing::MockServer;
+
+    fn sent_requests(server: &MockServer) -> Vec<SetCurrentModelPhysicsRequest> {
+        server
+            .received()
+            .into_iter()
+            .map(|req| req.data.deserialize().unwrap())
+            .collect()
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn refresh_and_expire_on_drop() -> Result<(), Error> {
+        let server = MockServer::new();
+        server.respond::<SetCurrentModelPhysicsRequest>(&SetCurrentModelPhysicsResponse {})?;
+        let (client, _events) = Client::builder().build_connector(server.connector());
+
+        let overrides = SetCurrentModelPhysicsRequest {
+            strength_overrides: vec![PhysicsOverride {
+                id: String::new(),
+                value: 50.0,
+                set_base_value: true,
+                override_seconds: 2.0,
+            }],
+            wind_overrides: vec![],
+        };
+
+        {
+            let _guard =
+                PhysicsOverrideGuard::new(client, overrides.clone(), Duration::from_secs(1));
+            server.wait_for_requests(1).await;
+
+            for count in 2..=3 {
+                tokio::time::advance(Duration::from_secs(1)).await;
+                server.wait_for_requests(count).await;
+            }
+        }
+
+        server.wait_for_requests(4).await;
+        tokio::time::advance(Duration::from_secs(5)).await;
+
+        let mut expiring = overrides.clone();
+        expiring.strength_overrides[0].override_seconds = PhysicsOverride::MIN_OVERRIDE_SECONDS;
+        assert_eq!(
+            sent_requests(&server),
+            vec![overrides.clone(), overrides.clone(), overrides, expiring]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn release() -> Result<(), Error> {
+        let server = MockServer::new();
+        server.respond::<SetCurrentModelPhysicsRequest>(&SetCurrentModelPhysicsResponse {})?;
+        let (client, _events) = Client::builder().build_connector(server.connector());
+
+        let overrides = SetCurrentModelPhysicsRequest {
+            strength_overrides: vec![],
+            wind_overrides: vec![PhysicsOverride {
+                id: "Hair".into(),
+                value: 1.5,
+                set_base_value: false,
+                override_seconds: 5.0,
+            }],
+        };
+
+        let guard = PhysicsOverrideGuard::new(client, overrides, Duration::from_secs(60));
+        server.wait_for_requests(1).await;
+        guard.release().await?;
+
+        let requests = sent_requests(&server);
+        assert_eq!(requests.len(), 2);
+        assert_eq!(
+            requests[1].wind_overrides,
+            vec![PhysicsOverride {
+                id: "Hair".into(),
+                value: 1.5,
+                set_base_value: false,
+                override_seconds: PhysicsOverride::MIN_OVERRIDE_SECONDS,
+            }]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn never_send_empty_overrides() -> Result<(), Error> {
+        use crate::data::{ApiError, ResponseEnvelope};
+        use crate::testing::RequestMatcher;
+
+        // Like VTube Studio, reject requests without any overrides
+        let server = MockServer::new();
+        server.respond_with(
+            RequestMatcher::message_type::<SetCurrentModelPhysicsRequest>(),
+            |req| {
+                let req: SetCurrentModelPhysicsRequest = req.data.deserialize().unwrap();
+                if req.strength_overrides.is_empty() && req.wind_overrides.is_empty() {
+                    ResponseEnvelope {
+                        data: Err(ApiError {
+                            error_id:
+                                ErrorId::SET_CURRENT_MODEL_PHYSICS_REQUEST_NO_OVERRIDES_PROVIDED,
+                            message: "No overrides provided".into(),
+                        }),
+                        ..Default::default()
+                    }
+                } else {
+                    ResponseEnvelope::new(&SetCurrentModelPhysicsResponse {}).unwrap()
+                }
+            },
+        );
+        let (client, _events) = Client::builder().build_connector(server.connector());
+
+        let overrides = SetCurrentModelPhysicsRequest::from_overrides(
+            vec![PhysicsOverride::base_strength(50.0, 2.0)],
+            vec![],
+        );
+        let guard = PhysicsOverrideGuard::new(client.clone(), overrides, Duration::from_secs(60));
+        server.wait_for_requests(1).await;
+        guard.release().await?;
+        assert_eq!(server.received().len(), 2);
+
+        // With no overrides at all, there's nothing to expire
+        let guard = PhysicsOverrideGuard::new(
+            client,
+            SetCurrentModelPhysicsRequest::default(),
+            Duration::from_secs(60),
+        );
+        server.wait_for_requests(3).await;
+        guard.release().await?;
+        assert_eq!(server.received().len(), 3);
+
+        Ok(())
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn controlled_by_other_plugin() -> Result<(), Error> {
+        use crate::data::{ApiError, ResponseEnvelope};
+        use crate::testing::RequestMatcher;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        // The second request fails because another plugin took control
+        let server = MockServer::new();
+        let count = AtomicUsize::new(0);
+        server.respond_with(
+            RequestMatcher::message_type::<SetCurrentModelPhysicsRequest>(),
+            move |_| {
+                if count.fetch_add(1, Ordering::Relaxed) == 0 {
+                    ResponseEnvelope::new(&SetCurrentModelPhysicsResponse {}).unwrap()
+                } else {
+                    ResponseEnvelope {
+                        data: Err(ApiError {
+                            error_id:
+                                ErrorId::SET_CURRENT_MODEL_PHYSICS_REQUEST_PHYSICS_CONTROLLED_BY_OTHER_PLUGIN,
+                            message: "Physics are controlled by another plugin".into(),
+                        }),
+                        ..Default::default()
+                    }
+                }
+            },
+        );
+        let (client, _events) = Client::builder().build_connector(server.connector());
+
+        let overrides = SetCurrentModelPhysicsRequest::from_overrides(
+            vec![],
+            vec![PhysicsOverride::base_wind(50.0, 2.0)],
+        );
+        let guard = PhysicsOverrideGuard::new(client, overrides, Duration::from_secs(1));
+        server.wait_for_requests(1).await;
+        assert!(guard.is_active());
+
+        tokio::time::advance(Duration::from_secs(1)).await;
+        server.wait_for_requests(2).await;
+        for _ in 0..100 {
+            if !guard.is_active() {
+                break;
+            }
+            tokio::task::yield_now().await;
+        }
+        assert!(!guard.is_active());
+
+        // No more refreshes, and no request is sent to expire the overrides
+        tokio::time::advance(Duration::from_secs(5)).await;
+        let err = guard.release().await.unwrap_err();
+        assert!(is_controlled_by_other_plugin(&err), "{:?}", err);
+        assert_eq!(server.received().len(), 2);
+
+        Ok(())
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn session_renews_until_stopped() -> Result<(), Error> {
+        let server = MockServer::new();
+        server.respond::<SetCurrentModelPhysicsRequest>(&SetCurrentModelPhysicsResponse {})?;
+        let (client, _events) = Client::builder().build_connector(server.connector());
+
+        let strength = PhysicsOverride::base_strength(50.0, 2.0);
+        let (session, handle) = PhysicsOverrideSession::start(
+            client,
+            vec![strength.clone()],
+            vec![],
+            Duration::from_secs(1),
+        );
+        server.wait_for_requests(1).await;
+
+        for count in 2..=3 {
+            tokio::time::advance(Duration::from_secs(1)).await;
+            server.wait_for_requests(count).await;
+        }
+
+        assert!(session.is_active());
+        session.stop().await?;
+        assert!(handle.await.unwrap_err().is_cancelled());
+
+        let requests = sent_requests(&server);
+        assert_eq!(requests.len(), 4);
+        assert!(requests[..3]
+            .iter()
+            .all(|req| req.strength_overrides == [strength.clone()]));
+        assert_eq!(
+            requests[3].strength_overrides[0].override_seconds,
+            PhysicsOverride::MIN_OVERRIDE_SECONDS
+        );
+
+        // No more renewals after stopping
+        tokio::time::advance(Duration::from_secs(5)).await;
+        assert_eq!(server.received().len(), 4);
+
+        Ok(())
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn session_target_duration() -> Result<(), Error> {
+        let server = MockServer::new();
+        server.respond::<SetCurrentModelPhysicsRequest>(&SetCurrentModelPhysicsResponse {})?;
+        let (client, _events) = Client::builder().build_connector(server.connector());
+
+        let wind = PhysicsOverride::base_wind(20.0, 2.0);
+        let start = Instant::now();
+        let (session, handle) = PhysicsOverrideSession::start_for(
+            client,
+            vec![],
+            vec![wind.clone()],
+            Duration::from_secs(1),
+            Duration::from_millis(2500),
+        );
+
+        // Renewed at 0s, 1s, and 2s, then expired at 2.5s
+        handle.await.unwrap()?;
+        assert_eq!(start.elapsed(), Duration::from_millis(2500));
+        assert!(!session.is_active());
+
+        let requests = sent_requests(&server);
+        assert_eq!(requests.len(), 4);
+        assert!(requests[..3]
+            .iter()
+            .all(|req| req.wind_overrides == [wind.clone()]));
+        assert_eq!(
+            requests[3].wind_overrides[0].override_seconds,
+            PhysicsOverride::MIN_OVERRIDE_SECONDS
+        );
+
+        // The session already ended, so stopping doesn't send anything
+        session.stop().await?;
+        assert_eq!(server.received().len(), 4);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,119 @@
+use crate::client::{ClientEvent, ClientState};
+use crate::error::{Error, ErrorKind};
+
+use futures_util::future::BoxFuture;
+use std::error::Error as StdError;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tower::Service;
+
+/// Limits for reconnection attempts, as configured in the
+/// [`ClientBuilder`](crate::ClientBuilder).
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct ReconnectConfig {
+    pub(crate) max_attempts: Option<usize>,
+    pub(crate) delay: Duration,
+}
+
+/// Wraps the connector used by [`Reconnect`](crate::service::Reconnect), emitting
+/// [`ClientEvent::Reconnecting`] before each reconnection attempt, waiting for the configured
+/// delay, and giving up once the maximum number of attempts has been reached.
+#[derive(Debug)]
+pub(crate) struct ReconnectGate<S> {
+    service: S,
+    config: ReconnectConfig,
+    state: Arc<ClientState>,
+    event_tx: async_channel::Sender<ClientEvent>,
+}
+
+impl<S> ReconnectGate<S> {
+    pub(crate) fn new(
+        service: S,
+        config: ReconnectConfig,
+        state: Arc<ClientState>,
+        event_tx: async_channel::Sender<ClientEvent>,
+    ) -> Self {
+        Self {
+            service,
+            config,
+            state,
+            event_tx,
+        }
+    }
+
+    fn send_event(&self, event: ClientEvent) {
+        if let Err(e) = self.event_tx.try_send(event) {
+            tracing::warn!(
+                event = ?e.into_inner(),
+                "Failed to send event to EventStream because buffer is full"
+            );
+        }
+    }
+}
+
+impl<S, R> Service<R> for ReconnectGate<S>
+where
+    S: Service<R>,
+    S::Error: StdError + Send + Sync + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = Error;
+    type Future = BoxFuture<'static, Result<S::Response, Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(cx).map_err(into_error)
+    }
+
+    fn call(&mut self, request: R) -> Self::Future {
+        // The first connection attempt isn't a reconnect
+        if !self
+            .state
+            .connection_attempted
+            .swap(true, Ordering::Relaxed)
+        {
+            let future = self.service.call(request);
+            return Box::pin(async move { future.await.map_err(into_error) });
+        }
+
+        let attempt = self
+            .state
+            .reconnect_attempts
+            .fetch_add(1, Ordering::Relaxed)
+            + 1;
+
+        if let Some(max_attempts) = self.config.max_attempts {
+            if attempt as usize > max_attempts {
+                if !self.state.gave_up.swap(true, Ordering::Relaxed) {
+                    self.send_event(ClientEvent::GaveUp);
+                }
+
+                let error = Error::new(ErrorKind::ConnectionRefused).with_source(format!(
+                    "gave up after {} reconnection attempts",
+                    max_attempts
+                ));
+                return Box::pin(async move { Err(error) });
+            }
+        }
+
+        self.send_event(ClientEvent::Reconnecting { attempt });
+
+        #[cfg(feature = "metrics")]
+        crate::service::metrics::record_reconnect();
+
+        let delay = self.config.delay;
+        let future = self.service.call(request);
+        Box::pin(async move {
+            if delay > Duration::ZERO {
+                crate::runtime::sleep(delay).await;
+            }
+            future.await.map_err(into_error)
+        })
+    }
+}
+
+fn into_error<E: StdError + Send + Sync + 'static>(error: E) -> Error {
+    Error::from_boxed(Box::new(error))
+}
@@ -0,0 +1,252 @@
+use crate::client::{Client, ClientEvent, ClientEventStream, DEFAULT_EVENT_BUFFER_SIZE};
+use crate::data::{
+    EnumString, EventConfig, EventData, EventSubscriptionRequest, EventSubscriptionResponse,
+    ResponseType,
+};
+use crate::error::Error;
+
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Keeps track of active event subscriptions, and resubscribes to them after reconnecting.
+///
+/// This can be cloned and shared between tasks. All clones share the same set of subscriptions.
+///
+/// # Example
+///
+#[cfg_attr(feature = "tokio-tungstenite", doc = "```no_run")]
+#[cfg_attr(not(feature = "tokio-tungstenite"), doc = "```ignore")]
+/// # async fn run() -> Result<(), vtubestudio::error::BoxError> {
+/// use vtubestudio::client::EventSubscriptionManager;
+/// use vtubestudio::data::{Event, TestEventConfig};
+/// use vtubestudio::{Client, ClientEvent};
+///
+/// let (client, events) = Client::builder()
+///     .authentication("Plugin name", "Developer name", None)
+///     .build_tungstenite();
+///
+/// let mut manager = EventSubscriptionManager::new(client);
+/// let (driver, mut events) = manager.run(events);
+/// tokio::spawn(driver);
+///
+/// manager
+///     .subscribe(&TestEventConfig {
+///         test_message_for_event: "Hello".to_owned(),
+///     })
+///     .await?;
+///
+/// while let Some(event) = events.next().await {
+///     if let ClientEvent::Api(Event::Test(event)) = event {
+///         println!("VTube Studio has been running for {}s", event.counter);
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct EventSubscriptionManager {
+    client: Client,
+    subscriptions: Arc<Mutex<Vec<EventSubscriptionRequest>>>,
+    retry_delay: Duration,
+}
+
+impl EventSubscriptionManager {
+    /// Creates a new [`EventSubscriptionManager`] with no active subscriptions.
+    pub fn new(client: Client) -> Self {
+        Self {
+            client,
+            subscriptions: Arc::default(),
+            retry_delay: Duration::from_secs(2),
+        }
+    }
+
+    /// How long to wait before retrying after failing to resubscribe (e.g., if VTube Studio isn't
+    /// running). The default value is 2 seconds.
+    pub fn retry_delay(mut self, delay: Duration) -> Self {
+        self.retry_delay = delay;
+        self
+    }
+
+    /// Subscribes to an event, and records the subscription so it can be resent after
+    /// reconnecting. If there was already a subscription for this event type, it is replaced.
+    pub async fn subscribe<C: EventConfig>(
+        &mut self,
+        config: &C,
+    ) -> Result<EventSubscriptionResponse, Error> {
+        let req = EventSubscriptionRequest::subscribe(config)?;
+        let resp = self.client.send(&req).await?;
+
+        let mut subscriptions = self.lock();
+        subscriptions.retain(|existing| existing.event_name != req.event_name);
+        subscriptions.push(req);
+
+        Ok(resp)
+    }
+
+    /// Unsubscribes from an event type, and removes it from the set of active subscriptions.
+    pub async fn unsubscribe<E: EventData>(&mut self) -> Result<EventSubscriptionResponse, Error> {
+        self.lock()
+            .retain(|existing| existing.event_name.as_ref() != Some(&E::MESSAGE_TYPE));
+
+        self.client
+            .send(&EventSubscriptionRequest::unsubscribe::<E>())
+            .await
+    }
+
+    /// Unsubscribes from all events, and clears the set of active subscriptions.
+    pub async fn unsubscribe_all(&mut self) -> Result<EventSubscriptionResponse, Error> {
+        self.lock().clear();
+
+        self.client
+            .send(&EventSubscriptionRequest::unsubscribe_all())
+            .await
+    }
+
+    /// Returns the event types of the active subscriptions.
+    pub fn subscribed_events(&self) -> Vec<EnumString<ResponseType>> {
+        self.lock()
+            .iter()
+            .filter_map(|req| req.event_name.clone())
+            .collect()
+    }
+
+    /// Resends all active subscriptions. This stops at the first failed request.
+    pub async fn resubscribe(&mut self) -> Result<(), Error> {
+        let subscriptions = self.lock().clone();
+
+        for req in &subscriptions {
+            self.client.send(req).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Drives the resubscription loop.
+    ///
+    /// This consumes the [`ClientEventStream`] returned by the
+    /// [`ClientBuilder`](crate::ClientBuilder), and returns a future and a new
+    /// [`ClientEventStream`] that yields the same events. The future should be spawned, and
+    /// whenever it sees a [`ClientEvent::Disconnected`], it resends all active subscriptions
+    /// (which causes the client to reconnect).
+    ///
+    /// Subscriptions that fail with an [`ApiError`](crate::data::ApiError) are forwarded as
+    /// [`ClientEvent::Error`]s and not retried. Other failures (e.g., failing to connect) are
+    /// retried after [`retry_delay`](Self::retry_delay).
+    pub fn run(
+        &self,
+        mut events: ClientEventStream,
+    ) -> (impl Future<Output = ()> + Send + 'static, ClientEventStream) {
+        let capacity = events
+            .receiver
+            .capacity()
+            .unwrap_or(DEFAULT_EVENT_BUFFER_SIZE);
+        let (event_tx, event_rx) = async_channel::bounded(capacity);
+        let mut manager = self.clone();
+
+        let driver = async move {
+            while let Some(event) = events.next().await {
+                let disconnected = matches!(event, ClientEvent::Disconnected);
+
+                // Ignore send errors (the consumer probably isn't reading the stream)
+                let _ = event_tx.send(event).await;
+
+                if disconnected {
+                    manager.resubscribe_until_success(&event_tx).await;
+                }
+            }
+        };
+
+        (driver, ClientEventStream::new(event_rx))
+    }
+
+    async fn resubscribe_until_success(&mut self, event_tx: &async_channel::Sender<ClientEvent>) {
+        let subscriptions = self.lock().clone();
+
+        for req in &subscriptions {
+            loop {
+                match self.client.send(req).await {
+                    Ok(_) => break,
+                    Err(e) if e.is_api_error() => {
+                        tracing::warn!(error = %e, "Failed to resubscribe to event");
+                        let _ = event_tx.send(ClientEvent::Error(e)).await;
+                        break;
+                    }
+                    Err(e) => {
+                        tracing::warn!(
+                            error = %e,
+                            retry_delay = ?self.retry_delay,
+                            "Failed to resubscribe to event, retrying"
+                        );
+                        crate::runtime::sleep(self.retry_delay).await;
+                    }
+                }
+            }
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<EventSubscriptionRequest>> {
+        self.subscriptions.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use super::*;
+    use crate::data::{
+        ItemEvent, ItemEventConfig, Response, ResponseEnvelope, TestEvent, TestEventConfig,
+    };
+    use crate::testing::{FakeVtsServer, RequestMatcher};
+
+    #[tokio::test]
+    async fn resubscribe_on_disconnect() -> Result<(), Error> {
+        let test_config = TestEventConfig {
+            test_message_for_event: "hello".into(),
+        };
+        let item_config = ItemEventConfig {
+            item_instance_ids: Vec::new(),
+            item_file_names: vec!["example.png".into()],
+        };
+
+        let test_req = EventSubscriptionRequest::subscribe(&test_config)?;
+        let item_req = EventSubscriptionRequest::subscribe(&item_config)?;
+        let resp = ResponseEnvelope::new(&EventSubscriptionResponse::default())?;
+
+        let server = FakeVtsServer::start(vec![
+            (RequestMatcher::request(&test_req)?, resp.clone()),
+            (RequestMatcher::request(&item_req)?, resp.clone()),
+            (RequestMatcher::request(&test_req)?, resp.clone()),
+            (RequestMatcher::request(&item_req)?, resp.clone()),
+        ])
+        .await
+        .unwrap();
+
+        let (client, events) = Client::builder().url(server.url()).build_tungstenite();
+        let mut manager =
+            EventSubscriptionManager::new(client).retry_delay(Duration::from_millis(10));
+        let (driver, _events) = manager.run(events);
+        tokio::spawn(driver);
+
+        manager.subscribe(&test_config).await?;
+        manager.subscribe(&item_config).await?;
+        assert_eq!(server.remaining(), 2);
+        assert_eq!(
+            manager.subscribed_events(),
+            vec![TestEvent::MESSAGE_TYPE, ItemEvent::MESSAGE_TYPE]
+        );
+
+        server.disconnect_all();
+
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while server.remaining() > 0 {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("timed out waiting for resubscription");
+
+        server.assert_all_matched();
+
+        Ok(())
+    }
+}
@@ -55,22 +55,41 @@ pub trait MessageCodec {
 
     /// Converts a string into a websocket text message.
     fn encode(text: String) -> Self::Output;
+
+    /// Creates a websocket ping message, used for keepalive (see
+    /// [`ApiTransport::with_keepalive`](crate::transport::ApiTransport::with_keepalive)).
+    ///
+    /// The default implementation returns `None`, meaning pings aren't supported.
+    fn ping() -> Option<Self::Output> {
+        None
+    }
+
+    /// Whether the message is a websocket pong message, sent in response to a [`ping`](Self::ping).
+    fn is_pong(_msg: &Self::Input) -> bool {
+        false
+    }
 }
 
 crate::cfg_feature! {
-    #![feature = "tokio-tungstenite"]
+    #![any(feature = "tokio-tungstenite", feature = "async-tungstenite")]
 
-    /// A codec describing how to encode/decode
-    /// [`tungstenite::Message`](::tokio_tungstenite::tungstenite::Message)s.
+    /// A codec describing how to encode/decode `tungstenite::Message`s.
+    ///
+    /// This is shared by [`tokio_tungstenite`](https://docs.rs/tokio-tungstenite) and
+    /// [`async_tungstenite`](https://docs.rs/async-tungstenite), which use the same message type.
     #[derive(Debug, Clone)]
     pub struct TungsteniteCodec;
 }
 
-#[cfg(feature = "tokio-tungstenite")]
+#[cfg(any(feature = "tokio-tungstenite", feature = "async-tungstenite"))]
 mod tungstenite {
     use super::*;
 
     use std::convert::Infallible;
+
+    #[cfg(all(feature = "async-tungstenite", not(feature = "tokio-tungstenite")))]
+    use async_tungstenite::tungstenite;
+    #[cfg(feature = "tokio-tungstenite")]
     use tokio_tungstenite::tungstenite;
 
     impl MessageCodec for TungsteniteCodec {
@@ -88,5 +107,47 @@ mod tungstenite {
         fn encode(text: String) -> Self::Output {
             Self::Output::Text(text)
         }
+
+        fn ping() -> Option<Self::Output> {
+            Some(Self::Output::Ping(Vec::new()))
+        }
+
+        fn is_pong(msg: &Self::Input) -> bool {
+            matches!(msg, Self::Input::Pong(_))
+        }
+    }
+}
+
+crate::cfg_feature! {
+    #![feature = "wasm"]
+
+    /// A codec describing how to encode/decode [`gloo_net`] websocket
+    /// [`Message`](::gloo_net::websocket::Message)s.
+    #[derive(Debug, Clone)]
+    pub struct WasmCodec;
+}
+
+#[cfg(feature = "wasm")]
+mod wasm {
+    use super::*;
+
+    use gloo_net::websocket::Message;
+    use std::convert::Infallible;
+
+    impl MessageCodec for WasmCodec {
+        type Input = Message;
+        type Output = Message;
+        type Error = Infallible;
+
+        fn decode(msg: Self::Input) -> Result<Option<String>, Self::Error> {
+            Ok(match msg {
+                Message::Text(s) => Some(s),
+                Message::Bytes(_) => None,
+            })
+        }
+
+        fn encode(text: String) -> Self::Output {
+            Message::Text(text)
+        }
     }
 }
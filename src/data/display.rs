@@ -0,0 +1,760 @@
+//! Human-readable [`Display`](fmt::Display) implementations for response and event types.
+//!
+//! These are meant to be concise one-line summaries (e.g., for CLI tools and logs), and only
+//! include the most important fields. Use [`Debug`](fmt::Debug) to see everything.
+
+use crate::data::*;
+
+use std::fmt;
+
+/// Writes `name` if a model is loaded, or `"no model loaded"` otherwise.
+fn write_model(f: &mut fmt::Formatter<'_>, model_loaded: bool, name: &str) -> fmt::Result {
+    if model_loaded {
+        write!(f, "{:?}", name)
+    } else {
+        f.write_str("no model loaded")
+    }
+}
+
+fn on_off(value: bool) -> &'static str {
+    if value {
+        "on"
+    } else {
+        "off"
+    }
+}
+
+/// Implements `Display` for a type by writing a fixed string.
+macro_rules! display_str {
+    ($($ty:ty => $text:expr,)*) => {
+        $(
+            impl fmt::Display for $ty {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    f.write_str($text)
+                }
+            }
+        )*
+    };
+}
+
+display_str! {
+    MoveModelResponse => "model moved",
+    InjectParameterDataResponse => "parameter data injected",
+    ExpressionActivationResponse => "expression updated",
+    SetCurrentModelPhysicsResponse => "physics updated",
+}
+
+/// ```
+/// use vtubestudio::data::ModelPosition;
+///
+/// let position = ModelPosition {
+///     position_x: -0.5,
+///     position_y: 0.2,
+///     rotation: 15.0,
+///     size: 75.0,
+/// };
+///
+/// assert_eq!(position.to_string(), "pos=(-0.5, 0.2) rot=15° size=75%");
+/// ```
+impl fmt::Display for ModelPosition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "pos=({}, {}) rot={}° size={}%",
+            self.position_x, self.position_y, self.rotation, self.size
+        )
+    }
+}
+
+/// ```
+/// use vtubestudio::data::Parameter;
+///
+/// let param = Parameter {
+///     name: "FaceAngleX".to_owned(),
+///     value: 12.5,
+///     min: -30.0,
+///     max: 30.0,
+///     ..Default::default()
+/// };
+///
+/// assert_eq!(param.to_string(), "FaceAngleX (value: 12.5, range: [-30, 30])");
+/// ```
+impl fmt::Display for Parameter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} (value: {}, range: [{}, {}])",
+            self.name, self.value, self.min, self.max
+        )
+    }
+}
+
+impl fmt::Display for ApiStateResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "VTS {}, API {}, {}",
+            self.vtubestudio_version,
+            if self.active { "active" } else { "inactive" },
+            if self.current_session_authenticated {
+                "authenticated"
+            } else {
+                "not authenticated"
+            }
+        )
+    }
+}
+
+impl fmt::Display for EventSubscriptionResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} subscribed events", self.subscribed_event_count)?;
+        if !self.subscribed_events.is_empty() {
+            let names = self
+                .subscribed_events
+                .iter()
+                .map(EnumString::as_str)
+                .collect::<Vec<_>>();
+            write!(f, ": {}", names.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for AuthenticationTokenResponse {
+    /// The token itself is redacted.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("authentication token received")
+    }
+}
+
+impl fmt::Display for AuthenticationResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(if self.authenticated {
+            "authenticated"
+        } else {
+            "not authenticated"
+        })?;
+        if !self.reason.is_empty() {
+            write!(f, " ({})", self.reason)?;
+        }
+        Ok(())
+    }
+}
+
+/// ```
+/// use vtubestudio::data::StatisticsResponse;
+///
+/// let stats = StatisticsResponse {
+///     vtubestudio_version: "1.28.3".to_owned(),
+///     uptime: 3_600_000,
+///     framerate: 60,
+///     connected_plugins: 3,
+///     ..Default::default()
+/// };
+///
+/// assert_eq!(stats.to_string(), "VTS 1.28.3, running for 3600s, 60fps, 3 plugins");
+/// ```
+impl fmt::Display for StatisticsResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "VTS {}, running for {}s, {}fps, {} plugins",
+            self.vtubestudio_version,
+            self.uptime / 1000,
+            self.framerate,
+            self.connected_plugins
+        )
+    }
+}
+
+impl fmt::Display for VtsFolderInfoResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "models: {}, backgrounds: {}, items: {}",
+            self.models, self.backgrounds, self.items
+        )
+    }
+}
+
+impl fmt::Display for CurrentModelResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_model(f, self.model_loaded, &self.model_name)?;
+        if self.model_loaded {
+            write!(f, " at {}", self.model_position)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for AvailableModelsResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} available models", self.number_of_models)
+    }
+}
+
+impl fmt::Display for ModelLoadResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "loaded model {}", self.model_id)
+    }
+}
+
+impl fmt::Display for HotkeysInCurrentModelResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} hotkeys in ", self.available_hotkeys.len())?;
+        write_model(f, self.model_loaded, &self.model_name)
+    }
+}
+
+impl fmt::Display for HotkeyTriggerResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "triggered hotkey {}", self.hotkey_id)
+    }
+}
+
+impl fmt::Display for ArtMeshListResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} art meshes, {} tags",
+            self.number_of_art_mesh_names, self.number_of_art_mesh_tags
+        )
+    }
+}
+
+impl fmt::Display for ColorTintResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "tinted {} art meshes", self.matched_art_meshes)
+    }
+}
+
+impl fmt::Display for SceneColorOverlayInfoResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "color overlay {}, average color ({}, {}, {})",
+            on_off(self.active),
+            self.color_avg_r,
+            self.color_avg_g,
+            self.color_avg_b
+        )
+    }
+}
+
+impl fmt::Display for FaceFoundResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(if self.found {
+            "face found"
+        } else {
+            "face not found"
+        })
+    }
+}
+
+impl fmt::Display for InputParameterListResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} default and {} custom parameters in ",
+            self.default_parameters.len(),
+            self.custom_parameters.len()
+        )?;
+        write_model(f, self.model_loaded, &self.model_name)
+    }
+}
+
+impl fmt::Display for ParameterValueResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl fmt::Display for Live2DParameterListResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} Live2D parameters in ", self.parameters.len())?;
+        write_model(f, self.model_loaded, &self.model_name)
+    }
+}
+
+impl fmt::Display for ParameterCreationResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "created parameter {}", self.parameter_name)
+    }
+}
+
+impl fmt::Display for ParameterDeletionResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "deleted parameter {}", self.parameter_name)
+    }
+}
+
+impl fmt::Display for ExpressionStateResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let active = self.expressions.iter().filter(|e| e.active).count();
+        write!(
+            f,
+            "{} expressions ({} active) in ",
+            self.expressions.len(),
+            active
+        )?;
+        write_model(f, self.model_loaded, &self.model_name)
+    }
+}
+
+impl fmt::Display for NdiConfigResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "NDI {}", on_off(self.ndi_active))?;
+        if self.use_custom_resolution {
+            write!(f, ", {}x{}", self.custom_width_ndi, self.custom_height_ndi)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for GetCurrentModelPhysicsResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_model(f, self.model_loaded, &self.model_name)?;
+        write!(
+            f,
+            ": physics {}, {} groups",
+            on_off(self.model_has_physics && self.physics_switched_on),
+            self.physics_groups.len()
+        )?;
+        if self.api_physics_override_active {
+            write!(
+                f,
+                ", overridden by {}",
+                self.api_physics_override_plugin_name
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for ItemListResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}/{} items in scene, {} item files available",
+            self.items_in_scene_count,
+            self.total_items_allowed_count,
+            self.available_item_files.len()
+        )
+    }
+}
+
+impl fmt::Display for ItemLoadResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "loaded {} ({})", self.file_name, self.instance_id)
+    }
+}
+
+impl fmt::Display for ItemUnloadResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unloaded {} items", self.unloaded_items.len())
+    }
+}
+
+impl fmt::Display for ItemAnimationControlResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "frame {}, {}",
+            self.frame,
+            if self.animation_playing {
+                "playing"
+            } else {
+                "stopped"
+            }
+        )
+    }
+}
+
+impl fmt::Display for ItemMoveResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let moved = self.moved_items.iter().filter(|item| item.success).count();
+        write!(f, "moved {}/{} items", moved, self.moved_items.len())
+    }
+}
+
+impl fmt::Display for ArtMeshSelectionResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.success {
+            write!(f, "{} art meshes selected", self.active_art_meshes.len())
+        } else {
+            f.write_str("art mesh selection cancelled")
+        }
+    }
+}
+
+impl fmt::Display for ItemPinResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} ({}) {}",
+            self.item_file_name,
+            self.item_instance_id,
+            if self.is_pinned { "pinned" } else { "unpinned" }
+        )
+    }
+}
+
+impl fmt::Display for PermissionResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let granted = self
+            .permissions
+            .iter()
+            .filter(|status| status.granted)
+            .map(|status| status.name.as_str())
+            .collect::<Vec<_>>();
+
+        if let Some(permission) = &self.requested_permission {
+            write!(
+                f,
+                "{} {}, ",
+                permission,
+                if self.grant_success {
+                    "granted"
+                } else {
+                    "denied"
+                }
+            )?;
+        }
+
+        if granted.is_empty() {
+            f.write_str("no permissions granted")
+        } else {
+            write!(f, "granted: {}", granted.join(", "))
+        }
+    }
+}
+
+impl fmt::Display for PostProcessingListResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.post_processing_supported {
+            return f.write_str("post-processing not supported");
+        }
+
+        write!(
+            f,
+            "post-processing {}, {} active effects",
+            on_off(self.post_processing_active),
+            self.active_effect_count
+        )?;
+        if self.preset_is_active {
+            write!(f, ", preset {:?}", self.active_preset)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for PostProcessingUpdateResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "post-processing {}, {} active effects",
+            on_off(self.post_processing_active),
+            self.active_effect_count
+        )?;
+        if self.preset_is_active {
+            write!(f, ", preset {:?}", self.active_preset)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for TestEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} ({}s)", self.your_test_message, self.counter)
+    }
+}
+
+impl fmt::Display for ModelLoadedEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "model {:?} {}",
+            self.model_name,
+            if self.model_loaded {
+                "loaded"
+            } else {
+                "unloaded"
+            }
+        )
+    }
+}
+
+impl fmt::Display for TrackingStatusChangedEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let found = |value| if value { "found" } else { "lost" };
+        write!(
+            f,
+            "face {}, left hand {}, right hand {}",
+            found(self.face_found),
+            found(self.left_hand_found),
+            found(self.right_hand_found)
+        )
+    }
+}
+
+impl fmt::Display for BackgroundChangedEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "background changed to {:?}", self.background_name)
+    }
+}
+
+impl fmt::Display for ModelConfigChangedEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "config changed for model {:?}", self.model_name)?;
+        if self.hotkey_config_changed {
+            f.write_str(" (hotkeys changed)")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for ModelMovedEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "model {:?} moved to {}",
+            self.model_name, self.model_position
+        )
+    }
+}
+
+impl fmt::Display for ModelOutlineEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "model {:?} outline: {} points around {}",
+            self.model_name,
+            self.convex_hull.len(),
+            self.convex_hull_center
+        )
+    }
+}
+
+impl fmt::Display for HotkeyTriggeredEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "hotkey {:?} ({}) triggered",
+            self.hotkey_name, self.hotkey_action
+        )?;
+        if self.hotkey_triggered_by_api {
+            f.write_str(" by API")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for ModelAnimationEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "animation {:?} {} at {}s",
+            self.animation_name, self.animation_event_type, self.animation_event_time
+        )
+    }
+}
+
+impl fmt::Display for ItemEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "item {} ({}) {} at {}",
+            self.item_file_name, self.item_instance_id, self.item_event_type, self.item_position
+        )
+    }
+}
+
+impl fmt::Display for ModelClickedEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:?} click at {}",
+            self.mouse_button_id, self.click_position
+        )?;
+        if self.model_was_clicked {
+            write!(
+                f,
+                " on model {:?} ({} art meshes)",
+                self.loaded_model_name, self.clicked_art_mesh_count
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for PostProcessingEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "post-processing {}", on_off(self.current_on_state))?;
+        if !self.current_preset.is_empty() {
+            write!(f, ", preset {:?}", self.current_preset)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Live2DCubismEditorConnectedEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(if self.connected {
+            "Live2D Cubism Editor connected"
+        } else if self.trying_to_connect {
+            "connecting to Live2D Cubism Editor"
+        } else {
+            "Live2D Cubism Editor disconnected"
+        })
+    }
+}
+
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Event::Test(event) => event.fmt(f),
+            Event::ModelLoaded(event) => event.fmt(f),
+            Event::TrackingStatusChanged(event) => event.fmt(f),
+            Event::BackgroundChanged(event) => event.fmt(f),
+            Event::ModelConfigChanged(event) => event.fmt(f),
+            Event::ModelMoved(event) => event.fmt(f),
+            Event::ModelOutline(event) => event.fmt(f),
+            Event::HotkeyTriggered(event) => event.fmt(f),
+            Event::ModelAnimation(event) => event.fmt(f),
+            Event::Item(event) => event.fmt(f),
+            Event::ModelClicked(event) => event.fmt(f),
+            Event::PostProcessing(event) => event.fmt(f),
+            Event::Live2DCubismEditorConnected(event) => event.fmt(f),
+            Event::Unknown(data) => write!(f, "unknown event {}", data.message_type),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Formats every response and event type with default values, to make sure nothing panics.
+    #[test]
+    fn display_defaults() {
+        macro_rules! display {
+            ($($ty:ty),* $(,)?) => {
+                vec![$(<$ty>::default().to_string()),*]
+            };
+        }
+
+        let output = display![
+            ApiStateResponse,
+            EventSubscriptionResponse,
+            AuthenticationTokenResponse,
+            AuthenticationResponse,
+            StatisticsResponse,
+            VtsFolderInfoResponse,
+            CurrentModelResponse,
+            AvailableModelsResponse,
+            ModelLoadResponse,
+            MoveModelResponse,
+            HotkeysInCurrentModelResponse,
+            HotkeyTriggerResponse,
+            ArtMeshListResponse,
+            ColorTintResponse,
+            SceneColorOverlayInfoResponse,
+            FaceFoundResponse,
+            InputParameterListResponse,
+            ParameterValueResponse,
+            Live2DParameterListResponse,
+            ParameterCreationResponse,
+            ParameterDeletionResponse,
+            InjectParameterDataResponse,
+            ExpressionStateResponse,
+            ExpressionActivationResponse,
+            NdiConfigResponse,
+            GetCurrentModelPhysicsResponse,
+            SetCurrentModelPhysicsResponse,
+            ItemListResponse,
+            ItemLoadResponse,
+            ItemUnloadResponse,
+            ItemAnimationControlResponse,
+            ItemMoveResponse,
+            ArtMeshSelectionResponse,
+            ItemPinResponse,
+            PermissionResponse,
+            PostProcessingListResponse,
+            PostProcessingUpdateResponse,
+            TestEvent,
+            ModelLoadedEvent,
+            TrackingStatusChangedEvent,
+            BackgroundChangedEvent,
+            ModelConfigChangedEvent,
+            ModelMovedEvent,
+            ModelOutlineEvent,
+            HotkeyTriggeredEvent,
+            ModelAnimationEvent,
+            ItemEvent,
+            ModelClickedEvent,
+            PostProcessingEvent,
+            Live2DCubismEditorConnectedEvent,
+        ];
+
+        assert!(output.iter().all(|s| !s.is_empty()));
+
+        let event = Event::Item(ItemEvent::default());
+        assert_eq!(event.to_string(), ItemEvent::default().to_string());
+    }
+
+    #[test]
+    fn important_fields() {
+        let model = CurrentModelResponse {
+            model_loaded: true,
+            model_name: "Akari".into(),
+            model_position: ModelPosition {
+                position_x: 0.5,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert_eq!(
+            model.to_string(),
+            r#""Akari" at pos=(0.5, 0) rot=0° size=0%"#
+        );
+        assert_eq!(
+            CurrentModelResponse::default().to_string(),
+            "no model loaded"
+        );
+
+        let token = AuthenticationTokenResponse {
+            authentication_token: "secret".into(),
+        };
+        assert!(!token.to_string().contains("secret"));
+
+        let permissions = PermissionResponse {
+            grant_success: true,
+            requested_permission: Some(Permission::LoadCustomImagesAsItems.into()),
+            permissions: vec![PermissionStatus {
+                name: Permission::LoadCustomImagesAsItems.into(),
+                granted: true,
+            }],
+        };
+        assert_eq!(
+            permissions.to_string(),
+            "LoadCustomImagesAsItems granted, granted: LoadCustomImagesAsItems"
+        );
+
+        let item = ItemLoadResponse {
+            instance_id: "abc".into(),
+            file_name: "akari.png".into(),
+        };
+        assert_eq!(item.to_string(), "loaded akari.png (abc)");
+
+        let event = HotkeyTriggeredEvent {
+            hotkey_name: "Smile".into(),
+            hotkey_action: HotkeyAction::ToggleExpression.into(),
+            hotkey_triggered_by_api: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            event.to_string(),
+            r#"hotkey "Smile" (ToggleExpression) triggered by API"#
+        );
+    }
+}
@@ -68,9 +68,124 @@ impl<T> EnumString<T> {
     }
 
     /// Creates a new value from a `const` static string slice.
+    ///
+    /// This can be used for the `MESSAGE_TYPE` of custom [`Request`](crate::data::Request) and
+    /// [`Response`](crate::data::Response) types (see [`Request`](crate::data::Request) for a
+    /// complete example).
+    ///
+    /// ```
+    /// use vtubestudio::data::{EnumString, RequestType};
+    ///
+    /// const MESSAGE_TYPE: EnumString<RequestType> =
+    ///     EnumString::const_new_from_str("SomeNewRequest");
+    ///
+    /// assert_eq!(MESSAGE_TYPE.as_str(), "SomeNewRequest");
+    /// assert_eq!(MESSAGE_TYPE, EnumString::new_from_str("SomeNewRequest"));
+    /// ```
     pub const fn const_new_from_str(value: &'static str) -> Self {
         Self(EnumStringInner::Unknown(std::borrow::Cow::Borrowed(value)))
     }
+
+    /// Returns a reference to the known variant, or `None` if the value is an unknown string.
+    ///
+    /// Note that values created from strings (e.g., with [`new_from_str`](Self::new_from_str))
+    /// are not parsed, so they are always considered unknown, even if the string matches a known
+    /// variant. Deserialized values are known if they match a known variant.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use vtubestudio::data::{EnumString, HotkeyAction, HotkeyTriggeredEvent};
+    ///
+    /// fn is_expression(event: &HotkeyTriggeredEvent) -> bool {
+    ///     matches!(
+    ///         event.hotkey_action.as_known(),
+    ///         Some(HotkeyAction::ToggleExpression | HotkeyAction::RemoveAllExpressions)
+    ///     )
+    /// }
+    ///
+    /// let known = EnumString::new(HotkeyAction::ToggleExpression);
+    /// assert_eq!(known.as_known(), Some(&HotkeyAction::ToggleExpression));
+    ///
+    /// let unknown = EnumString::<HotkeyAction>::new_from_str("SomeNewAction");
+    /// assert_eq!(unknown.as_known(), None);
+    /// ```
+    pub fn as_known(&self) -> Option<&T> {
+        match &self.0 {
+            Enum::Known(value) => Some(value),
+            Enum::Unknown(_) => None,
+        }
+    }
+
+    /// Consumes the value and returns the known variant, or `Err(self)` if the value is an unknown
+    /// string. See [`as_known`](Self::as_known) for details.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use vtubestudio::data::{EnumString, HotkeyAction};
+    ///
+    /// let known = EnumString::new(HotkeyAction::MoveModel);
+    /// assert_eq!(known.try_into_known().ok(), Some(HotkeyAction::MoveModel));
+    ///
+    /// let unknown = EnumString::<HotkeyAction>::new_from_str("SomeNewAction");
+    /// let err = unknown.try_into_known().unwrap_err();
+    /// assert_eq!(err.as_str(), "SomeNewAction");
+    /// ```
+    pub fn try_into_known(self) -> Result<T, Self> {
+        match self.0 {
+            Enum::Known(value) => Ok(value),
+            unknown @ Enum::Unknown(_) => Err(Self(unknown)),
+        }
+    }
+
+    /// Consumes the value and returns the known variant, or `None` if the value is an unknown
+    /// string. Use [`try_into_known`](Self::try_into_known) to get the unknown value back instead.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use vtubestudio::data::{EnumString, HotkeyAction};
+    ///
+    /// let known = EnumString::new(HotkeyAction::MoveModel);
+    /// assert_eq!(known.into_known(), Some(HotkeyAction::MoveModel));
+    ///
+    /// let unknown = EnumString::<HotkeyAction>::new_from_str("SomeNewAction");
+    /// assert_eq!(unknown.into_known(), None);
+    /// ```
+    pub fn into_known(self) -> Option<T> {
+        self.try_into_known().ok()
+    }
+
+    /// Returns `true` if the value is a known variant. See [`as_known`](Self::as_known) for
+    /// details.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use vtubestudio::data::{EnumString, HotkeyAction};
+    ///
+    /// assert!(EnumString::new(HotkeyAction::MoveModel).is_known());
+    /// assert!(!EnumString::<HotkeyAction>::new_from_str("SomeNewAction").is_known());
+    /// ```
+    pub fn is_known(&self) -> bool {
+        matches!(self.0, Enum::Known(_))
+    }
+
+    /// Returns `true` if the value is an unknown string. See [`as_known`](Self::as_known) for
+    /// details.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use vtubestudio::data::{EnumString, HotkeyAction};
+    ///
+    /// assert!(EnumString::<HotkeyAction>::new_from_str("SomeNewAction").is_unknown());
+    /// assert!(!EnumString::new(HotkeyAction::MoveModel).is_unknown());
+    /// ```
+    pub fn is_unknown(&self) -> bool {
+        !self.is_known()
+    }
 }
 
 impl<T> From<T> for EnumString<T>
@@ -91,6 +206,29 @@ where
     }
 }
 
+impl<T> Eq for EnumString<T> where T: Serialize + Eq {}
+
+/// Hashes the string representation, so that known and unknown values that compare as equal also
+/// hash the same way.
+///
+/// ```
+/// use std::collections::HashSet;
+/// use vtubestudio::data::{EnumString, HotkeyAction};
+///
+/// let mut actions = HashSet::new();
+/// actions.insert(EnumString::new(HotkeyAction::ToggleExpression));
+///
+/// assert!(actions.contains(&EnumString::new_from_str("ToggleExpression")));
+/// ```
+impl<T> std::hash::Hash for EnumString<T>
+where
+    T: Serialize,
+{
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state);
+    }
+}
+
 impl<T> PartialEq<T> for EnumString<T>
 where
     T: Serialize + PartialEq,
@@ -197,6 +335,56 @@ where
     }
 }
 
+crate::cfg_feature! {
+    #![feature = "schemars"]
+    /// Described as a `string`, with the known variants as `examples`, since unknown values are
+    /// also accepted.
+    impl<T: schemars::JsonSchema> schemars::JsonSchema for EnumString<T> {
+        fn inline_schema() -> bool {
+            true
+        }
+
+        fn schema_name() -> Cow<'static, str> {
+            format!("EnumString_{}", T::schema_name()).into()
+        }
+
+        fn schema_id() -> Cow<'static, str> {
+            format!("{}::EnumString<{}>", module_path!(), T::schema_id()).into()
+        }
+
+        fn json_schema(generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+            let examples = known_variants(T::json_schema(generator).to_value());
+            schemars::json_schema!({
+                "type": "string",
+                "examples": examples,
+            })
+        }
+    }
+}
+
+// Collects the string values of a unit-only enum schema, which is either an `enum` array, or a
+// `oneOf` array of `const` schemas (for documented variants).
+#[cfg(feature = "schemars")]
+fn known_variants(schema: serde_json::Value) -> Vec<serde_json::Value> {
+    if let Some(values) = schema.get("enum").and_then(|v| v.as_array()) {
+        return values.clone();
+    }
+
+    schema
+        .get("oneOf")
+        .and_then(|v| v.as_array())
+        .map(|schemas| {
+            schemas
+                .iter()
+                .flat_map(|schema| match schema.get("const") {
+                    Some(value) => vec![value.clone()],
+                    None => known_variants(schema.clone()),
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
 #[derive(Debug)]
 pub(crate) struct VariantName;
 impl VariantName {
@@ -236,7 +424,7 @@ impl SerializeTupleVariant for TupleVariantName {
 }
 
 // Verbose serializer implementation that just extracts the enum variant name.
-impl<'a> Serializer for &'a mut VariantName {
+impl Serializer for &mut VariantName {
     type Ok = &'static str;
     type Error = VariantNameError;
 
@@ -420,20 +608,15 @@ mod tests {
 
     type Result = std::result::Result<(), Box<dyn std::error::Error>>;
 
-    #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+    #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
     pub enum LazuLight {
         #[serde(rename = "DaPomky")]
+        #[default]
         Pomu,
         Elira,
         Finana,
     }
 
-    impl Default for LazuLight {
-        fn default() -> Self {
-            LazuLight::Pomu
-        }
-    }
-
     type Nijisanji = EnumString<LazuLight>;
 
     #[test]
@@ -534,20 +717,80 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn known_variants() -> Result {
+        let known = Nijisanji::new(LazuLight::Elira);
+        assert!(known.is_known());
+        assert!(!known.is_unknown());
+        assert_eq!(known.as_known(), Some(&LazuLight::Elira));
+        assert_eq!(known.try_into_known().ok(), Some(LazuLight::Elira));
+
+        let unknown = Nijisanji::new_from_str("Petra");
+        assert!(!unknown.is_known());
+        assert!(unknown.is_unknown());
+        assert_eq!(unknown.as_known(), None);
+        assert_eq!(unknown.clone().try_into_known().unwrap_err(), unknown);
+
+        // Strings are not parsed, even if they match a known variant
+        let unparsed = Nijisanji::new_from_str("DaPomky");
+        assert!(unparsed.is_unknown());
+        assert_eq!(unparsed.as_known(), None);
+
+        // Deserialized values are known if they match a known variant
+        let deserialized = serde_json::from_value::<Nijisanji>(json!("DaPomky"))?;
+        assert_eq!(deserialized.as_known(), Some(&LazuLight::Pomu));
+
+        let deserialized = serde_json::from_value::<Nijisanji>(json!("Oliver"))?;
+        assert!(deserialized.is_unknown());
+        assert_eq!(
+            deserialized.try_into_known().unwrap_err().as_str(),
+            "Oliver"
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn is_event() -> Result {
         assert!(EnumString::new(ResponseType::TestEvent).is_event());
         assert!(EnumString::new_from_str("CoolNewEvent").is_event());
 
+        assert!(!EnumString::new(ResponseType::VtsFolderInfoResponse).is_event());
+        assert!(!EnumString::new_from_str("ExampleResponse").is_event());
+
+        Ok(())
+    }
+
+    #[test]
+    fn hash() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::collections::HashMap;
+        use std::hash::{Hash, Hasher};
+
+        fn hash_of<T: Hash>(value: &T) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            value.hash(&mut hasher);
+            hasher.finish()
+        }
+
         assert_eq!(
-            EnumString::new(ResponseType::VtsFolderInfoResponse).is_event(),
-            false
+            hash_of(&Nijisanji::new(LazuLight::Pomu)),
+            hash_of(&Nijisanji::new_from_str("DaPomky")),
         );
+
+        let mut handlers = HashMap::new();
+        handlers.insert(EnumString::new(ResponseType::ItemEvent), "item");
+        handlers.insert(EnumString::new_from_str("CoolNewEvent"), "new");
+
+        assert!(handlers.contains_key(&EnumString::new_from_str("ItemEvent")));
         assert_eq!(
-            EnumString::new_from_str("ExampleResponse").is_event(),
-            false
+            handlers.get(&EnumString::new_from_str("CoolNewEvent")),
+            Some(&"new")
         );
+        assert!(!handlers.contains_key(&EnumString::new(ResponseType::TestEvent)));
 
-        Ok(())
+        let mut ids = HashMap::new();
+        ids.insert(crate::data::RequestId::new("abc".into()), 1);
+        assert!(ids.contains_key(&crate::data::RequestId::new("abc".into())));
     }
 }
@@ -11,17 +11,20 @@ use std::convert::TryFrom;
 use std::fmt;
 
 /// The default `api_name` value in requests and responses.
-pub const API_NAME: &'static str = "VTubeStudioPublicAPI";
+pub const API_NAME: &str = "VTubeStudioPublicAPI";
 
 /// The default `api_version` value in requests and responses.
-pub const API_VERSION: &'static str = "1.0";
+pub const API_VERSION: &str = "1.0";
 
 /// Request ID used in [`RequestEnvelope`] and [`ResponseEnvelope`].
 ///
 /// This is a newtype wrapper rather than a plain `String` to allow for possible optimizations to
 /// the internal representation (using types optimized for small strings, etc).
-#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
-pub struct RequestId(smol_str::SmolStr);
+#[derive(Default, Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct RequestId(
+    #[cfg_attr(feature = "schemars", schemars(with = "String"))] smol_str::SmolStr,
+);
 
 impl RequestId {
     /// Creates a new [`RequestId`].
@@ -40,6 +43,46 @@ impl RequestId {
     }
 }
 
+crate::cfg_feature! {
+    #![any(feature = "rand", feature = "uuid")]
+    impl RequestId {
+        /// Generates a random request ID.
+        ///
+        /// With the `uuid` feature, this is a random (version 4) UUID formatted as 32 hex digits.
+        /// Otherwise, with the `rand` feature, this is a random 64-bit integer formatted as 16 hex
+        /// digits, which is short enough to be stored without allocating.
+        ///
+        /// ```
+        /// use vtubestudio::data::RequestId;
+        ///
+        /// assert_ne!(RequestId::generate(), RequestId::generate());
+        /// ```
+        pub fn generate() -> Self {
+            #[cfg(feature = "uuid")]
+            {
+                let mut buf = uuid::Uuid::encode_buffer();
+                let id = uuid::Uuid::new_v4().simple().encode_lower(&mut buf);
+                Self(smol_str::SmolStr::new(id))
+            }
+
+            #[cfg(not(feature = "uuid"))]
+            {
+                const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+                let mut buf = [0u8; 16];
+                let mut n = rand::random::<u64>();
+                for byte in buf.iter_mut().rev() {
+                    *byte = HEX_DIGITS[(n & 0xf) as usize];
+                    n >>= 4;
+                }
+
+                let digits = std::str::from_utf8(&buf).expect("digits are ASCII");
+                Self(smol_str::SmolStr::new_inline(digits))
+            }
+        }
+    }
+}
+
 impl From<String> for RequestId {
     fn from(value: String) -> Self {
         Self(value.into())
@@ -52,6 +95,35 @@ impl From<&str> for RequestId {
     }
 }
 
+/// Creates a request ID from the decimal representation of an integer, without allocating.
+///
+/// ```
+/// use vtubestudio::data::RequestId;
+///
+/// assert_eq!(RequestId::from(42).as_str(), "42");
+/// ```
+impl From<usize> for RequestId {
+    fn from(value: usize) -> Self {
+        // Enough digits for a 64-bit `usize`, which also fits in `SmolStr`'s inline representation
+        const MAX_DIGITS: usize = 20;
+
+        let mut buf = [0u8; MAX_DIGITS];
+        let mut start = MAX_DIGITS;
+        let mut n = value;
+        loop {
+            start -= 1;
+            buf[start] = b'0' + (n % 10) as u8;
+            n /= 10;
+            if n == 0 {
+                break;
+            }
+        }
+
+        let digits = std::str::from_utf8(&buf[start..]).expect("digits are ASCII");
+        Self(smol_str::SmolStr::new_inline(digits))
+    }
+}
+
 impl fmt::Display for RequestId {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.0)
@@ -82,13 +154,98 @@ impl OpaqueValue {
 
     /// Deserializes the value.
     pub fn deserialize<T: DeserializeOwned>(&self) -> Result<T, serde_json::Error> {
-        Ok(serde_json::from_str(self.0.get())?)
+        serde_json::from_str(self.0.get())
+    }
+
+    /// Returns the underlying raw JSON value, without deserializing it.
+    ///
+    /// ```
+    /// use vtubestudio::data::OpaqueValue;
+    ///
+    /// let value = OpaqueValue::new(&[1, 2, 3])?;
+    /// assert_eq!(value.get().get(), "[1,2,3]");
+    /// # Ok::<_, serde_json::Error>(())
+    /// ```
+    pub fn get(&self) -> &RawValue {
+        &self.0
+    }
+
+    /// Returns `true` if the value is the JSON literal `null`.
+    pub fn is_null(&self) -> bool {
+        self.0.get().trim() == "null"
+    }
+
+    /// Returns `true` if the value is an empty JSON object (`{}`).
+    ///
+    /// ```
+    /// use vtubestudio::data::OpaqueValue;
+    ///
+    /// let value: OpaqueValue = serde_json::from_str("{ }")?;
+    /// assert!(value.is_empty_object());
+    /// assert!(!value.is_null());
+    /// assert!(OpaqueValue::default().is_null());
+    /// # Ok::<_, serde_json::Error>(())
+    /// ```
+    pub fn is_empty_object(&self) -> bool {
+        self.0
+            .get()
+            .trim()
+            .strip_prefix('{')
+            .and_then(|s| s.strip_suffix('}'))
+            .is_some_and(|inner| inner.trim().is_empty())
+    }
+
+    /// Merges the fields of two JSON objects (non-recursively), with fields in `other` taking
+    /// priority over fields in `self`.
+    ///
+    /// Returns an error if either value is not a JSON object.
+    ///
+    /// ```
+    /// use serde_json::json;
+    /// use vtubestudio::data::OpaqueValue;
+    ///
+    /// let base = OpaqueValue::new(&json!({ "a": 1, "b": { "c": 2 } }))?;
+    /// let other = OpaqueValue::new(&json!({ "b": 3, "d": 4 }))?;
+    ///
+    /// assert_eq!(
+    ///     base.merge(&other)?.deserialize::<serde_json::Value>()?,
+    ///     json!({ "a": 1, "b": 3, "d": 4 }),
+    /// );
+    ///
+    /// assert!(base.merge(&OpaqueValue::new(&[1, 2])?).is_err());
+    /// # Ok::<_, serde_json::Error>(())
+    /// ```
+    pub fn merge(&self, other: &OpaqueValue) -> Result<OpaqueValue, serde_json::Error> {
+        type Object = serde_json::Map<String, serde_json::Value>;
+
+        let mut merged = self.deserialize::<Object>()?;
+        merged.extend(other.deserialize::<Object>()?);
+        OpaqueValue::new(&merged)
+    }
+}
+
+crate::cfg_feature! {
+    #![feature = "schemars"]
+    /// Described as an arbitrary JSON value.
+    impl schemars::JsonSchema for OpaqueValue {
+        fn inline_schema() -> bool {
+            true
+        }
+
+        fn schema_name() -> Cow<'static, str> {
+            "OpaqueValue".into()
+        }
+
+        fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+            true.into()
+        }
     }
 }
 
 /// A VTube Studio API request.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(test, derive(PartialEq))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct RequestEnvelope {
     /// API name, typically `"VTubeStudioPublicAPI"`.
@@ -126,7 +283,7 @@ impl RequestEnvelope {
 
     /// Sets the `data` field of a request.
     pub fn set_data<Req: Request>(&mut self, data: &Req) -> Result<(), serde_json::Error> {
-        self.message_type = Req::MESSAGE_TYPE.into();
+        self.message_type = Req::MESSAGE_TYPE;
         self.data = OpaqueValue::new(data)?;
         Ok(())
     }
@@ -136,6 +293,30 @@ impl RequestEnvelope {
         self.request_id = id.into();
         self
     }
+
+    /// Sets the API name, which defaults to [`API_NAME`].
+    pub fn with_api_name<T: Into<Cow<'static, str>>>(mut self, name: T) -> Self {
+        self.api_name = name.into();
+        self
+    }
+
+    /// Sets the API version, which defaults to [`API_VERSION`].
+    ///
+    /// This only changes the version sent in the request envelope, e.g., for testing against a
+    /// newer version of the API. The request and response types are unaffected.
+    ///
+    /// ```
+    /// use vtubestudio::data::{RequestEnvelope, StatisticsRequest, API_NAME};
+    ///
+    /// let req = RequestEnvelope::new(&StatisticsRequest {})?.with_api_version("1.1");
+    /// assert_eq!(req.api_name, API_NAME);
+    /// assert_eq!(req.api_version, "1.1");
+    /// # Ok::<(), serde_json::Error>(())
+    /// ```
+    pub fn with_api_version<T: Into<Cow<'static, str>>>(mut self, version: T) -> Self {
+        self.api_version = version.into();
+        self
+    }
 }
 
 /// A VTube Studio API response.
@@ -154,8 +335,23 @@ pub struct ResponseEnvelope {
     pub data: Result<ResponseData, ApiError>,
 }
 
-const API_ERROR_MESSAGE_TYPE: &'static EnumString<ResponseType> =
-    &EnumString::new(ResponseType::ApiError);
+/// Metadata from a [`ResponseEnvelope`], excluding the response data.
+///
+/// Returned by [`Client::send_with_meta`](crate::Client::send_with_meta).
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ResponseMeta {
+    /// API name, typically `"VTubeStudioPublicAPI"`.
+    pub api_name: Cow<'static, str>,
+    /// API version, typically `"1.0"`.
+    pub api_version: Cow<'static, str>,
+    /// Unix timestamp (in milliseconds) of the response, according to the VTube Studio host's
+    /// clock.
+    pub timestamp: i64,
+    /// The original request ID.
+    pub request_id: RequestId,
+}
+
+const API_ERROR_MESSAGE_TYPE: &EnumString<ResponseType> = &EnumString::new(ResponseType::ApiError);
 
 impl ResponseEnvelope {
     /// Creates a new response with the underlying typed data.
@@ -169,8 +365,8 @@ impl ResponseEnvelope {
     }
 
     /// Sets the request ID.
-    pub fn with_id(mut self, id: RequestId) -> Self {
-        self.request_id = id;
+    pub fn with_id<T: Into<RequestId>>(mut self, id: T) -> Self {
+        self.request_id = id.into();
         self
     }
 
@@ -180,7 +376,7 @@ impl ResponseEnvelope {
         Resp: Response + Serialize,
     {
         self.data = Ok(ResponseData {
-            message_type: Resp::MESSAGE_TYPE.into(),
+            message_type: Resp::MESSAGE_TYPE,
             data: OpaqueValue::new(data)?,
         });
         Ok(())
@@ -190,7 +386,7 @@ impl ResponseEnvelope {
     pub fn message_type(&self) -> &EnumString<ResponseType> {
         match &self.data {
             Ok(data) => &data.message_type,
-            Err(_) => &API_ERROR_MESSAGE_TYPE,
+            Err(_) => API_ERROR_MESSAGE_TYPE,
         }
     }
 
@@ -211,6 +407,54 @@ impl ResponseEnvelope {
         }
     }
 
+    /// Attempts to parse the response into the given [`Response`] type, without consuming the
+    /// envelope.
+    ///
+    /// This can return an error for the same reasons as [`parse`](Self::parse), but leaves the
+    /// envelope intact so that it can be parsed as another type afterwards.
+    ///
+    /// ```
+    /// use vtubestudio::data::{ApiStateResponse, ResponseEnvelope, StatisticsResponse};
+    ///
+    /// let resp = ResponseEnvelope::new(&ApiStateResponse::default())?;
+    ///
+    /// assert!(resp.try_parse::<StatisticsResponse>().is_err());
+    /// assert!(resp.try_parse::<ApiStateResponse>().is_ok());
+    /// # Ok::<(), vtubestudio::error::Error>(())
+    /// ```
+    pub fn try_parse<Resp: Response>(&self) -> Result<Resp, Error> {
+        let data = self.data.as_ref().map_err(|e| e.clone())?;
+
+        if data.message_type == Resp::MESSAGE_TYPE {
+            Ok(data.data.deserialize()?)
+        } else {
+            Err(UnexpectedResponseError {
+                expected: Resp::MESSAGE_TYPE,
+                received: data.message_type.clone(),
+            }
+            .into())
+        }
+    }
+
+    /// Attempts to parse the response into the given [`Response`] type, also returning the
+    /// response metadata.
+    ///
+    /// This can return an error for the same reasons as [`parse`](Self::parse).
+    pub fn parse_with_meta<Resp: Response>(self) -> Result<(Resp, ResponseMeta), Error> {
+        let meta = self.meta();
+        Ok((self.parse()?, meta))
+    }
+
+    /// Returns the metadata of this response, excluding the data.
+    pub fn meta(&self) -> ResponseMeta {
+        ResponseMeta {
+            api_name: self.api_name.clone(),
+            api_version: self.api_version.clone(),
+            timestamp: self.timestamp,
+            request_id: self.request_id.clone(),
+        }
+    }
+
     /// Attempts to parse the response as an [`Event`].
     ///
     /// This can return an error if the message type is an [`ApiError`] or has an unexpected JSON
@@ -227,10 +471,16 @@ impl ResponseEnvelope {
     }
 
     /// Returns `true` if the message is an `APIError` with
-    /// [`ErrorId::REQUEST_REQUIRES_AUTHENTICATION`](crate::data::ErrorId).
+    /// [`ErrorId::REQUEST_REQUIRES_AUTHENTICATION`](crate::data::ErrorId::REQUEST_REQUIRES_AUTHENTICATION).
     pub fn is_unauthenticated_error(&self) -> bool {
         matches!(&self.data, Err(e) if e.is_unauthenticated())
     }
+
+    /// Returns `true` if the message is an `APIError` with
+    /// [`ErrorId::REQUEST_REQUIRES_PERMISSION`](crate::data::ErrorId::REQUEST_REQUIRES_PERMISSION).
+    pub fn is_permission_error(&self) -> bool {
+        matches!(&self.data, Err(e) if e.is_permission_error())
+    }
 }
 
 /// Response data wrapper for [`ResponseEnvelope`] (typically for non-error responses).
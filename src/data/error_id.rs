@@ -1,10 +1,25 @@
-use serde::{Deserialize, Serialize};
+use crate::data::lenient::de_i32_lenient;
+
+use serde::{Deserialize, Deserializer, Serialize};
 use std::fmt;
+use std::ops::RangeInclusive;
 
 /// Error ID returned in [`ApiError`](crate::data::ApiError) responses.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+///
+/// This serializes as a number, but can be deserialized from either a number or a numeric string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ErrorId(i32);
 
+impl<'de> Deserialize<'de> for ErrorId {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        de_i32_lenient(deserializer).map(Self)
+    }
+}
+
 impl ErrorId {
     /// Creates a new error ID.
     pub fn new(value: i32) -> Self {
@@ -20,6 +35,305 @@ impl ErrorId {
     pub fn is_unauthenticated(&self) -> bool {
         self == Self::REQUEST_REQUIRES_AUTHENTICATION
     }
+
+    /// Returns true if this is an [`ErrorId::REQUEST_REQUIRES_PERMISSION`] error.
+    ///
+    /// Unlike [`is_unauthenticated`](Self::is_unauthenticated), this can't be fixed by
+    /// reauthenticating. The user needs to grant the plugin the required permission first (e.g.,
+    /// via a [`PermissionRequest`](crate::data::PermissionRequest)).
+    pub fn is_permission_error(&self) -> bool {
+        self == Self::REQUEST_REQUIRES_PERMISSION
+    }
+
+    /// Returns true if the same request might succeed if it's retried later.
+    ///
+    /// This includes cooldown errors (see [`is_cooldown`](Self::is_cooldown)) and errors caused
+    /// by VTube Studio being temporarily busy (e.g., [`ErrorId::HOTKEY_QUEUE_FULL`]). Validation
+    /// errors (e.g., [`ErrorId::CUSTOM_PARAM_NAME_INVALID`]) are not retryable.
+    ///
+    /// Note that [`ErrorId::REQUEST_REQUIRES_AUTHENTICATION`] returns `false`, since it needs
+    /// reauthentication before retrying (which is handled by
+    /// [`Authentication`](crate::service::Authentication)).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use vtubestudio::error::{ApiError, ErrorId};
+    /// # let err = ApiError { error_id: ErrorId::HOTKEY_COOLDOWN_NOT_OVER, message: "".into() };
+    /// if err.error_id.is_retryable() {
+    ///     // Wait a bit and try again
+    /// } else {
+    ///     // Give up
+    /// }
+    /// # assert!(err.error_id.is_retryable());
+    /// # assert!(!ErrorId::CUSTOM_PARAM_NAME_INVALID.is_retryable());
+    /// ```
+    pub fn is_retryable(&self) -> bool {
+        self.is_cooldown()
+            || Self::RETRYABLE_RANGES
+                .iter()
+                .any(|range| range.contains(&self.0))
+    }
+
+    /// Ranges of error IDs that are [`is_retryable`](Self::is_retryable), other than
+    /// [`COOLDOWN_ERRORS`](Self::COOLDOWN_ERRORS).
+    ///
+    /// Each range is contained in one of the [`CATEGORY_RANGES`](Self::CATEGORY_RANGES), and
+    /// covers errors caused by VTube Studio being temporarily busy.
+    pub const RETRYABLE_RANGES: &'static [RangeInclusive<i32>] = &[
+        // InternalServerError
+        0..=0,
+        // TokenRequestCurrentlyOngoing
+        51..=51,
+        // CannotCurrentlyChangeModel
+        154..=154,
+        // HotkeyQueueFull
+        200..=200,
+        // CannotCurrentlyLoadItem
+        753..=753,
+        // ItemCustomDataCannotAskRightNow
+        759..=759,
+        // ItemUnloadRequest
+        800..=849,
+        // ItemMoveRequestCannotCurrentlyChangeOrder
+        903..=903,
+        // ArtMeshSelectionRequestOtherWindowsOpen
+        1001..=1001,
+        // PermissionRequestCannotRequestRightNow
+        1101..=1101,
+        // PostProcessingUpdateReqestCannotUpdateRightNow
+        1200..=1200,
+    ];
+
+    /// Ranges of error IDs and the [`category`](Self::category) they belong to.
+    ///
+    /// Some categories span multiple ranges (e.g., `"model"` includes both the
+    /// [`ModelLoadRequest`](crate::data::ModelLoadRequest) and
+    /// [`MoveModelRequest`](crate::data::MoveModelRequest) errors).
+    pub const CATEGORY_RANGES: &'static [(RangeInclusive<i32>, &'static str)] = &[
+        (0..=49, "general"),
+        (50..=149, "authentication"),
+        // ModelLoadRequest
+        (150..=199, "model"),
+        (200..=249, "hotkey"),
+        // ColorTintRequest, MoveModelRequest
+        (250..=349, "model"),
+        // ParameterCreationRequest, ParameterDeletionRequest, InjectParameterDataRequest,
+        // ParameterValueRequest
+        (350..=549, "parameter"),
+        (550..=599, "ndi"),
+        (600..=699, "expression"),
+        (700..=749, "physics"),
+        // ItemLoadRequest, ItemUnloadRequest, ItemAnimationControlRequest, ItemMoveRequest
+        (750..=949, "item"),
+        (950..=999, "event"),
+        (1000..=1049, "art_mesh"),
+        // ItemPinRequest
+        (1050..=1099, "item"),
+        (1100..=1149, "permission"),
+        (1150..=1249, "post_processing"),
+        (100_000..=199_999, "event"),
+    ];
+
+    /// Returns a short name for the group of requests this error relates to, based on the
+    /// numeric range of the error ID.
+    ///
+    /// One of `"general"`, `"authentication"`, `"model"`, `"hotkey"`, `"parameter"`, `"ndi"`,
+    /// `"expression"`, `"physics"`, `"item"`, `"event"`, `"art_mesh"`, `"permission"`,
+    /// `"post_processing"`, or `"unknown"`.
+    ///
+    /// ```
+    /// # use vtubestudio::error::ErrorId;
+    /// assert_eq!(ErrorId::HOTKEY_QUEUE_FULL.category(), "hotkey");
+    /// assert_eq!(ErrorId::ITEM_PIN_REQUEST_MODEL_NOT_FOUND.category(), "item");
+    /// assert_eq!(ErrorId::new(99_999).category(), "unknown");
+    /// ```
+    pub fn category(&self) -> &'static str {
+        Self::CATEGORY_RANGES
+            .iter()
+            .find(|(range, _)| range.contains(&self.0))
+            .map_or("unknown", |(_, category)| category)
+    }
+
+    /// Alias for [`category`](Self::category).
+    ///
+    /// ```
+    /// # use vtubestudio::error::ErrorId;
+    /// assert_eq!(ErrorId::INTERNAL_SERVER_ERROR.subsystem(), "general");
+    /// ```
+    pub fn subsystem(&self) -> &'static str {
+        self.category()
+    }
+
+    /// Returns true if this is one of the general API errors (e.g., [`ErrorId::JSON_INVALID`]).
+    ///
+    /// Equivalent to checking whether [`category`](Self::category) is `"general"`.
+    pub fn is_general_error(&self) -> bool {
+        self.category() == "general"
+    }
+
+    /// Returns true if this error relates to [`AuthenticationTokenRequest`](crate::data::AuthenticationTokenRequest) or
+    /// [`AuthenticationRequest`](crate::data::AuthenticationRequest).
+    ///
+    /// Equivalent to checking whether [`category`](Self::category) is `"authentication"`.
+    pub fn is_authentication_error(&self) -> bool {
+        self.category() == "authentication"
+    }
+
+    /// Returns true if this error relates to [`ModelLoadRequest`](crate::data::ModelLoadRequest),
+    /// [`ColorTintRequest`](crate::data::ColorTintRequest), or
+    /// [`MoveModelRequest`](crate::data::MoveModelRequest).
+    ///
+    /// Equivalent to checking whether [`category`](Self::category) is `"model"`.
+    pub fn is_model_error(&self) -> bool {
+        self.category() == "model"
+    }
+
+    /// Returns true if this error relates to [`HotkeyTriggerRequest`](crate::data::HotkeyTriggerRequest).
+    ///
+    /// Equivalent to checking whether [`category`](Self::category) is `"hotkey"`.
+    pub fn is_hotkey_error(&self) -> bool {
+        self.category() == "hotkey"
+    }
+
+    /// Returns true if this error relates to parameter creation, deletion, injection, or value requests.
+    ///
+    /// Equivalent to checking whether [`category`](Self::category) is `"parameter"`.
+    pub fn is_parameter_error(&self) -> bool {
+        self.category() == "parameter"
+    }
+
+    /// Returns true if this error relates to [`NdiConfigRequest`](crate::data::NdiConfigRequest).
+    ///
+    /// Equivalent to checking whether [`category`](Self::category) is `"ndi"`.
+    pub fn is_ndi_error(&self) -> bool {
+        self.category() == "ndi"
+    }
+
+    /// Returns true if this error relates to [`ExpressionStateRequest`](crate::data::ExpressionStateRequest) or
+    /// [`ExpressionActivationRequest`](crate::data::ExpressionActivationRequest).
+    ///
+    /// Equivalent to checking whether [`category`](Self::category) is `"expression"`.
+    pub fn is_expression_error(&self) -> bool {
+        self.category() == "expression"
+    }
+
+    /// Returns true if this error relates to [`SetCurrentModelPhysicsRequest`](crate::data::SetCurrentModelPhysicsRequest).
+    ///
+    /// Equivalent to checking whether [`category`](Self::category) is `"physics"`.
+    pub fn is_physics_error(&self) -> bool {
+        self.category() == "physics"
+    }
+
+    /// Returns true if this error relates to item load, unload, animation control, move, or pin requests.
+    ///
+    /// Equivalent to checking whether [`category`](Self::category) is `"item"`.
+    pub fn is_item_error(&self) -> bool {
+        self.category() == "item"
+    }
+
+    /// Returns true if this error relates to [`EventSubscriptionRequest`](crate::data::EventSubscriptionRequest) or event configs.
+    ///
+    /// Equivalent to checking whether [`category`](Self::category) is `"event"`.
+    pub fn is_event_error(&self) -> bool {
+        self.category() == "event"
+    }
+
+    /// Returns true if this error relates to [`ArtMeshSelectionRequest`](crate::data::ArtMeshSelectionRequest).
+    ///
+    /// Equivalent to checking whether [`category`](Self::category) is `"art_mesh"`.
+    pub fn is_art_mesh_error(&self) -> bool {
+        self.category() == "art_mesh"
+    }
+
+    /// Returns true if this error relates to [`PermissionRequest`](crate::data::PermissionRequest).
+    ///
+    /// Equivalent to checking whether [`category`](Self::category) is `"permission"`.
+    ///
+    /// This is unrelated to [`is_permission_error`](Self::is_permission_error), which checks
+    /// whether a request was rejected due to a missing permission.
+    pub fn is_permission_request_error(&self) -> bool {
+        self.category() == "permission"
+    }
+
+    /// Returns true if this error relates to post-processing requests.
+    ///
+    /// Equivalent to checking whether [`category`](Self::category) is `"post_processing"`.
+    pub fn is_post_processing_error(&self) -> bool {
+        self.category() == "post_processing"
+    }
+
+    /// Alias for [`is_authentication_error`](Self::is_authentication_error).
+    pub fn is_authentication_related(&self) -> bool {
+        self.is_authentication_error()
+    }
+
+    /// Alias for [`is_model_error`](Self::is_model_error).
+    pub fn is_model_related(&self) -> bool {
+        self.is_model_error()
+    }
+
+    /// Alias for [`is_hotkey_error`](Self::is_hotkey_error).
+    pub fn is_hotkey_related(&self) -> bool {
+        self.is_hotkey_error()
+    }
+
+    /// Alias for [`is_parameter_error`](Self::is_parameter_error).
+    pub fn is_parameter_related(&self) -> bool {
+        self.is_parameter_error()
+    }
+
+    /// Alias for [`is_item_error`](Self::is_item_error).
+    pub fn is_item_related(&self) -> bool {
+        self.is_item_error()
+    }
+
+    /// Alias for [`is_physics_error`](Self::is_physics_error).
+    pub fn is_physics_related(&self) -> bool {
+        self.is_physics_error()
+    }
+
+    /// Error IDs indicating that a cooldown period has not elapsed yet.
+    pub const COOLDOWN_ERRORS: &'static [ErrorId] = &[
+        Self::MODEL_LOAD_COOLDOWN_NOT_OVER,
+        Self::HOTKEY_COOLDOWN_NOT_OVER,
+        Self::NDI_CONFIG_COOLDOWN_NOT_OVER,
+        Self::ITEM_LOAD_LOAD_COOLDOWN_NOT_OVER,
+    ];
+
+    /// Error IDs indicating that the requested model, hotkey, parameter, etc. could not be found.
+    pub const NOT_FOUND_ERRORS: &'static [ErrorId] = &[
+        Self::MODEL_ID_NOT_FOUND,
+        Self::HOTKEY_ID_NOT_FOUND_IN_MODEL,
+        Self::HOTKEY_EXECUTION_FAILED_BECAUSE_LIVE2D_ITEM_NOT_FOUND,
+        Self::CUSTOM_PARAM_DELETION_NOT_FOUND,
+        Self::INJECT_DATA_PARAM_NAME_NOT_FOUND,
+        Self::PARAMETER_VALUE_REQUEST_PARAMETER_NOT_FOUND,
+        Self::EXPRESSION_STATE_REQUEST_FILE_NOT_FOUND,
+        Self::EXPRESSION_ACTIVATION_REQUEST_FILE_NOT_FOUND,
+        Self::SET_CURRENT_MODEL_PHYSICS_REQUEST_PHYSICS_GROUP_ID_NOT_FOUND,
+        Self::ITEM_FILE_NAME_NOT_FOUND,
+        Self::ITEM_ANIMATION_CONTROL_INSTANCE_ID_NOT_FOUND,
+        Self::ITEM_MOVE_REQUEST_INSTANCE_ID_NOT_FOUND,
+        Self::ITEM_PIN_REQUEST_MODEL_NOT_FOUND,
+        Self::ITEM_PIN_REQUEST_ART_MESH_NOT_FOUND,
+    ];
+
+    /// Returns true if this error indicates that a cooldown period has not elapsed yet (e.g.,
+    /// [`ErrorId::MODEL_LOAD_COOLDOWN_NOT_OVER`]).
+    ///
+    /// See [`ErrorId::COOLDOWN_ERRORS`] for the full list.
+    pub fn is_cooldown(&self) -> bool {
+        Self::COOLDOWN_ERRORS.contains(self)
+    }
+
+    /// Returns true if this error indicates that the requested model, hotkey, parameter, etc.
+    /// could not be found (e.g., [`ErrorId::MODEL_ID_NOT_FOUND`]).
+    ///
+    /// See [`ErrorId::NOT_FOUND_ERRORS`] for the full list.
+    pub fn is_not_found(&self) -> bool {
+        Self::NOT_FOUND_ERRORS.contains(self)
+    }
 }
 
 impl From<i32> for ErrorId {
@@ -91,6 +405,10 @@ macro_rules! define_error_ids {
                 pub const $rust_name: ErrorId = ErrorId($id);
             )+
 
+            /// All known error IDs, in order.
+            #[cfg(test)]
+            const ALL: &'static [ErrorId] = &[$(Self::$rust_name),+];
+
         }
     }
 }
@@ -263,3 +581,246 @@ define_error_ids! {
     (100_000, EVENT_TEST_EVENT_TEST_MESSAGE_TOO_LONG, Event_TestEvent_TestMessageTooLong),
     (100_050, EVENT_MODEL_LOADED_EVENT_MODEL_ID_INVALID, Event_ModelLoadedEvent_ModelIDInvalid),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Predicate = fn(&ErrorId) -> bool;
+
+    const GROUPS: &[Predicate] = &[
+        ErrorId::is_general_error,
+        ErrorId::is_authentication_error,
+        ErrorId::is_model_error,
+        ErrorId::is_hotkey_error,
+        ErrorId::is_parameter_error,
+        ErrorId::is_ndi_error,
+        ErrorId::is_expression_error,
+        ErrorId::is_physics_error,
+        ErrorId::is_item_error,
+        ErrorId::is_event_error,
+        ErrorId::is_art_mesh_error,
+        ErrorId::is_permission_request_error,
+        ErrorId::is_post_processing_error,
+    ];
+
+    #[test]
+    fn group_helpers() {
+        let cases: &[(ErrorId, Predicate)] = &[
+            (ErrorId::JSON_INVALID, ErrorId::is_general_error),
+            (
+                ErrorId::TOKEN_REQUEST_DENIED,
+                ErrorId::is_authentication_error,
+            ),
+            (
+                ErrorId::AUTHENTICATION_TOKEN_MISSING,
+                ErrorId::is_authentication_error,
+            ),
+            (ErrorId::MODEL_ID_NOT_FOUND, ErrorId::is_model_error),
+            (
+                ErrorId::COLOR_TINT_REQUEST_NO_MODEL_LOADED,
+                ErrorId::is_model_error,
+            ),
+            (
+                ErrorId::MOVE_MODEL_REQUEST_VALUES_OUT_OF_RANGE,
+                ErrorId::is_model_error,
+            ),
+            (ErrorId::HOTKEY_QUEUE_FULL, ErrorId::is_hotkey_error),
+            (
+                ErrorId::CUSTOM_PARAM_NAME_INVALID,
+                ErrorId::is_parameter_error,
+            ),
+            (
+                ErrorId::PARAMETER_VALUE_REQUEST_PARAMETER_NOT_FOUND,
+                ErrorId::is_parameter_error,
+            ),
+            (
+                ErrorId::NDI_CONFIG_RESOLUTION_INVALID,
+                ErrorId::is_ndi_error,
+            ),
+            (
+                ErrorId::EXPRESSION_ACTIVATION_REQUEST_FILE_NOT_FOUND,
+                ErrorId::is_expression_error,
+            ),
+            (
+                ErrorId::SET_CURRENT_MODEL_PHYSICS_REQUEST_DUPLICATE_PHYSICS_GROUP_ID,
+                ErrorId::is_physics_error,
+            ),
+            (
+                ErrorId::ITEM_CUSTOM_DATA_LOAD_REQUEST_REJECTED_BY_USER,
+                ErrorId::is_item_error,
+            ),
+            (
+                ErrorId::ITEM_PIN_REQUEST_PIN_POSITION_INVALID,
+                ErrorId::is_item_error,
+            ),
+            (
+                ErrorId::EVENT_SUBSCRIPTION_REQUEST_EVENT_TYPE_UNKNOWN,
+                ErrorId::is_event_error,
+            ),
+            (
+                ErrorId::EVENT_MODEL_LOADED_EVENT_MODEL_ID_INVALID,
+                ErrorId::is_event_error,
+            ),
+            (
+                ErrorId::ART_MESH_SELECTION_REQUEST_OTHER_WINDOWS_OPEN,
+                ErrorId::is_art_mesh_error,
+            ),
+            (
+                ErrorId::PERMISSION_REQUEST_FILE_PROBLEM,
+                ErrorId::is_permission_request_error,
+            ),
+            (
+                ErrorId::POST_PROCESSING_UPDATE_REQUEST_FADE_TIME_INVALID,
+                ErrorId::is_post_processing_error,
+            ),
+        ];
+
+        for (id, predicate) in cases {
+            assert!(predicate(id), "{}", id);
+
+            // Each error ID belongs to exactly one group
+            let matching = GROUPS.iter().filter(|group| group(id)).count();
+            assert_eq!(matching, 1, "{}", id);
+        }
+
+        let unknown = ErrorId::new(99_999);
+        assert!(GROUPS.iter().all(|group| !group(&unknown)));
+    }
+
+    #[test]
+    fn error_kinds() {
+        use crate::data::ApiError;
+        use crate::error::{Error, ErrorKind};
+
+        for id in ErrorId::ALL {
+            // Also catches new cooldown/not-found IDs that haven't been added to the lists
+            let name = id.name().unwrap();
+            let expected = if name.ends_with("CooldownNotOver") {
+                ErrorKind::Cooldown
+            } else if name.contains("NotFound") {
+                ErrorKind::NotFound
+            } else {
+                ErrorKind::Api
+            };
+
+            let error = Error::from(ApiError {
+                error_id: *id,
+                message: String::new(),
+            });
+            assert_eq!(error.kind(), &expected, "{}", id);
+        }
+
+        // Unknown IDs are never classified
+        assert!(!ErrorId::new(99_999).is_cooldown());
+        assert!(!ErrorId::new(99_999).is_not_found());
+    }
+
+    #[test]
+    fn retryable() {
+        for range in ErrorId::RETRYABLE_RANGES {
+            let (start, end) = (ErrorId::new(*range.start()), ErrorId::new(*range.end()));
+            assert!(start.is_retryable() && end.is_retryable(), "{:?}", range);
+
+            // Each range stays within a single category
+            assert_ne!(start.category(), "unknown", "{:?}", range);
+            assert!(
+                ErrorId::CATEGORY_RANGES
+                    .iter()
+                    .any(|(r, _)| r.contains(range.start()) && r.contains(range.end())),
+                "{:?}",
+                range
+            );
+        }
+
+        let retryable = [
+            ErrorId::INTERNAL_SERVER_ERROR,
+            ErrorId::TOKEN_REQUEST_CURRENTLY_ONGOING,
+            ErrorId::MODEL_LOAD_COOLDOWN_NOT_OVER,
+            ErrorId::CANNOT_CURRENTLY_CHANGE_MODEL,
+            ErrorId::HOTKEY_QUEUE_FULL,
+            ErrorId::HOTKEY_COOLDOWN_NOT_OVER,
+            ErrorId::NDI_CONFIG_COOLDOWN_NOT_OVER,
+            ErrorId::ITEM_LOAD_LOAD_COOLDOWN_NOT_OVER,
+            ErrorId::CANNOT_CURRENTLY_LOAD_ITEM,
+            ErrorId::ITEM_CUSTOM_DATA_CANNOT_ASK_RIGHT_NOW,
+            ErrorId::CANNOT_CURRENTLY_UNLOAD_ITEM,
+            ErrorId::ITEM_MOVE_REQUEST_CANNOT_CURRENTLY_CHANGE_ORDER,
+            ErrorId::ART_MESH_SELECTION_REQUEST_OTHER_WINDOWS_OPEN,
+            ErrorId::PERMISSION_REQUEST_CANNOT_REQUEST_RIGHT_NOW,
+            ErrorId::POST_PROCESSING_UPDATE_REQEST_CANNOT_UPDATE_RIGHT_NOW,
+        ];
+        for id in ErrorId::ALL {
+            assert_eq!(id.is_retryable(), retryable.contains(id), "{}", id);
+        }
+
+        // New IDs in a retryable range are retried too
+        assert!(ErrorId::new(801).is_retryable());
+        assert!(!ErrorId::new(99_999).is_retryable());
+    }
+
+    #[test]
+    fn related_aliases() {
+        let cases: &[(Predicate, &[i32], &[i32])] = &[
+            (
+                ErrorId::is_authentication_related,
+                &[50, 54, 100, 149],
+                &[49, 150],
+            ),
+            (
+                ErrorId::is_model_related,
+                &[150, 199, 250, 349],
+                &[149, 200, 350],
+            ),
+            (ErrorId::is_hotkey_related, &[200, 208, 249], &[199, 250]),
+            (ErrorId::is_parameter_related, &[350, 455, 549], &[349, 550]),
+            (ErrorId::is_physics_related, &[700, 749], &[699, 750]),
+            (
+                ErrorId::is_item_related,
+                &[750, 949, 1050, 1099],
+                &[749, 950, 1100],
+            ),
+        ];
+
+        for (predicate, inside, outside) in cases {
+            for id in *inside {
+                assert!(predicate(&ErrorId::new(*id)), "{}", id);
+            }
+            for id in *outside {
+                assert!(!predicate(&ErrorId::new(*id)), "{}", id);
+            }
+        }
+
+        assert_eq!(ErrorId::INTERNAL_SERVER_ERROR.subsystem(), "general");
+        assert_eq!(ErrorId::HOTKEY_QUEUE_FULL.subsystem(), "hotkey");
+    }
+
+    #[test]
+    fn category_range_boundaries() {
+        assert_eq!(ErrorId::INTERNAL_SERVER_ERROR.category(), "general");
+
+        for (range, category) in ErrorId::CATEGORY_RANGES {
+            for id in [*range.start(), *range.end()] {
+                assert_eq!(ErrorId::new(id).category(), *category, "{}", id);
+            }
+        }
+
+        let cases = [
+            (49, "general"),
+            (50, "authentication"),
+            (149, "authentication"),
+            (150, "model"),
+            (249, "hotkey"),
+            (250, "model"),
+            (749, "physics"),
+            (750, "item"),
+            (1249, "post_processing"),
+            (1250, "unknown"),
+            (-1, "unknown"),
+            (200_000, "unknown"),
+        ];
+        for (id, category) in cases {
+            assert_eq!(ErrorId::new(id).category(), category, "{}", id);
+        }
+    }
+}
@@ -0,0 +1,218 @@
+//! Lenient `serde` deserializers for fields whose JSON type has changed between VTube Studio
+//! versions (e.g., numbers sent as strings, or booleans sent as `0`/`1`).
+//!
+//! These are used with `#[serde(deserialize_with = "...")]`, and always serialize as the
+//! documented type.
+
+use serde::de::{self, Deserializer, Unexpected, Visitor};
+use std::convert::TryFrom;
+use std::fmt;
+
+/// Accepts a boolean, the numbers `0` and `1`, or the strings `"true"`, `"false"`, `"0"` and `"1"`.
+pub(crate) fn de_bool_lenient<'de, D>(deserializer: D) -> Result<bool, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    struct BoolVisitor;
+
+    impl<'de> Visitor<'de> for BoolVisitor {
+        type Value = bool;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("a boolean, 0, 1, or a string containing one of those")
+        }
+
+        fn visit_bool<E: de::Error>(self, value: bool) -> Result<bool, E> {
+            Ok(value)
+        }
+
+        fn visit_i64<E: de::Error>(self, value: i64) -> Result<bool, E> {
+            match value {
+                0 => Ok(false),
+                1 => Ok(true),
+                _ => Err(E::invalid_value(Unexpected::Signed(value), &self)),
+            }
+        }
+
+        fn visit_u64<E: de::Error>(self, value: u64) -> Result<bool, E> {
+            match value {
+                0 => Ok(false),
+                1 => Ok(true),
+                _ => Err(E::invalid_value(Unexpected::Unsigned(value), &self)),
+            }
+        }
+
+        fn visit_str<E: de::Error>(self, value: &str) -> Result<bool, E> {
+            match value.trim() {
+                "true" | "True" | "1" => Ok(true),
+                "false" | "False" | "0" => Ok(false),
+                _ => Err(E::invalid_value(Unexpected::Str(value), &self)),
+            }
+        }
+    }
+
+    deserializer.deserialize_any(BoolVisitor)
+}
+
+/// Accepts an integer, a float with no fractional part, or a string containing either.
+pub(crate) fn de_i64_lenient<'de, D>(deserializer: D) -> Result<i64, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    struct I64Visitor;
+
+    impl<'de> Visitor<'de> for I64Visitor {
+        type Value = i64;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("an integer, or a string containing an integer")
+        }
+
+        fn visit_i64<E: de::Error>(self, value: i64) -> Result<i64, E> {
+            Ok(value)
+        }
+
+        fn visit_u64<E: de::Error>(self, value: u64) -> Result<i64, E> {
+            i64::try_from(value).map_err(|_| E::invalid_value(Unexpected::Unsigned(value), &self))
+        }
+
+        fn visit_f64<E: de::Error>(self, value: f64) -> Result<i64, E> {
+            if value.fract() == 0.0 && value >= i64::MIN as f64 && value <= i64::MAX as f64 {
+                Ok(value as i64)
+            } else {
+                Err(E::invalid_value(Unexpected::Float(value), &self))
+            }
+        }
+
+        fn visit_str<E: de::Error>(self, value: &str) -> Result<i64, E> {
+            let trimmed = value.trim();
+            match trimmed.parse::<i64>() {
+                Ok(value) => Ok(value),
+                Err(_) => match trimmed.parse::<f64>() {
+                    Ok(float) => self.visit_f64(float),
+                    Err(_) => Err(E::invalid_value(Unexpected::Str(value), &self)),
+                },
+            }
+        }
+    }
+
+    deserializer.deserialize_any(I64Visitor)
+}
+
+/// Like [`de_i64_lenient`], but fails if the value doesn't fit in an `i32`.
+pub(crate) fn de_i32_lenient<'de, D>(deserializer: D) -> Result<i32, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = de_i64_lenient(deserializer)?;
+    i32::try_from(value).map_err(|_| {
+        de::Error::invalid_value(Unexpected::Signed(value), &"an integer that fits in an i32")
+    })
+}
+
+/// Accepts a number, or a string containing a number.
+pub(crate) fn de_f64_lenient<'de, D>(deserializer: D) -> Result<f64, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    struct F64Visitor;
+
+    impl<'de> Visitor<'de> for F64Visitor {
+        type Value = f64;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("a number, or a string containing a number")
+        }
+
+        fn visit_i64<E: de::Error>(self, value: i64) -> Result<f64, E> {
+            Ok(value as f64)
+        }
+
+        fn visit_u64<E: de::Error>(self, value: u64) -> Result<f64, E> {
+            Ok(value as f64)
+        }
+
+        fn visit_f64<E: de::Error>(self, value: f64) -> Result<f64, E> {
+            Ok(value)
+        }
+
+        fn visit_str<E: de::Error>(self, value: &str) -> Result<f64, E> {
+            value
+                .trim()
+                .parse()
+                .map_err(|_| E::invalid_value(Unexpected::Str(value), &self))
+        }
+    }
+
+    deserializer.deserialize_any(F64Visitor)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use serde_json::json;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Lenient {
+        #[serde(deserialize_with = "de_bool_lenient")]
+        flag: bool,
+        #[serde(deserialize_with = "de_i32_lenient")]
+        int: i32,
+        #[serde(deserialize_with = "de_i64_lenient")]
+        long: i64,
+        #[serde(deserialize_with = "de_f64_lenient")]
+        float: f64,
+    }
+
+    fn parse(value: serde_json::Value) -> Result<Lenient, serde_json::Error> {
+        serde_json::from_value(value)
+    }
+
+    #[test]
+    fn documented_types() -> Result<(), serde_json::Error> {
+        let expected = Lenient {
+            flag: true,
+            int: -5,
+            long: 3_600_000,
+            float: 0.5,
+        };
+
+        assert_eq!(
+            parse(json!({ "flag": true, "int": -5, "long": 3_600_000, "float": 0.5 }))?,
+            expected
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn alternate_types() -> Result<(), serde_json::Error> {
+        let expected = Lenient {
+            flag: false,
+            int: 60,
+            long: 42,
+            float: 2.0,
+        };
+
+        assert_eq!(
+            parse(json!({ "flag": "false", "int": "60", "long": 42.0, "float": 2 }))?,
+            expected
+        );
+        assert_eq!(
+            parse(json!({ "flag": 0, "int": 60.0, "long": " 42 ", "float": "2.0" }))?,
+            expected
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_values() {
+        assert!(parse(json!({ "flag": 2, "int": 0, "long": 0, "float": 0 })).is_err());
+        assert!(parse(json!({ "flag": true, "int": 1.5, "long": 0, "float": 0 })).is_err());
+        assert!(parse(json!({ "flag": true, "int": "abc", "long": 0, "float": 0 })).is_err());
+        assert!(parse(json!({ "flag": true, "int": 1e10, "long": 0, "float": 0 })).is_err());
+        assert!(parse(json!({ "flag": true, "int": 0, "long": 0, "float": null })).is_err());
+    }
+}
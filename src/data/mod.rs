@@ -7,13 +7,16 @@
 //!   * [`EventData`] is a trait used to correlate [`Event`]s with their corresponding [`EventConfig`]s.
 //!   * [`Event`] is an enum of known event types.
 
+mod display;
 mod enumeration;
 mod envelope;
 mod error_id;
+mod lenient;
 
 pub use crate::data::enumeration::EnumString;
 pub use crate::data::envelope::{
-    OpaqueValue, RequestEnvelope, RequestId, ResponseData, ResponseEnvelope, API_NAME, API_VERSION,
+    OpaqueValue, RequestEnvelope, RequestId, ResponseData, ResponseEnvelope, ResponseMeta,
+    API_NAME, API_VERSION,
 };
 pub use crate::data::error_id::ErrorId;
 
@@ -22,9 +25,61 @@ use paste::paste;
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::borrow::Cow;
+use std::collections::HashMap;
 use std::convert::TryFrom;
 
 /// Trait describing a VTube Studio request. Used to set data in [`RequestEnvelope`].
+///
+/// This can be implemented outside of this crate, to use request types that aren't supported by
+/// this library yet. Use [`EnumString::const_new_from_str`] for the message types.
+///
+/// # Example
+///
+/// ```
+/// use serde::{Deserialize, Serialize};
+/// use vtubestudio::data::{
+///     EnumString, Request, RequestEnvelope, RequestType, Response, ResponseEnvelope, ResponseType,
+/// };
+/// use vtubestudio::error::Error;
+/// use vtubestudio::Client;
+///
+/// #[derive(Serialize)]
+/// #[serde(rename_all = "camelCase")]
+/// struct SomeNewRequest {
+///     some_field: bool,
+/// }
+///
+/// #[derive(Serialize, Deserialize)]
+/// #[serde(rename_all = "camelCase")]
+/// struct SomeNewResponse {
+///     some_count: i32,
+/// }
+///
+/// impl Request for SomeNewRequest {
+///     const MESSAGE_TYPE: EnumString<RequestType> =
+///         EnumString::const_new_from_str("SomeNewRequest");
+///     type Response = SomeNewResponse;
+/// }
+///
+/// impl Response for SomeNewResponse {
+///     const MESSAGE_TYPE: EnumString<ResponseType> =
+///         EnumString::const_new_from_str("SomeNewResponse");
+/// }
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Error> {
+/// // A fake service, in place of a real VTube Studio connection
+/// let service = tower::service_fn(|req: RequestEnvelope| async move {
+///     assert_eq!(req.message_type.as_str(), "SomeNewRequest");
+///     Ok::<_, Error>(ResponseEnvelope::new(&SomeNewResponse { some_count: 3 })?)
+/// });
+///
+/// let mut client = Client::new_from_service(service);
+/// let resp = client.send(&SomeNewRequest { some_field: true }).await?;
+/// assert_eq!(resp.some_count, 3);
+/// # Ok(())
+/// # }
+/// ```
 pub trait Request: Serialize {
     /// The message type of this request.
     const MESSAGE_TYPE: EnumString<RequestType>;
@@ -53,12 +108,128 @@ pub trait EventConfig: Serialize {
     type Event: EventData;
 }
 
+/// Trait describing a list of [`EventData`] types, implemented for tuples of up to 8 event types.
+/// Used in [`EventSubscriptionRequest::unsubscribe_many`].
+pub trait EventDataList {
+    /// The message types of the events in this list.
+    fn message_types() -> Vec<EnumString<ResponseType>>;
+}
+
+macro_rules! impl_event_data_list {
+    ($($name:ident),+) => {
+        impl<$($name: EventData),+> EventDataList for ($($name,)+) {
+            fn message_types() -> Vec<EnumString<ResponseType>> {
+                vec![$($name::MESSAGE_TYPE),+]
+            }
+        }
+    };
+}
+
+impl_event_data_list!(A);
+impl_event_data_list!(A, B);
+impl_event_data_list!(A, B, C);
+impl_event_data_list!(A, B, C, D);
+impl_event_data_list!(A, B, C, D, E);
+impl_event_data_list!(A, B, C, D, E, F);
+impl_event_data_list!(A, B, C, D, E, F, G);
+impl_event_data_list!(A, B, C, D, E, F, G, H);
+
+/// Trait describing a list of [`Request`]s of different types, implemented for tuples of up to 8
+/// request types. Used in [`Client::send_all`](crate::Client::send_all).
+pub trait RequestList {
+    /// A tuple of results, one for each request in the list.
+    type Output;
+
+    /// Wraps each request in a [`RequestEnvelope`].
+    fn into_envelopes(self) -> Vec<Result<RequestEnvelope, crate::error::Error>>;
+
+    /// Parses the responses, which are expected to be in the same order as the requests.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of responses doesn't match the number of requests.
+    fn parse_responses(
+        responses: Vec<Result<ResponseEnvelope, crate::error::Error>>,
+    ) -> Self::Output;
+}
+
+macro_rules! impl_request_list {
+    ($($name:ident),+) => {
+        #[allow(non_snake_case)]
+        impl<$($name: Request),+> RequestList for ($($name,)+) {
+            type Output = ($(Result<$name::Response, crate::error::Error>,)+);
+
+            fn into_envelopes(self) -> Vec<Result<RequestEnvelope, crate::error::Error>> {
+                let ($($name,)+) = self;
+                vec![$(RequestEnvelope::new(&$name).map_err(Into::into)),+]
+            }
+
+            fn parse_responses(responses: Vec<Result<ResponseEnvelope, crate::error::Error>>) -> Self::Output {
+                let mut responses = responses.into_iter();
+                let output = ($(
+                    responses
+                        .next()
+                        .expect("missing response")
+                        .and_then(|resp| resp.parse::<$name::Response>()),
+                )+);
+                assert!(responses.next().is_none(), "too many responses");
+                output
+            }
+        }
+    };
+}
+
+impl_request_list!(A);
+impl_request_list!(A, B);
+impl_request_list!(A, B, C);
+impl_request_list!(A, B, C, D);
+impl_request_list!(A, B, C, D, E);
+impl_request_list!(A, B, C, D, E, F);
+impl_request_list!(A, B, C, D, E, F, G);
+impl_request_list!(A, B, C, D, E, F, G, H);
+
+impl Event {
+    /// Attempts to interpret this event as the event type `T`.
+    ///
+    /// Returns `None` if the event's message type isn't `T::MESSAGE_TYPE`. This is mainly useful
+    /// for [`Event::Unknown`], e.g., for event types that were added to the API but aren't yet
+    /// known to this library, by defining your own [`EventData`] type.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use vtubestudio::data::{Event, TestEvent};
+    ///
+    /// fn handle(event: &Event) {
+    ///     if let Some(Ok(event)) = event.try_as::<TestEvent>() {
+    ///         println!("Received test event: {}", event.your_test_message);
+    ///     }
+    /// }
+    /// ```
+    pub fn try_as<T: EventData>(&self) -> Option<Result<T, serde_json::Error>> {
+        match self {
+            Event::Unknown(data) if data.message_type == T::MESSAGE_TYPE => {
+                Some(data.data.deserialize::<T>())
+            }
+            Event::Unknown(_) => None,
+            event if event.as_any()?.is::<T>() => {
+                // Known events are converted by round-tripping through JSON, since `T` might not
+                // implement `Clone`
+                Some(serde_json::to_value(event).and_then(serde_json::from_value))
+            }
+            _ => None,
+        }
+    }
+}
+
 // https://github.com/DenchiSoft/VTubeStudio/blob/4d284d4d62d9a7349d6e069cad1ff06391332283/Files/HotkeyAction.cs
 /// Known hotkey types for [`EnumString<HotkeyAction>`]. Used in [`Hotkey`].
 #[non_exhaustive]
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum HotkeyAction {
     /// Unset.
+    #[default]
     Unset,
     /// Play an animation.
     TriggerAnimation,
@@ -103,15 +274,10 @@ pub enum HotkeyAction {
     LoadEffectPreset,
 }
 
-impl Default for HotkeyAction {
-    fn default() -> Self {
-        Self::Unset
-    }
-}
-
 /// Known animation event types for [`EnumString<AnimationEventType>`]. Used in [`ModelAnimationEvent`].
 #[non_exhaustive]
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum AnimationEventType {
     /// Triggered when an animation **starts** (idle-animations or normal one-time animations).
     Start,
@@ -122,18 +288,14 @@ pub enum AnimationEventType {
     /// These events can be added at any point in the animation when creating animations in the
     /// Live2D Cubism Animation Editor. You can find more details about how to create/use those
     /// events below.
+    #[default]
     Custom,
 }
 
-impl Default for AnimationEventType {
-    fn default() -> Self {
-        Self::Custom
-    }
-}
-
 /// Known event types for [`EnumString<ItemEventType>`]. Used in [`ItemEvent`].
 #[non_exhaustive]
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum ItemEventType {
     /// Item was added to the scene.
     Added,
@@ -144,6 +306,7 @@ pub enum ItemEventType {
     /// Item was dropped but not on the model. It is now unpinned.
     DroppedUnpinned,
     /// Item was clicked.
+    #[default]
     Clicked,
     /// Item was locked.
     Locked,
@@ -151,21 +314,17 @@ pub enum ItemEventType {
     Unlocked,
 }
 
-impl Default for ItemEventType {
-    fn default() -> Self {
-        Self::Clicked
-    }
-}
-
 /// Known values for [`EnumString<AngleRelativeTo>`]. Used in [`ItemPinRequest`].
 #[non_exhaustive]
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum AngleRelativeTo {
     /// Absolute angle.
     ///
     /// That means if you pass in 0 as angle, the item will be pinned upright at an angle of 0
     /// compared to the VTS window. You'd use this if you want the item to face a certain direction
     /// relative to the VTS window.
+    #[default]
     RelativeToWorld,
     /// Relative to the angle the item currently is at.
     ///
@@ -191,19 +350,15 @@ pub enum AngleRelativeTo {
     RelativeToPinPosition,
 }
 
-impl Default for AngleRelativeTo {
-    fn default() -> Self {
-        Self::RelativeToWorld
-    }
-}
-
 /// Known values for [`EnumString<SizeRelativeTo>`]. Used in [`ItemPinRequest`].
 #[non_exhaustive]
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum SizeRelativeTo {
     /// Absolute size. Between 0 (smallest) and 1 (largest).
     ///
     /// See also [`ItemLoadRequest`].
+    #[default]
     RelativeToWorld,
     /// Relative to current item size.
     ///
@@ -213,19 +368,15 @@ pub enum SizeRelativeTo {
     RelativeToCurrentItemSize,
 }
 
-impl Default for SizeRelativeTo {
-    fn default() -> Self {
-        Self::RelativeToWorld
-    }
-}
-
 /// Known animation event types for [`EnumString<VertexPinType>`]. Used in [`ItemPinRequest`].
 #[non_exhaustive]
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum VertexPinType {
     /// The item will be pinned to the given ArtMesh using the pin position provided in the fields
     /// `vertexID1`, `vertexID2`, `vertexID3`, `vertexWeight1`, `vertexWeight2` and
     /// `vertexWeight3`.
+    #[default]
     Provided,
     /// The item will be pinned to the "center" of the given ArtMesh. It's not really the center
     /// (spacially) but actually the triangle in the middle of the triangle list of the mesh. This
@@ -235,28 +386,19 @@ pub enum VertexPinType {
     Random,
 }
 
-impl Default for VertexPinType {
-    fn default() -> Self {
-        Self::Provided
-    }
-}
-
 /// Known animation event types for [`EnumString<Permission>`]. Used in [`PermissionRequest`] and [`PermissionResponse`].
 #[non_exhaustive]
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum Permission {
     /// Load custom images as items.
+    #[default]
     LoadCustomImagesAsItems,
 }
 
-impl Default for Permission {
-    fn default() -> Self {
-        Self::LoadCustomImagesAsItems
-    }
-}
-
 /// Whether a permission was granted. Used in [`PermissionResponse`].
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct PermissionStatus {
     /// Permission name.
     pub name: EnumString<Permission>,
@@ -264,24 +406,79 @@ pub struct PermissionStatus {
     pub granted: bool,
 }
 
-/// Mouse button ID. Used in [`ModelClickedEvent`].
-#[derive(Default, Debug, Clone, Serialize, Deserialize, PartialEq)]
-pub struct MouseButtonId(pub i32);
+/// Mouse button. Used in [`ModelClickedEvent`].
+///
+/// This is serialized as the numeric button ID, and unknown IDs are preserved as
+/// [`MouseButton::Other`].
+///
+/// ```
+/// use vtubestudio::data::MouseButton;
+///
+/// assert_eq!(serde_json::from_str::<MouseButton>("1")?, MouseButton::Right);
+/// assert_eq!(serde_json::from_str::<MouseButton>("4")?, MouseButton::Other(4));
+/// assert_eq!(serde_json::to_string(&MouseButton::Middle)?, "2");
+/// # Ok::<(), serde_json::Error>(())
+/// ```
+#[derive(Default, Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(from = "i32", into = "i32")]
+pub enum MouseButton {
+    /// Left mouse button (`0`).
+    #[default]
+    Left,
+    /// Right mouse button (`1`).
+    Right,
+    /// Middle mouse button (`2`).
+    Middle,
+    /// Any other mouse button ID.
+    Other(i32),
+}
+
+/// Previous name of [`MouseButton`].
+#[deprecated(note = "use `MouseButton` instead")]
+pub type MouseButtonId = MouseButton;
+
+impl MouseButton {
+    /// Returns the numeric ID of the mouse button.
+    pub fn as_i32(&self) -> i32 {
+        match self {
+            Self::Left => 0,
+            Self::Right => 1,
+            Self::Middle => 2,
+            Self::Other(id) => *id,
+        }
+    }
 
-impl MouseButtonId {
     /// Is left mouse button.
     pub fn is_left(&self) -> bool {
-        self.0 == 0
+        self.as_i32() == 0
     }
 
     /// Is right mouse button.
     pub fn is_right(&self) -> bool {
-        self.0 == 1
+        self.as_i32() == 1
     }
 
     /// Is middle mouse button.
     pub fn is_middle(&self) -> bool {
-        self.0 == 2
+        self.as_i32() == 2
+    }
+}
+
+impl From<i32> for MouseButton {
+    fn from(id: i32) -> Self {
+        match id {
+            0 => Self::Left,
+            1 => Self::Right,
+            2 => Self::Middle,
+            other => Self::Other(other),
+        }
+    }
+}
+
+impl From<MouseButton> for i32 {
+    fn from(button: MouseButton) -> Self {
+        button.as_i32()
     }
 }
 
@@ -314,7 +511,8 @@ macro_rules! define_request_response {
             /// Known message types for [`EnumString<RequestType>`].
             #[allow(missing_docs)]
             #[non_exhaustive]
-            #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+            #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+            #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
             pub enum RequestType {
                 $(
                     $(#[serde(rename = $req_name)])?
@@ -325,7 +523,8 @@ macro_rules! define_request_response {
             /// Known message types for [`EnumString<ResponseType>`].
             #[allow(missing_docs)]
             #[non_exhaustive]
-            #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+            #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+            #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
             pub enum ResponseType {
                 #[serde(rename = "APIError")]
                 ApiError,
@@ -351,6 +550,44 @@ macro_rules! define_request_response {
                         _ => false
                     }
                 }
+
+                /// All known event types, in the order they're defined.
+                ///
+                /// ```
+                /// use vtubestudio::data::ResponseType;
+                ///
+                /// let events = ResponseType::all_events();
+                /// assert!(events.contains(&ResponseType::TestEvent));
+                /// assert!(events.iter().all(ResponseType::is_event));
+                /// ```
+                pub fn all_events() -> &'static [ResponseType] {
+                    &[ $( Self::[<$rust_event_name Event>], )* ]
+                }
+            }
+
+            /// Generates a JSON schema for each known request type.
+            ///
+            /// ```
+            /// use vtubestudio::data::{schema_for_all_requests, RequestType};
+            ///
+            /// let schemas = schema_for_all_requests();
+            /// let (_, schema) = schemas
+            ///     .iter()
+            ///     .find(|(req_type, _)| *req_type == RequestType::StatisticsRequest)
+            ///     .unwrap();
+            /// assert_eq!(schema["title"], "StatisticsRequest");
+            /// ```
+            #[cfg(feature = "schemars")]
+            #[cfg_attr(docsrs, doc(cfg(feature = "schemars")))]
+            pub fn schema_for_all_requests() -> Vec<(RequestType, serde_json::Value)> {
+                vec![
+                    $(
+                        (
+                            RequestType::[<$rust_name Request>],
+                            schemars::schema_for!([<$rust_name Request>]).to_value(),
+                        ),
+                    )*
+                ]
             }
         }
 
@@ -360,6 +597,7 @@ macro_rules! define_request_response {
                 ///
                 #[doc = concat!("This event can be configured using [`", stringify!($rust_event_name), "EventConfig`].")]
                 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+                #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
                 #[serde(rename_all = "camelCase")]
                 pub struct [<$rust_event_name Event>] { $($event_data_fields)* }
 
@@ -387,6 +625,17 @@ macro_rules! define_request_response {
                 Unknown(ResponseData),
             }
 
+            impl Event {
+                /// Returns the inner event data as [`Any`](std::any::Any), or `None` for
+                /// [`Event::Unknown`].
+                pub(crate) fn as_any(&self) -> Option<&dyn std::any::Any> {
+                    match self {
+                        $( Event::$rust_event_name(event) => Some(event), )*
+                        Event::Unknown(_) => None,
+                    }
+                }
+            }
+
             impl TryFrom<ResponseData> for Event {
                 type Error = serde_json::Error;
 
@@ -409,6 +658,7 @@ macro_rules! define_request_response {
                 ///
                 $(#[doc = $event_config_doc])*
                 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+                #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
                 #[serde(rename_all = "camelCase")]
                 pub struct [<$rust_event_name EventConfig>] { $($event_config_fields)* }
 
@@ -424,6 +674,7 @@ macro_rules! define_request_response {
                 ///
                 #[doc = concat!("This request returns [`", stringify!($rust_name), "Response`].")]
                 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
+                #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
                 $(#[derive($extra_derives)])*
                 #[serde(rename_all = "camelCase")]
                 pub struct [<$rust_name Request>] { $($req)* }
@@ -439,6 +690,7 @@ macro_rules! define_request_response {
                 ///
                 #[doc = concat!("This is the return value of [`", stringify!($rust_name), "Request`].")]
                 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+                #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
                 #[serde(rename_all = "camelCase")]
                 pub struct [<$rust_name Response>] $(( $($resp_inner)+ );)? $({ $($resp_fields)* })?
 
@@ -489,6 +741,55 @@ impl EventSubscriptionRequest {
         }
     }
 
+    /// Creates a subscription request for each config.
+    ///
+    /// The VTube Studio API doesn't support subscribing to multiple events in a single request,
+    /// so these need to be sent separately (e.g., with
+    /// [`Client::subscribe_many`](crate::Client::subscribe_many)).
+    ///
+    /// ```
+    /// use vtubestudio::data::{EventSubscriptionRequest, ItemEventConfig};
+    /// let reqs = EventSubscriptionRequest::subscribe_many(&[
+    ///     ItemEventConfig {
+    ///         item_instance_ids: Vec::new(),
+    ///         item_file_names: vec!["a.png".to_owned()],
+    ///     },
+    ///     ItemEventConfig {
+    ///         item_instance_ids: Vec::new(),
+    ///         item_file_names: vec!["b.png".to_owned()],
+    ///     },
+    /// ])?;
+    /// assert_eq!(reqs.len(), 2);
+    /// # Ok::<_, serde_json::Error>(())
+    /// ```
+    pub fn subscribe_many<T>(configs: &[T]) -> Result<Vec<Self>, serde_json::Error>
+    where
+        T: EventConfig,
+    {
+        configs.iter().map(Self::subscribe).collect()
+    }
+
+    /// Creates an unsubscription request for each event type in a tuple.
+    ///
+    /// ```
+    /// use vtubestudio::data::{EventSubscriptionRequest, ModelLoadedEvent, TestEvent};
+    /// let reqs = EventSubscriptionRequest::unsubscribe_many::<(TestEvent, ModelLoadedEvent)>();
+    /// assert_eq!(reqs.len(), 2);
+    /// ```
+    pub fn unsubscribe_many<T>() -> Vec<Self>
+    where
+        T: EventDataList,
+    {
+        T::message_types()
+            .into_iter()
+            .map(|event_name| Self {
+                subscribe: false,
+                event_name: Some(event_name),
+                config: None,
+            })
+            .collect()
+    }
+
     /// Unsubscribe from all events.
     pub fn unsubscribe_all() -> Self {
         Self {
@@ -499,6 +800,276 @@ impl EventSubscriptionRequest {
     }
 }
 
+impl InjectParameterDataRequest {
+    /// The max number of parameter values sent per request by [`chunked`](Self::chunked).
+    pub const CHUNK_SIZE: usize = 100;
+
+    /// Splits a list of parameter values into multiple requests, each containing at most
+    /// [`CHUNK_SIZE`](Self::CHUNK_SIZE) values.
+    ///
+    /// ```
+    /// use vtubestudio::data::{InjectParameterDataRequest, ParameterValue};
+    ///
+    /// let values = vec![ParameterValue::default(); 250];
+    /// let reqs = InjectParameterDataRequest::chunked(&values, None, true);
+    ///
+    /// let sizes = reqs.map(|req| req.parameter_values.len()).collect::<Vec<_>>();
+    /// assert_eq!(sizes, vec![100, 100, 50]);
+    /// ```
+    pub fn chunked<'a>(
+        values: &'a [ParameterValue],
+        mode: Option<EnumString<InjectParameterDataMode>>,
+        face_found: bool,
+    ) -> impl Iterator<Item = Self> + 'a {
+        values
+            .chunks(Self::CHUNK_SIZE)
+            .map(move |chunk| InjectParameterDataRequest {
+                parameter_values: chunk.to_vec(),
+                face_found,
+                mode: mode.clone(),
+            })
+    }
+}
+
+impl ParameterCreationRequest {
+    /// The min length of [`parameter_name`](Self::parameter_name), in characters.
+    pub const MIN_NAME_LENGTH: usize = 4;
+
+    /// The max length of [`parameter_name`](Self::parameter_name), in characters.
+    pub const MAX_NAME_LENGTH: usize = 32;
+
+    /// The max length of [`explanation`](Self::explanation), in characters.
+    pub const MAX_EXPLANATION_LENGTH: usize = 255;
+
+    /// The lowest allowed value for [`min`](Self::min), [`max`](Self::max), and
+    /// [`default_value`](Self::default_value).
+    pub const MIN_VALUE: f64 = -1_000_000.0;
+
+    /// The highest allowed value for [`min`](Self::min), [`max`](Self::max), and
+    /// [`default_value`](Self::default_value).
+    pub const MAX_VALUE: f64 = 1_000_000.0;
+
+    /// Checks the request against the limits documented by VTube Studio, instead of waiting for
+    /// VTube Studio to reject it.
+    ///
+    /// This returns an error with [`ErrorKind::InvalidRequest`](crate::error::ErrorKind) if the
+    /// name isn't alphanumeric or isn't between [`MIN_NAME_LENGTH`](Self::MIN_NAME_LENGTH) and
+    /// [`MAX_NAME_LENGTH`](Self::MAX_NAME_LENGTH) characters long, if the explanation is longer
+    /// than [`MAX_EXPLANATION_LENGTH`](Self::MAX_EXPLANATION_LENGTH), if any value is not finite or
+    /// is outside of [`MIN_VALUE`](Self::MIN_VALUE)`..=`[`MAX_VALUE`](Self::MAX_VALUE), if `min`
+    /// is greater than `max`, or if the default value isn't between `min` and `max`.
+    ///
+    /// ```
+    /// use vtubestudio::data::ParameterCreationRequest;
+    ///
+    /// let mut req = ParameterCreationRequest {
+    ///     parameter_name: "MyNewParam".into(),
+    ///     explanation: Some("This is my new parameter".into()),
+    ///     min: -50.0,
+    ///     max: 50.0,
+    ///     default_value: 10.0,
+    /// };
+    /// assert!(req.validate().is_ok());
+    ///
+    /// req.parameter_name = "My New Param".into();
+    /// assert!(req.validate().is_err());
+    /// ```
+    pub fn validate(&self) -> Result<(), crate::error::Error> {
+        use crate::error::{Error, ErrorKind};
+
+        let invalid = |message: String| Error::new(ErrorKind::InvalidRequest).with_source(message);
+
+        let name = &self.parameter_name;
+        let name_len = name.chars().count();
+        if !(Self::MIN_NAME_LENGTH..=Self::MAX_NAME_LENGTH).contains(&name_len) {
+            return Err(invalid(format!(
+                "parameter name {:?} must be between {} and {} characters long",
+                name,
+                Self::MIN_NAME_LENGTH,
+                Self::MAX_NAME_LENGTH
+            )));
+        }
+
+        if !name.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return Err(invalid(format!(
+                "parameter name {:?} must only contain letters and numbers",
+                name
+            )));
+        }
+
+        if let Some(explanation) = &self.explanation {
+            if explanation.chars().count() > Self::MAX_EXPLANATION_LENGTH {
+                return Err(invalid(format!(
+                    "explanation must be at most {} characters long",
+                    Self::MAX_EXPLANATION_LENGTH
+                )));
+            }
+        }
+
+        let values = [
+            ("min", self.min),
+            ("max", self.max),
+            ("default value", self.default_value),
+        ];
+        for (field, value) in values.iter() {
+            if !(Self::MIN_VALUE..=Self::MAX_VALUE).contains(value) {
+                return Err(invalid(format!(
+                    "{} must be between {} and {}",
+                    field,
+                    Self::MIN_VALUE,
+                    Self::MAX_VALUE
+                )));
+            }
+        }
+
+        if self.min > self.max {
+            return Err(invalid("min must not be greater than max".to_owned()));
+        }
+
+        if !(self.min..=self.max).contains(&self.default_value) {
+            return Err(invalid(
+                "default value must be between min and max".to_owned(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl InjectParameterDataRequest {
+    /// Creates a [`ParameterInjection`] builder, which validates parameter values before sending
+    /// them.
+    pub fn builder() -> ParameterInjection {
+        ParameterInjection::new()
+    }
+}
+
+/// Builder for an [`InjectParameterDataRequest`] that validates values up-front.
+///
+/// VTube Studio rejects requests with invalid weights (with
+/// [`ErrorId::INJECT_DATA_WEIGHT_INVALID`]), and only allows one [`InjectParameterDataMode`] per
+/// request. This builder checks for those mistakes when calling [`build`](Self::build), instead of
+/// waiting for VTube Studio to reject the request.
+///
+/// ```
+/// use vtubestudio::data::{InjectParameterDataMode, ParameterInjection};
+///
+/// let req = ParameterInjection::new()
+///     .set("FaceAngleX", 15.0)
+///     .set_weighted("MouthOpen", 1.0, 0.5)
+///     .face_found(true)
+///     .build()?;
+///
+/// assert_eq!(req.parameter_values.len(), 2);
+/// assert_eq!(req.mode, Some(InjectParameterDataMode::Set.into()));
+///
+/// // Weights must be between 0 and 1
+/// assert!(ParameterInjection::new().set_weighted("MouthOpen", 1.0, 1.5).build().is_err());
+///
+/// // Values can't be both set and added in the same request
+/// assert!(ParameterInjection::new().set("FaceAngleX", 1.0).add("FaceAngleY", 1.0).build().is_err());
+/// # Ok::<_, vtubestudio::error::Error>(())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ParameterInjection {
+    values: Vec<(InjectParameterDataMode, ParameterValue)>,
+    face_found: bool,
+}
+
+impl ParameterInjection {
+    /// Creates an empty [`ParameterInjection`] builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Overrides the value of a parameter, using [`InjectParameterDataMode::Set`].
+    pub fn set<S: Into<String>>(self, id: S, value: f64) -> Self {
+        self.push(InjectParameterDataMode::Set, id.into(), value, None)
+    }
+
+    /// Overrides the value of a parameter, using [`InjectParameterDataMode::Set`], with a weight
+    /// (between `0` and `1`) relative to the value from face tracking.
+    pub fn set_weighted<S: Into<String>>(self, id: S, value: f64, weight: f64) -> Self {
+        self.push(InjectParameterDataMode::Set, id.into(), value, Some(weight))
+    }
+
+    /// Adds to the current value of a parameter, using [`InjectParameterDataMode::Add`].
+    pub fn add<S: Into<String>>(self, id: S, value: f64) -> Self {
+        self.push(InjectParameterDataMode::Add, id.into(), value, None)
+    }
+
+    /// Whether to consider the user's face as found. The default value is `false`.
+    pub fn face_found(mut self, face_found: bool) -> Self {
+        self.face_found = face_found;
+        self
+    }
+
+    fn push(
+        mut self,
+        mode: InjectParameterDataMode,
+        id: String,
+        value: f64,
+        weight: Option<f64>,
+    ) -> Self {
+        self.values
+            .push((mode, ParameterValue { id, value, weight }));
+        self
+    }
+
+    /// Builds the [`InjectParameterDataRequest`].
+    ///
+    /// This returns an error with [`ErrorKind::InvalidRequest`](crate::error::ErrorKind) if no
+    /// values were provided, if any parameter ID is empty, if any value is not finite, if any
+    /// weight is outside of the range `0..=1`, or if values were both set and added.
+    pub fn build(self) -> Result<InjectParameterDataRequest, crate::error::Error> {
+        use crate::error::{Error, ErrorKind};
+
+        let invalid = |message: String| Error::new(ErrorKind::InvalidRequest).with_source(message);
+
+        let mode = match self.values.first() {
+            Some((mode, _)) => mode.clone(),
+            None => return Err(invalid("no parameter values were provided".to_owned())),
+        };
+
+        let mut parameter_values = Vec::with_capacity(self.values.len());
+        for (value_mode, value) in self.values {
+            if value_mode != mode {
+                return Err(invalid(
+                    "parameter values can't be both set and added in the same request".to_owned(),
+                ));
+            }
+
+            if value.id.is_empty() {
+                return Err(invalid("parameter ID is empty".to_owned()));
+            }
+
+            if !value.value.is_finite() {
+                return Err(invalid(format!(
+                    "value for parameter {} is not finite",
+                    value.id
+                )));
+            }
+
+            if let Some(weight) = value.weight {
+                if !(0.0..=1.0).contains(&weight) {
+                    return Err(invalid(format!(
+                        "weight for parameter {} must be between 0 and 1",
+                        value.id
+                    )));
+                }
+            }
+
+            parameter_values.push(value);
+        }
+
+        Ok(InjectParameterDataRequest {
+            parameter_values,
+            face_found: self.face_found,
+            mode: Some(mode.into()),
+        })
+    }
+}
+
 impl Default for RequestType {
     fn default() -> Self {
         Self::ApiStateRequest
@@ -522,11 +1093,13 @@ define_request_response!(
         /// The API state.
         resp = {
             /// Whether the API is active.
+            #[serde(deserialize_with = "lenient::de_bool_lenient")]
             pub active: bool,
             /// The VTube Studio version.
             #[serde(rename = "vTubeStudioVersion")]
             pub vtubestudio_version: String,
             /// Whether the current session is authenticated.
+            #[serde(deserialize_with = "lenient::de_bool_lenient")]
             pub current_session_authenticated: bool,
         },
     },
@@ -603,23 +1176,31 @@ define_request_response!(
         /// Statistics about the VTube Studio session.
         resp = {
             /// Uptime in milliseconds.
+            #[serde(deserialize_with = "lenient::de_i64_lenient")]
             pub uptime: i64,
             /// The frame rate.
+            #[serde(deserialize_with = "lenient::de_i32_lenient")]
             pub framerate: i32,
             /// The VTube Studio version.
             #[serde(rename = "vTubeStudioVersion")]
             pub vtubestudio_version: String,
             /// Number of plugins registered.
+            #[serde(deserialize_with = "lenient::de_i32_lenient")]
             pub allowed_plugins: i32,
             /// Number of plugins currently connected.
+            #[serde(deserialize_with = "lenient::de_i32_lenient")]
             pub connected_plugins: i32,
             /// Whether VTube Studio was started with Steam.
+            #[serde(deserialize_with = "lenient::de_bool_lenient")]
             pub started_with_steam: bool,
             /// Width of the window.
+            #[serde(deserialize_with = "lenient::de_i32_lenient")]
             pub window_width: i32,
             /// Height of the window.
+            #[serde(deserialize_with = "lenient::de_i32_lenient")]
             pub window_height: i32,
             /// Whether the window is in fullscreen mode.
+            #[serde(deserialize_with = "lenient::de_bool_lenient")]
             pub window_is_fullscreen: bool,
         },
     },
@@ -758,7 +1339,10 @@ define_request_response!(
         /// If `model_id` is absent, hotkeys for the current model are returned.
         ///
         /// If both `model_id` and `live2d_item_file_name` are provided, only `model_id` is used
-        /// and the other field will be ignored.
+        /// and the other field will be ignored. Prefer the
+        /// [`for_model`](HotkeysInCurrentModelRequest::for_model) and
+        /// [`for_live2d_item`](HotkeysInCurrentModelRequest::for_live2d_item) constructors, which
+        /// only set one of the fields.
         #[derive(PartialEq)]
         req = {
             /// The ID of the model.
@@ -791,7 +1375,7 @@ define_request_response!(
         req = {
             /// The ID of the hotkey.
             #[serde(rename = "hotkeyID")]
-            pub hotkey_id: String,
+            pub hotkey_id: HotkeyId,
             /// If present, trigger the hotkey for the given Live2D item. If absent, the hotkey
             /// will be triggered for the currently loaded model.
             #[serde(rename = "itemInstanceID", skip_serializing_if = "Option::is_none")]
@@ -801,7 +1385,7 @@ define_request_response!(
         resp = {
             /// The ID of the hotkey.
             #[serde(rename = "hotkeyID")]
-            pub hotkey_id: String,
+            pub hotkey_id: HotkeyId,
         },
     },
 
@@ -1814,7 +2398,7 @@ define_request_response!(
             data = {
                 /// Hotkey ID. E.g., `"21bf7ade9e664f3ec29d05156e4ce5c1"`.
                 #[serde(rename = "hotkeyID")]
-                pub hotkey_id: String,
+                pub hotkey_id: HotkeyId,
                 /// Hotkey name. E.g., `"Eyes Cry"`.
                 pub hotkey_name: String,
                 /// Hotkey action. E.g., `"ToggleExpression"`.
@@ -1851,12 +2435,14 @@ define_request_response!(
                 /// Animation event type.
                 pub animation_event_type: EnumString<AnimationEventType>,
                 /// Time in seconds within the animation when the animation-event was encountered.
+                #[serde(deserialize_with = "lenient::de_f64_lenient")]
                 pub animation_event_time: f64,
                 /// Animation event data. E.g., `"My Test Event 123456789"`.
                 pub animation_event_data: String,
                 /// Animation name. E.g., `"event_test_anim_4_final.motion3.json"`.
                 pub animation_name: String,
                 /// Length of animation in seconds.
+                #[serde(deserialize_with = "lenient::de_f64_lenient")]
                 pub animation_length: f64,
                 /// Whether the animation is an idle animation.
                 pub is_idle_animation: bool,
@@ -1917,14 +2503,13 @@ define_request_response!(
                 pub loaded_model_name: String,
                 /// Whether model was clicked.
                 pub model_was_clicked: bool,
-                /// ID of the mouse button. 0 for left click, 1 for right click, 2 for middle click.
-                // TODO: Turn this into an enum?
+                /// The mouse button that was clicked.
                 #[serde(rename = "mouseButtonID")]
-                pub mouse_button_id: MouseButtonId,
+                pub mouse_button_id: MouseButton,
                 /// The position of the click in the usual coordinate system.
                 ///
-                /// If you need the exact pixel position of the click, you can use `windowSize`
-                /// (current VTS window size in pixels) to calculate that.
+                /// If you need the exact pixel position of the click, use
+                /// [`click_position_pixels`](Self::click_position_pixels).
                 pub click_position: Vec2,
                 /// Current VTS window size in pixels.
                 pub window_size: Vec2,
@@ -1979,6 +2564,7 @@ define_request_response!(
 
 /// Art mesh hit. Used in [`ModelClickedEvent`].
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ArtMeshHit {
     /// The order in the ArtMesh stack at the click position.
@@ -2006,6 +2592,7 @@ pub struct ArtMeshHit {
 ///
 /// [barycentric coordinates]: https://en.wikipedia.org/wiki/Barycentric_coordinate_system
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ArtMeshPosition {
     /// Model ID. E.g., `"d87b771d2902473bbaa0226d03ef4754"`.
@@ -2035,8 +2622,105 @@ pub struct ArtMeshPosition {
     pub vertex_weight3: f64,
 }
 
+impl ArtMeshPosition {
+    /// Maximum difference from `1.0` allowed for the sum of the vertex weights in
+    /// [`is_valid`](Self::is_valid). VTube Studio computes the weights with 32-bit floats, so
+    /// their sum is often slightly off.
+    const WEIGHT_SUM_TOLERANCE: f64 = 1e-6;
+
+    /// Creates an [`ItemPinRequest`] that pins an item at this position, using
+    /// [`VertexPinType::Provided`].
+    ///
+    /// The [`angle`](Self::angle) and [`size`](Self::size) of this position are used as the
+    /// angle and size of the pinned item, interpreted according to `angle_relative_to` and
+    /// `size_relative_to`.
+    ///
+    /// ```
+    /// use vtubestudio::data::{AngleRelativeTo, ArtMeshPosition, SizeRelativeTo, VertexPinType};
+    ///
+    /// let hit_info = ArtMeshPosition {
+    ///     art_mesh_id: "hair_right6".to_owned(),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let req = hit_info.as_item_pin_request(
+    ///     "4a241269394f463ca16b8b21aa636568",
+    ///     AngleRelativeTo::RelativeToModel,
+    ///     SizeRelativeTo::RelativeToCurrentItemSize,
+    /// );
+    ///
+    /// assert!(req.pin);
+    /// assert_eq!(req.vertex_pin_type, VertexPinType::Provided);
+    /// assert_eq!(req.pin_info, hit_info);
+    /// ```
+    pub fn as_item_pin_request<S, A, Z>(
+        &self,
+        item_instance_id: S,
+        angle_relative_to: A,
+        size_relative_to: Z,
+    ) -> ItemPinRequest
+    where
+        S: Into<String>,
+        A: Into<EnumString<AngleRelativeTo>>,
+        Z: Into<EnumString<SizeRelativeTo>>,
+    {
+        ItemPinRequest {
+            pin: true,
+            item_instance_id: item_instance_id.into(),
+            angle_relative_to: angle_relative_to.into(),
+            size_relative_to: size_relative_to.into(),
+            vertex_pin_type: VertexPinType::Provided.into(),
+            pin_info: self.clone(),
+        }
+    }
+
+    /// Returns `true` if none of the vertex IDs are negative, and the vertex weights are valid
+    /// barycentric coordinates (they add up to approximately `1.0`).
+    ///
+    /// ```
+    /// use vtubestudio::data::ArtMeshPosition;
+    ///
+    /// let mut position = ArtMeshPosition {
+    ///     vertex_id1: 10,
+    ///     vertex_id2: 11,
+    ///     vertex_id3: 12,
+    ///     vertex_weight1: 0.25,
+    ///     vertex_weight2: 0.25,
+    ///     vertex_weight3: 0.5,
+    ///     ..Default::default()
+    /// };
+    /// assert!(position.is_valid());
+    ///
+    /// position.vertex_weight3 = 0.75;
+    /// assert!(!position.is_valid());
+    /// ```
+    pub fn is_valid(&self) -> bool {
+        let ids_valid = self.vertex_id1 >= 0 && self.vertex_id2 >= 0 && self.vertex_id3 >= 0;
+        let weight_sum = self.vertex_weight1 + self.vertex_weight2 + self.vertex_weight3;
+
+        ids_valid && (weight_sum - 1.0).abs() <= Self::WEIGHT_SUM_TOLERANCE
+    }
+}
+
 /// Struct representing a coordinate or dimensions. Used in [`ModelOutlineEvent`].
-#[derive(Default, Deserialize, Serialize, Debug, PartialEq, Clone)]
+///
+/// Supports basic vector arithmetic:
+///
+/// ```
+/// use vtubestudio::data::Vec2;
+///
+/// let center = Vec2::from((0.5, 0.5));
+/// let click = Vec2::from((1.0, 0.5));
+///
+/// let offset = click - center;
+/// assert_eq!(offset, Vec2 { x: 0.5, y: 0.0 });
+/// assert_eq!(offset.length(), 0.5);
+/// assert_eq!(center.distance(click), 0.5);
+/// assert_eq!(offset.normalize() * 2.0, Vec2 { x: 2.0, y: 0.0 });
+/// assert_eq!(format!("{:.1}", offset), "(0.5, 0.0)");
+/// ```
+#[derive(Default, Deserialize, Serialize, Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Vec2 {
     /// X coordinate.
     pub x: f64,
@@ -2044,49 +2728,436 @@ pub struct Vec2 {
     pub y: f64,
 }
 
-#[allow(missing_docs)]
-#[derive(Deserialize, Serialize, Debug, PartialEq, Clone)]
-#[non_exhaustive]
-/// Known message types for [`EnumString<InjectParameterDataMode>`] (used in
-/// [`InjectParameterDataRequest`]).
-pub enum InjectParameterDataMode {
-    #[serde(rename = "set")]
-    Set,
-    #[serde(rename = "add")]
-    Add,
-}
-
-impl Default for InjectParameterDataMode {
-    fn default() -> Self {
-        Self::Set
+impl Vec2 {
+    /// Creates a new [`Vec2`].
+    #[inline]
+    pub const fn new(x: f64, y: f64) -> Self {
+        Self { x, y }
     }
-}
 
-#[allow(missing_docs)]
-#[derive(Deserialize, Serialize, Debug, PartialEq, Clone)]
-#[non_exhaustive]
-/// Known message types for [`EnumString<ItemType>`]. Used in [`ItemInstanceInScene`].
-pub enum ItemType {
-    #[serde(rename = "PNG")]
-    Png,
-    #[serde(rename = "JPG")]
-    Jpg,
-    #[serde(rename = "GIF")]
-    Gif,
-    AnimationFolder,
-    #[serde(rename = "Live2D")]
-    Live2D,
-    Unknown,
-}
+    /// Returns the length (magnitude) of this vector.
+    #[inline]
+    pub fn length(self) -> f64 {
+        self.x.hypot(self.y)
+    }
 
-impl Default for ItemType {
-    fn default() -> Self {
-        Self::Unknown
+    /// Returns the distance between two points.
+    #[inline]
+    pub fn distance(self, other: Self) -> f64 {
+        (other - self).length()
     }
-}
+
+    /// Converts a position in VTube Studio's coordinate system (where `x` goes from `-1` at the
+    /// left edge of the window to `1` at the right edge, and `y` goes from `-1` at the bottom to
+    /// `1` at the top) to pixels, with the origin at the top-left corner of the window.
+    ///
+    /// ```
+    /// use vtubestudio::data::Vec2;
+    ///
+    /// let window_size = Vec2::from((1920.0, 1080.0));
+    /// assert_eq!(Vec2::from((0.0, 0.0)).to_pixels(window_size), Vec2::from((960.0, 540.0)));
+    /// assert_eq!(Vec2::from((-1.0, 1.0)).to_pixels(window_size), Vec2::from((0.0, 0.0)));
+    /// assert_eq!(Vec2::from((1.0, -1.0)).to_pixels(window_size), window_size);
+    /// ```
+    #[inline]
+    pub fn to_pixels(self, window_size: Self) -> Self {
+        Self {
+            x: (self.x + 1.0) / 2.0 * window_size.x,
+            y: (1.0 - self.y) / 2.0 * window_size.y,
+        }
+    }
+
+    /// Returns a vector with the same direction and a length of `1.0`. If the length is zero (or
+    /// not finite), a zero vector is returned instead.
+    #[inline]
+    pub fn normalize(self) -> Self {
+        let length = self.length();
+        if length == 0.0 || !length.is_finite() {
+            Self::default()
+        } else {
+            self / length
+        }
+    }
+
+    /// Returns the dot product of two vectors.
+    #[inline]
+    pub fn dot(self, other: Self) -> f64 {
+        self.x * other.x + self.y * other.y
+    }
+}
+
+impl std::ops::Add for Vec2 {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, other: Self) -> Self {
+        Self {
+            x: self.x + other.x,
+            y: self.y + other.y,
+        }
+    }
+}
+
+impl std::ops::Sub for Vec2 {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, other: Self) -> Self {
+        Self {
+            x: self.x - other.x,
+            y: self.y - other.y,
+        }
+    }
+}
+
+impl std::ops::Mul<f64> for Vec2 {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, scale: f64) -> Self {
+        Self {
+            x: self.x * scale,
+            y: self.y * scale,
+        }
+    }
+}
+
+impl std::ops::Div<f64> for Vec2 {
+    type Output = Self;
+
+    #[inline]
+    fn div(self, scale: f64) -> Self {
+        Self {
+            x: self.x / scale,
+            y: self.y / scale,
+        }
+    }
+}
+
+impl std::ops::Neg for Vec2 {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self {
+        Self {
+            x: -self.x,
+            y: -self.y,
+        }
+    }
+}
+
+impl From<(f64, f64)> for Vec2 {
+    #[inline]
+    fn from((x, y): (f64, f64)) -> Self {
+        Self { x, y }
+    }
+}
+
+impl From<Vec2> for (f64, f64) {
+    #[inline]
+    fn from(v: Vec2) -> Self {
+        (v.x, v.y)
+    }
+}
+
+impl From<[f64; 2]> for Vec2 {
+    #[inline]
+    fn from([x, y]: [f64; 2]) -> Self {
+        Self { x, y }
+    }
+}
+
+impl From<Vec2> for [f64; 2] {
+    #[inline]
+    fn from(v: Vec2) -> Self {
+        [v.x, v.y]
+    }
+}
+
+crate::cfg_feature! {
+    #![feature = "glam"]
+
+    impl From<glam::DVec2> for Vec2 {
+        #[inline]
+        fn from(v: glam::DVec2) -> Self {
+            Self { x: v.x, y: v.y }
+        }
+    }
+
+    impl From<Vec2> for glam::DVec2 {
+        #[inline]
+        fn from(v: Vec2) -> Self {
+            glam::DVec2::new(v.x, v.y)
+        }
+    }
+
+    impl From<glam::Vec2> for Vec2 {
+        #[inline]
+        fn from(v: glam::Vec2) -> Self {
+            Self {
+                x: v.x.into(),
+                y: v.y.into(),
+            }
+        }
+    }
+
+    /// Converts to a single-precision vector, which may lose precision.
+    impl From<Vec2> for glam::Vec2 {
+        #[inline]
+        fn from(v: Vec2) -> Self {
+            glam::Vec2::new(v.x as f32, v.y as f32)
+        }
+    }
+}
+
+/// Formats as `(x, y)`. The precision, if specified (e.g., `{:.2}`), applies to both coordinates.
+impl std::fmt::Display for Vec2 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match f.precision() {
+            Some(precision) => write!(f, "({:.*}, {:.*})", precision, self.x, precision, self.y),
+            None => write!(f, "({}, {})", self.x, self.y),
+        }
+    }
+}
+
+impl HotkeyTriggeredEventConfig {
+    /// Receive events for all hotkeys, including hotkeys triggered by plugins. This is the same
+    /// as [`HotkeyTriggeredEventConfig::default`].
+    pub fn any() -> Self {
+        Self::default()
+    }
+
+    /// Only receive events for hotkeys with the given action (e.g.,
+    /// [`HotkeyAction::ToggleExpression`]).
+    ///
+    /// ```
+    /// use vtubestudio::data::{HotkeyAction, HotkeyTriggeredEventConfig};
+    ///
+    /// let config = HotkeyTriggeredEventConfig::for_action(HotkeyAction::TriggerAnimation);
+    /// assert_eq!(config.only_for_action, Some(HotkeyAction::TriggerAnimation.into()));
+    /// assert!(!config.ignore_hotkeys_triggered_by_api);
+    /// ```
+    pub fn for_action<A: Into<EnumString<HotkeyAction>>>(action: A) -> Self {
+        Self {
+            only_for_action: Some(action.into()),
+            ..Self::default()
+        }
+    }
+
+    /// Receive events for all hotkeys, except those triggered by plugins via
+    /// [`HotkeyTriggerRequest`].
+    pub fn all_except_api() -> Self {
+        Self {
+            ignore_hotkeys_triggered_by_api: true,
+            ..Self::default()
+        }
+    }
+}
+
+impl ModelAnimationEventConfig {
+    /// Only receive events from animations that aren't idle animations, played by the main
+    /// model (ignoring Live2D items).
+    pub fn for_main_model() -> Self {
+        Self {
+            ignore_live2d_items: true,
+            ignore_idle_animations: true,
+        }
+    }
+
+    /// Receive events from all animations, including idle animations and Live2D items. This is
+    /// the same as [`ModelAnimationEventConfig::default`].
+    pub fn for_all() -> Self {
+        Self::default()
+    }
+}
+
+impl ModelClickedEvent {
+    /// Returns the [`click_position`](Self::click_position) in pixels, relative to the top-left
+    /// corner of the window (see [`Vec2::to_pixels`]).
+    ///
+    /// ```
+    /// use vtubestudio::data::{ModelClickedEvent, Vec2};
+    ///
+    /// let event = ModelClickedEvent {
+    ///     click_position: Vec2::from((0.5, 0.5)),
+    ///     window_size: Vec2::from((800.0, 600.0)),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// assert_eq!(event.click_position_pixels(), Vec2::from((600.0, 150.0)));
+    /// ```
+    pub fn click_position_pixels(&self) -> Vec2 {
+        self.click_position.to_pixels(self.window_size)
+    }
+}
+
+impl ModelOutlineEvent {
+    /// Returns the [`convex_hull`](Self::convex_hull) points in pixels, relative to the top-left
+    /// corner of the window (see [`Vec2::to_pixels`]).
+    pub fn convex_hull_pixels(&self) -> Vec<Vec2> {
+        self.convex_hull
+            .iter()
+            .map(|point| point.to_pixels(self.window_size))
+            .collect()
+    }
+
+    /// Returns the [`convex_hull_center`](Self::convex_hull_center) in pixels, relative to the
+    /// top-left corner of the window.
+    pub fn convex_hull_center_pixels(&self) -> Vec2 {
+        self.convex_hull_center.to_pixels(self.window_size)
+    }
+
+    /// Returns the min and max corners of the axis-aligned bounding box of the
+    /// [`convex_hull`](Self::convex_hull), or `None` if the hull is empty.
+    ///
+    /// Points with non-finite coordinates are ignored.
+    ///
+    /// ```
+    /// use vtubestudio::data::{ModelOutlineEvent, Vec2};
+    ///
+    /// let event = ModelOutlineEvent {
+    ///     convex_hull: vec![
+    ///         Vec2::from((0.0, -0.5)),
+    ///         Vec2::from((0.5, 0.5)),
+    ///         Vec2::from((-0.5, 0.25)),
+    ///     ],
+    ///     ..Default::default()
+    /// };
+    ///
+    /// assert_eq!(
+    ///     event.convex_hull_bounding_box(),
+    ///     Some((Vec2::from((-0.5, -0.5)), Vec2::from((0.5, 0.5))))
+    /// );
+    /// ```
+    pub fn convex_hull_bounding_box(&self) -> Option<(Vec2, Vec2)> {
+        let mut points = self
+            .convex_hull
+            .iter()
+            .filter(|point| point.x.is_finite() && point.y.is_finite());
+
+        let first = *points.next()?;
+
+        Some(points.fold((first, first), |(min, max), point| {
+            (
+                Vec2 {
+                    x: min.x.min(point.x),
+                    y: min.y.min(point.y),
+                },
+                Vec2 {
+                    x: max.x.max(point.x),
+                    y: max.y.max(point.y),
+                },
+            )
+        }))
+    }
+
+    /// Returns the area of the [`convex_hull`](Self::convex_hull) polygon (using the [shoelace
+    /// formula]), or `0.0` if the hull has fewer than 3 points.
+    ///
+    /// [shoelace formula]: https://en.wikipedia.org/wiki/Shoelace_formula
+    pub fn convex_hull_area(&self) -> f64 {
+        self.signed_area_relative_to_first().abs()
+    }
+
+    /// Returns the centroid (center of mass) of the [`convex_hull`](Self::convex_hull) polygon,
+    /// or `None` if the hull is empty.
+    ///
+    /// Unlike [`convex_hull_center`](Self::convex_hull_center) (the average of the points), this
+    /// isn't skewed towards parts of the outline that have more points. If the hull has fewer
+    /// than 3 points or has no area, this falls back to the average of the points.
+    pub fn convex_hull_centroid(&self) -> Option<Vec2> {
+        let points = &self.convex_hull;
+        let origin = *points.first()?;
+
+        // Coordinates are made relative to the first point to reduce rounding errors for models
+        // that are far from the origin
+        let mut twice_area = 0.0;
+        let mut sum = Vec2::default();
+        for (a, b) in Self::edges(points) {
+            let (a, b) = (a - origin, b - origin);
+            let cross = a.x * b.y - b.x * a.y;
+            twice_area += cross;
+            sum = sum + (a + b) * cross;
+        }
+
+        let centroid = origin + sum / (3.0 * twice_area);
+        if points.len() >= 3
+            && twice_area != 0.0
+            && centroid.x.is_finite()
+            && centroid.y.is_finite()
+        {
+            Some(centroid)
+        } else {
+            let total = points
+                .iter()
+                .fold(Vec2::default(), |acc, point| acc + *point);
+            Some(total / points.len() as f64)
+        }
+    }
+
+    fn signed_area_relative_to_first(&self) -> f64 {
+        let points = &self.convex_hull;
+        let origin = match points.first() {
+            Some(origin) if points.len() >= 3 => *origin,
+            _ => return 0.0,
+        };
+
+        let twice_area: f64 = Self::edges(points)
+            .map(|(a, b)| {
+                let (a, b) = (a - origin, b - origin);
+                a.x * b.y - b.x * a.y
+            })
+            .sum();
+
+        twice_area / 2.0
+    }
+
+    /// Iterates over consecutive pairs of points, including the pair from the last point back to
+    /// the first.
+    fn edges(points: &[Vec2]) -> impl Iterator<Item = (Vec2, Vec2)> + '_ {
+        points
+            .iter()
+            .zip(points.iter().cycle().skip(1))
+            .map(|(a, b)| (*a, *b))
+    }
+}
+
+#[allow(missing_docs)]
+#[derive(Deserialize, Serialize, Debug, PartialEq, Eq, Hash, Clone, Default)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+/// Known message types for [`EnumString<InjectParameterDataMode>`] (used in
+/// [`InjectParameterDataRequest`]).
+pub enum InjectParameterDataMode {
+    #[serde(rename = "set")]
+    #[default]
+    Set,
+    #[serde(rename = "add")]
+    Add,
+}
+
+#[allow(missing_docs)]
+#[derive(Deserialize, Serialize, Debug, PartialEq, Eq, Hash, Clone, Default)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+/// Known message types for [`EnumString<ItemType>`]. Used in [`ItemInstanceInScene`].
+pub enum ItemType {
+    #[serde(rename = "PNG")]
+    Png,
+    #[serde(rename = "JPG")]
+    Jpg,
+    #[serde(rename = "GIF")]
+    Gif,
+    AnimationFolder,
+    #[serde(rename = "Live2D")]
+    Live2D,
+    #[default]
+    Unknown,
+}
 
 /// Used in [`ItemUnloadResponse`].
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct UnloadedItem {
     /// Instance ID.
@@ -2098,6 +3169,7 @@ pub struct UnloadedItem {
 
 /// Used in [`ItemListResponse`].
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ItemInstanceInScene {
     /// File name
@@ -2140,8 +3212,63 @@ pub struct ItemInstanceInScene {
     pub from_workshop: bool,
 }
 
+impl ItemListResponse {
+    /// Returns the item instances in the scene that were loaded from the given file.
+    ///
+    /// ```
+    /// use vtubestudio::data::{ItemInstanceInScene, ItemListResponse, ItemType};
+    ///
+    /// let resp = ItemListResponse {
+    ///     item_instances_in_scene: vec![
+    ///         ItemInstanceInScene {
+    ///             file_name: "akari.png".into(),
+    ///             type_: ItemType::Png.into(),
+    ///             pinned_to_model: true,
+    ///             ..Default::default()
+    ///         },
+    ///         ItemInstanceInScene {
+    ///             file_name: "my_live2d_item".into(),
+    ///             type_: ItemType::Live2D.into(),
+    ///             ..Default::default()
+    ///         },
+    ///     ],
+    ///     ..Default::default()
+    /// };
+    ///
+    /// assert_eq!(resp.items_by_file_name("akari.png").count(), 1);
+    /// assert_eq!(resp.items_by_type(ItemType::Live2D).count(), 1);
+    /// assert_eq!(resp.pinned_items().count(), 1);
+    /// ```
+    pub fn items_by_file_name<'a>(
+        &'a self,
+        file_name: &'a str,
+    ) -> impl Iterator<Item = &'a ItemInstanceInScene> + 'a {
+        self.item_instances_in_scene
+            .iter()
+            .filter(move |item| item.file_name == file_name)
+    }
+
+    /// Returns the item instances in the scene with the given item type.
+    pub fn items_by_type(
+        &self,
+        item_type: ItemType,
+    ) -> impl Iterator<Item = &ItemInstanceInScene> + '_ {
+        self.item_instances_in_scene
+            .iter()
+            .filter(move |item| item.type_ == item_type)
+    }
+
+    /// Returns the item instances in the scene that are pinned to a model.
+    pub fn pinned_items(&self) -> impl Iterator<Item = &ItemInstanceInScene> + '_ {
+        self.item_instances_in_scene
+            .iter()
+            .filter(|item| item.pinned_to_model)
+    }
+}
+
 /// Used in [`ItemListResponse`].
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct AvailableItemFile {
     /// File name.
@@ -2155,6 +3282,7 @@ pub struct AvailableItemFile {
 
 /// Used in [`ItemMoveRequest`].
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ItemToMove {
     /// Item instance ID.
@@ -2197,8 +3325,158 @@ pub struct ItemToMove {
     pub user_can_stop: bool,
 }
 
+impl ItemToMove {
+    /// The maximum allowed value of [`time_in_seconds`](Self::time_in_seconds).
+    pub const MAX_TIME_IN_SECONDS: f64 = 30.0;
+
+    /// Creates an [`ItemToMove`] that doesn't change anything about the item, to be configured
+    /// with the setter methods below. This is the same as [`ItemToMove::no_op`].
+    ///
+    /// Values that aren't set are left unchanged, without needing to use the `-1000` value
+    /// described in the API documentation.
+    ///
+    /// ```
+    /// use vtubestudio::data::{FadeMode, ItemToMove};
+    ///
+    /// let item = ItemToMove::new("item-id")
+    ///     .position(100, -200)
+    ///     .rotation(90.0)
+    ///     .in_seconds(1.5)
+    ///     .fade_mode(FadeMode::EaseBoth);
+    /// item.validate()?;
+    ///
+    /// let json = serde_json::to_value(&item)?;
+    /// assert_eq!(json["positionX"], 100);
+    /// assert_eq!(json["rotation"], 90.0);
+    /// assert_eq!(json["size"], -1000.0);
+    /// # Ok::<_, vtubestudio::error::BoxError>(())
+    /// ```
+    pub fn new<S: Into<String>>(instance_id: S) -> Self {
+        Self::no_op(instance_id)
+    }
+
+    /// Sets the position to move the item to.
+    pub fn position(mut self, x: i32, y: i32) -> Self {
+        self.position_x = Some(x);
+        self.position_y = Some(y);
+        self
+    }
+
+    /// Sets the size to change the item to.
+    pub fn size(mut self, size: f64) -> Self {
+        self.size = Some(size);
+        self
+    }
+
+    /// Sets the rotation (in degrees) to change the item to.
+    pub fn rotation(mut self, rotation: f64) -> Self {
+        self.rotation = Some(rotation);
+        self
+    }
+
+    /// Sets the order to change the item to.
+    pub fn order(mut self, order: i32) -> Self {
+        self.order = Some(order);
+        self
+    }
+
+    /// Sets how long the movement should take. Must be between `0` and
+    /// [`ItemToMove::MAX_TIME_IN_SECONDS`]. Defaults to `0` (instant).
+    pub fn in_seconds(mut self, time_in_seconds: f64) -> Self {
+        self.time_in_seconds = time_in_seconds;
+        self
+    }
+
+    /// Sets the [`FadeMode`], used if the movement isn't instant.
+    pub fn fade_mode<M: Into<EnumString<FadeMode>>>(mut self, mode: M) -> Self {
+        self.fade_mode = mode.into();
+        self
+    }
+
+    /// Checks that the values are within the ranges accepted by the API.
+    ///
+    /// This returns an error with [`ErrorKind::InvalidRequest`](crate::error::ErrorKind) if
+    /// `time_in_seconds` is negative or greater than [`ItemToMove::MAX_TIME_IN_SECONDS`] (since
+    /// VTube Studio would otherwise clamp it silently).
+    pub fn validate(&self) -> Result<(), crate::error::Error> {
+        use crate::error::{Error, ErrorKind};
+
+        if !(0.0..=Self::MAX_TIME_IN_SECONDS).contains(&self.time_in_seconds) {
+            return Err(Error::new(ErrorKind::InvalidRequest).with_source(format!(
+                "time_in_seconds for item {} must be between 0 and 30",
+                self.item_instance_id
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Creates an [`ItemToMove`] that doesn't change anything about the item.
+    ///
+    /// All optional fields are `None`, so they're serialized as `-1000` (meaning "unchanged").
+    ///
+    /// ```
+    /// use vtubestudio::data::ItemToMove;
+    ///
+    /// let item = ItemToMove::no_op("item-id");
+    /// let json = serde_json::to_value(&item)?;
+    /// assert_eq!(json["positionX"], -1000);
+    /// assert_eq!(json["size"], -1000.0);
+    /// # Ok::<_, serde_json::Error>(())
+    /// ```
+    pub fn no_op<S: Into<String>>(instance_id: S) -> Self {
+        Self {
+            item_instance_id: instance_id.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Moves the item to a position instantly.
+    pub fn teleport<S: Into<String>>(instance_id: S, x: i32, y: i32) -> Self {
+        Self {
+            position_x: Some(x),
+            position_y: Some(y),
+            ..Self::no_op(instance_id)
+        }
+    }
+
+    /// Moves the item to a position over the given number of seconds, using the given
+    /// [`FadeMode`].
+    pub fn ease_to<S, M>(instance_id: S, x: i32, y: i32, seconds: f64, mode: M) -> Self
+    where
+        S: Into<String>,
+        M: Into<EnumString<FadeMode>>,
+    {
+        Self {
+            time_in_seconds: seconds,
+            fade_mode: mode.into(),
+            ..Self::teleport(instance_id, x, y)
+        }
+    }
+
+    /// Rotates the item to the given angle (in degrees) over the given number of seconds, without
+    /// moving it.
+    pub fn rotate_to<S: Into<String>>(instance_id: S, rotation: f64, seconds: f64) -> Self {
+        Self {
+            rotation: Some(rotation),
+            time_in_seconds: seconds,
+            ..Self::no_op(instance_id)
+        }
+    }
+
+    /// Resizes the item over the given number of seconds, without moving it.
+    pub fn resize<S: Into<String>>(instance_id: S, size: f64, seconds: f64) -> Self {
+        Self {
+            size: Some(size),
+            time_in_seconds: seconds,
+            ..Self::no_op(instance_id)
+        }
+    }
+}
+
 /// Used in [`ItemMoveResponse`].
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct MovedItem {
     /// Item instance ID.
@@ -2217,6 +3495,7 @@ pub struct MovedItem {
 
 /// Post-processing effect, used in [`PostProcessingListResponse`]
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct PostProcessingEffect {
     /// Internal ID. E.g., `"color_grading"`.
@@ -2238,6 +3517,7 @@ pub struct PostProcessingEffect {
 // TODO: Make this an enum
 /// Config entry used in [`PostProcessingEffect`].
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct PostProcessingEffectConfigEntry {
     /// Internal ID. E.g., `"color_grading-strength"`.
@@ -2291,6 +3571,7 @@ pub struct PostProcessingEffectConfigEntry {
 
 /// Post-processing value used in [`PostProcessingUpdateRequest`].
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct PostProcessingValue {
     /// Config ID. E.g., `"Backlight_Strength"`.
@@ -2304,7 +3585,7 @@ fn moved_item_error_deserialize<'de, D>(deserializer: D) -> Result<Option<ErrorI
 where
     D: Deserializer<'de>,
 {
-    let id = i32::deserialize(deserializer)?;
+    let id = lenient::de_i32_lenient(deserializer)?;
     if id == -1 {
         Ok(None)
     } else {
@@ -2339,11 +3620,13 @@ where
 }
 
 #[allow(missing_docs)]
-#[derive(Deserialize, Serialize, Debug, PartialEq, Clone)]
+#[derive(Deserialize, Serialize, Debug, PartialEq, Eq, Hash, Clone, Default)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 #[non_exhaustive]
 /// Known message types for [`EnumString<FadeMode>`]. Used in [`ItemToMove`].
 pub enum FadeMode {
+    #[default]
     Linear,
     EaseIn,
     EaseOut,
@@ -2352,12 +3635,6 @@ pub enum FadeMode {
     Zip,
 }
 
-impl Default for FadeMode {
-    fn default() -> Self {
-        Self::Linear
-    }
-}
-
 // Per the docs, we should send `-1` if the user doesn't want to change the NDI width or height.
 fn ndi_default_size<S>(value: &Option<i32>, serializer: S) -> Result<S::Ok, S::Error>
 where
@@ -2368,6 +3645,7 @@ where
 
 /// Error returned by the VTube Studio API.
 #[derive(thiserror::Error, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 #[error("APIError {error_id}: {message}")]
 pub struct ApiError {
@@ -2387,10 +3665,16 @@ impl ApiError {
     pub fn is_unauthenticated(&self) -> bool {
         self.error_id.is_unauthenticated()
     }
+
+    /// Returns `true` if this error indicates the plugin lacks a required permission.
+    pub fn is_permission_error(&self) -> bool {
+        self.error_id.is_permission_error()
+    }
 }
 
 /// API server discovery message (sent over UDP).
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct VTubeStudioApiStateBroadcast {
     /// Whether the API is active.
@@ -2411,6 +3695,7 @@ impl Response for VTubeStudioApiStateBroadcast {
 
 /// Used in [`CurrentModelResponse`] and [`ModelMovedEvent`].
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ModelPosition {
     /// The X position of the model.
@@ -2423,27 +3708,530 @@ pub struct ModelPosition {
     pub size: f64,
 }
 
-/// Used in [`AvailableModelsResponse`].
-#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct Model {
-    /// Whether the model is loaded.
-    pub model_loaded: bool,
-    /// The name of the model.
-    pub model_name: String,
-    /// The ID of the model.
-    #[serde(rename = "modelID")]
-    pub model_id: String,
-    /// The VTube Studio JSON file for this model.
-    pub vts_model_name: String,
-    /// The image name of this model's VTube Studio icon.
-    pub vts_model_icon_name: String,
-}
-
-/// Used in [`HotkeysInCurrentModelResponse`].
-#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct Hotkey {
+impl ModelPosition {
+    /// Linearly interpolates between `self` and `other`. The value of `t` is clamped between `0.0`
+    /// (returning `self`) and `1.0` (returning `other`).
+    ///
+    /// ```
+    /// use vtubestudio::data::ModelPosition;
+    ///
+    /// let from = ModelPosition::default();
+    /// let to = ModelPosition {
+    ///     position_x: 1.0,
+    ///     position_y: -1.0,
+    ///     rotation: 90.0,
+    ///     size: 50.0,
+    /// };
+    ///
+    /// let midpoint = from.lerp(&to, 0.5);
+    /// assert_eq!(midpoint.position_x, 0.5);
+    /// assert_eq!(midpoint.rotation, 45.0);
+    /// ```
+    pub fn lerp(&self, other: &ModelPosition, t: f64) -> ModelPosition {
+        let t = if t.is_nan() { 0.0 } else { t.clamp(0.0, 1.0) };
+        let lerp = |a: f64, b: f64| a + (b - a) * t;
+
+        ModelPosition {
+            position_x: lerp(self.position_x, other.position_x),
+            position_y: lerp(self.position_y, other.position_y),
+            rotation: lerp(self.rotation, other.rotation),
+            size: lerp(self.size, other.size),
+        }
+    }
+
+    /// Returns the distance between the positions of two models, ignoring rotation and size.
+    pub fn distance_to(&self, other: &ModelPosition) -> f64 {
+        (self.position_x - other.position_x).hypot(self.position_y - other.position_y)
+    }
+
+    /// Returns a copy of this position with values clamped to the ranges accepted by
+    /// [`MoveModelRequest`]: `-1000` to `1000` for the position, `-360` to `360` for the rotation,
+    /// and `-100` to `100` for the size.
+    pub fn clamped(&self) -> ModelPosition {
+        ModelPosition {
+            position_x: self.position_x.clamp(-1000.0, 1000.0),
+            position_y: self.position_y.clamp(-1000.0, 1000.0),
+            rotation: self.rotation.clamp(-360.0, 360.0),
+            size: self.size.clamp(-100.0, 100.0),
+        }
+    }
+
+    /// Sets the position, rotation, and size of a [`MoveModelRequest`] to these values.
+    ///
+    /// Since these are absolute values, this also sets
+    /// [`values_are_relative_to_model`](MoveModelRequest::values_are_relative_to_model) to
+    /// `false`. The [`time_in_seconds`](MoveModelRequest::time_in_seconds) is left unchanged.
+    pub fn apply_to_move_request(&self, req: &mut MoveModelRequest) {
+        req.values_are_relative_to_model = false;
+        req.position_x = Some(self.position_x);
+        req.position_y = Some(self.position_y);
+        req.rotation = Some(self.rotation);
+        req.size = Some(self.size);
+    }
+}
+
+impl From<CurrentModelResponse> for ModelPosition {
+    fn from(resp: CurrentModelResponse) -> Self {
+        resp.model_position
+    }
+}
+
+impl MoveModelRequest {
+    /// The maximum allowed value of [`time_in_seconds`](Self::time_in_seconds).
+    pub const MAX_TIME_IN_SECONDS: f64 = 2.0;
+
+    /// Creates a [`MoveModelRequestBuilder`], for constructing a request from a current and
+    /// target [`ModelPosition`].
+    pub fn builder() -> MoveModelRequestBuilder {
+        MoveModelRequestBuilder::default()
+    }
+}
+
+/// Builder for a [`MoveModelRequest`] that moves a model from one [`ModelPosition`] to another.
+///
+/// In absolute mode (the default), the request moves the model directly to the target position.
+/// In [`relative`](Self::relative) mode, the request contains the difference between the current
+/// and target positions, which requires the current position to be set with
+/// [`from`](Self::from).
+///
+/// Target values are clamped to the ranges accepted by VTube Studio (see
+/// [`ModelPosition::clamped`]).
+///
+/// ```
+/// use vtubestudio::data::{ModelPosition, MoveModelRequest};
+///
+/// let current = ModelPosition {
+///     position_x: 0.5,
+///     position_y: 0.0,
+///     rotation: 10.0,
+///     size: -20.0,
+/// };
+///
+/// let target = ModelPosition {
+///     position_x: -0.5,
+///     rotation: 400.0,
+///     ..current.clone()
+/// };
+///
+/// let req = MoveModelRequest::builder()
+///     .from(&current)
+///     .to(&target)
+///     .in_seconds(0.5)
+///     .relative()
+///     .build()?;
+///
+/// assert!(req.values_are_relative_to_model);
+/// assert_eq!(req.position_x, Some(-1.0));
+/// assert_eq!(req.rotation, Some(350.0)); // Rotation is clamped to 360 degrees
+/// assert_eq!(req.time_in_seconds, 0.5);
+/// # Ok::<_, vtubestudio::error::Error>(())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MoveModelRequestBuilder {
+    from: Option<ModelPosition>,
+    to: Option<ModelPosition>,
+    time_in_seconds: f64,
+    relative: bool,
+}
+
+impl MoveModelRequestBuilder {
+    /// Sets the current position of the model. Required in [`relative`](Self::relative) mode.
+    pub fn from(mut self, current: &ModelPosition) -> Self {
+        self.from = Some(current.clone());
+        self
+    }
+
+    /// Sets the position to move the model to. This is required.
+    pub fn to(mut self, target: &ModelPosition) -> Self {
+        self.to = Some(target.clone());
+        self
+    }
+
+    /// Sets how long the movement should take. Must be between `0` and
+    /// [`MoveModelRequest::MAX_TIME_IN_SECONDS`]. Defaults to `0` (instant).
+    pub fn in_seconds(mut self, time_in_seconds: f64) -> Self {
+        self.time_in_seconds = time_in_seconds;
+        self
+    }
+
+    /// Sends the difference between the current and target positions, with
+    /// [`values_are_relative_to_model`](MoveModelRequest::values_are_relative_to_model) set to
+    /// `true`.
+    pub fn relative(mut self) -> Self {
+        self.relative = true;
+        self
+    }
+
+    /// Sends the target position as-is, with
+    /// [`values_are_relative_to_model`](MoveModelRequest::values_are_relative_to_model) set to
+    /// `false`. This is the default.
+    pub fn absolute(mut self) -> Self {
+        self.relative = false;
+        self
+    }
+
+    /// Builds the [`MoveModelRequest`].
+    ///
+    /// This returns an error with [`ErrorKind::InvalidRequest`](crate::error::ErrorKind) if the
+    /// time is negative or greater than [`MoveModelRequest::MAX_TIME_IN_SECONDS`], if no target
+    /// position was set, or if no current position was set in relative mode.
+    pub fn build(self) -> Result<MoveModelRequest, crate::error::Error> {
+        use crate::error::{Error, ErrorKind};
+
+        let invalid = |message: &str| Error::new(ErrorKind::InvalidRequest).with_source(message);
+
+        if !(0.0..=MoveModelRequest::MAX_TIME_IN_SECONDS).contains(&self.time_in_seconds) {
+            return Err(invalid("time_in_seconds must be between 0 and 2"));
+        }
+
+        let target = self
+            .to
+            .ok_or_else(|| invalid("no target position was provided"))?
+            .clamped();
+
+        let values = if self.relative {
+            let current = self
+                .from
+                .ok_or_else(|| invalid("relative moves require a current position"))?;
+
+            ModelPosition {
+                position_x: target.position_x - current.position_x,
+                position_y: target.position_y - current.position_y,
+                rotation: target.rotation - current.rotation,
+                size: target.size - current.size,
+            }
+        } else {
+            target
+        };
+
+        Ok(MoveModelRequest {
+            time_in_seconds: self.time_in_seconds,
+            values_are_relative_to_model: self.relative,
+            position_x: Some(values.position_x),
+            position_y: Some(values.position_y),
+            rotation: Some(values.rotation),
+            size: Some(values.size),
+        })
+    }
+}
+
+impl ItemLoadRequest {
+    /// The maximum size of [`custom_data_base64`](Self::custom_data_base64), in bytes, before it's
+    /// encoded to base64.
+    pub const MAX_CUSTOM_DATA_SIZE: usize = 5 * 1024 * 1024;
+
+    /// The maximum allowed value of [`fade_time`](Self::fade_time).
+    pub const MAX_FADE_TIME: f64 = 2.0;
+
+    /// Creates an [`ItemLoadRequestBuilder`] for the given file name.
+    pub fn builder<S: Into<String>>(file_name: S) -> ItemLoadRequestBuilder {
+        ItemLoadRequestBuilder::new(file_name)
+    }
+}
+
+impl HotkeysInCurrentModelRequest {
+    /// Requests the hotkeys of the model with the given ID.
+    ///
+    /// ```
+    /// use vtubestudio::data::HotkeysInCurrentModelRequest;
+    ///
+    /// let req = HotkeysInCurrentModelRequest::for_model("some-model-id");
+    /// assert_eq!(req.model_id.as_deref(), Some("some-model-id"));
+    /// assert_eq!(req.live2d_item_file_name, None);
+    /// ```
+    pub fn for_model<S: Into<String>>(model_id: S) -> Self {
+        Self {
+            model_id: Some(model_id.into()),
+            live2d_item_file_name: None,
+        }
+    }
+
+    /// Requests the hotkeys of the Live2D item with the given file name.
+    pub fn for_live2d_item<S: Into<String>>(file_name: S) -> Self {
+        Self {
+            model_id: None,
+            live2d_item_file_name: Some(file_name.into()),
+        }
+    }
+}
+
+/// Builder for an [`ItemLoadRequest`] that validates values up-front.
+///
+/// Fields that aren't set use the same defaults as VTube Studio when the user loads an item
+/// manually (e.g., a [`size`](Self::size) of `0.32`). Values are checked when calling
+/// [`build`](Self::build), instead of waiting for VTube Studio to reject the request.
+///
+/// ```
+/// use vtubestudio::data::ItemLoadRequest;
+///
+/// let req = ItemLoadRequest::builder("akari_3.png")
+///     .position(0.5, -0.5)
+///     .rotation(90.0)
+///     .fade_time(0.5)
+///     .unload_when_plugin_disconnects(true)
+///     .build()?;
+///
+/// assert_eq!(req.file_name, "akari_3.png");
+/// assert_eq!(req.size, 0.32);
+/// assert_eq!((req.position_x, req.position_y), (0.5, -0.5));
+///
+/// // Size must be between 0 and 1
+/// assert!(ItemLoadRequest::builder("akari_3.png").size(1.5).build().is_err());
+/// # Ok::<_, vtubestudio::error::Error>(())
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ItemLoadRequestBuilder {
+    req: ItemLoadRequest,
+}
+
+impl ItemLoadRequestBuilder {
+    /// Creates a new [`ItemLoadRequestBuilder`] for the given file name.
+    pub fn new<S: Into<String>>(file_name: S) -> Self {
+        Self {
+            req: ItemLoadRequest {
+                file_name: file_name.into(),
+                size: 0.32,
+                custom_data_skip_asking_user_if_whitelisted: true,
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Sets the X and Y position of the item. Defaults to `(0, 0)` (the center of the screen).
+    pub fn position(mut self, x: f64, y: f64) -> Self {
+        self.req.position_x = x;
+        self.req.position_y = y;
+        self
+    }
+
+    /// Sets the size of the item, between `0` and `1`. Defaults to `0.32`.
+    pub fn size(mut self, size: f64) -> Self {
+        self.req.size = size;
+        self
+    }
+
+    /// Sets the rotation of the item, in degrees. Defaults to `0`.
+    pub fn rotation(mut self, rotation: f64) -> Self {
+        self.req.rotation = rotation;
+        self
+    }
+
+    /// Sets the fade time, in seconds, between `0` and [`ItemLoadRequest::MAX_FADE_TIME`].
+    /// Defaults to `0`.
+    pub fn fade_time(mut self, fade_time: f64) -> Self {
+        self.req.fade_time = fade_time;
+        self
+    }
+
+    /// Sets base64-encoded PNG, JPG or GIF data to load as a custom item.
+    ///
+    /// VTube Studio asks the user for permission before loading custom data, unless the plugin
+    /// has been whitelisted.
+    pub fn custom_data_base64<S: Into<String>>(mut self, data: S) -> Self {
+        self.req.custom_data_base64 = Some(data.into());
+        self
+    }
+
+    /// Whether to unload the item when the plugin disconnects. Defaults to `false`.
+    pub fn unload_when_plugin_disconnects(mut self, unload: bool) -> Self {
+        self.req.unload_when_plugin_disconnects = unload;
+        self
+    }
+
+    /// Builds the [`ItemLoadRequest`].
+    ///
+    /// This returns an error with [`ErrorKind::InvalidRequest`](crate::error::ErrorKind) if the
+    /// file name is empty, if the size is outside of the range `0..=1`, if the fade time is
+    /// outside of the range `0..=2`, if any value is not finite, or if the custom data is empty
+    /// or larger than [`ItemLoadRequest::MAX_CUSTOM_DATA_SIZE`] once decoded.
+    pub fn build(self) -> Result<ItemLoadRequest, crate::error::Error> {
+        use crate::error::{Error, ErrorKind};
+
+        let invalid = |message: &str| Error::new(ErrorKind::InvalidRequest).with_source(message);
+        let req = self.req;
+
+        if req.file_name.is_empty() {
+            return Err(invalid("file name is empty"));
+        }
+
+        if !(0.0..=1.0).contains(&req.size) {
+            return Err(invalid("size must be between 0 and 1"));
+        }
+
+        if !(0.0..=ItemLoadRequest::MAX_FADE_TIME).contains(&req.fade_time) {
+            return Err(invalid("fade_time must be between 0 and 2"));
+        }
+
+        if !(req.position_x.is_finite() && req.position_y.is_finite() && req.rotation.is_finite()) {
+            return Err(invalid("position and rotation must be finite"));
+        }
+
+        if let Some(data) = &req.custom_data_base64 {
+            let padding = data.bytes().rev().take_while(|&b| b == b'=').count();
+            let decoded_len = (data.len() / 4 * 3).saturating_sub(padding);
+
+            if decoded_len == 0 {
+                return Err(invalid("custom data is empty"));
+            }
+
+            if decoded_len > ItemLoadRequest::MAX_CUSTOM_DATA_SIZE {
+                return Err(invalid("custom data must be smaller than 5 MB"));
+            }
+        }
+
+        Ok(req)
+    }
+}
+
+/// Used in [`AvailableModelsResponse`].
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct Model {
+    /// Whether the model is loaded.
+    pub model_loaded: bool,
+    /// The name of the model.
+    pub model_name: String,
+    /// The ID of the model.
+    #[serde(rename = "modelID")]
+    pub model_id: String,
+    /// The VTube Studio JSON file for this model.
+    pub vts_model_name: String,
+    /// The image name of this model's VTube Studio icon.
+    pub vts_model_icon_name: String,
+}
+
+impl AvailableModelsResponse {
+    /// Returns the model with the given ID, if any.
+    ///
+    /// This scans the list of models. For repeated lookups, use a [`ModelIndex`].
+    ///
+    /// ```
+    /// use vtubestudio::data::{AvailableModelsResponse, Model};
+    ///
+    /// let model = |id: &str, name: &str, loaded: bool| Model {
+    ///     model_id: id.into(),
+    ///     model_name: name.into(),
+    ///     model_loaded: loaded,
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let resp = AvailableModelsResponse {
+    ///     number_of_models: 3,
+    ///     available_models: vec![
+    ///         model("a", "Akari", false),
+    ///         model("b", "Akari", true),
+    ///         model("c", "Hiyori", false),
+    ///     ],
+    /// };
+    ///
+    /// assert_eq!(resp.find_by_id("c").unwrap().model_name, "Hiyori");
+    /// assert_eq!(resp.find_by_name("akari").len(), 2);
+    /// assert_eq!(resp.find_loaded().unwrap().model_id, "b");
+    /// assert!(!resp.contains_id("d"));
+    /// ```
+    pub fn find_by_id(&self, id: &str) -> Option<&Model> {
+        self.available_models
+            .iter()
+            .find(|model| model.model_id == id)
+    }
+
+    /// Returns all models with the given name, ignoring case (since model names aren't unique).
+    pub fn find_by_name(&self, name: &str) -> Vec<&Model> {
+        let name = name.to_lowercase();
+        self.available_models
+            .iter()
+            .filter(|model| model.model_name.to_lowercase() == name)
+            .collect()
+    }
+
+    /// Returns the currently loaded model, if any.
+    pub fn find_loaded(&self) -> Option<&Model> {
+        self.available_models
+            .iter()
+            .find(|model| model.model_loaded)
+    }
+
+    /// Returns `true` if there is a model with the given ID.
+    pub fn contains_id(&self, id: &str) -> bool {
+        self.find_by_id(id).is_some()
+    }
+}
+
+/// An index of [`Model`]s by ID, borrowed from a list of models.
+///
+/// # Example
+///
+/// ```
+/// use vtubestudio::data::{AvailableModelsResponse, Model, ModelIndex};
+///
+/// let resp = AvailableModelsResponse {
+///     number_of_models: 1,
+///     available_models: vec![Model {
+///         model_id: "abc".into(),
+///         model_name: "Akari".into(),
+///         ..Default::default()
+///     }],
+/// };
+///
+/// let index = ModelIndex::from(&resp);
+/// assert_eq!(index.get("abc").unwrap().model_name, "Akari");
+/// assert_eq!(index.get("def"), None);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ModelIndex<'a> {
+    models: HashMap<&'a str, &'a Model>,
+}
+
+impl<'a> ModelIndex<'a> {
+    /// Returns the model with the given ID, if any.
+    pub fn get(&self, id: &str) -> Option<&'a Model> {
+        self.models.get(id).copied()
+    }
+
+    /// Returns `true` if there is a model with the given ID.
+    pub fn contains_id(&self, id: &str) -> bool {
+        self.models.contains_key(id)
+    }
+
+    /// The number of indexed models.
+    pub fn len(&self) -> usize {
+        self.models.len()
+    }
+
+    /// Returns `true` if no models are indexed.
+    pub fn is_empty(&self) -> bool {
+        self.models.is_empty()
+    }
+}
+
+impl<'a> std::iter::FromIterator<&'a Model> for ModelIndex<'a> {
+    fn from_iter<T: IntoIterator<Item = &'a Model>>(iter: T) -> Self {
+        let mut models = HashMap::new();
+        for model in iter {
+            models.entry(model.model_id.as_str()).or_insert(model);
+        }
+        Self { models }
+    }
+}
+
+impl<'a> From<&'a [Model]> for ModelIndex<'a> {
+    fn from(models: &'a [Model]) -> Self {
+        models.iter().collect()
+    }
+}
+
+impl<'a> From<&'a AvailableModelsResponse> for ModelIndex<'a> {
+    fn from(resp: &'a AvailableModelsResponse) -> Self {
+        Self::from(resp.available_models.as_slice())
+    }
+}
+
+/// Used in [`HotkeysInCurrentModelResponse`].
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct Hotkey {
     /// The name of the hotkey.
     pub name: String,
     /// The hotkey type.
@@ -2455,7 +4243,7 @@ pub struct Hotkey {
     pub file: String,
     /// Unique ID of the hotkey.
     #[serde(rename = "hotkeyID")]
-    pub hotkey_id: String,
+    pub hotkey_id: HotkeyId,
     /// Human-readable description of the hotkey type.
     pub description: Option<String>,
     /// Keyboard/mouse key combination that will trigger this hotkey.
@@ -2470,8 +4258,131 @@ pub struct Hotkey {
     pub on_screen_button_id: i32,
 }
 
+impl Hotkey {
+    fn action(&self) -> Option<&HotkeyAction> {
+        self.type_.as_known()
+    }
+
+    /// Returns true if this hotkey toggles an expression or removes all expressions.
+    pub fn is_expression(&self) -> bool {
+        matches!(
+            self.action(),
+            Some(HotkeyAction::ToggleExpression | HotkeyAction::RemoveAllExpressions)
+        )
+    }
+
+    /// Returns true if this hotkey plays an animation or changes the idle animation.
+    pub fn is_animation(&self) -> bool {
+        matches!(
+            self.action(),
+            Some(HotkeyAction::TriggerAnimation | HotkeyAction::ChangeIdleAnimation)
+        )
+    }
+
+    /// Returns true if this hotkey changes the current VTube Studio model.
+    pub fn is_model_change(&self) -> bool {
+        matches!(self.action(), Some(HotkeyAction::ChangeVtsModel))
+    }
+
+    /// Returns true if this hotkey loads, removes, or interacts with items.
+    pub fn is_item_action(&self) -> bool {
+        matches!(
+            self.action(),
+            Some(
+                HotkeyAction::ToggleItemScene
+                    | HotkeyAction::RemoveAllItems
+                    | HotkeyAction::DownloadRandomWorkshopItem
+                    | HotkeyAction::ExecuteItemAction
+            )
+        )
+    }
+
+    /// The file extension expected in [`file`](Self::file) for this hotkey type, or `None` if
+    /// the hotkey type isn't associated with a specific kind of file.
+    ///
+    /// ```
+    /// use vtubestudio::data::{Hotkey, HotkeyAction};
+    ///
+    /// let hotkey = Hotkey {
+    ///     type_: HotkeyAction::ToggleExpression.into(),
+    ///     file: "myExpression_1.exp3.json".to_owned(),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let ext = hotkey.expected_file_extension();
+    /// assert_eq!(ext, Some(".exp3.json"));
+    /// assert!(ext.map_or(true, |ext| hotkey.file.ends_with(ext)));
+    /// ```
+    pub fn expected_file_extension(&self) -> Option<&'static str> {
+        match self.action()? {
+            HotkeyAction::ToggleExpression => Some(".exp3.json"),
+            HotkeyAction::TriggerAnimation | HotkeyAction::ChangeIdleAnimation => {
+                Some(".motion3.json")
+            }
+            HotkeyAction::ChangeVtsModel => Some(".vtube.json"),
+            _ => None,
+        }
+    }
+}
+
+/// Unique ID of a hotkey. Used in [`Hotkey`] and [`HotkeyTriggerRequest`].
+///
+/// ```
+/// use vtubestudio::data::HotkeyId;
+///
+/// let id = HotkeyId::from("21bf7ade9e664f3ec29d05156e4ce5c1");
+/// assert_eq!(id.as_str(), "21bf7ade9e664f3ec29d05156e4ce5c1");
+/// assert_eq!(serde_json::to_string(&id)?, r#""21bf7ade9e664f3ec29d05156e4ce5c1""#);
+/// # Ok::<_, serde_json::Error>(())
+/// ```
+#[derive(Default, Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct HotkeyId(String);
+
+impl HotkeyId {
+    /// Creates a new [`HotkeyId`].
+    pub fn new(value: String) -> Self {
+        Self(value)
+    }
+
+    /// Returns the string representation of the hotkey ID.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Consumes this value and returns the inner `String`.
+    pub fn into_string(self) -> String {
+        self.0
+    }
+}
+
+impl From<String> for HotkeyId {
+    fn from(value: String) -> Self {
+        Self(value)
+    }
+}
+
+impl From<&str> for HotkeyId {
+    fn from(value: &str) -> Self {
+        Self(value.to_owned())
+    }
+}
+
+impl From<HotkeyId> for String {
+    fn from(id: HotkeyId) -> Self {
+        id.0
+    }
+}
+
+impl std::fmt::Display for HotkeyId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
 /// Used in [`ColorTintRequest`].
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ColorTint {
     /// The red component of the color.
@@ -2506,32 +4417,216 @@ impl Default for ColorTint {
     }
 }
 
-/// Used in [`ColorTintRequest`].
-#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct ArtMeshMatcher {
-    /// Whether to tint all art meshes.
-    pub tint_all: bool,
-    /// The number of this art mesh.
-    #[serde(skip_serializing_if = "Vec::is_empty")]
-    pub art_mesh_number: Vec<i32>,
-    /// Match art meshes with these exact names.
-    #[serde(skip_serializing_if = "Vec::is_empty")]
-    pub name_exact: Vec<String>,
-    /// Match art meshes that contain these strings.
-    #[serde(skip_serializing_if = "Vec::is_empty")]
-    pub name_contains: Vec<String>,
-    /// Match art meshes with these exact tags.
-    #[serde(skip_serializing_if = "Vec::is_empty")]
-    pub tag_exact: Vec<String>,
-    /// Match art meshes that have tags that contain these strings.
-    #[serde(skip_serializing_if = "Vec::is_empty")]
-    pub tag_contains: Vec<String>,
+impl ColorTint {
+    /// Creates an opaque color tint.
+    ///
+    /// ```
+    /// use vtubestudio::data::{ArtMeshMatcher, ColorTint, ColorTintRequest};
+    ///
+    /// // Tint everything red
+    /// let req = ColorTintRequest {
+    ///     color_tint: ColorTint::rgb(255, 0, 0),
+    ///     art_mesh_matcher: ArtMeshMatcher::all(),
+    /// };
+    /// # assert!(req.art_mesh_matcher.tint_all);
+    /// ```
+    pub fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Self::rgba(r, g, b, 255)
+    }
+
+    /// Creates a color tint with an alpha component.
+    pub fn rgba(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Self {
+            color_r: r,
+            color_g: g,
+            color_b: b,
+            color_a: a,
+            ..Self::default()
+        }
+    }
+
+    /// Creates a color tint with rainbow mode enabled.
+    pub fn rainbow() -> Self {
+        Self {
+            color_r: 255,
+            color_g: 255,
+            color_b: 255,
+            jeb_: true,
+            ..Self::default()
+        }
+    }
 }
 
-/// Used in [`SceneColorOverlayInfoResponse`].
-#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+impl From<[u8; 4]> for ColorTint {
+    /// Creates a color tint from `[r, g, b, a]` components.
+    fn from([r, g, b, a]: [u8; 4]) -> Self {
+        Self::rgba(r, g, b, a)
+    }
+}
+
+impl ColorTintRequest {
+    /// Resets the tint of all art meshes to opaque white (the default).
+    pub fn reset_all() -> Self {
+        Self::set_color_for(ArtMeshMatcher::all(), 255, 255, 255, 255)
+    }
+
+    /// Sets the color tint of the matching art meshes.
+    ///
+    /// ```
+    /// use vtubestudio::data::{ArtMeshMatcher, ColorTint, ColorTintRequest};
+    ///
+    /// let req = ColorTintRequest::set_color_for(ArtMeshMatcher::from_tags(&["hair"]), 255, 0, 0, 255);
+    /// assert_eq!(req.color_tint, ColorTint::rgb(255, 0, 0));
+    /// ```
+    pub fn set_color_for(matcher: ArtMeshMatcher, r: u8, g: u8, b: u8, a: u8) -> Self {
+        Self {
+            color_tint: ColorTint::rgba(r, g, b, a),
+            art_mesh_matcher: matcher,
+        }
+    }
+
+    /// Sets the alpha of the matching art meshes, without tinting their color (i.e., with RGB
+    /// components of 255).
+    pub fn set_alpha_for(matcher: ArtMeshMatcher, alpha: u8) -> Self {
+        Self::set_color_for(matcher, 255, 255, 255, alpha)
+    }
+
+    /// Enables rainbow mode for all art meshes.
+    pub fn rainbow_mode() -> Self {
+        Self {
+            color_tint: ColorTint::rainbow(),
+            art_mesh_matcher: ArtMeshMatcher::all(),
+        }
+    }
+}
+
+impl ArtMeshListResponse {
+    /// Returns the art mesh names that contain `pattern`, ignoring case.
+    ///
+    /// ```
+    /// use vtubestudio::data::ArtMeshListResponse;
+    ///
+    /// let resp = ArtMeshListResponse {
+    ///     art_mesh_names: vec!["HairFront".into(), "HairBack".into(), "EyeL".into()],
+    ///     ..Default::default()
+    /// };
+    ///
+    /// assert_eq!(resp.find_by_name("hair"), ["HairFront", "HairBack"]);
+    /// assert_eq!(resp.find_exact_name("EyeL"), Some("EyeL"));
+    /// ```
+    pub fn find_by_name(&self, pattern: &str) -> Vec<&str> {
+        find_containing(&self.art_mesh_names, pattern)
+    }
+
+    /// Returns the art mesh tags that contain `pattern`, ignoring case.
+    pub fn find_by_tag(&self, pattern: &str) -> Vec<&str> {
+        find_containing(&self.art_mesh_tags, pattern)
+    }
+
+    /// Returns the art mesh name that exactly matches `name`, if any.
+    pub fn find_exact_name(&self, name: &str) -> Option<&str> {
+        self.art_mesh_names
+            .iter()
+            .map(String::as_str)
+            .find(|art_mesh_name| *art_mesh_name == name)
+    }
+}
+
+fn find_containing<'a>(values: &'a [String], pattern: &str) -> Vec<&'a str> {
+    let pattern = pattern.to_lowercase();
+    values
+        .iter()
+        .map(String::as_str)
+        .filter(|value| value.to_lowercase().contains(&pattern))
+        .collect()
+}
+
+/// Used in [`ColorTintRequest`].
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct ArtMeshMatcher {
+    /// Whether to tint all art meshes.
+    pub tint_all: bool,
+    /// The number of this art mesh.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub art_mesh_number: Vec<i32>,
+    /// Match art meshes with these exact names.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub name_exact: Vec<String>,
+    /// Match art meshes that contain these strings.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub name_contains: Vec<String>,
+    /// Match art meshes with these exact tags.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tag_exact: Vec<String>,
+    /// Match art meshes that have tags that contain these strings.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tag_contains: Vec<String>,
+}
+
+impl ArtMeshMatcher {
+    /// Matches all art meshes.
+    pub fn all() -> Self {
+        Self {
+            tint_all: true,
+            ..Self::default()
+        }
+    }
+
+    /// Matches art meshes with these exact names.
+    pub fn from_names(names: &[&str]) -> Self {
+        Self {
+            name_exact: names.iter().map(|&name| name.to_owned()).collect(),
+            ..Self::default()
+        }
+    }
+
+    /// Matches art meshes with these exact tags.
+    pub fn from_tags(tags: &[&str]) -> Self {
+        Self {
+            tag_exact: tags.iter().map(|&tag| tag.to_owned()).collect(),
+            ..Self::default()
+        }
+    }
+
+    /// Matches the art meshes in an [`ArtMeshListResponse`] whose names satisfy the predicate.
+    ///
+    /// If no names match, the resulting matcher doesn't match any art meshes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use vtubestudio::data::{ArtMeshListResponse, ArtMeshMatcher};
+    ///
+    /// let resp = ArtMeshListResponse {
+    ///     art_mesh_names: vec!["HairFront".into(), "HairBack".into(), "EyeL".into()],
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let matcher = ArtMeshMatcher::from_response_match(&resp, |name| name.starts_with("Hair"));
+    /// assert_eq!(matcher.name_exact, ["HairFront", "HairBack"]);
+    /// ```
+    pub fn from_response_match<F>(response: &ArtMeshListResponse, predicate: F) -> Self
+    where
+        F: Fn(&str) -> bool,
+    {
+        Self {
+            name_exact: response
+                .art_mesh_names
+                .iter()
+                .filter(|name| predicate(name))
+                .cloned()
+                .collect(),
+            ..Self::default()
+        }
+    }
+}
+
+/// Used in [`SceneColorOverlayInfoResponse`].
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
 pub struct CapturePart {
     /// Whether this capture part is active.
     pub active: bool,
@@ -2545,6 +4640,7 @@ pub struct CapturePart {
 
 /// Used in [`InputParameterListResponse`], [`ParameterValueResponse`], [`Live2DParameterListResponse`].
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct Parameter {
     /// The name of the parameter.
@@ -2562,8 +4658,202 @@ pub struct Parameter {
     pub default_value: f64,
 }
 
+impl Parameter {
+    /// Clamps a value to this parameter's range, the same way VTube Studio does when a value
+    /// outside of the range is injected.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use vtubestudio::data::Parameter;
+    ///
+    /// let param = Parameter {
+    ///     min: -30.0,
+    ///     max: 30.0,
+    ///     ..Default::default()
+    /// };
+    ///
+    /// assert_eq!(param.clamp(45.0), 30.0);
+    /// assert_eq!(param.clamp(-10.0), -10.0);
+    /// ```
+    pub fn clamp(&self, value: f64) -> f64 {
+        let (min, max) = self.bounds();
+        value.max(min).min(max)
+    }
+
+    /// Maps a value to the range `0.0..=1.0`, where `0.0` is [`min`](Self::min) and `1.0` is
+    /// [`max`](Self::max). Values outside of the range are clamped first.
+    ///
+    /// If the range is empty (i.e., `min == max`), this returns `0.0`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use vtubestudio::data::Parameter;
+    ///
+    /// let param = Parameter {
+    ///     min: -30.0,
+    ///     max: 30.0,
+    ///     ..Default::default()
+    /// };
+    ///
+    /// assert_eq!(param.normalize(15.0), 0.75);
+    /// assert_eq!(param.normalize(-45.0), 0.0);
+    /// ```
+    pub fn normalize(&self, value: f64) -> f64 {
+        let range = self.max - self.min;
+        if range == 0.0 {
+            return 0.0;
+        }
+
+        (self.clamp(value) - self.min) / range
+    }
+
+    /// The inverse of [`normalize`](Self::normalize): maps a value in the range `0.0..=1.0` to
+    /// this parameter's range. Values outside of `0.0..=1.0` are clamped first, and `NaN` is
+    /// treated as `0.0`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use vtubestudio::data::Parameter;
+    ///
+    /// let param = Parameter {
+    ///     min: -30.0,
+    ///     max: 30.0,
+    ///     ..Default::default()
+    /// };
+    ///
+    /// assert_eq!(param.denormalize(0.75), 15.0);
+    /// assert_eq!(param.denormalize(2.0), 30.0);
+    /// ```
+    pub fn denormalize(&self, t: f64) -> f64 {
+        let t = if t.is_nan() { 0.0 } else { t.clamp(0.0, 1.0) };
+        self.min + t * (self.max - self.min)
+    }
+
+    /// Returns `true` if the value is within this parameter's range (inclusive). `NaN` is never
+    /// in range.
+    pub fn is_in_range(&self, value: f64) -> bool {
+        let (min, max) = self.bounds();
+        (min..=max).contains(&value)
+    }
+
+    /// Returns `true` if [`default_value`](Self::default_value) is within this parameter's
+    /// range.
+    pub fn default_is_valid(&self) -> bool {
+        self.is_in_range(self.default_value)
+    }
+
+    // The range as `(lower, upper)`, in case `min` and `max` are reversed
+    fn bounds(&self) -> (f64, f64) {
+        if self.min <= self.max {
+            (self.min, self.max)
+        } else {
+            (self.max, self.min)
+        }
+    }
+}
+
+impl Live2DParameterListResponse {
+    /// Returns the parameter with the given name, if any.
+    ///
+    /// This scans the list of parameters. For repeated lookups, use a [`ParameterIndex`].
+    pub fn find_by_name(&self, name: &str) -> Option<&Parameter> {
+        find_parameter(&self.parameters, name)
+    }
+}
+
+impl InputParameterListResponse {
+    /// Returns the custom parameter with the given name, if any.
+    pub fn find_custom(&self, name: &str) -> Option<&Parameter> {
+        find_parameter(&self.custom_parameters, name)
+    }
+
+    /// Returns the default parameter with the given name, if any.
+    pub fn find_default(&self, name: &str) -> Option<&Parameter> {
+        find_parameter(&self.default_parameters, name)
+    }
+}
+
+fn find_parameter<'a>(params: &'a [Parameter], name: &str) -> Option<&'a Parameter> {
+    params.iter().find(|param| param.name == name)
+}
+
+/// An index of [`Parameter`]s by name, borrowed from a list of parameters.
+///
+/// If multiple parameters have the same name, the first one is used.
+///
+/// # Example
+///
+/// ```
+/// use vtubestudio::data::{Live2DParameterListResponse, Parameter, ParameterIndex};
+///
+/// let resp = Live2DParameterListResponse {
+///     parameters: vec![Parameter {
+///         name: "ParamAngleX".into(),
+///         value: 12.5,
+///         ..Default::default()
+///     }],
+///     ..Default::default()
+/// };
+///
+/// let index = ParameterIndex::from(&resp);
+/// assert_eq!(index.value("ParamAngleX"), Some(12.5));
+/// assert_eq!(index.get("ParamAngleY"), None);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ParameterIndex<'a> {
+    params: HashMap<&'a str, &'a Parameter>,
+}
+
+impl<'a> ParameterIndex<'a> {
+    /// Returns the parameter with the given name, if any.
+    pub fn get(&self, name: &str) -> Option<&'a Parameter> {
+        self.params.get(name).copied()
+    }
+
+    /// Returns the current value of the parameter with the given name, if any.
+    pub fn value(&self, name: &str) -> Option<f64> {
+        self.get(name).map(|param| param.value)
+    }
+
+    /// The number of indexed parameters.
+    pub fn len(&self) -> usize {
+        self.params.len()
+    }
+
+    /// Returns `true` if no parameters are indexed.
+    pub fn is_empty(&self) -> bool {
+        self.params.is_empty()
+    }
+}
+
+impl<'a> std::iter::FromIterator<&'a Parameter> for ParameterIndex<'a> {
+    fn from_iter<T: IntoIterator<Item = &'a Parameter>>(iter: T) -> Self {
+        let mut params = HashMap::new();
+        for param in iter {
+            params.entry(param.name.as_str()).or_insert(param);
+        }
+        Self { params }
+    }
+}
+
+impl<'a> From<&'a [Parameter]> for ParameterIndex<'a> {
+    fn from(params: &'a [Parameter]) -> Self {
+        params.iter().collect()
+    }
+}
+
+impl<'a> From<&'a Live2DParameterListResponse> for ParameterIndex<'a> {
+    fn from(resp: &'a Live2DParameterListResponse) -> Self {
+        Self::from(resp.parameters.as_slice())
+    }
+}
+
 /// Used in [`InjectParameterDataRequest`].
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ParameterValue {
     /// The ID (name) of the parameter.
@@ -2578,8 +4868,40 @@ pub struct ParameterValue {
     pub weight: Option<f64>,
 }
 
+impl ParameterValue {
+    /// Returns a copy of this value, clamped to the range of the given parameter (see
+    /// [`Parameter::clamp`]). The weight is unchanged.
+    ///
+    /// ```
+    /// use vtubestudio::data::{Parameter, ParameterValue};
+    ///
+    /// let param = Parameter {
+    ///     min: 0.0,
+    ///     max: 1.0,
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let value = ParameterValue {
+    ///     id: "MouthOpen".into(),
+    ///     value: 1.5,
+    ///     weight: Some(0.5),
+    /// };
+    ///
+    /// let clamped = value.clamped_for(&param);
+    /// assert_eq!(clamped.value, 1.0);
+    /// assert_eq!(clamped.weight, Some(0.5));
+    /// ```
+    pub fn clamped_for(&self, param: &Parameter) -> ParameterValue {
+        ParameterValue {
+            value: param.clamp(self.value),
+            ..self.clone()
+        }
+    }
+}
+
 /// Used in [`ExpressionStateResponse`].
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct Expression {
     /// Name of the expression.
@@ -2606,8 +4928,71 @@ pub struct Expression {
     pub parameters: Vec<ExpressionParameter>,
 }
 
+impl Expression {
+    /// Returns the seconds remaining until the expression deactivates, or `None` if it doesn't
+    /// auto-deactivate.
+    pub fn seconds_remaining_if_auto_deactivating(&self) -> Option<f64> {
+        if self.auto_deactivate_after_seconds {
+            Some(self.seconds_remaining)
+        } else {
+            None
+        }
+    }
+}
+
+impl ExpressionStateResponse {
+    /// Returns an iterator over the currently active expressions.
+    ///
+    /// ```
+    /// use vtubestudio::data::{Expression, ExpressionStateResponse};
+    ///
+    /// let expression = |name: &str, active: bool| Expression {
+    ///     name: name.into(),
+    ///     file: format!("{}.exp3.json", name),
+    ///     active,
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let resp = ExpressionStateResponse {
+    ///     expressions: vec![expression("Smile", true), expression("Angry", false)],
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let active = resp.active_expressions().map(|e| e.name.as_str()).collect::<Vec<_>>();
+    /// assert_eq!(active, ["Smile"]);
+    /// assert_eq!(resp.total_active_count(), 1);
+    /// assert!(resp.find_by_file("Angry.exp3.json").is_some());
+    /// assert!(resp.find_by_name("Sad").is_none());
+    /// ```
+    pub fn active_expressions(&self) -> impl Iterator<Item = &Expression> {
+        self.expressions
+            .iter()
+            .filter(|expression| expression.active)
+    }
+
+    /// Returns the number of currently active expressions.
+    pub fn total_active_count(&self) -> usize {
+        self.active_expressions().count()
+    }
+
+    /// Returns the expression with the given file name (case-sensitive), if any.
+    pub fn find_by_file(&self, file: &str) -> Option<&Expression> {
+        self.expressions
+            .iter()
+            .find(|expression| expression.file == file)
+    }
+
+    /// Returns the first expression with the given name, if any.
+    pub fn find_by_name(&self, name: &str) -> Option<&Expression> {
+        self.expressions
+            .iter()
+            .find(|expression| expression.name == name)
+    }
+}
+
 /// Used in [`Expression`].
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ExpressionParameter {
     /// Live2D parameter name of the expression.
@@ -2618,16 +5003,18 @@ pub struct ExpressionParameter {
 
 /// Used in [`Expression`].
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ExpressionUsedInHotkey {
     /// Name of the hotkey.
     pub name: String,
     /// ID of the hotkey.
-    pub id: String,
+    pub id: HotkeyId,
 }
 
 /// Used in [`GetCurrentModelPhysicsResponse`].
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct PhysicsGroup {
     /// The physics group ID.
@@ -2643,6 +5030,7 @@ pub struct PhysicsGroup {
 
 /// Used in [`SetCurrentModelPhysicsRequest`].
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct PhysicsOverride {
     /// Group ID of the physics override.
@@ -2666,253 +5054,1837 @@ pub struct PhysicsOverride {
     pub override_seconds: f64,
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use serde_json::json;
+impl PhysicsOverride {
+    /// Minimum value of [`override_seconds`](Self::override_seconds).
+    pub const MIN_OVERRIDE_SECONDS: f64 = 0.5;
+    /// Maximum value of [`override_seconds`](Self::override_seconds).
+    pub const MAX_OVERRIDE_SECONDS: f64 = 5.0;
+
+    /// Overrides the base physics strength of the entire model (between 0 and 100).
+    ///
+    /// `override_seconds` is clamped between [`MIN_OVERRIDE_SECONDS`](Self::MIN_OVERRIDE_SECONDS)
+    /// and [`MAX_OVERRIDE_SECONDS`](Self::MAX_OVERRIDE_SECONDS).
+    ///
+    /// Whether this is a strength or wind override is determined by which list it's placed in
+    /// within [`SetCurrentModelPhysicsRequest`], so this is identical to
+    /// [`base_wind`](Self::base_wind).
+    pub fn base_strength(value: f64, override_seconds: f64) -> Self {
+        Self::base_value(value, override_seconds)
+    }
+
+    /// Overrides the base wind strength of the entire model (between 0 and 100).
+    ///
+    /// `override_seconds` is clamped between [`MIN_OVERRIDE_SECONDS`](Self::MIN_OVERRIDE_SECONDS)
+    /// and [`MAX_OVERRIDE_SECONDS`](Self::MAX_OVERRIDE_SECONDS).
+    pub fn base_wind(value: f64, override_seconds: f64) -> Self {
+        Self::base_value(value, override_seconds)
+    }
+
+    /// Overrides the multiplier (between 0 and 2) of a specific physics group.
+    ///
+    /// `override_seconds` is clamped between [`MIN_OVERRIDE_SECONDS`](Self::MIN_OVERRIDE_SECONDS)
+    /// and [`MAX_OVERRIDE_SECONDS`](Self::MAX_OVERRIDE_SECONDS).
+    pub fn group_multiplier<S: Into<String>>(id: S, value: f64, override_seconds: f64) -> Self {
+        Self {
+            id: id.into(),
+            value,
+            set_base_value: false,
+            override_seconds: Self::clamp_seconds(override_seconds),
+        }
+    }
+
+    fn base_value(value: f64, override_seconds: f64) -> Self {
+        Self {
+            id: String::new(),
+            value,
+            set_base_value: true,
+            override_seconds: Self::clamp_seconds(override_seconds),
+        }
+    }
+
+    fn clamp_seconds(override_seconds: f64) -> f64 {
+        override_seconds.clamp(Self::MIN_OVERRIDE_SECONDS, Self::MAX_OVERRIDE_SECONDS)
+    }
+}
+
+impl SetCurrentModelPhysicsRequest {
+    /// Creates a request from lists of strength and wind overrides.
+    ///
+    /// ```
+    /// use vtubestudio::data::{PhysicsOverride, SetCurrentModelPhysicsRequest};
+    ///
+    /// let req = SetCurrentModelPhysicsRequest::from_overrides(
+    ///     vec![PhysicsOverride::base_strength(50.0, 2.0)],
+    ///     vec![
+    ///         PhysicsOverride::base_wind(20.0, 10.0),
+    ///         PhysicsOverride::group_multiplier("PhysicsSetting1", 1.5, 2.0),
+    ///     ],
+    /// );
+    ///
+    /// assert!(req.strength_overrides[0].set_base_value);
+    /// assert_eq!(req.wind_overrides[0].override_seconds, 5.0);
+    /// assert!(!req.wind_overrides[1].set_base_value);
+    /// ```
+    pub fn from_overrides<S, W>(strength_overrides: S, wind_overrides: W) -> Self
+    where
+        S: IntoIterator<Item = PhysicsOverride>,
+        W: IntoIterator<Item = PhysicsOverride>,
+    {
+        Self {
+            strength_overrides: strength_overrides.into_iter().collect(),
+            wind_overrides: wind_overrides.into_iter().collect(),
+        }
+    }
+}
+
+impl GetCurrentModelPhysicsResponse {
+    /// Returns `true` if a plugin other than `plugin_name` is currently overriding the physics
+    /// settings of this model.
+    ///
+    /// ```
+    /// use vtubestudio::data::GetCurrentModelPhysicsResponse;
+    ///
+    /// let resp = GetCurrentModelPhysicsResponse {
+    ///     api_physics_override_active: true,
+    ///     api_physics_override_plugin_name: "Other plugin".into(),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// assert!(resp.is_overridden_by_other_plugin("My plugin"));
+    /// assert!(!resp.is_overridden_by_other_plugin("Other plugin"));
+    /// ```
+    pub fn is_overridden_by_other_plugin(&self, plugin_name: &str) -> bool {
+        self.api_physics_override_active && self.api_physics_override_plugin_name != plugin_name
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    type Result<T = ()> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+    #[test]
+    fn response_type_json() -> Result {
+        assert_eq!(
+            serde_json::from_value::<EnumString<ResponseType>>(json!("APIError"))?,
+            EnumString::new(ResponseType::ApiError),
+        );
+
+        assert_eq!(
+            serde_json::to_value::<EnumString<ResponseType>>(EnumString::new(
+                ResponseType::ApiError
+            ))?,
+            json!("APIError"),
+        );
+
+        assert_eq!(
+            serde_json::from_value::<EnumString<ResponseType>>(json!("ColorTintResponse"))?,
+            ResponseType::ColorTintResponse,
+        );
+
+        assert_eq!(
+            serde_json::to_value::<EnumString<ResponseType>>(
+                ResponseType::ColorTintResponse.into()
+            )?,
+            json!("ColorTintResponse"),
+        );
+
+        assert_eq!(
+            serde_json::from_value::<EnumString<ResponseType>>(json!("WalfieResponse"))?,
+            EnumString::new_from_str("WalfieResponse"),
+        );
+
+        assert_eq!(
+            serde_json::to_value(EnumString::<ResponseType>::new_from_str("WalfieResponse"))?,
+            json!("WalfieResponse"),
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn request() -> Result {
+        let mut req = RequestEnvelope::new(&ApiStateRequest {})?;
+        req.request_id = Some("MyIDWithLessThan64Characters".into());
+
+        let json = json!({
+            "apiName": "VTubeStudioPublicAPI",
+            "apiVersion": "1.0",
+            "requestID": "MyIDWithLessThan64Characters",
+            "messageType": "APIStateRequest",
+            "data": {}
+        });
+
+        assert_eq!(serde_json::to_value(&req)?, json);
+        assert_eq!(serde_json::from_value::<RequestEnvelope>(json)?, req);
+
+        Ok(())
+    }
+
+    #[test]
+    fn response() -> Result {
+        let json = json!({
+            "apiName": "VTubeStudioPublicAPI",
+            "apiVersion": "1.0",
+            "timestamp": 1625405710728i64,
+            "messageType": "APIStateResponse",
+            "requestID": "MyIDWithLessThan64Characters",
+            "data": {
+                "active": true,
+                "vTubeStudioVersion": "1.9.0",
+                "currentSessionAuthenticated": false
+            }
+        });
+
+        let resp = ResponseEnvelope {
+            api_name: "VTubeStudioPublicAPI".into(),
+            api_version: "1.0".into(),
+            request_id: "MyIDWithLessThan64Characters".into(),
+            timestamp: 1625405710728,
+            data: Ok(ResponseData {
+                message_type: ApiStateResponse::MESSAGE_TYPE,
+                data: OpaqueValue::new(&ApiStateResponse {
+                    active: true,
+                    vtubestudio_version: "1.9.0".into(),
+                    current_session_authenticated: false,
+                })?,
+            }),
+        };
+
+        assert_eq!(serde_json::to_value(&resp)?, json);
+        assert_eq!(serde_json::from_value::<ResponseEnvelope>(json)?, resp);
+
+        Ok(())
+    }
+
+    #[test]
+    fn api_error() -> Result {
+        let json = json!({
+            "apiName": "VTubeStudioPublicAPI",
+            "apiVersion": "1.0",
+            "timestamp": 1625405710728i64,
+            "requestID": "SomeID",
+            "messageType": "APIError",
+            "data": {
+                "errorID": 1,
+                "message": "Error message"
+            }
+        });
+
+        let resp = ResponseEnvelope {
+            api_name: "VTubeStudioPublicAPI".into(),
+            api_version: "1.0".into(),
+            request_id: "SomeID".into(),
+            timestamp: 1625405710728,
+            data: Err(ApiError {
+                error_id: ErrorId::API_ACCESS_DEACTIVATED,
+                message: "Error message".into(),
+            }),
+        };
+
+        assert_eq!(serde_json::to_value(&resp)?, json);
+        assert_eq!(serde_json::from_value::<ResponseEnvelope>(json)?, resp);
+
+        Ok(())
+    }
+
+    #[test]
+    fn unauthenticated_error() -> Result {
+        let json = json!({
+            "apiName": "VTubeStudioPublicAPI",
+            "apiVersion": "1.0",
+            "timestamp": 1625405710728i64,
+            "requestID": "SomeID",
+            "messageType": "APIError",
+            "data": {
+                "errorID": 8,
+                "message": "Authentication required"
+            }
+        });
+
+        assert!(ErrorId::new(8).is_unauthenticated());
+        assert!(!ErrorId::new(9).is_unauthenticated());
+
+        let resp = serde_json::from_value::<ResponseEnvelope>(json)?;
+        assert!(resp.is_unauthenticated_error());
+
+        let error = resp.parse::<StatisticsResponse>().unwrap_err();
+        assert!(error.is_unauthenticated_error());
+        assert!(error.to_api_error().unwrap().is_unauthenticated());
+
+        Ok(())
+    }
+
+    #[test]
+    fn permission_error() -> Result {
+        let json = json!({
+            "apiName": "VTubeStudioPublicAPI",
+            "apiVersion": "1.0",
+            "timestamp": 1625405710728i64,
+            "requestID": "SomeID",
+            "messageType": "APIError",
+            "data": {
+                "errorID": 9,
+                "message": "Permission required"
+            }
+        });
+
+        assert!(ErrorId::new(9).is_permission_error());
+        assert!(!ErrorId::new(8).is_permission_error());
+
+        let resp = serde_json::from_value::<ResponseEnvelope>(json)?;
+        assert!(resp.is_permission_error());
+        assert!(!resp.is_unauthenticated_error());
+
+        let error = resp.parse::<StatisticsResponse>().unwrap_err();
+        assert!(error.is_permission_error());
+        assert!(!error.is_unauthenticated_error());
+        assert!(error.to_api_error().unwrap().is_permission_error());
+
+        Ok(())
+    }
+
+    #[test]
+    fn cooldown_and_not_found_errors() {
+        use crate::error::{Error, ErrorKind};
+
+        let mut cooldown_count = 0;
+        let mut not_found_count = 0;
+
+        for id in 0..=100_100 {
+            let error_id = ErrorId::new(id);
+            let name = match error_id.name() {
+                Some(name) => name,
+                None => continue,
+            };
+
+            let expect_cooldown = name.contains("CooldownNotOver");
+            let expect_not_found = name.contains("NotFound");
+
+            assert_eq!(error_id.is_cooldown(), expect_cooldown, "{}", error_id);
+            assert_eq!(error_id.is_not_found(), expect_not_found, "{}", error_id);
+
+            let error = Error::from(ApiError {
+                error_id,
+                message: String::new(),
+            });
+
+            let expected_kind = if expect_cooldown {
+                cooldown_count += 1;
+                ErrorKind::Cooldown
+            } else if expect_not_found {
+                not_found_count += 1;
+                ErrorKind::NotFound
+            } else {
+                ErrorKind::Api
+            };
+
+            assert_eq!(error.kind(), &expected_kind, "{}", error_id);
+            assert_eq!(error.is_cooldown(), expect_cooldown);
+            assert_eq!(error.is_not_found(), expect_not_found);
+            assert!(error.is_api_error());
+        }
+
+        assert_eq!(cooldown_count, 4);
+        assert_eq!(not_found_count, 14);
+    }
+
+    #[test]
+    fn parameter_value_response() -> Result {
+        let json = json!({
+            "apiName": "VTubeStudioPublicAPI",
+            "apiVersion": "1.0",
+            "timestamp": 1625405710728i64,
+            "requestID": "SomeID",
+            "messageType": "ParameterValueResponse",
+            "data": {
+                "name": "MyCustomParamName1",
+                "addedBy": "My Plugin Name",
+                "value": 12.4,
+                "min": -30.0,
+                "max": 30.0,
+                "defaultValue": 0.0
+            }
+        });
+
+        let resp = ResponseEnvelope {
+            api_name: "VTubeStudioPublicAPI".into(),
+            api_version: "1.0".into(),
+            request_id: "SomeID".into(),
+            timestamp: 1625405710728,
+            data: Ok(ResponseData {
+                message_type: ParameterValueResponse::MESSAGE_TYPE,
+                data: OpaqueValue::new(&ParameterValueResponse(Parameter {
+                    name: "MyCustomParamName1".into(),
+                    added_by: Some("My Plugin Name".into()),
+                    value: 12.4,
+                    min: -30.0,
+                    max: 30.0,
+                    default_value: 0.0,
+                }))?,
+            }),
+        };
+
+        assert_eq!(serde_json::to_value(&resp)?, json);
+        assert_eq!(serde_json::from_value::<ResponseEnvelope>(json)?, resp);
+
+        Ok(())
+    }
+
+    #[test]
+    fn scene_color_overlay_info() -> Result {
+        let req = RequestEnvelope::new(&SceneColorOverlayInfoRequest {})?
+            .with_id(RequestId::from("SomeID"));
+        let req_json = json!({
+            "apiName": "VTubeStudioPublicAPI",
+            "apiVersion": "1.0",
+            "requestID": "SomeID",
+            "messageType": "SceneColorOverlayInfoRequest",
+            "data": {}
+        });
+
+        assert_eq!(serde_json::to_value(&req)?, req_json);
+        assert_eq!(serde_json::from_value::<RequestEnvelope>(req_json)?, req);
+
+        let resp_json = json!({
+            "apiName": "VTubeStudioPublicAPI",
+            "apiVersion": "1.0",
+            "timestamp": 1625405710728i64,
+            "messageType": "SceneColorOverlayInfoResponse",
+            "requestID": "SomeID",
+            "data": {
+                "active": true,
+                "itemsIncluded": true,
+                "isWindowCapture": false,
+                "baseBrightness": 16,
+                "colorBoost": 35,
+                "smoothing": 6,
+                "colorOverlayR": 110,
+                "colorOverlayG": 72,
+                "colorOverlayB": 43,
+                "colorAvgR": 220,
+                "colorAvgG": 144,
+                "colorAvgB": 86,
+                "leftCapturePart": {
+                    "active": true,
+                    "colorR": 125,
+                    "colorG": 132,
+                    "colorB": 143
+                },
+                "middleCapturePart": {
+                    "active": true,
+                    "colorR": 201,
+                    "colorG": 130,
+                    "colorB": 71
+                },
+                "rightCapturePart": {
+                    "active": false,
+                    "colorR": 0,
+                    "colorG": 0,
+                    "colorB": 0
+                }
+            }
+        });
+
+        let resp = serde_json::from_value::<ResponseEnvelope>(resp_json.clone())?;
+        assert_eq!(serde_json::to_value(&resp)?, resp_json);
+
+        let data = resp.parse::<SceneColorOverlayInfoResponse>()?;
+        assert_eq!(
+            data,
+            SceneColorOverlayInfoResponse {
+                active: true,
+                items_included: true,
+                is_window_capture: false,
+                base_brightness: 16,
+                color_boost: 35,
+                smoothing: 6,
+                color_overlay_r: 110,
+                color_overlay_g: 72,
+                color_overlay_b: 43,
+                color_avg_r: 220,
+                color_avg_g: 144,
+                color_avg_b: 86,
+                left_capture_part: CapturePart {
+                    active: true,
+                    color_r: 125,
+                    color_g: 132,
+                    color_b: 143,
+                },
+                middle_capture_part: CapturePart {
+                    active: true,
+                    color_r: 201,
+                    color_g: 130,
+                    color_b: 71,
+                },
+                right_capture_part: CapturePart::default(),
+            }
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn expression_state() -> Result {
+        let req = RequestEnvelope::new(&ExpressionStateRequest {
+            details: true,
+            expression_file: Some("myExpression_optional_1.exp3.json".into()),
+        })?
+        .with_id(RequestId::from("SomeID"));
+
+        let req_json = json!({
+            "apiName": "VTubeStudioPublicAPI",
+            "apiVersion": "1.0",
+            "requestID": "SomeID",
+            "messageType": "ExpressionStateRequest",
+            "data": {
+                "details": true,
+                "expressionFile": "myExpression_optional_1.exp3.json"
+            }
+        });
+
+        assert_eq!(serde_json::to_value(&req)?, req_json);
+        assert_eq!(serde_json::from_value::<RequestEnvelope>(req_json)?, req);
+
+        let resp_json = json!({
+            "apiName": "VTubeStudioPublicAPI",
+            "apiVersion": "1.0",
+            "timestamp": 1625405710728i64,
+            "messageType": "ExpressionStateResponse",
+            "requestID": "SomeID",
+            "data": {
+                "modelLoaded": true,
+                "modelName": "My Currently Loaded Model",
+                "modelID": "UniqueIDToIdentifyThisModelBy",
+                "expressions": [{
+                    "name": "myExpression_optional_1",
+                    "file": "myExpression_optional_1.exp3.json",
+                    "active": false,
+                    "deactivateWhenKeyIsLetGo": false,
+                    "autoDeactivateAfterSeconds": false,
+                    "secondsRemaining": 0.0,
+                    "usedInHotkeys": [{
+                        "name": "Eye Cry",
+                        "id": "562bfcee4a3a4d3ca6ce6beb1f0d5ab1"
+                    }],
+                    "parameters": [{
+                        "name": "EyeCry",
+                        "value": 0.75
+                    }]
+                }]
+            }
+        });
+
+        let resp = serde_json::from_value::<ResponseEnvelope>(resp_json.clone())?;
+        assert_eq!(serde_json::to_value(&resp)?, resp_json);
+
+        let data = resp.parse::<ExpressionStateResponse>()?;
+        assert_eq!(
+            data.expressions[0].used_in_hotkeys,
+            vec![ExpressionUsedInHotkey {
+                name: "Eye Cry".into(),
+                id: "562bfcee4a3a4d3ca6ce6beb1f0d5ab1".into(),
+            }]
+        );
+        assert_eq!(
+            data.expressions[0].parameters,
+            vec![ExpressionParameter {
+                name: "EyeCry".into(),
+                value: 0.75,
+            }]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_response() -> Result {
+        let data = ApiStateResponse {
+            active: true,
+            vtubestudio_version: "1.9.0".into(),
+            current_session_authenticated: false,
+        };
+
+        let resp = ResponseEnvelope::new(&data)?;
+
+        let parsed = resp.parse::<ApiStateResponse>()?;
+
+        assert_eq!(parsed, data);
+
+        Ok(())
+    }
+
+    #[test]
+    fn try_parse_response() -> Result {
+        let data = ApiStateResponse {
+            active: true,
+            vtubestudio_version: "1.9.0".into(),
+            current_session_authenticated: false,
+        };
+
+        let resp = ResponseEnvelope::new(&data)?;
+
+        let error = resp.try_parse::<StatisticsResponse>().unwrap_err();
+        assert!(error.has_kind(crate::error::ErrorKind::UnexpectedResponse));
+        assert_eq!(resp.try_parse::<ApiStateResponse>()?, data);
+        assert_eq!(resp.parse::<ApiStateResponse>()?, data);
+
+        Ok(())
+    }
+
+    #[test]
+    fn serialize_event_request() -> Result {
+        let req = RequestEnvelope::new(&EventSubscriptionRequest::subscribe(&TestEventConfig {
+            test_message_for_event: "text the event will return".to_owned(),
+        })?)?
+        .with_id(Some("SomeID".into()));
+
+        // https://github.com/DenchiSoft/VTubeStudio/tree/5e45a961/Events#test-event
+        let expected = json!({
+            "apiName": "VTubeStudioPublicAPI",
+            "apiVersion": "1.0",
+            "requestID": "SomeID",
+            "messageType": "EventSubscriptionRequest",
+            "data": {
+                "eventName": "TestEvent",
+                "subscribe": true,
+                "config": {
+                    "testMessageForEvent": "text the event will return"
+                }
+            }
+        });
+
+        assert_eq!(
+            serde_json::from_value::<RequestEnvelope>(expected.clone())?,
+            req
+        );
+        assert_eq!(serde_json::to_value(&req)?, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_response_as_event() -> Result {
+        // https://github.com/DenchiSoft/VTubeStudio/tree/5e45a961/Events#test-event
+        let json = json!({
+            "apiName": "VTubeStudioPublicAPI",
+            "apiVersion": "1.0",
+            "timestamp": 1625405710728i64,
+            "requestID": "SomeID",
+            "messageType": "TestEvent",
+            "data": {
+                "yourTestMessage": "text the event will return",
+                "counter": 672
+            }
+        });
+
+        let resp = serde_json::from_value::<ResponseEnvelope>(json)?;
+        let parsed = resp.parse_event()?;
+
+        let expected = TestEvent {
+            your_test_message: "text the event will return".to_owned(),
+            counter: 672,
+        };
+
+        assert!(matches!(parsed, Event::Test(event) if event == expected));
+
+        Ok(())
+    }
+
+    #[test]
+    fn vec2_arithmetic() {
+        let a = Vec2 { x: 1.5, y: -2.0 };
+        let b = Vec2::from((0.5, 4.0));
+
+        assert_eq!(a + b, Vec2 { x: 2.0, y: 2.0 });
+        assert_eq!(a - b, Vec2 { x: 1.0, y: -6.0 });
+        assert_eq!(a + b - b, a);
+        assert_eq!(a + Vec2::default(), a);
+        assert_eq!(a - a, Vec2::default());
+        assert_eq!(-a, Vec2 { x: -1.5, y: 2.0 });
+        assert_eq!(-(-a), a);
+        assert_eq!(a * 2.0, Vec2 { x: 3.0, y: -4.0 });
+        assert_eq!(a * 2.0 / 2.0, a);
+        assert_eq!(a * 1.0, a);
+        assert_eq!(a.dot(b), -7.25);
+        assert_eq!(a.dot(b), b.dot(a));
+        assert_eq!(<(f64, f64)>::from(a), (1.5, -2.0));
+    }
+
+    #[test]
+    fn vec2_length_and_normalize() {
+        let v = Vec2 { x: 3.0, y: -4.0 };
+        assert_eq!(v.length(), 5.0);
+        assert_eq!(v.normalize(), Vec2 { x: 0.6, y: -0.8 });
+        assert!((v.normalize().length() - 1.0).abs() < f64::EPSILON);
+
+        assert_eq!(Vec2::default().normalize(), Vec2::default());
+        let infinite = Vec2 {
+            x: f64::INFINITY,
+            y: 0.0,
+        };
+        assert_eq!(infinite.normalize(), Vec2::default());
+    }
+
+    #[test]
+    fn vec2_display() {
+        let v = Vec2 { x: 1.0, y: 0.375 };
+        assert_eq!(v.to_string(), "(1, 0.375)");
+        assert_eq!(format!("{:.1}", v), "(1.0, 0.4)");
+        assert_eq!(format!("{:.0}", v), "(1, 0)");
+    }
+
+    #[test]
+    fn vec2_serialization() -> Result {
+        let v = Vec2 { x: 0.5, y: -1.0 };
+        let json = json!({ "x": 0.5, "y": -1.0 });
+
+        assert_eq!(serde_json::to_value(v)?, json);
+        assert_eq!(serde_json::from_value::<Vec2>(json)?, v);
+
+        Ok(())
+    }
+
+    #[test]
+    fn model_position_lerp() {
+        let from = ModelPosition {
+            position_x: -1.0,
+            position_y: 0.5,
+            rotation: 10.0,
+            size: -20.0,
+        };
+        let to = ModelPosition {
+            position_x: 1.0,
+            position_y: -0.5,
+            rotation: 30.0,
+            size: 40.0,
+        };
+
+        assert_eq!(from.lerp(&to, 0.0), from);
+        assert_eq!(from.lerp(&to, 1.0), to);
+        assert_eq!(
+            from.lerp(&to, 0.5),
+            ModelPosition {
+                position_x: 0.0,
+                position_y: 0.0,
+                rotation: 20.0,
+                size: 10.0,
+            }
+        );
+
+        // Out-of-range values are clamped
+        assert_eq!(from.lerp(&to, -1.0), from);
+        assert_eq!(from.lerp(&to, 2.0), to);
+        assert_eq!(from.lerp(&to, f64::NAN), from);
+
+        assert_eq!(from.distance_to(&to), 5.0f64.sqrt());
+        assert_eq!(from.distance_to(&from), 0.0);
+    }
+
+    #[test]
+    fn model_position_move_request() {
+        let position = ModelPosition {
+            position_x: 0.25,
+            position_y: -0.5,
+            rotation: 15.0,
+            size: -30.0,
+        };
+
+        let mut req = MoveModelRequest {
+            time_in_seconds: 0.5,
+            values_are_relative_to_model: true,
+            ..Default::default()
+        };
+        position.apply_to_move_request(&mut req);
+
+        assert_eq!(
+            req,
+            MoveModelRequest {
+                time_in_seconds: 0.5,
+                values_are_relative_to_model: false,
+                position_x: Some(0.25),
+                position_y: Some(-0.5),
+                rotation: Some(15.0),
+                size: Some(-30.0),
+            }
+        );
+
+        let resp = CurrentModelResponse {
+            model_position: position.clone(),
+            ..Default::default()
+        };
+        assert_eq!(ModelPosition::from(resp), position);
+    }
+
+    #[test]
+    fn parameter_injection_builder() -> Result {
+        use crate::error::ErrorKind;
+
+        let req = ParameterInjection::new()
+            .add("FaceAngleX", 5.0)
+            .add("FaceAngleY", -5.0)
+            .build()?;
+
+        assert_eq!(
+            serde_json::to_value(&req)?,
+            json!({
+                "parameterValues": [
+                    { "id": "FaceAngleX", "value": 5.0 },
+                    { "id": "FaceAngleY", "value": -5.0 },
+                ],
+                "faceFound": false,
+                "mode": "add",
+            })
+        );
+
+        let req = InjectParameterDataRequest::builder()
+            .set_weighted("MouthOpen", 1.0, 0.0)
+            .set_weighted("MouthSmile", 1.0, 1.0)
+            .face_found(true)
+            .build()?;
+        assert!(req.face_found);
+        assert_eq!(req.parameter_values[0].weight, Some(0.0));
+
+        let invalid = [
+            ParameterInjection::new(),
+            ParameterInjection::new().set("", 1.0),
+            ParameterInjection::new().set("MouthOpen", f64::NAN),
+            ParameterInjection::new().set_weighted("MouthOpen", 1.0, -0.1),
+            ParameterInjection::new().set_weighted("MouthOpen", 1.0, f64::NAN),
+            ParameterInjection::new().add("A", 1.0).set("B", 1.0),
+        ];
+        for builder in invalid.iter() {
+            let err = builder.clone().build().unwrap_err();
+            assert!(err.has_kind(ErrorKind::InvalidRequest), "{:?}", builder);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn physics_override_constructors() -> Result {
+        let req = SetCurrentModelPhysicsRequest::from_overrides(
+            vec![PhysicsOverride::base_strength(50.0, 0.1)],
+            vec![PhysicsOverride::group_multiplier("Hair", 1.5, 3.0)],
+        );
+
+        assert_eq!(
+            serde_json::to_value(&req)?,
+            json!({
+                "strengthOverrides": [{
+                    "id": "",
+                    "value": 50.0,
+                    "setBaseValue": true,
+                    "overrideSeconds": 0.5,
+                }],
+                "windOverrides": [{
+                    "id": "Hair",
+                    "value": 1.5,
+                    "setBaseValue": false,
+                    "overrideSeconds": 3.0,
+                }],
+            })
+        );
+
+        assert_eq!(PhysicsOverride::base_wind(10.0, 60.0).override_seconds, 5.0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn color_tint_helpers() -> Result {
+        assert_eq!(
+            serde_json::to_value(&ColorTintRequest {
+                color_tint: ColorTint::rgb(255, 0, 0),
+                art_mesh_matcher: ArtMeshMatcher::all(),
+            })?,
+            json!({
+                "colorTint": {
+                    "colorR": 255,
+                    "colorG": 0,
+                    "colorB": 0,
+                    "colorA": 255,
+                    "jeb_": false,
+                },
+                "artMeshMatcher": {
+                    "tintAll": true,
+                },
+            })
+        );
+
+        assert_eq!(ColorTint::from([1, 2, 3, 4]), ColorTint::rgba(1, 2, 3, 4));
+        assert_eq!(ColorTint::rgba(1, 2, 3, 4).color_a, 4);
+        assert!(ColorTint::rainbow().jeb_);
+
+        assert_eq!(
+            serde_json::to_value(ColorTintRequest::reset_all())?,
+            json!({
+                "colorTint": {
+                    "colorR": 255,
+                    "colorG": 255,
+                    "colorB": 255,
+                    "colorA": 255,
+                    "jeb_": false,
+                },
+                "artMeshMatcher": {
+                    "tintAll": true,
+                },
+            })
+        );
+
+        assert_eq!(
+            serde_json::to_value(ColorTintRequest::set_alpha_for(
+                ArtMeshMatcher::from_names(&["EyeL"]),
+                128
+            ))?,
+            json!({
+                "colorTint": {
+                    "colorR": 255,
+                    "colorG": 255,
+                    "colorB": 255,
+                    "colorA": 128,
+                    "jeb_": false,
+                },
+                "artMeshMatcher": {
+                    "tintAll": false,
+                    "nameExact": ["EyeL"],
+                },
+            })
+        );
+
+        let req = ColorTintRequest::rainbow_mode();
+        assert!(req.color_tint.jeb_);
+        assert!(req.art_mesh_matcher.tint_all);
+
+        Ok(())
+    }
+
+    #[test]
+    fn parameter_clamp_and_normalize() {
+        let param = Parameter {
+            min: 0.0,
+            max: 10.0,
+            ..Default::default()
+        };
+        assert_eq!(param.clamp(-1.0), 0.0);
+        assert_eq!(param.clamp(11.0), 10.0);
+        assert_eq!(param.normalize(2.5), 0.25);
+        assert_eq!(param.normalize(100.0), 1.0);
+
+        // Reversed ranges still map `min` to 0 and `max` to 1
+        let reversed = Parameter {
+            min: 10.0,
+            max: 0.0,
+            ..Default::default()
+        };
+        assert_eq!(reversed.clamp(11.0), 10.0);
+        assert_eq!(reversed.normalize(2.5), 0.75);
+
+        let empty = Parameter {
+            min: 5.0,
+            max: 5.0,
+            ..Default::default()
+        };
+        assert_eq!(empty.clamp(1.0), 5.0);
+        assert_eq!(empty.normalize(1.0), 0.0);
+    }
+
+    #[test]
+    fn art_mesh_search() -> Result {
+        let empty = ArtMeshListResponse::default();
+        assert!(empty.find_by_name("hair").is_empty());
+        assert!(empty.find_by_tag("hair").is_empty());
+        assert_eq!(empty.find_exact_name("Hair"), None);
+        assert!(ArtMeshMatcher::from_response_match(&empty, |_| true)
+            .name_exact
+            .is_empty());
+
+        let resp = ArtMeshListResponse {
+            art_mesh_names: vec!["HairFront".into(), "HairBack".into(), "EyeL".into()],
+            art_mesh_tags: vec!["hair".into(), "eyes".into()],
+            ..Default::default()
+        };
+        assert_eq!(resp.find_by_name("EYE"), ["EyeL"]);
+        assert_eq!(resp.find_by_name("hair"), ["HairFront", "HairBack"]);
+        assert_eq!(resp.find_by_tag("Eye"), ["eyes"]);
+        assert_eq!(resp.find_exact_name("hairfront"), None);
+
+        let req = ColorTintRequest {
+            color_tint: ColorTint::rgb(255, 0, 0),
+            art_mesh_matcher: ArtMeshMatcher::from_response_match(&resp, |name| {
+                name.starts_with("Hair")
+            }),
+        };
+        assert_eq!(
+            serde_json::to_value(&req)?["artMeshMatcher"],
+            json!({ "tintAll": false, "nameExact": ["HairFront", "HairBack"] })
+        );
+
+        assert_eq!(
+            ArtMeshMatcher::from_tags(&["eyes"]),
+            ArtMeshMatcher {
+                tag_exact: vec!["eyes".into()],
+                ..Default::default()
+            }
+        );
+        assert_eq!(ArtMeshMatcher::from_names(&["EyeL"]).name_exact, ["EyeL"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn parameter_lookup() {
+        let param = |name: &str, value: f64| Parameter {
+            name: name.into(),
+            value,
+            ..Default::default()
+        };
+
+        let resp = InputParameterListResponse {
+            custom_parameters: vec![param("Shared", 1.0), param("Custom", 2.0)],
+            default_parameters: vec![param("Shared", 3.0)],
+            ..Default::default()
+        };
+        assert_eq!(resp.find_custom("Shared").unwrap().value, 1.0);
+        assert_eq!(resp.find_default("Shared").unwrap().value, 3.0);
+        assert_eq!(resp.find_default("Custom"), None);
+
+        let resp = Live2DParameterListResponse {
+            parameters: vec![param("A", 1.0), param("B", 2.0), param("A", 3.0)],
+            ..Default::default()
+        };
+        assert_eq!(resp.find_by_name("B").unwrap().value, 2.0);
+        assert_eq!(resp.find_by_name("C"), None);
+
+        let index = ParameterIndex::from(&resp);
+        assert_eq!(index.len(), 2);
+        assert_eq!(index.value("A"), Some(1.0));
+        assert_eq!(index.value("C"), None);
+
+        // The index points to the original parameters
+        assert!(std::ptr::eq(index.get("B").unwrap(), &resp.parameters[1]));
+
+        assert!(ParameterIndex::from(&Live2DParameterListResponse::default()).is_empty());
+    }
+
+    #[test]
+    fn event_try_as() -> Result {
+        #[derive(Debug, PartialEq, Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct FutureEvent {
+            new_field: i32,
+        }
+
+        // Pretend that this is an event that isn't known to this library yet
+        impl Response for FutureEvent {
+            const MESSAGE_TYPE: EnumString<ResponseType> =
+                EnumString::const_new_from_str("FutureEvent");
+        }
+
+        impl EventData for FutureEvent {
+            type Config = TestEventConfig;
+        }
+
+        let unknown = Event::try_from(ResponseData {
+            message_type: FutureEvent::MESSAGE_TYPE,
+            data: OpaqueValue::new(&json!({ "newField": 5 }))?,
+        })?;
+        assert!(matches!(unknown, Event::Unknown(_)));
+        assert_eq!(
+            unknown.try_as::<FutureEvent>().transpose()?,
+            Some(FutureEvent { new_field: 5 })
+        );
+        assert!(unknown.try_as::<TestEvent>().is_none());
+
+        let known = Event::Test(TestEvent {
+            your_test_message: "hello".into(),
+            counter: 1,
+        });
+        assert_eq!(
+            known
+                .try_as::<TestEvent>()
+                .transpose()?
+                .unwrap()
+                .your_test_message,
+            "hello"
+        );
+        assert!(known.try_as::<FutureEvent>().is_none());
+
+        Ok(())
+    }
+
+    #[cfg(feature = "schemars")]
+    #[test]
+    fn request_json_schema() {
+        let schemas = schema_for_all_requests();
+        assert!(schemas
+            .iter()
+            .any(|(req_type, _)| *req_type == RequestType::StatisticsRequest));
+
+        let (_, schema) = schemas
+            .iter()
+            .find(|(req_type, _)| *req_type == RequestType::AuthenticationTokenRequest)
+            .unwrap();
+
+        assert_eq!(schema["title"], "AuthenticationTokenRequest");
+        assert_eq!(schema["type"], "object");
+        assert_eq!(schema["required"], json!(["pluginName", "pluginDeveloper"]));
+        for field in &["pluginName", "pluginDeveloper", "pluginIcon"] {
+            assert!(schema["properties"].get(field).is_some());
+        }
+
+        let schema = schemars::schema_for!(RequestEnvelope).to_value();
+        let message_type = &schema["properties"]["messageType"];
+        assert_eq!(message_type["type"], "string");
+        assert!(message_type["examples"]
+            .as_array()
+            .unwrap()
+            .contains(&json!("AuthenticationTokenRequest")));
+        assert_eq!(
+            schema["properties"]["data"],
+            json!({ "description": "The request data." })
+        );
+    }
+
+    #[test]
+    fn item_load_builder() -> Result {
+        let req = ItemLoadRequest::builder("a.png")
+            .size(0.5)
+            .custom_data_base64("aGVsbG8=")
+            .build()?;
+        assert_eq!(req.size, 0.5);
+        assert_eq!(req.custom_data_base64.as_deref(), Some("aGVsbG8="));
+        assert!(req.custom_data_skip_asking_user_if_whitelisted);
+
+        let invalid = |builder: ItemLoadRequestBuilder| {
+            builder
+                .build()
+                .unwrap_err()
+                .has_kind(crate::error::ErrorKind::InvalidRequest)
+        };
+
+        assert!(invalid(ItemLoadRequest::builder("")));
+        assert!(invalid(ItemLoadRequest::builder("a.png").size(-0.1)));
+        assert!(invalid(ItemLoadRequest::builder("a.png").fade_time(2.5)));
+        assert!(invalid(
+            ItemLoadRequest::builder("a.png").rotation(f64::NAN)
+        ));
+        assert!(invalid(
+            ItemLoadRequest::builder("a.png").custom_data_base64("")
+        ));
+        assert!(invalid(
+            ItemLoadRequest::builder("a.png").custom_data_base64("A".repeat(8 * 1024 * 1024))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn outline_pixels() {
+        let event = ModelOutlineEvent {
+            convex_hull: vec![
+                Vec2::from((-1.0, 1.0)),
+                Vec2::from((1.0, 1.0)),
+                Vec2::from((0.0, -1.0)),
+            ],
+            convex_hull_center: Vec2::from((0.0, 1.0 / 3.0)),
+            window_size: Vec2::from((300.0, 300.0)),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            event.convex_hull_pixels(),
+            vec![
+                Vec2::from((0.0, 0.0)),
+                Vec2::from((300.0, 0.0)),
+                Vec2::from((150.0, 300.0)),
+            ]
+        );
+
+        let center = event.convex_hull_center_pixels();
+        assert_eq!(center.x, 150.0);
+        assert!((center.y - 100.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn vec2_conversions() {
+        let v = Vec2::new(1.0, 2.0);
+        assert_eq!(v, (1.0, 2.0).into());
+        assert_eq!(v, [1.0, 2.0].into());
+
+        let (x, y) = v.into();
+        assert_eq!((x, y), (1.0, 2.0));
+        let [x, y]: [f64; 2] = v.into();
+        assert_eq!(Vec2::from([x, y]), v);
+
+        const ORIGIN: Vec2 = Vec2::new(0.0, 0.0);
+        assert_eq!(ORIGIN, Vec2::default());
+    }
+
+    #[cfg(feature = "glam")]
+    #[test]
+    fn vec2_glam() {
+        let v = Vec2::new(0.5, -0.25);
+        assert_eq!(Vec2::from(glam::DVec2::from(v)), v);
+        assert_eq!(Vec2::from(glam::Vec2::from(v)), v);
+        assert_eq!(glam::DVec2::from(v), glam::DVec2::new(0.5, -0.25));
+    }
+
+    #[test]
+    fn lenient_fields() -> Result {
+        let resp = serde_json::from_value::<StatisticsResponse>(json!({
+            "uptime": "3600000",
+            "framerate": 60.0,
+            "vTubeStudioVersion": "1.28.3",
+            "allowedPlugins": "5",
+            "connectedPlugins": 3,
+            "startedWithSteam": 1,
+            "windowWidth": 1920,
+            "windowHeight": "1080",
+            "windowIsFullscreen": "false",
+        }))?;
+
+        assert_eq!(resp.uptime, 3_600_000);
+        assert_eq!(resp.framerate, 60);
+        assert_eq!(resp.allowed_plugins, 5);
+        assert!(resp.started_with_steam);
+        assert_eq!(resp.window_height, 1080);
+        assert!(!resp.window_is_fullscreen);
+
+        // Serialization is unchanged
+        let json = serde_json::to_value(&resp)?;
+        assert_eq!(json["uptime"], json!(3_600_000));
+        assert_eq!(json["startedWithSteam"], json!(true));
+
+        Ok(())
+    }
+
+    #[test]
+    fn error_id_from_string() -> Result {
+        let number = serde_json::from_value::<ApiError>(json!({
+            "errorID": 8,
+            "message": "Unauthenticated",
+        }))?;
+        let string = serde_json::from_value::<ApiError>(json!({
+            "errorID": "8",
+            "message": "Unauthenticated",
+        }))?;
+
+        assert_eq!(number.error_id, ErrorId::REQUEST_REQUIRES_AUTHENTICATION);
+        assert_eq!(number, string);
+        assert_eq!(serde_json::to_value(string.error_id)?, json!(8));
+        assert!(serde_json::from_value::<ErrorId>(json!("eight")).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn hotkey_categories() {
+        fn hotkey(type_: EnumString<HotkeyAction>) -> Hotkey {
+            Hotkey {
+                type_,
+                ..Default::default()
+            }
+        }
+
+        use HotkeyAction::*;
+        let known = [
+            Unset,
+            TriggerAnimation,
+            ChangeIdleAnimation,
+            ToggleExpression,
+            RemoveAllExpressions,
+            MoveModel,
+            ChangeBackground,
+            ReloadMicrophone,
+            ReloadTextures,
+            CalibrateCam,
+            ChangeVtsModel,
+            TakeScreenshot,
+            ScreenColorOverlay,
+            RemoveAllItems,
+            ToggleItemScene,
+            DownloadRandomWorkshopItem,
+            ExecuteItemAction,
+            ArtMeshColorPreset,
+            ToggleTracker,
+            ToggleTwitchFeature,
+            LoadEffectPreset,
+        ];
+
+        for action in known {
+            let h = hotkey(action.clone().into());
+            let categories = [
+                h.is_expression(),
+                h.is_animation(),
+                h.is_model_change(),
+                h.is_item_action(),
+            ];
+            let expected = match action {
+                ToggleExpression | RemoveAllExpressions => [true, false, false, false],
+                TriggerAnimation | ChangeIdleAnimation => [false, true, false, false],
+                ChangeVtsModel => [false, false, true, false],
+                ToggleItemScene
+                | RemoveAllItems
+                | DownloadRandomWorkshopItem
+                | ExecuteItemAction => [false, false, false, true],
+                _ => [false; 4],
+            };
+            assert_eq!(categories, expected, "{:?}", action);
+
+            let ext = match action {
+                ToggleExpression => Some(".exp3.json"),
+                TriggerAnimation | ChangeIdleAnimation => Some(".motion3.json"),
+                ChangeVtsModel => Some(".vtube.json"),
+                _ => None,
+            };
+            assert_eq!(h.expected_file_extension(), ext, "{:?}", action);
+        }
 
-    type Result<T = ()> = std::result::Result<T, Box<dyn std::error::Error>>;
+        let unknown = hotkey(EnumString::new_from_str("ToggleExpressionV2"));
+        assert!(!unknown.is_expression());
+        assert!(!unknown.is_animation());
+        assert!(!unknown.is_model_change());
+        assert!(!unknown.is_item_action());
+        assert_eq!(unknown.expected_file_extension(), None);
+    }
 
     #[test]
-    fn response_type_json() -> Result {
-        assert_eq!(
-            serde_json::from_value::<EnumString<ResponseType>>(json!("APIError"))?,
-            EnumString::new(ResponseType::ApiError),
-        );
+    fn event_config_constructors() -> Result {
+        fn subscribe<C: EventConfig>(config: &C) -> serde_json::Result<serde_json::Value> {
+            serde_json::to_value(EventSubscriptionRequest::subscribe(config)?)
+        }
 
         assert_eq!(
-            serde_json::to_value::<EnumString<ResponseType>>(EnumString::new(
-                ResponseType::ApiError
-            ))?,
-            json!("APIError"),
+            subscribe(&HotkeyTriggeredEventConfig::any())?,
+            json!({
+                "eventName": "HotkeyTriggeredEvent",
+                "subscribe": true,
+                "config": { "onlyForAction": null, "ignoreHotkeysTriggeredByAPI": false },
+            })
         );
-
         assert_eq!(
-            serde_json::from_value::<EnumString<ResponseType>>(json!("ColorTintResponse"))?,
-            ResponseType::ColorTintResponse,
+            subscribe(&HotkeyTriggeredEventConfig::for_action(
+                HotkeyAction::ToggleExpression
+            ))?["config"],
+            json!({ "onlyForAction": "ToggleExpression", "ignoreHotkeysTriggeredByAPI": false })
         );
-
         assert_eq!(
-            serde_json::to_value::<EnumString<ResponseType>>(
-                ResponseType::ColorTintResponse.into()
-            )?,
-            json!("ColorTintResponse"),
+            subscribe(&HotkeyTriggeredEventConfig::all_except_api())?["config"],
+            json!({ "onlyForAction": null, "ignoreHotkeysTriggeredByAPI": true })
         );
 
         assert_eq!(
-            serde_json::from_value::<EnumString<ResponseType>>(json!("WalfieResponse"))?,
-            EnumString::new_from_str("WalfieResponse"),
+            subscribe(&ModelAnimationEventConfig::for_main_model())?,
+            json!({
+                "eventName": "ModelAnimationEvent",
+                "subscribe": true,
+                "config": { "ignoreLive2DItems": true, "ignoreIdleAnimations": true },
+            })
         );
-
         assert_eq!(
-            serde_json::to_value(EnumString::<ResponseType>::new_from_str("WalfieResponse"))?,
-            json!("WalfieResponse"),
+            subscribe(&ModelAnimationEventConfig::for_all())?["config"],
+            json!({ "ignoreLive2DItems": false, "ignoreIdleAnimations": false })
         );
 
         Ok(())
     }
 
     #[test]
-    fn request() -> Result {
-        let mut req = RequestEnvelope::new(&ApiStateRequest {})?;
-        req.request_id = Some("MyIDWithLessThan64Characters".into());
-
-        let json = json!({
-            "apiName": "VTubeStudioPublicAPI",
-            "apiVersion": "1.0",
-            "requestID": "MyIDWithLessThan64Characters",
-            "messageType": "APIStateRequest",
-            "data": {}
-        });
-
-        assert_eq!(serde_json::to_value(&req)?, json);
-        assert_eq!(serde_json::from_value::<RequestEnvelope>(json)?, req);
+    fn all_events() -> Result {
+        let events = ResponseType::all_events();
+        let names = events
+            .iter()
+            .map(|event| EnumString::new(event.clone()).as_str().to_owned())
+            .collect::<std::collections::HashSet<_>>();
+
+        assert_eq!(names.len(), events.len());
+        assert!(names.iter().all(|name| name.ends_with("Event")));
+        assert!(names.contains("ModelOutlineEvent"));
+        assert!(!events.contains(&ResponseType::ApiError));
+        assert!(!events.contains(&ResponseType::VTubeStudioApiStateBroadcast));
 
         Ok(())
     }
 
     #[test]
-    fn response() -> Result {
-        let json = json!({
-            "apiName": "VTubeStudioPublicAPI",
-            "apiVersion": "1.0",
-            "timestamp": 1625405710728i64,
-            "messageType": "APIStateResponse",
-            "requestID": "MyIDWithLessThan64Characters",
-            "data": {
-                "active": true,
-                "vTubeStudioVersion": "1.9.0",
-                "currentSessionAuthenticated": false
-            }
-        });
+    fn parameter_ranges() {
+        let param = Parameter {
+            min: -1.0,
+            max: 1.0,
+            default_value: 0.0,
+            ..Default::default()
+        };
 
-        let resp = ResponseEnvelope {
-            api_name: "VTubeStudioPublicAPI".into(),
-            api_version: "1.0".into(),
-            request_id: "MyIDWithLessThan64Characters".into(),
-            timestamp: 1625405710728,
-            data: Ok(ResponseData {
-                message_type: ApiStateResponse::MESSAGE_TYPE.into(),
-                data: OpaqueValue::new(&ApiStateResponse {
-                    active: true,
-                    vtubestudio_version: "1.9.0".into(),
-                    current_session_authenticated: false,
-                })?,
-            }),
+        // Boundaries are inclusive
+        assert!(param.is_in_range(-1.0));
+        assert!(param.is_in_range(1.0));
+        assert!(!param.is_in_range(1.0 + f64::EPSILON));
+        assert!(param.default_is_valid());
+        assert_eq!(param.normalize(-1.0), 0.0);
+        assert_eq!(param.normalize(1.0), 1.0);
+        assert_eq!(param.denormalize(0.0), -1.0);
+        assert_eq!(param.denormalize(1.0), 1.0);
+        assert_eq!(param.denormalize(param.normalize(0.25)), 0.25);
+
+        // NaN is never in range, and clamps to the lower bound
+        assert!(!param.is_in_range(f64::NAN));
+        assert_eq!(param.clamp(f64::NAN), -1.0);
+        assert_eq!(param.normalize(f64::NAN), 0.0);
+        assert_eq!(param.denormalize(f64::NAN), -1.0);
+        assert!(!Parameter {
+            default_value: f64::NAN,
+            ..param.clone()
+        }
+        .default_is_valid());
+
+        // Infinite values are clamped
+        assert_eq!(param.clamp(f64::INFINITY), 1.0);
+        assert_eq!(param.clamp(f64::NEG_INFINITY), -1.0);
+
+        // Reversed and empty ranges
+        let reversed = Parameter {
+            min: 1.0,
+            max: -1.0,
+            ..Default::default()
         };
+        assert!(reversed.is_in_range(0.5));
+        assert_eq!(reversed.clamp(5.0), 1.0);
 
-        assert_eq!(serde_json::to_value(&resp)?, json);
-        assert_eq!(serde_json::from_value::<ResponseEnvelope>(json)?, resp);
+        let empty = Parameter {
+            min: 2.0,
+            max: 2.0,
+            ..Default::default()
+        };
+        assert_eq!(empty.normalize(2.0), 0.0);
+        assert_eq!(empty.denormalize(0.5), 2.0);
+        assert!(!empty.default_is_valid());
+    }
 
-        Ok(())
+    #[test]
+    fn parameter_creation_validate() {
+        let valid = ParameterCreationRequest {
+            parameter_name: "Abcd".into(),
+            explanation: Some("x".repeat(ParameterCreationRequest::MAX_EXPLANATION_LENGTH)),
+            min: ParameterCreationRequest::MIN_VALUE,
+            max: ParameterCreationRequest::MAX_VALUE,
+            default_value: ParameterCreationRequest::MAX_VALUE,
+        };
+        assert!(valid.validate().is_ok());
+
+        let invalid = [
+            ParameterCreationRequest {
+                parameter_name: "Abc".into(),
+                ..valid.clone()
+            },
+            ParameterCreationRequest {
+                parameter_name: "A".repeat(33),
+                ..valid.clone()
+            },
+            ParameterCreationRequest {
+                parameter_name: "My_Param".into(),
+                ..valid.clone()
+            },
+            ParameterCreationRequest {
+                explanation: Some("x".repeat(256)),
+                ..valid.clone()
+            },
+            ParameterCreationRequest {
+                min: -1_000_001.0,
+                ..valid.clone()
+            },
+            ParameterCreationRequest {
+                max: f64::NAN,
+                ..valid.clone()
+            },
+            ParameterCreationRequest {
+                min: 1.0,
+                max: 0.0,
+                default_value: 0.5,
+                ..valid.clone()
+            },
+            ParameterCreationRequest {
+                min: 0.0,
+                max: 1.0,
+                default_value: 2.0,
+                ..valid.clone()
+            },
+        ];
+
+        for req in invalid.iter() {
+            let err = req.validate().unwrap_err();
+            assert!(
+                err.has_kind(crate::error::ErrorKind::InvalidRequest),
+                "{:?}",
+                req
+            );
+        }
     }
 
     #[test]
-    fn api_error() -> Result {
-        let json = json!({
-            "apiName": "VTubeStudioPublicAPI",
-            "apiVersion": "1.0",
-            "timestamp": 1625405710728i64,
-            "requestID": "SomeID",
-            "messageType": "APIError",
-            "data": {
-                "errorID": 1,
-                "message": "Error message"
-            }
+    fn item_to_move_constructors() -> Result {
+        let unchanged = json!({
+            "itemInstanceID": "id",
+            "timeInSeconds": 0.0,
+            "fadeMode": "linear",
+            "positionX": -1000,
+            "positionY": -1000,
+            "size": -1000.0,
+            "rotation": -1000.0,
+            "order": -1000,
+            "setFlip": false,
+            "flip": false,
+            "userCanStop": false,
         });
-
-        let resp = ResponseEnvelope {
-            api_name: "VTubeStudioPublicAPI".into(),
-            api_version: "1.0".into(),
-            request_id: "SomeID".into(),
-            timestamp: 1625405710728,
-            data: Err(ApiError {
-                error_id: ErrorId::API_ACCESS_DEACTIVATED,
-                message: "Error message".into(),
-            }),
+        let with = |fields: serde_json::Value| {
+            let mut expected = unchanged.clone();
+            for (key, value) in fields.as_object().unwrap() {
+                expected[key] = value.clone();
+            }
+            expected
         };
 
-        assert_eq!(serde_json::to_value(&resp)?, json);
-        assert_eq!(serde_json::from_value::<ResponseEnvelope>(json)?, resp);
+        assert_eq!(serde_json::to_value(ItemToMove::no_op("id"))?, unchanged);
+        assert_eq!(
+            serde_json::to_value(ItemToMove::teleport("id", 10, -20))?,
+            with(json!({ "positionX": 10, "positionY": -20 }))
+        );
+        assert_eq!(
+            serde_json::to_value(ItemToMove::ease_to("id", 1, 2, 1.5, FadeMode::EaseBoth))?,
+            with(json!({
+                "positionX": 1,
+                "positionY": 2,
+                "timeInSeconds": 1.5,
+                "fadeMode": "easeBoth",
+            }))
+        );
+        assert_eq!(
+            serde_json::to_value(ItemToMove::rotate_to("id", 90.0, 0.5))?,
+            with(json!({ "rotation": 90.0, "timeInSeconds": 0.5 }))
+        );
+        assert_eq!(
+            serde_json::to_value(ItemToMove::resize("id", 0.25, 2.0))?,
+            with(json!({ "size": 0.25, "timeInSeconds": 2.0 }))
+        );
 
         Ok(())
     }
 
     #[test]
-    fn parameter_value_response() -> Result {
-        let json = json!({
-            "apiName": "VTubeStudioPublicAPI",
-            "apiVersion": "1.0",
-            "timestamp": 1625405710728i64,
-            "requestID": "SomeID",
-            "messageType": "ParameterValueResponse",
-            "data": {
-                "name": "MyCustomParamName1",
-                "addedBy": "My Plugin Name",
-                "value": 12.4,
-                "min": -30.0,
-                "max": 30.0,
-                "defaultValue": 0.0
-            }
-        });
+    fn hotkeys_in_current_model_constructors() -> Result {
+        assert_eq!(
+            serde_json::to_value(HotkeysInCurrentModelRequest::for_model("abc"))?,
+            json!({ "modelID": "abc" })
+        );
+        assert_eq!(
+            serde_json::to_value(HotkeysInCurrentModelRequest::for_live2d_item(
+                "item.model3.json"
+            ))?,
+            json!({ "live2DItemFileName": "item.model3.json" })
+        );
 
-        let resp = ResponseEnvelope {
-            api_name: "VTubeStudioPublicAPI".into(),
-            api_version: "1.0".into(),
-            request_id: "SomeID".into(),
-            timestamp: 1625405710728,
-            data: Ok(ResponseData {
-                message_type: ParameterValueResponse::MESSAGE_TYPE.into(),
-                data: OpaqueValue::new(&ParameterValueResponse(Parameter {
-                    name: "MyCustomParamName1".into(),
-                    added_by: Some("My Plugin Name".into()),
-                    value: 12.4,
-                    min: -30.0,
-                    max: 30.0,
-                    default_value: 0.0,
-                }))?,
-            }),
-        };
+        Ok(())
+    }
 
-        assert_eq!(serde_json::to_value(&resp)?, json);
-        assert_eq!(serde_json::from_value::<ResponseEnvelope>(json)?, resp);
+    #[test]
+    fn available_models_search() {
+        let empty = AvailableModelsResponse::default();
+        assert_eq!(empty.find_by_id("a"), None);
+        assert!(empty.find_by_name("Akari").is_empty());
+        assert_eq!(empty.find_loaded(), None);
+        assert!(!empty.contains_id("a"));
+        assert!(ModelIndex::from(&empty).is_empty());
+
+        let model = |id: &str, name: &str| Model {
+            model_id: id.into(),
+            model_name: name.into(),
+            ..Default::default()
+        };
+        let resp = AvailableModelsResponse {
+            number_of_models: 3,
+            available_models: vec![
+                model("a", "Akari"),
+                model("b", "AKARI"),
+                model("c", "Hiyori"),
+            ],
+        };
 
-        Ok(())
+        assert_eq!(resp.find_by_id("b"), Some(&resp.available_models[1]));
+        assert_eq!(
+            resp.find_by_name("akari"),
+            [&resp.available_models[0], &resp.available_models[1]]
+        );
+        assert!(resp.find_by_name("Aka").is_empty());
+        assert_eq!(resp.find_loaded(), None);
+        assert!(resp.contains_id("c"));
+
+        let index = ModelIndex::from(&resp);
+        assert_eq!(index.len(), 3);
+        assert_eq!(index.get("c"), Some(&resp.available_models[2]));
+        assert!(!index.contains_id("d"));
     }
 
     #[test]
-    fn parse_response() -> Result {
-        let data = ApiStateResponse {
-            active: true,
-            vtubestudio_version: "1.9.0".into(),
-            current_session_authenticated: false,
+    fn expression_state_helpers() {
+        let empty = ExpressionStateResponse::default();
+        assert_eq!(empty.active_expressions().count(), 0);
+        assert_eq!(empty.total_active_count(), 0);
+        assert_eq!(empty.find_by_file("a.exp3.json"), None);
+        assert_eq!(empty.find_by_name("a"), None);
+
+        let expression = |name: &str, active: bool| Expression {
+            name: name.into(),
+            file: format!("{}.exp3.json", name),
+            active,
+            ..Default::default()
+        };
+        let resp = ExpressionStateResponse {
+            expressions: vec![
+                expression("a", true),
+                expression("b", false),
+                Expression {
+                    auto_deactivate_after_seconds: true,
+                    seconds_remaining: 2.5,
+                    ..expression("c", true)
+                },
+            ],
+            ..Default::default()
         };
 
-        let resp = ResponseEnvelope::new(&data)?;
+        let active = resp
+            .active_expressions()
+            .map(|expression| expression.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(active, ["a", "c"]);
+        assert_eq!(resp.total_active_count(), 2);
+        assert_eq!(resp.find_by_file("b.exp3.json"), Some(&resp.expressions[1]));
+        assert_eq!(resp.find_by_file("B.exp3.json"), None);
+        assert_eq!(resp.find_by_name("c"), Some(&resp.expressions[2]));
 
-        let parsed = resp.parse::<ApiStateResponse>()?;
+        assert_eq!(
+            resp.expressions[0].seconds_remaining_if_auto_deactivating(),
+            None
+        );
+        assert_eq!(
+            resp.expressions[2].seconds_remaining_if_auto_deactivating(),
+            Some(2.5)
+        );
+    }
 
-        assert_eq!(parsed, data);
+    #[test]
+    fn item_to_move_setters() -> Result {
+        let item = ItemToMove::new("abc")
+            .position(1, 2)
+            .size(0.5)
+            .rotation(-45.0)
+            .order(3)
+            .in_seconds(30.0)
+            .fade_mode(FadeMode::Linear);
+        item.validate()?;
+
+        assert_eq!(
+            serde_json::to_value(&item)?,
+            json!({
+                "itemInstanceID": "abc",
+                "timeInSeconds": 30.0,
+                "fadeMode": "linear",
+                "positionX": 1,
+                "positionY": 2,
+                "size": 0.5,
+                "rotation": -45.0,
+                "order": 3,
+                "setFlip": false,
+                "flip": false,
+                "userCanStop": false,
+            })
+        );
+
+        assert_eq!(ItemToMove::new("abc"), ItemToMove::no_op("abc"));
+
+        for time in [-1.0, 30.5, f64::NAN] {
+            let err = ItemToMove::new("abc")
+                .in_seconds(time)
+                .validate()
+                .unwrap_err();
+            assert!(
+                err.has_kind(crate::error::ErrorKind::InvalidRequest),
+                "{}",
+                time
+            );
+        }
 
         Ok(())
     }
 
     #[test]
-    fn serialize_event_request() -> Result {
-        let req = RequestEnvelope::new(&EventSubscriptionRequest::subscribe(&TestEventConfig {
-            test_message_for_event: "text the event will return".to_owned(),
-        })?)?
-        .with_id(Some("SomeID".into()));
+    fn move_model_builder() -> Result {
+        use crate::error::ErrorKind;
+
+        let current = ModelPosition {
+            position_x: 0.5,
+            position_y: 0.5,
+            rotation: -90.0,
+            size: 50.0,
+        };
+        let target = ModelPosition {
+            position_x: 2000.0,
+            position_y: -0.25,
+            rotation: -720.0,
+            size: 150.0,
+        };
 
-        // https://github.com/DenchiSoft/VTubeStudio/tree/5e45a961/Events#test-event
-        let expected = json!({
-            "apiName": "VTubeStudioPublicAPI",
-            "apiVersion": "1.0",
-            "requestID": "SomeID",
-            "messageType": "EventSubscriptionRequest",
-            "data": {
-                "eventName": "TestEvent",
-                "subscribe": true,
-                "config": {
-                    "testMessageForEvent": "text the event will return"
-                }
+        // Absolute values are clamped, and the current position is ignored
+        let req = MoveModelRequest::builder()
+            .from(&current)
+            .to(&target)
+            .in_seconds(2.0)
+            .build()?;
+        assert_eq!(
+            req,
+            MoveModelRequest {
+                time_in_seconds: 2.0,
+                values_are_relative_to_model: false,
+                position_x: Some(1000.0),
+                position_y: Some(-0.25),
+                rotation: Some(-360.0),
+                size: Some(100.0),
             }
-        });
+        );
 
+        // Relative values are the difference between the current and clamped target positions
+        let req = MoveModelRequest::builder()
+            .from(&current)
+            .to(&target)
+            .relative()
+            .build()?;
         assert_eq!(
-            serde_json::from_value::<RequestEnvelope>(expected.clone())?,
-            req
+            req,
+            MoveModelRequest {
+                time_in_seconds: 0.0,
+                values_are_relative_to_model: true,
+                position_x: Some(999.5),
+                position_y: Some(-0.75),
+                rotation: Some(-270.0),
+                size: Some(50.0),
+            }
         );
-        assert_eq!(serde_json::to_value(&req)?, expected);
+
+        let invalid = [
+            MoveModelRequest::builder().to(&target).in_seconds(2.5),
+            MoveModelRequest::builder().to(&target).in_seconds(-1.0),
+            MoveModelRequest::builder().to(&target).in_seconds(f64::NAN),
+            MoveModelRequest::builder().from(&current),
+            MoveModelRequest::builder()
+                .to(&target)
+                .absolute()
+                .relative(),
+        ];
+        for builder in invalid.iter() {
+            let err = builder.clone().build().unwrap_err();
+            assert!(err.has_kind(ErrorKind::InvalidRequest), "{:?}", builder);
+        }
 
         Ok(())
     }
 
     #[test]
-    fn parse_response_as_event() -> Result {
-        // https://github.com/DenchiSoft/VTubeStudio/tree/5e45a961/Events#test-event
-        let json = json!({
-            "apiName": "VTubeStudioPublicAPI",
-            "apiVersion": "1.0",
-            "timestamp": 1625405710728i64,
-            "requestID": "SomeID",
-            "messageType": "TestEvent",
-            "data": {
-                "yourTestMessage": "text the event will return",
-                "counter": 672
-            }
-        });
+    fn art_mesh_position_pin_request() -> Result {
+        let hit_info = serde_json::from_value::<ArtMeshPosition>(json!({
+            "modelID": "d87b771d2902473bbaa0226d03ef4754",
+            "artMeshID": "hair_right4",
+            "angle": 0.0,
+            "size": 0.0,
+            "vertexID1": 29,
+            "vertexID2": 27,
+            "vertexID3": 30,
+            "vertexWeight1": 0.6696313619613648,
+            "vertexWeight2": 0.1282328963279724,
+            "vertexWeight3": 0.20213575661182404
+        }))?;
+
+        assert!(hit_info.is_valid());
+        assert!(!ArtMeshPosition {
+            vertex_id2: -1,
+            ..hit_info.clone()
+        }
+        .is_valid());
+        assert!(!ArtMeshPosition::default().is_valid());
 
-        let resp = serde_json::from_value::<ResponseEnvelope>(json)?;
-        let parsed = resp.parse_event()?;
+        let req = hit_info.as_item_pin_request(
+            "4a241269394f463ca16b8b21aa636568",
+            AngleRelativeTo::RelativeToModel,
+            SizeRelativeTo::RelativeToCurrentItemSize,
+        );
 
-        let expected = TestEvent {
-            your_test_message: "text the event will return".to_owned(),
-            counter: 672,
+        assert_eq!(
+            serde_json::to_value(&req)?,
+            json!({
+                "pin": true,
+                "itemInstanceID": "4a241269394f463ca16b8b21aa636568",
+                "angleRelativeTo": "RelativeToModel",
+                "sizeRelativeTo": "RelativeToCurrentItemSize",
+                "vertexPinType": "Provided",
+                "pinInfo": {
+                    "modelID": "d87b771d2902473bbaa0226d03ef4754",
+                    "artMeshID": "hair_right4",
+                    "angle": 0.0,
+                    "size": 0.0,
+                    "vertexID1": 29,
+                    "vertexID2": 27,
+                    "vertexID3": 30,
+                    "vertexWeight1": 0.6696313619613648,
+                    "vertexWeight2": 0.1282328963279724,
+                    "vertexWeight3": 0.20213575661182404
+                }
+            })
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn model_outline_helpers() {
+        let outline = |points: &[(f64, f64)]| ModelOutlineEvent {
+            convex_hull: points.iter().copied().map(Vec2::from).collect(),
+            ..Default::default()
         };
 
-        assert!(matches!(parsed, Event::Test(event) if event == expected));
+        let square = outline(&[(1.0, 1.0), (2.0, 1.0), (2.0, 2.0), (1.0, 2.0)]);
+        assert_eq!(
+            square.convex_hull_bounding_box(),
+            Some((Vec2 { x: 1.0, y: 1.0 }, Vec2 { x: 2.0, y: 2.0 }))
+        );
+        assert_eq!(square.convex_hull_area(), 1.0);
+        assert_eq!(square.convex_hull_centroid(), Some(Vec2 { x: 1.5, y: 1.5 }));
 
-        Ok(())
+        // Clockwise order has the same area
+        let triangle = outline(&[(0.0, 0.0), (0.0, 3.0), (3.0, 0.0)]);
+        assert_eq!(triangle.convex_hull_area(), 4.5);
+        assert_eq!(
+            triangle.convex_hull_centroid(),
+            Some(Vec2 { x: 1.0, y: 1.0 })
+        );
+
+        // Extra points along one edge don't move the centroid
+        let skewed = outline(&[
+            (0.0, 0.0),
+            (0.5, 0.0),
+            (0.75, 0.0),
+            (1.0, 0.0),
+            (1.0, 1.0),
+            (0.0, 1.0),
+        ]);
+        assert_eq!(skewed.convex_hull_centroid(), Some(Vec2 { x: 0.5, y: 0.5 }));
+
+        let line = outline(&[(0.0, 0.0), (2.0, 2.0)]);
+        assert_eq!(line.convex_hull_area(), 0.0);
+        assert_eq!(line.convex_hull_centroid(), Some(Vec2 { x: 1.0, y: 1.0 }));
+
+        let empty = outline(&[]);
+        assert_eq!(empty.convex_hull_bounding_box(), None);
+        assert_eq!(empty.convex_hull_area(), 0.0);
+        assert_eq!(empty.convex_hull_centroid(), None);
     }
 }
@@ -0,0 +1,286 @@
+//! Discovery of VTube Studio instances on the local network.
+//!
+//! When the API is enabled, VTube Studio periodically broadcasts a
+//! [`VTubeStudioApiStateBroadcast`] over UDP (on port [`DEFAULT_PORT`]), which includes the
+//! websocket port of the instance.
+//!
+//! # Example
+//!
+//! ```no_run
+//! # async fn run() -> Result<(), vtubestudio::error::BoxError> {
+//! use std::time::Duration;
+//! use vtubestudio::discovery;
+//!
+//! let instance = discovery::discover_first(Duration::from_secs(5)).await?;
+//! println!("Found VTube Studio on port {}", instance.port);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::data::{ResponseEnvelope, VTubeStudioApiStateBroadcast};
+use crate::error::{Error, ErrorKind};
+
+use futures_core::Stream;
+use futures_util::{stream, StreamExt};
+use std::collections::HashMap;
+use std::net::{Ipv4Addr, SocketAddr};
+use std::time::Duration;
+use tokio::net::UdpSocket;
+use tokio::time::Instant;
+
+/// The UDP port that VTube Studio broadcasts [`VTubeStudioApiStateBroadcast`] messages to.
+pub const DEFAULT_PORT: u16 = 47779;
+
+/// Listens for [`VTubeStudioApiStateBroadcast`] messages on the [`DEFAULT_PORT`] for the given
+/// duration, yielding each VTube Studio instance once.
+///
+/// See [`Discovery`] for more options.
+pub async fn discover_instances(
+    timeout: Duration,
+) -> Result<impl Stream<Item = VTubeStudioApiStateBroadcast>, Error> {
+    Discovery::new().discover(timeout).await
+}
+
+/// Returns the first [`VTubeStudioApiStateBroadcast`] received on the [`DEFAULT_PORT`].
+///
+/// Returns an [`ErrorKind::InstanceNotFound`] error if no instance was found before the timeout.
+pub async fn discover_first(timeout: Duration) -> Result<VTubeStudioApiStateBroadcast, Error> {
+    Discovery::new().discover_first(timeout).await
+}
+
+/// Builder for discovering VTube Studio instances.
+///
+/// # Example
+///
+/// ```no_run
+/// # async fn run() -> Result<(), vtubestudio::error::BoxError> {
+/// use futures_util::StreamExt;
+/// use std::time::Duration;
+/// use vtubestudio::discovery::Discovery;
+///
+/// let instances = Discovery::new()
+///     .dedupe_window(Duration::from_secs(10))
+///     .discover(Duration::from_secs(30))
+///     .await?;
+/// futures_util::pin_mut!(instances);
+///
+/// while let Some(instance) = instances.next().await {
+///     println!("{} is on port {}", instance.window_title, instance.port);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Discovery {
+    port: u16,
+    dedupe_window: Duration,
+}
+
+impl Default for Discovery {
+    fn default() -> Self {
+        Self {
+            port: DEFAULT_PORT,
+            dedupe_window: Duration::MAX,
+        }
+    }
+}
+
+impl Discovery {
+    /// Creates a new builder with default values.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the UDP port to listen on. The default value is [`DEFAULT_PORT`].
+    pub fn port(mut self, port: u16) -> Self {
+        self.port = port;
+        self
+    }
+
+    /// Broadcasts from an instance that was already yielded are ignored until this much time has
+    /// passed. By default, each instance is only yielded once.
+    pub fn dedupe_window(mut self, window: Duration) -> Self {
+        self.dedupe_window = window;
+        self
+    }
+
+    /// Binds the UDP socket, and listens for broadcasts for the given duration.
+    ///
+    /// Messages that can't be parsed as a [`VTubeStudioApiStateBroadcast`] are ignored.
+    pub async fn discover(
+        self,
+        timeout: Duration,
+    ) -> Result<impl Stream<Item = VTubeStudioApiStateBroadcast>, Error> {
+        let addr = SocketAddr::from((Ipv4Addr::UNSPECIFIED, self.port));
+        let socket = UdpSocket::bind(addr)
+            .await
+            .map_err(|e| Error::new(ErrorKind::ConnectionRefused).with_source(e))?;
+
+        let state = DiscoveryState {
+            socket,
+            deadline: Instant::now() + timeout,
+            dedupe_window: self.dedupe_window,
+            last_seen: HashMap::new(),
+            buf: vec![0; 4096],
+        };
+
+        Ok(stream::unfold(state, |mut state| async move {
+            let broadcast = state.next().await?;
+            Some((broadcast, state))
+        }))
+    }
+
+    /// Returns the first [`VTubeStudioApiStateBroadcast`] received.
+    ///
+    /// Returns an [`ErrorKind::InstanceNotFound`] error if no instance was found before the
+    /// timeout.
+    pub async fn discover_first(
+        self,
+        timeout: Duration,
+    ) -> Result<VTubeStudioApiStateBroadcast, Error> {
+        let instances = self.discover(timeout).await?;
+        futures_util::pin_mut!(instances);
+
+        instances.next().await.ok_or_else(|| {
+            Error::new(ErrorKind::InstanceNotFound)
+                .with_source(format!("no broadcast received within {:?}", timeout))
+        })
+    }
+}
+
+struct DiscoveryState {
+    socket: UdpSocket,
+    deadline: Instant,
+    dedupe_window: Duration,
+    last_seen: HashMap<String, Instant>,
+    buf: Vec<u8>,
+}
+
+impl DiscoveryState {
+    async fn next(&mut self) -> Option<VTubeStudioApiStateBroadcast> {
+        loop {
+            let len = match tokio::time::timeout_at(self.deadline, self.socket.recv(&mut self.buf))
+                .await
+            {
+                Ok(Ok(len)) => len,
+                Ok(Err(e)) => {
+                    tracing::warn!(error = %e, "Failed to receive discovery broadcast");
+                    return None;
+                }
+                Err(_elapsed) => return None,
+            };
+
+            let broadcast = match parse_broadcast(&self.buf[..len]) {
+                Ok(broadcast) => broadcast,
+                Err(e) => {
+                    tracing::debug!(error = %e, "Ignoring invalid discovery broadcast");
+                    continue;
+                }
+            };
+
+            let now = Instant::now();
+            match self.last_seen.get(&broadcast.instance_id) {
+                Some(last) if now.duration_since(*last) < self.dedupe_window => continue,
+                _ => {
+                    self.last_seen.insert(broadcast.instance_id.clone(), now);
+                    return Some(broadcast);
+                }
+            }
+        }
+    }
+}
+
+fn parse_broadcast(bytes: &[u8]) -> Result<VTubeStudioApiStateBroadcast, Error> {
+    serde_json::from_slice::<ResponseEnvelope>(bytes)?.parse()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::BoxError;
+
+    async fn broadcaster() -> Result<(UdpSocket, u16), BoxError> {
+        // Find an unused port to listen on
+        let port = std::net::UdpSocket::bind("127.0.0.1:0")?
+            .local_addr()?
+            .port();
+        let socket = UdpSocket::bind("127.0.0.1:0").await?;
+        socket.connect(("127.0.0.1", port)).await?;
+        Ok((socket, port))
+    }
+
+    fn broadcast(instance_id: &str) -> Result<Vec<u8>, BoxError> {
+        let envelope = ResponseEnvelope::new(&VTubeStudioApiStateBroadcast {
+            active: true,
+            port: 8001,
+            instance_id: instance_id.to_owned(),
+            window_title: "VTube Studio".to_owned(),
+        })?;
+        Ok(serde_json::to_vec(&envelope)?)
+    }
+
+    #[tokio::test]
+    async fn discover() -> Result<(), BoxError> {
+        let (sender, port) = broadcaster().await?;
+        let instances = Discovery::new()
+            .port(port)
+            .discover(Duration::from_millis(200))
+            .await?;
+
+        sender.send(&broadcast("a")?).await?;
+        sender.send(b"not json").await?;
+        sender.send(&broadcast("a")?).await?;
+        sender.send(&broadcast("b")?).await?;
+
+        let ids = instances
+            .map(|instance| instance.instance_id)
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(ids, ["a", "b"]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn dedupe_window() -> Result<(), BoxError> {
+        let (sender, port) = broadcaster().await?;
+        let instances = Discovery::new()
+            .port(port)
+            .dedupe_window(Duration::ZERO)
+            .discover(Duration::from_millis(200))
+            .await?;
+
+        sender.send(&broadcast("a")?).await?;
+        sender.send(&broadcast("a")?).await?;
+
+        assert_eq!(instances.count().await, 2);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn discover_first() -> Result<(), BoxError> {
+        let (sender, port) = broadcaster().await?;
+        let discovery = Discovery::new().port(port);
+
+        let err = discovery
+            .clone()
+            .discover_first(Duration::from_millis(50))
+            .await
+            .unwrap_err();
+        assert!(err.has_kind(ErrorKind::InstanceNotFound));
+
+        let instance = tokio::join!(
+            discovery.discover_first(Duration::from_secs(5)),
+            async {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                sender.send(&broadcast("a")?).await?;
+                Ok::<_, BoxError>(())
+            }
+        );
+        assert_eq!(instance.0?.instance_id, "a");
+        instance.1?;
+
+        Ok(())
+    }
+}
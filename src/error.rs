@@ -1,6 +1,7 @@
 use futures_core::TryStream;
 use futures_sink::Sink;
 pub(crate) use std::error::Error as StdError;
+use std::fmt;
 
 use crate::data::ResponseType;
 pub use crate::data::{ApiError, EnumString, ErrorId};
@@ -12,19 +13,61 @@ pub type BoxError = Box<dyn StdError + Send + Sync>;
 pub type Result<T, E = Error> = std::result::Result<T, E>;
 
 /// Represents errors that can occur while communicating with the VTube Studio API.
+///
+/// If the error was caused by an [`ApiError`], its error ID and message are included when
+/// displayed.
+///
+/// ```
+/// use vtubestudio::data::{ApiError, ErrorId};
+/// use vtubestudio::Error;
+///
+/// let error = Error::from(ApiError {
+///     error_id: ErrorId::REQUEST_REQUIRES_AUTHENTICATION,
+///     message: "Please authenticate".to_owned(),
+/// });
+///
+/// assert_eq!(
+///     error.to_string(),
+///     "received APIError 8 (RequestRequiresAuthentication): Please authenticate"
+/// );
+/// ```
 #[derive(thiserror::Error, Debug)]
-#[error("{kind}")]
+#[error("{}", DisplayError(self))]
 pub struct Error {
     kind: ErrorKind,
     source: Option<BoxError>,
 }
 
+// Includes the underlying `ApiError` (if any) in the displayed error.
+struct DisplayError<'a>(&'a Error);
+
+impl fmt::Display for DisplayError<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self
+            .0
+            .source
+            .as_ref()
+            .and_then(|e| e.downcast_ref::<ApiError>())
+        {
+            Some(api_error) => write!(f, "received {}", api_error),
+            None => write!(f, "{}", self.0.kind),
+        }
+    }
+}
+
 /// Describes the type of underlying error.
+///
+/// API errors are split into [`Cooldown`](Self::Cooldown), [`NotFound`](Self::NotFound), and
+/// [`Api`](Self::Api) for everything else. Use [`Error::to_api_error`] to check for any API error.
 #[derive(thiserror::Error, displaydoc::Display, Debug, PartialEq)]
 #[non_exhaustive]
 pub enum ErrorKind {
     /// received APIError from server
     Api,
+    /// received APIError from server (cooldown not over)
+    Cooldown,
+    /// received APIError from server (requested resource not found)
+    NotFound,
     /// no more in-flight requests allowed
     TransportFull,
     /// failed to establish connection
@@ -33,12 +76,25 @@ pub enum ErrorKind {
     ConnectionDropped,
     /// received unexpected response from server
     UnexpectedResponse,
-    /// received server response with unexpected request ID
+    /// received server response with unexpected request ID (responses with unknown IDs are
+    /// dropped by [`ApiService`](crate::service::ApiService), so this shouldn't normally occur)
     Desynchronized,
     /// JSON error
     Json,
     /// underlying transport failed while attempting to receive a response
     Read,
+    /// received message exceeding the configured size limit
+    MessageTooLarge,
+    /// no hotkey found with the given name
+    HotkeyNotFound,
+    /// request contains invalid values
+    InvalidRequest,
+    /// invalid websocket URL
+    InvalidUrl,
+    /// one or more requests in a batch failed
+    Batch,
+    /// no VTube Studio instance was discovered
+    InstanceNotFound,
     /// underlying transport failed to send a request
     Write,
     /// other error
@@ -65,6 +121,54 @@ impl UnexpectedResponseError {
     }
 }
 
+/// One or more requests in a batch failed (e.g., in
+/// [`Client::inject_parameters`](crate::Client::inject_parameters)).
+///
+/// The error of the first failed request is returned as this error's `source`, so
+/// [`Error::has_kind`] and [`Error::to_api_error`] can be used to inspect it.
+#[derive(Debug)]
+pub struct BatchError {
+    pub(crate) total: usize,
+    pub(crate) failed: Vec<(usize, Error)>,
+}
+
+impl BatchError {
+    /// The number of requests in the batch.
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    /// The index of each failed request in the batch, and its error.
+    pub fn failed(&self) -> &[(usize, Error)] {
+        &self.failed
+    }
+
+    /// Consumes the error, returning the index of each failed request and its error.
+    pub fn into_failed(self) -> Vec<(usize, Error)> {
+        self.failed
+    }
+}
+
+impl fmt::Display for BatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} of {} requests failed", self.failed.len(), self.total)
+    }
+}
+
+impl StdError for BatchError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        self.failed
+            .first()
+            .map(|(_, e)| e as &(dyn StdError + 'static))
+    }
+}
+
+impl From<BatchError> for Error {
+    fn from(error: BatchError) -> Self {
+        Self::new(ErrorKind::Batch).with_source(error)
+    }
+}
+
 impl From<serde_json::Error> for Error {
     fn from(error: serde_json::Error) -> Self {
         Self::new(ErrorKind::Json).with_source(error)
@@ -73,7 +177,15 @@ impl From<serde_json::Error> for Error {
 
 impl From<ApiError> for Error {
     fn from(error: ApiError) -> Self {
-        Self::new(ErrorKind::Api).with_source(error)
+        let kind = if error.error_id.is_cooldown() {
+            ErrorKind::Cooldown
+        } else if error.error_id.is_not_found() {
+            ErrorKind::NotFound
+        } else {
+            ErrorKind::Api
+        };
+
+        Self::new(kind).with_source(error)
     }
 }
 
@@ -121,6 +233,24 @@ impl Error {
         matches!(self.to_api_error(), Some(e) if e.is_unauthenticated())
     }
 
+    /// Returns `true` if this error's underlying [`ApiError`] indicates the plugin lacks a
+    /// required permission (see [`ErrorId::is_permission_error`]).
+    pub fn is_permission_error(&self) -> bool {
+        matches!(self.to_api_error(), Some(e) if e.is_permission_error())
+    }
+
+    /// Returns `true` if this error's underlying [`ApiError`] indicates a cooldown period is not
+    /// over yet (see [`ErrorId::is_cooldown`]).
+    pub fn is_cooldown(&self) -> bool {
+        matches!(self.to_api_error(), Some(e) if e.error_id.is_cooldown())
+    }
+
+    /// Returns `true` if this error's underlying [`ApiError`] indicates the requested resource
+    /// was not found (see [`ErrorId::is_not_found`]).
+    pub fn is_not_found(&self) -> bool {
+        matches!(self.to_api_error(), Some(e) if e.error_id.is_not_found())
+    }
+
     /// Converts a [`BoxError`] into this error type. If the underlying [`Error`](std::error::Error)
     /// is not this error type, a new [`Error`] is created with [`ErrorKind::Other`].
     pub fn from_boxed(error: BoxError) -> Self {
@@ -137,7 +267,11 @@ impl Error {
 
     /// Checks if any error in this error's `source` chain matches the given [`ErrorKind`].
     pub fn has_kind(&self, kind: ErrorKind) -> bool {
-        if self.kind == kind {
+        self.has_kind_ref(&kind)
+    }
+
+    pub(crate) fn has_kind_ref(&self, kind: &ErrorKind) -> bool {
+        if &self.kind == kind {
             return true;
         }
 
@@ -145,7 +279,7 @@ impl Error {
 
         while let Some(e) = source {
             match e.downcast_ref::<Self>() {
-                Some(ref found) if found.kind == kind => return true,
+                Some(found) if &found.kind == kind => return true,
                 _ => source = e.source(),
             }
         }
@@ -159,7 +293,7 @@ impl Error {
 
         while let Some(e) = source {
             match e.downcast_ref::<E>() {
-                Some(ref found) => return Some(found),
+                Some(found) => return Some(found),
                 None => source = e.source(),
             }
         }
@@ -168,6 +302,27 @@ impl Error {
     }
 }
 
+/// Whether a transport error was caused by an incoming message exceeding the size limit.
+#[cfg(any(feature = "tokio-tungstenite", feature = "async-tungstenite"))]
+fn is_message_too_large(error: &(dyn StdError + 'static)) -> bool {
+    #[cfg(all(feature = "async-tungstenite", not(feature = "tokio-tungstenite")))]
+    use async_tungstenite::tungstenite;
+    #[cfg(feature = "tokio-tungstenite")]
+    use tokio_tungstenite::tungstenite;
+
+    use tungstenite::error::{CapacityError, Error as WsError};
+
+    matches!(
+        error.downcast_ref::<WsError>(),
+        Some(WsError::Capacity(CapacityError::MessageTooLong { .. }))
+    )
+}
+
+#[cfg(not(any(feature = "tokio-tungstenite", feature = "async-tungstenite")))]
+fn is_message_too_large(_error: &(dyn StdError + 'static)) -> bool {
+    false
+}
+
 #[doc(hidden)]
 impl<T, I> From<tokio_tower::Error<T, I>> for Error
 where
@@ -179,10 +334,46 @@ where
 
         match error {
             BrokenTransportSend(e) => Self::new(ErrorKind::Write).with_source(e),
-            BrokenTransportRecv(Some(e)) => Self::new(ErrorKind::Read).with_source(e),
+            BrokenTransportRecv(Some(e)) => {
+                let e = BoxError::from(e);
+                let kind = if is_message_too_large(e.as_ref()) {
+                    ErrorKind::MessageTooLarge
+                } else {
+                    ErrorKind::Read
+                };
+                Self::new(kind).with_source(e)
+            }
             BrokenTransportRecv(None) | ClientDropped => Self::new(ErrorKind::ConnectionDropped),
             TransportFull => Self::new(ErrorKind::TransportFull),
             Desynchronized => Self::new(ErrorKind::Desynchronized),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display() {
+        let error = |error_id| {
+            Error::from(ApiError {
+                error_id,
+                message: "Some message".to_owned(),
+            })
+        };
+
+        assert_eq!(
+            error(ErrorId::REQUEST_REQUIRES_AUTHENTICATION).to_string(),
+            "received APIError 8 (RequestRequiresAuthentication): Some message"
+        );
+        assert_eq!(
+            error(ErrorId::new(12345)).to_string(),
+            "received APIError 12345: Some message"
+        );
+        assert_eq!(
+            Error::new(ErrorKind::ConnectionDropped).to_string(),
+            "connection was dropped"
+        );
+    }
+}
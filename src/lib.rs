@@ -9,7 +9,7 @@
 //! A library for interacting with the [VTube Studio API].
 //!
 //! This crate exposes a [`Client`] for making requests to the VTube Studio websocket API, and
-//! handles the work of mapping requests to responses (using [`tokio_tower::multiplex`]).
+//! handles the work of mapping requests to responses.
 //!
 //! The client wraps a set of configurable [`tower::Service`] middleware for handling the
 //! [authentication flow](crate::service::Authentication), [retries](crate::service::RetryPolicy),
@@ -56,6 +56,9 @@
 #![doc = include_str!("../examples/events.rs")]
 //! ```
 //!
+//! To handle events and request results in a single loop, see
+//! [`ClientEventStream::with_responses`].
+//!
 //! # Project structure
 //!
 //! * [`client`] provides a high level API dealing with typed [`Request`]/[`Response`] types, which wraps a... ⏎
@@ -70,7 +73,8 @@
 //! * using a different combination of tower middleware
 //! * using a different websocket library
 //! * adding custom request/response types (as an escape hatch, if new request types or fields are
-//!   added to the API and you don't feel like waiting for them to be added to this library)
+//!   added to the API and you don't feel like waiting for them to be added to this library). For
+//!   one-off requests, [`Client::send_raw`] sends arbitrary JSON without defining any types.
 //!
 //! [`Request`]: crate::data::Request
 //! [`Response`]: crate::data::Response
@@ -87,6 +91,68 @@
 //! [dependencies]
 #![doc = concat!("vtubestudio = { version = \"", env!("CARGO_PKG_VERSION"), "\", default-features = false }")]
 //! ```
+//!
+//! The `testing` feature enables the [`testing`] module, which provides a fake VTube Studio
+//! websocket server and an in-memory mock server for testing [`Client`]s end-to-end.
+//!
+//! The `async-tungstenite` feature enables [`ClientBuilder::build_async_tungstenite`], which uses
+//! [`async_tungstenite`](https://docs.rs/async-tungstenite) with a connection from any async
+//! runtime (e.g., `async-std` or `smol`), instead of [`tokio_tungstenite`].
+//! The `async-std` feature additionally enables [`ClientBuilder::build_async_std`], which connects
+//! using [`async_std`](https://docs.rs/async-std)'s `TcpStream`. Outside of a Tokio runtime, the
+//! `async-std` feature is required (see below).
+//!
+//! [`ClientEventStream`] implements [`Stream`](futures_core::Stream), so events can be consumed
+//! from any executor. The [`Client`] spawns its background tasks (and sets timers) on the current
+//! [`tokio`](https://docs.rs/tokio) runtime if there is one. Otherwise, with the `async-std`
+//! feature enabled, they run on `async-std` instead, so no Tokio runtime is needed.
+//!
+//! The `wasm` feature enables [`ClientBuilder::build_web`], which uses the browser's `WebSocket`
+//! API (via [`gloo_net`](https://docs.rs/gloo-net)) instead of [`tokio_tungstenite`].
+//! On `wasm32-unknown-unknown`, the [`Client`]'s background tasks are spawned on the browser's
+//! event loop (with `wasm_bindgen_futures::spawn_local`) and timers use `gloo_timers`, so no
+//! Tokio runtime is needed. Since `std::time::Instant` isn't available in the browser,
+//! [`Client::ping`] and the optional layers that measure time (such as rate limiting, circuit
+//! breaking, metrics and tracing) aren't supported there.
+//! See the [`web` example](https://github.com/walfie/vtubestudio-rs/tree/master/examples/web)
+//! for a browser plugin built with [`wasm-pack`](https://rustwasm.github.io/wasm-pack/).
+//!
+//! The `blocking` feature enables the [`blocking`] module, which provides a synchronous client for
+//! use outside of async code.
+//!
+//! The `discovery` feature enables the [`discovery`] module, for finding VTube Studio instances
+//! on the local network via their UDP broadcasts.
+//!
+//! The `rand` and `uuid` features enable [`RequestId::generate`](crate::data::RequestId) and
+//! [`IdTagger::random`](crate::service::IdTagger), for assigning random request IDs instead of
+//! sequential ones.
+//!
+//! The `metrics` feature enables [`MetricsLayer`](crate::service::MetricsLayer), which records
+//! request counts, errors, reconnects, and latencies using the [`metrics`](https://docs.rs/metrics)
+//! crate facade. Clients created with [`ClientBuilder`] include this layer automatically.
+//!
+//! The `tracing` feature enables [`TracingLayer`](crate::service::TracingLayer), which opens a
+//! [`tracing`](https://docs.rs/tracing) span for each request, with its message type and request
+//! ID. Clients created with [`ClientBuilder`] include this layer automatically.
+//!
+//! The `opentelemetry` feature (which implies `tracing`) adds the `otel.kind` and
+//! `otel.status_code` fields to these spans, for use with
+//! [`tracing-opentelemetry`](https://docs.rs/tracing-opentelemetry).
+//!
+//! The `proxy` feature enables [`ClientBuilder::http_proxy`] and
+//! [`ClientBuilder::proxy_from_env`], for connecting through an HTTP proxy.
+//!
+//! The `native-tls`, `rustls-tls-native-roots`, and `rustls-tls-webpki-roots` features enable the
+//! corresponding TLS support in [`tokio_tungstenite`] for `wss://` URLs, along with
+//! `ClientBuilder::tls_connector` for providing a custom TLS connector.
+//!
+//! The `schemars` feature implements [`JsonSchema`](https://docs.rs/schemars) for the request,
+//! response, and event types in [`data`], and enables
+//! [`schema_for_all_requests`](crate::data::schema_for_all_requests) for generating JSON schemas
+//! for every request type.
+//!
+//! The `glam` feature adds conversions between [`Vec2`](crate::data::Vec2) and the
+//! [`glam`](https://docs.rs/glam) vector types.
 
 /// Utilities for creating [`Client`]s.
 pub mod client;
@@ -108,6 +174,24 @@ pub mod data;
 /// Types related to error handling.
 pub mod error;
 
+mod runtime;
+
+crate::cfg_feature! {
+    #![feature = "blocking"]
+    pub mod blocking;
+}
+
+crate::cfg_feature! {
+    #![feature = "discovery"]
+    pub mod discovery;
+}
+
+crate::cfg_feature! {
+    #![feature = "testing"]
+    /// Utilities for testing code that uses a [`Client`], without running VTube Studio.
+    pub mod testing;
+}
+
 // Macro for enabling `doc_cfg` on docs.rs
 macro_rules! cfg_feature {
     (
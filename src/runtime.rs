@@ -0,0 +1,104 @@
+//! Spawning background tasks and timers, without depending on a particular async runtime.
+//!
+//! A [`tokio`] runtime is used if one is running on the current thread. Otherwise, this falls back
+//! to [`async_std`](https://docs.rs/async-std) if the `async-std` feature is enabled.
+//!
+//! On `wasm32` targets with the `wasm` feature enabled, tasks are spawned on the browser's event
+//! loop with [`wasm_bindgen_futures::spawn_local`](https://docs.rs/wasm-bindgen-futures), and
+//! timers use [`gloo_timers`](https://docs.rs/gloo-timers).
+
+use std::future::Future;
+use std::pin::Pin;
+use std::time::Duration;
+use tokio::runtime::Handle;
+
+/// A future returned by [`sleep`].
+pub(crate) type Sleep = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+/// Spawns a background task on the current runtime.
+///
+/// # Panics
+///
+/// Panics if no supported runtime is available.
+pub(crate) fn spawn<F>(future: F)
+where
+    F: Future<Output = ()> + Send + 'static,
+{
+    match Handle::try_current() {
+        Ok(handle) => drop(handle.spawn(future)),
+        Err(_) => fallback::spawn(future),
+    }
+}
+
+/// Returns a future that completes after `duration` has elapsed.
+///
+/// # Panics
+///
+/// The returned future panics when polled if no supported runtime is available.
+pub(crate) fn sleep(duration: Duration) -> Sleep {
+    match Handle::try_current() {
+        Ok(_) => Box::pin(tokio::time::sleep(duration)),
+        Err(_) => fallback::sleep(duration),
+    }
+}
+
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+mod fallback {
+    use super::Sleep;
+    use send_wrapper::SendWrapper;
+    use std::future::Future;
+    use std::time::Duration;
+
+    pub(super) fn spawn<F>(future: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        wasm_bindgen_futures::spawn_local(future);
+    }
+
+    // Browser timers aren't `Send`, but everything runs on the same thread (see `transport::wasm`)
+    pub(super) fn sleep(duration: Duration) -> Sleep {
+        Box::pin(SendWrapper::new(gloo_timers::future::sleep(duration)))
+    }
+}
+
+#[cfg(all(
+    feature = "async-std",
+    not(all(feature = "wasm", target_arch = "wasm32"))
+))]
+mod fallback {
+    use super::Sleep;
+    use std::future::Future;
+    use std::time::Duration;
+
+    pub(super) fn spawn<F>(future: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        async_std::task::spawn(future);
+    }
+
+    pub(super) fn sleep(duration: Duration) -> Sleep {
+        Box::pin(async_std::task::sleep(duration))
+    }
+}
+
+#[cfg(not(any(feature = "async-std", all(feature = "wasm", target_arch = "wasm32"))))]
+mod fallback {
+    use super::Sleep;
+    use std::future::Future;
+    use std::time::Duration;
+
+    // These panic with Tokio's "no reactor running" message
+
+    pub(super) fn spawn<F>(future: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        tokio::spawn(future);
+    }
+
+    pub(super) fn sleep(duration: Duration) -> Sleep {
+        Box::pin(async move { tokio::time::sleep(duration).await })
+    }
+}
@@ -1,15 +1,16 @@
 use crate::data::{RequestEnvelope, RequestId, ResponseEnvelope};
 use crate::error::{BoxError, Error};
+use crate::service::multiplex;
 use crate::transport::buffered::BufferedApiTransport;
 use crate::transport::event::{EventStream, EventlessApiTransport};
 
 use futures_core::TryStream;
 use futures_sink::Sink;
-use std::fmt::Write;
 use std::future::Future;
+use std::marker::PhantomData;
 use std::pin::Pin;
 use std::task::{Context, Poll};
-use tokio_tower::multiplex::{Client as MultiplexClient, MultiplexTransport, TagStore};
+use tokio_tower::multiplex::{MultiplexTransport, TagStore};
 use tower::Service;
 
 crate::cfg_feature! {
@@ -20,11 +21,45 @@ crate::cfg_feature! {
     pub type TungsteniteApiService = ApiService<TungsteniteApiTransport>;
 }
 
+crate::cfg_feature! {
+    #![feature = "async-tungstenite"]
+    use crate::transport::AsyncTungsteniteApiTransport;
+
+    /// Type alias for an [`ApiService`] wrapping an [`AsyncTungsteniteApiTransport`].
+    pub type AsyncTungsteniteApiService<S> = ApiService<AsyncTungsteniteApiTransport<S>>;
+}
+
 /// Struct describing how to tag [`RequestEnvelope`]s and extract tags from [`ResponseEnvelope`]s.
-#[derive(Debug)]
+///
+/// By default, requests without an ID are assigned sequential numeric IDs (`"0"`, `"1"`, etc).
+#[derive(Debug, Default)]
 pub struct IdTagger {
     next: usize,
-    buffer: String,
+    #[cfg_attr(not(any(feature = "rand", feature = "uuid")), allow(dead_code))]
+    random: bool,
+}
+
+impl IdTagger {
+    /// Creates an [`IdTagger`] that assigns sequential numeric IDs.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+crate::cfg_feature! {
+    #![any(feature = "rand", feature = "uuid")]
+    impl IdTagger {
+        /// Creates an [`IdTagger`] that assigns random IDs using [`RequestId::generate`].
+        ///
+        /// Unlike sequential IDs, these are unlikely to collide across multiple clients or
+        /// restarts.
+        pub fn random() -> Self {
+            Self {
+                next: 0,
+                random: true,
+            }
+        }
+    }
 }
 
 impl TagStore<RequestEnvelope, ResponseEnvelope> for IdTagger {
@@ -36,17 +71,8 @@ impl TagStore<RequestEnvelope, ResponseEnvelope> for IdTagger {
             return id.clone();
         }
 
-        let id = self.next;
-        if write!(self.buffer, "{}", id).is_err() {
-            // We don't expect this to happen, but recover just in case
-            self.buffer = id.to_string();
-        }
-
-        let id = RequestId::from(self.buffer.as_str());
+        let id = self.next_id();
         request.request_id = Some(id.clone());
-
-        self.next += 1;
-        self.buffer.clear();
         id
     }
 
@@ -55,22 +81,38 @@ impl TagStore<RequestEnvelope, ResponseEnvelope> for IdTagger {
     }
 }
 
-type ServiceInner<T> = MultiplexClient<
-    MultiplexTransport<BufferedApiTransport<EventlessApiTransport<T>>, IdTagger>,
-    Error,
-    RequestEnvelope,
->;
+impl IdTagger {
+    fn next_id(&mut self) -> RequestId {
+        #[cfg(any(feature = "rand", feature = "uuid"))]
+        if self.random {
+            return RequestId::generate();
+        }
+
+        let id = RequestId::from(self.next);
+        self.next = self.next.wrapping_add(1);
+        id
+    }
+}
 
 /// A [`Service`] that assigns request IDs to [`RequestEnvelope`]s and matches them to incoming
 /// [`ResponseEnvelope`]s.
 ///
-/// This uses [`tokio_tower::multiplex`] to wrap an underlying transport.
+/// The underlying transport is driven by a background task, which is spawned on the current
+/// [`tokio`](https://docs.rs/tokio) runtime if there is one, or on
+/// [`async_std`](https://docs.rs/async-std) otherwise (with the `async-std` feature).
+///
+/// Events are split off into the [`EventStream`] before responses are matched to requests. Any
+/// other message whose request ID doesn't belong to an in-flight request (e.g., a late response
+/// to a request that was sent before reconnecting) is logged and dropped, rather than failing
+/// with [`ErrorKind::Desynchronized`](crate::error::ErrorKind::Desynchronized) and closing the
+/// connection.
 #[derive(Debug)]
 pub struct ApiService<T>
 where
     T: Sink<RequestEnvelope> + TryStream<Ok = ResponseEnvelope>,
 {
-    service: ServiceInner<T>,
+    client: multiplex::Client<RequestEnvelope, ResponseEnvelope>,
+    _transport: PhantomData<fn(T)>,
 }
 
 impl<T> ApiService<T>
@@ -98,18 +140,38 @@ where
     where
         F: FnOnce(Error) + Send + 'static,
     {
-        let tagger = IdTagger {
-            next: 0,
-            buffer: String::new(),
-        };
+        Self::with_id_tagger(transport, buffer_size, IdTagger::new(), on_service_error)
+    }
 
+    /// Create a new [`ApiService`] that assigns request IDs using the given [`IdTagger`] (e.g.,
+    /// [`IdTagger::random`] to use random IDs instead of sequential ones).
+    pub fn with_id_tagger<F>(
+        transport: T,
+        buffer_size: usize,
+        tagger: IdTagger,
+        on_service_error: F,
+    ) -> (Self, EventStream<T>)
+    where
+        F: FnOnce(Error) + Send + 'static,
+    {
         let (eventless_transport, event_stream) = EventlessApiTransport::new(transport);
         let buffered_transport = BufferedApiTransport::new(eventless_transport, buffer_size);
 
         let multiplex_transport = MultiplexTransport::new(buffered_transport, tagger);
-        let service = MultiplexClient::with_error_handler(multiplex_transport, on_service_error);
+        let (client, dispatcher) = multiplex::Client::new(multiplex_transport);
+
+        crate::runtime::spawn(async move {
+            if let Err(error) = dispatcher.await {
+                on_service_error(error);
+            }
+        });
 
-        (Self { service }, event_stream)
+        let service = Self {
+            client,
+            _transport: PhantomData,
+        };
+
+        (service, event_stream)
     }
 }
 
@@ -124,10 +186,63 @@ where
     type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
 
     fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
-        self.service.poll_ready(cx)
+        self.client.poll_ready(cx)
     }
 
     fn call(&mut self, req: RequestEnvelope) -> Self::Future {
-        self.service.call(req)
+        Box::pin(self.client.call(req))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::StatisticsRequest;
+
+    #[test]
+    fn id_tagger() -> Result<(), serde_json::Error> {
+        let mut tagger = IdTagger::new();
+        let mut tagger = Pin::new(&mut tagger);
+
+        for expected in ["0", "1", "2"] {
+            let mut req = RequestEnvelope::new(&StatisticsRequest {})?;
+            let tag = tagger.as_mut().assign_tag(&mut req);
+
+            assert_eq!(tag.as_str(), expected);
+            assert_eq!(req.request_id, Some(tag));
+            assert_eq!(serde_json::to_value(&req)?["requestID"], expected);
+        }
+
+        // Existing IDs are kept, and don't advance the counter
+        let mut req = RequestEnvelope::new(&StatisticsRequest {})?.with_id(RequestId::from("abc"));
+        assert_eq!(tagger.as_mut().assign_tag(&mut req).as_str(), "abc");
+
+        let mut req = RequestEnvelope::new(&StatisticsRequest {})?;
+        assert_eq!(tagger.as_mut().assign_tag(&mut req).as_str(), "3");
+
+        Ok(())
+    }
+
+    #[test]
+    fn request_id_from_usize() {
+        for n in [0, 7, 10, 1234567890, usize::MAX] {
+            assert_eq!(RequestId::from(n).as_str(), n.to_string());
+        }
+    }
+
+    #[cfg(any(feature = "rand", feature = "uuid"))]
+    #[test]
+    fn random_id_tagger() -> Result<(), serde_json::Error> {
+        let mut tagger = IdTagger::random();
+        let mut tagger = Pin::new(&mut tagger);
+
+        let mut ids = std::collections::HashSet::new();
+        for _ in 0..10_000 {
+            let mut req = RequestEnvelope::new(&StatisticsRequest {})?;
+            let id = tagger.as_mut().assign_tag(&mut req);
+            assert!(ids.insert(id.into_string()));
+        }
+
+        Ok(())
     }
 }
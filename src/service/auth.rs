@@ -5,6 +5,7 @@ use crate::error::{Error, ErrorKind};
 use crate::service::send_request;
 
 use futures_util::TryFutureExt;
+use std::borrow::Cow;
 use std::fmt;
 use std::future::Future;
 use std::pin::Pin;
@@ -19,14 +20,17 @@ use tracing::debug;
 pub struct AuthenticationLayer {
     token: Option<String>,
     token_request: Arc<AuthenticationTokenRequest>,
+    is_authenticated: Arc<AtomicBool>,
+    token_storage: Option<SharedToken>,
 }
 
 impl fmt::Debug for AuthenticationLayer {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         // Avoid printing the token
         f.debug_struct("AuthenticationLayer")
-            .field("token", &self.token.is_some().then(|| "..."))
+            .field("token", &self.token.is_some().then_some("..."))
             .field("token_request", &self.token_request)
+            .field("is_authenticated", &self.is_authenticated)
             .finish()
     }
 }
@@ -37,6 +41,8 @@ impl AuthenticationLayer {
         Self {
             token_request: Arc::new(token_request),
             token: None,
+            is_authenticated: Arc::new(AtomicBool::new(false)),
+            token_storage: None,
         }
     }
 
@@ -48,6 +54,25 @@ impl AuthenticationLayer {
         self.token = token;
         self
     }
+
+    /// The token that services produced by this layer will initially authenticate with, if any.
+    pub fn token(&self) -> Option<Cow<'_, str>> {
+        self.token.as_deref().map(Cow::Borrowed)
+    }
+
+    /// Shares the authentication status flag of services produced by this layer.
+    pub(crate) fn with_authentication_status(mut self, is_authenticated: Arc<AtomicBool>) -> Self {
+        self.is_authenticated = is_authenticated;
+        self
+    }
+
+    /// Shares the token stored by services produced by this layer, initializing it with the token
+    /// set via [`with_token`](Self::with_token).
+    pub(crate) fn with_token_storage(mut self, storage: SharedToken) -> Self {
+        storage.set(self.token.clone());
+        self.token_storage = Some(storage);
+        self
+    }
 }
 
 impl<S> Layer<S> for AuthenticationLayer
@@ -60,7 +85,40 @@ where
     type Service = Authentication<S>;
 
     fn layer(&self, service: S) -> Self::Service {
-        Authentication::new(service, self.token_request.clone(), self.token.clone())
+        Authentication {
+            service,
+            token_request: self.token_request.clone(),
+            token: match &self.token_storage {
+                Some(storage) => storage.clone(),
+                None => SharedToken::new(self.token.clone()),
+            },
+            is_authenticated: self.is_authenticated.clone(),
+        }
+    }
+}
+
+/// An auth token shared between clones of an [`Authentication`] service.
+#[derive(Clone, Default)]
+pub(crate) struct SharedToken(Arc<Mutex<Option<String>>>);
+
+impl SharedToken {
+    pub(crate) fn new(token: Option<String>) -> Self {
+        Self(Arc::new(Mutex::new(token)))
+    }
+
+    pub(crate) fn get(&self) -> Option<String> {
+        self.0.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    pub(crate) fn set(&self, token: Option<String>) {
+        *self.0.lock().unwrap_or_else(|e| e.into_inner()) = token;
+    }
+}
+
+impl fmt::Debug for SharedToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Avoid printing the token
+        f.debug_tuple("SharedToken").field(&"...").finish()
     }
 }
 
@@ -72,13 +130,17 @@ where
 /// * encountering a disconnection error
 /// * receiving an auth error from the API
 ///
+/// Permission errors ([`ErrorId::REQUEST_REQUIRES_PERMISSION`](crate::data::ErrorId::REQUEST_REQUIRES_PERMISSION))
+/// don't trigger reauthentication, since they can only be resolved by the user granting the
+/// permission.
+///
 /// If no stored token is available, or the token is invalid, it will request a new auth token by
 /// sending an [`AuthenticationTokenRequest`] (which will require the user to accept the pop-up in
 /// the VTube Studio app).
 #[derive(Clone)]
 pub struct Authentication<S> {
     service: S,
-    token: Arc<Mutex<Option<String>>>,
+    token: SharedToken,
     token_request: Arc<AuthenticationTokenRequest>,
     is_authenticated: Arc<AtomicBool>,
 }
@@ -99,13 +161,39 @@ where
         Self {
             service,
             token_request,
-            token: Arc::new(Mutex::new(token)),
+            token: SharedToken::new(token),
             is_authenticated: Arc::new(AtomicBool::new(false)),
         }
     }
 }
 
 impl<S> Authentication<S> {
+    /// Returns `true` if the last authentication attempt succeeded, and the session hasn't become
+    /// unauthenticated since then.
+    pub fn is_authenticated(&self) -> bool {
+        self.is_authenticated.load(Ordering::Relaxed)
+    }
+
+    /// Returns the auth token currently stored by this service, if any.
+    ///
+    /// This is either the token that was initially provided, or the most recent token obtained
+    /// through an [`AuthenticationTokenRequest`].
+    pub fn current_token(&self) -> Option<String> {
+        self.token.get()
+    }
+
+    /// Clears the stored auth token and marks the session as unauthenticated.
+    ///
+    /// The next request will go through the full authentication flow again, requesting a new token
+    /// from VTube Studio. This is useful if the token is known to be revoked (e.g., if the user
+    /// removed the plugin's access in the VTube Studio settings).
+    ///
+    /// Since clones of this service share the same state, this affects all of them.
+    pub fn invalidate(&self) {
+        self.token.set(None);
+        self.is_authenticated.store(false, Ordering::Relaxed);
+    }
+
     /// Consumes `self`, returning the inner service.
     pub fn into_inner(self) -> S {
         self.service
@@ -211,8 +299,8 @@ where
 {
     // Helper for authenticating using a stored token, and managing internal state (updating
     // current authentication status and storing new tokens).
-    async fn authenticate(&mut self) -> Result<Option<String>, Error> {
-        let stored_token = (*self.token.lock().unwrap()).clone();
+    pub(crate) async fn authenticate(&mut self) -> Result<Option<String>, Error> {
+        let stored_token = self.token.get();
 
         let token_result =
             authenticate(&mut self.service, stored_token, self.token_request.as_ref()).await;
@@ -228,7 +316,7 @@ where
                 None
             }
             Ok(ReceivedNewValidToken { token }) => {
-                *self.token.lock().unwrap() = Some(token.clone());
+                self.token.set(Some(token.clone()));
                 self.set_authentication_status(true);
                 Some(token)
             }
@@ -303,3 +391,151 @@ where
         Box::pin(f)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::{
+        ApiError, AuthenticationResponse, AuthenticationTokenResponse, ErrorId, Request,
+        StatisticsRequest, StatisticsResponse,
+    };
+    use std::sync::atomic::AtomicUsize;
+
+    // Responds to auth requests successfully, and to all other requests with the given error.
+    async fn auth_attempts_after_error(error_id: ErrorId) -> Result<usize, Error> {
+        let auth_requests = Arc::new(AtomicUsize::new(0));
+
+        let service = tower::service_fn({
+            let auth_requests = Arc::clone(&auth_requests);
+            move |req: RequestEnvelope| {
+                let auth_requests = Arc::clone(&auth_requests);
+                async move {
+                    if req.message_type == AuthenticationRequest::MESSAGE_TYPE {
+                        auth_requests.fetch_add(1, Ordering::Relaxed);
+                        return ResponseEnvelope::new(&AuthenticationResponse {
+                            authenticated: true,
+                            reason: String::new(),
+                        })
+                        .map_err(Error::from);
+                    }
+
+                    Ok(ResponseEnvelope {
+                        data: Err(ApiError {
+                            error_id,
+                            message: String::new(),
+                        }),
+                        ..Default::default()
+                    })
+                }
+            }
+        });
+
+        let token_request = AuthenticationTokenRequest {
+            plugin_name: "Plugin name".into(),
+            plugin_developer: "Developer name".into(),
+            plugin_icon: None,
+        };
+
+        let mut auth = AuthenticationLayer::new(token_request)
+            .with_token(Some("token".into()))
+            .layer(service);
+
+        let resp = auth
+            .ready()
+            .await?
+            .call(RequestEnvelope::new(&StatisticsRequest {})?)
+            .await?;
+        assert!(resp.response.parse::<StatisticsResponse>().is_err());
+
+        Ok(auth_requests.load(Ordering::Relaxed))
+    }
+
+    #[tokio::test]
+    async fn reauthenticate_on_auth_error() -> Result<(), Error> {
+        // Initial authentication, then reauthentication after the error
+        let attempts = auth_attempts_after_error(ErrorId::REQUEST_REQUIRES_AUTHENTICATION).await?;
+        assert_eq!(attempts, 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn no_reauthenticate_on_permission_error() -> Result<(), Error> {
+        // Only the initial authentication
+        let attempts = auth_attempts_after_error(ErrorId::REQUEST_REQUIRES_PERMISSION).await?;
+        assert_eq!(attempts, 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn invalidate() -> Result<(), Error> {
+        let requests = Arc::new(Mutex::new(Vec::new()));
+
+        let service = tower::service_fn({
+            let requests = Arc::clone(&requests);
+            move |req: RequestEnvelope| {
+                requests
+                    .lock()
+                    .unwrap()
+                    .push(req.message_type.as_str().to_owned());
+                let resp = if req.message_type == AuthenticationTokenRequest::MESSAGE_TYPE {
+                    ResponseEnvelope::new(&AuthenticationTokenResponse {
+                        authentication_token: "new-token".into(),
+                    })
+                } else if req.message_type == AuthenticationRequest::MESSAGE_TYPE {
+                    ResponseEnvelope::new(&AuthenticationResponse {
+                        authenticated: true,
+                        reason: String::new(),
+                    })
+                } else {
+                    ResponseEnvelope::new(&StatisticsResponse::default())
+                };
+                async move { resp.map_err(Error::from) }
+            }
+        });
+
+        let layer = AuthenticationLayer::new(AuthenticationTokenRequest {
+            plugin_name: "Plugin name".into(),
+            plugin_developer: "Developer name".into(),
+            plugin_icon: None,
+        })
+        .with_token(Some("token".into()));
+        assert_eq!(layer.token().as_deref(), Some("token"));
+
+        let mut auth = layer.layer(service);
+        assert_eq!(auth.current_token().as_deref(), Some("token"));
+
+        let resp = auth
+            .ready()
+            .await?
+            .call(RequestEnvelope::new(&StatisticsRequest {})?)
+            .await?;
+        assert_eq!(resp.new_token, None);
+        assert!(auth.is_authenticated());
+
+        auth.invalidate();
+        assert!(!auth.is_authenticated());
+        assert_eq!(auth.current_token(), None);
+
+        let resp = auth
+            .ready()
+            .await?
+            .call(RequestEnvelope::new(&StatisticsRequest {})?)
+            .await?;
+        assert_eq!(resp.new_token.as_deref(), Some("new-token"));
+        assert_eq!(auth.current_token().as_deref(), Some("new-token"));
+        assert!(auth.is_authenticated());
+
+        assert_eq!(
+            *requests.lock().unwrap(),
+            [
+                "AuthenticationRequest",
+                "StatisticsRequest",
+                "AuthenticationTokenRequest",
+                "AuthenticationRequest",
+                "StatisticsRequest",
+            ]
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,527 @@
+use crate::error::{Error, ErrorKind};
+
+use pin_project_lite::pin_project;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tokio::sync::watch;
+use tokio::time::Instant;
+use tower::{Layer, Service};
+use tracing::debug;
+
+/// The state of a [`CircuitBreaker`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CircuitBreakerState {
+    /// Requests are passed through to the inner service.
+    Closed,
+    /// Requests are rejected immediately with [`ErrorKind::ConnectionRefused`].
+    Open,
+    /// A single probe request at a time is passed through to the inner service. If enough probes
+    /// succeed, the circuit is closed again. If a probe fails, the circuit is opened again.
+    HalfOpen,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Config {
+    failure_threshold: u32,
+    reset_timeout: Duration,
+    success_threshold: u32,
+}
+
+#[derive(Debug)]
+struct Counters {
+    state: CircuitBreakerState,
+    failures: u32,
+    successes: u32,
+    opened_at: Instant,
+    probe_in_flight: bool,
+}
+
+/// State shared between a [`CircuitBreakerLayer`] and all services produced by it.
+#[derive(Debug)]
+struct Shared {
+    counters: Mutex<Counters>,
+    state_tx: watch::Sender<CircuitBreakerState>,
+}
+
+impl Shared {
+    fn new() -> Self {
+        Self {
+            counters: Mutex::new(Counters {
+                state: CircuitBreakerState::Closed,
+                failures: 0,
+                successes: 0,
+                opened_at: Instant::now(),
+                probe_in_flight: false,
+            }),
+            state_tx: watch::channel(CircuitBreakerState::Closed).0,
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Counters> {
+        self.counters.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn transition(&self, counters: &mut Counters, state: CircuitBreakerState) {
+        debug!(from = ?counters.state, to = ?state, "Circuit breaker changed state");
+
+        counters.state = state;
+        counters.failures = 0;
+        counters.successes = 0;
+        counters.probe_in_flight = false;
+        if state == CircuitBreakerState::Open {
+            counters.opened_at = Instant::now();
+        }
+
+        self.state_tx.send_replace(state);
+    }
+
+    /// Returns `true` if a request would be rejected right now, without reserving a probe.
+    fn would_reject(&self, config: &Config) -> bool {
+        let counters = self.lock();
+        match counters.state {
+            CircuitBreakerState::Closed => false,
+            CircuitBreakerState::Open => counters.opened_at.elapsed() < config.reset_timeout,
+            CircuitBreakerState::HalfOpen => counters.probe_in_flight,
+        }
+    }
+
+    /// Returns `None` if the request should be rejected, or `Some(is_probe)` if it's allowed
+    /// through. In the half-open state, this reserves the probe slot.
+    fn try_acquire(&self, config: &Config) -> Option<bool> {
+        let mut counters = self.lock();
+
+        if counters.state == CircuitBreakerState::Open
+            && counters.opened_at.elapsed() >= config.reset_timeout
+        {
+            self.transition(&mut counters, CircuitBreakerState::HalfOpen);
+        }
+
+        match counters.state {
+            CircuitBreakerState::Closed => Some(false),
+            CircuitBreakerState::Open => None,
+            CircuitBreakerState::HalfOpen if counters.probe_in_flight => None,
+            CircuitBreakerState::HalfOpen => {
+                counters.probe_in_flight = true;
+                Some(true)
+            }
+        }
+    }
+
+    /// Frees the probe slot without recording a result, if a probe is still in flight.
+    fn release_probe(&self) {
+        let mut counters = self.lock();
+        if counters.state == CircuitBreakerState::HalfOpen {
+            counters.probe_in_flight = false;
+        }
+    }
+
+    fn record(&self, config: &Config, success: bool) {
+        let mut counters = self.lock();
+
+        match (counters.state, success) {
+            (CircuitBreakerState::Closed, true) => counters.failures = 0,
+            (CircuitBreakerState::Closed, false) => {
+                counters.failures += 1;
+                if counters.failures >= config.failure_threshold {
+                    self.transition(&mut counters, CircuitBreakerState::Open);
+                }
+            }
+            (CircuitBreakerState::HalfOpen, true) => {
+                counters.probe_in_flight = false;
+                counters.successes += 1;
+                if counters.successes >= config.success_threshold {
+                    self.transition(&mut counters, CircuitBreakerState::Closed);
+                }
+            }
+            (CircuitBreakerState::HalfOpen, false) => {
+                self.transition(&mut counters, CircuitBreakerState::Open);
+            }
+            // Results of requests that were sent before the circuit was opened
+            (CircuitBreakerState::Open, _) => {}
+        }
+    }
+}
+
+/// A [`Layer`] that produces a [`CircuitBreaker`] service.
+///
+/// All services produced by this layer (and their clones) share the same circuit state.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+/// use vtubestudio::service::{CircuitBreakerLayer, CircuitBreakerState};
+///
+/// let layer = CircuitBreakerLayer::new()
+///     .failure_threshold(3)
+///     .reset_timeout(Duration::from_secs(5))
+///     .success_threshold(2);
+///
+/// let mut states = layer.subscribe();
+/// assert_eq!(*states.borrow_and_update(), CircuitBreakerState::Closed);
+/// ```
+#[derive(Debug, Clone)]
+pub struct CircuitBreakerLayer {
+    config: Config,
+    shared: Arc<Shared>,
+}
+
+impl CircuitBreakerLayer {
+    /// Creates a new [`CircuitBreakerLayer`] with default values.
+    pub fn new() -> Self {
+        Self {
+            config: Config {
+                failure_threshold: 5,
+                reset_timeout: Duration::from_secs(10),
+                success_threshold: 1,
+            },
+            shared: Arc::new(Shared::new()),
+        }
+    }
+
+    /// The number of consecutive failed requests before the circuit is opened. The default value
+    /// is 5. Values less than 1 are treated as 1.
+    pub fn failure_threshold(mut self, count: u32) -> Self {
+        self.config.failure_threshold = count.max(1);
+        self
+    }
+
+    /// How long the circuit stays open before allowing a probe request (in the half-open state).
+    /// The default value is 10 seconds.
+    pub fn reset_timeout(mut self, timeout: Duration) -> Self {
+        self.config.reset_timeout = timeout;
+        self
+    }
+
+    /// The number of consecutive successful probe requests before the circuit is closed again.
+    /// The default value is 1. Values less than 1 are treated as 1.
+    pub fn success_threshold(mut self, count: u32) -> Self {
+        self.config.success_threshold = count.max(1);
+        self
+    }
+
+    /// Returns the current state of the circuit.
+    pub fn state(&self) -> CircuitBreakerState {
+        *self.shared.state_tx.borrow()
+    }
+
+    /// Returns a receiver that is notified whenever the circuit changes state.
+    pub fn subscribe(&self) -> watch::Receiver<CircuitBreakerState> {
+        self.shared.state_tx.subscribe()
+    }
+}
+
+impl Default for CircuitBreakerLayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S> Layer<S> for CircuitBreakerLayer {
+    type Service = CircuitBreaker<S>;
+
+    fn layer(&self, service: S) -> Self::Service {
+        CircuitBreaker {
+            service,
+            config: self.config,
+            shared: Arc::clone(&self.shared),
+        }
+    }
+}
+
+/// A [`Service`] that stops sending requests to the inner service after repeated failures.
+///
+/// Only errors returned by the inner service (e.g., failing to connect or disconnections) count as
+/// failures. Successful responses containing an [`ApiError`](crate::data::ApiError) count as
+/// successes, since they indicate the connection is working.
+///
+/// See [`CircuitBreakerState`] for a description of each state.
+#[derive(Clone)]
+pub struct CircuitBreaker<S> {
+    service: S,
+    config: Config,
+    shared: Arc<Shared>,
+}
+
+impl<S> CircuitBreaker<S> {
+    /// Returns the current state of the circuit.
+    pub fn state(&self) -> CircuitBreakerState {
+        *self.shared.state_tx.borrow()
+    }
+
+    /// Returns a receiver that is notified whenever the circuit changes state.
+    pub fn subscribe(&self) -> watch::Receiver<CircuitBreakerState> {
+        self.shared.state_tx.subscribe()
+    }
+
+    /// Consumes `self`, returning the inner service.
+    pub fn into_inner(self) -> S {
+        self.service
+    }
+}
+
+impl<S: fmt::Debug> fmt::Debug for CircuitBreaker<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CircuitBreaker")
+            .field("service", &self.service)
+            .field("config", &self.config)
+            .field("state", &self.state())
+            .finish()
+    }
+}
+
+impl<S, Request> Service<Request> for CircuitBreaker<S>
+where
+    S: Service<Request>,
+    Error: From<S::Error>,
+{
+    type Response = S::Response;
+    type Error = Error;
+    type Future = ResponseFuture<S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        // Avoid polling the inner service (which might attempt to reconnect) if the request will
+        // be rejected anyway
+        if self.shared.would_reject(&self.config) {
+            return Poll::Ready(Ok(()));
+        }
+
+        match self.service.poll_ready(cx) {
+            Poll::Ready(Err(e)) => {
+                self.shared.record(&self.config, false);
+                Poll::Ready(Err(e.into()))
+            }
+            other => other.map_err(Error::from),
+        }
+    }
+
+    fn call(&mut self, req: Request) -> Self::Future {
+        let acquired = self.shared.try_acquire(&self.config);
+
+        ResponseFuture {
+            future: acquired.map(|_| self.service.call(req)),
+            probe: acquired.unwrap_or(false),
+            config: self.config,
+            shared: Arc::clone(&self.shared),
+        }
+    }
+}
+
+pin_project! {
+    /// Response future for [`CircuitBreaker`].
+    pub struct ResponseFuture<F> {
+        #[pin]
+        future: Option<F>,
+        // Whether this future holds the half-open probe slot
+        probe: bool,
+        config: Config,
+        shared: Arc<Shared>,
+    }
+
+    impl<F> PinnedDrop for ResponseFuture<F> {
+        fn drop(this: Pin<&mut Self>) {
+            // If a probe is dropped before completing (e.g., due to a timeout), let the next
+            // request be sent as a probe instead of rejecting requests forever
+            let this = this.project();
+            if *this.probe {
+                this.shared.release_probe();
+            }
+        }
+    }
+}
+
+impl<F> fmt::Debug for ResponseFuture<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResponseFuture")
+            .field("rejected", &self.future.is_none())
+            .finish()
+    }
+}
+
+impl<F, T, E> Future for ResponseFuture<F>
+where
+    F: Future<Output = Result<T, E>>,
+    Error: From<E>,
+{
+    type Output = Result<T, Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+
+        let future = match this.future.as_pin_mut() {
+            Some(future) => future,
+            None => {
+                return Poll::Ready(Err(
+                    Error::new(ErrorKind::ConnectionRefused).with_source("circuit breaker is open")
+                ))
+            }
+        };
+
+        let result = futures_util::ready!(future.poll(cx));
+        *this.probe = false;
+        this.shared.record(this.config, result.is_ok());
+        Poll::Ready(result.map_err(Error::from))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use tower::ServiceExt;
+
+    /// A service that fails on demand, and counts how many requests reached it.
+    #[derive(Clone, Default)]
+    struct MockService {
+        fail: Arc<AtomicBool>,
+        calls: Arc<AtomicUsize>,
+    }
+
+    impl MockService {
+        fn set_failing(&self, fail: bool) {
+            self.fail.store(fail, Ordering::Relaxed);
+        }
+
+        fn calls(&self) -> usize {
+            self.calls.load(Ordering::Relaxed)
+        }
+    }
+
+    impl Service<()> for MockService {
+        type Response = ();
+        type Error = Error;
+        type Future = futures_util::future::Ready<Result<(), Error>>;
+
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, _req: ()) -> Self::Future {
+            self.calls.fetch_add(1, Ordering::Relaxed);
+            futures_util::future::ready(if self.fail.load(Ordering::Relaxed) {
+                Err(Error::new(ErrorKind::ConnectionDropped))
+            } else {
+                Ok(())
+            })
+        }
+    }
+
+    async fn send<S>(service: &mut S) -> Result<(), Error>
+    where
+        S: Service<(), Response = (), Error = Error>,
+    {
+        service.ready().await?.call(()).await
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn state_transitions() {
+        let mock = MockService::default();
+        let layer = CircuitBreakerLayer::new()
+            .failure_threshold(2)
+            .reset_timeout(Duration::from_secs(5))
+            .success_threshold(2);
+        let mut states = layer.subscribe();
+        let mut service = layer.layer(mock.clone());
+
+        // Closed: successes reset the failure count
+        mock.set_failing(true);
+        assert!(send(&mut service).await.is_err());
+        mock.set_failing(false);
+        send(&mut service).await.unwrap();
+        mock.set_failing(true);
+        assert!(send(&mut service).await.is_err());
+        assert_eq!(service.state(), CircuitBreakerState::Closed);
+
+        // Closed -> Open after consecutive failures
+        let err = send(&mut service).await.unwrap_err();
+        assert!(err.has_kind(ErrorKind::ConnectionDropped));
+        assert_eq!(service.state(), CircuitBreakerState::Open);
+        assert!(states.has_changed().unwrap());
+        assert_eq!(*states.borrow_and_update(), CircuitBreakerState::Open);
+
+        // Open: requests are rejected without reaching the inner service
+        let calls = mock.calls();
+        let err = send(&mut service).await.unwrap_err();
+        assert!(err.has_kind(ErrorKind::ConnectionRefused));
+        assert_eq!(mock.calls(), calls);
+
+        // Open -> HalfOpen after the timeout, and a failed probe reopens the circuit
+        tokio::time::advance(Duration::from_secs(5)).await;
+        assert!(send(&mut service)
+            .await
+            .unwrap_err()
+            .has_kind(ErrorKind::ConnectionDropped));
+        assert_eq!(mock.calls(), calls + 1);
+        assert_eq!(service.state(), CircuitBreakerState::Open);
+        assert!(send(&mut service)
+            .await
+            .unwrap_err()
+            .has_kind(ErrorKind::ConnectionRefused));
+
+        // HalfOpen -> Closed after enough successful probes
+        tokio::time::advance(Duration::from_secs(5)).await;
+        mock.set_failing(false);
+        send(&mut service).await.unwrap();
+        assert_eq!(service.state(), CircuitBreakerState::HalfOpen);
+        send(&mut service).await.unwrap();
+        assert_eq!(service.state(), CircuitBreakerState::Closed);
+        assert_eq!(*states.borrow_and_update(), CircuitBreakerState::Closed);
+        assert_eq!(mock.calls(), calls + 3);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn single_probe_in_half_open() {
+        let mock = MockService::default();
+        let layer = CircuitBreakerLayer::new()
+            .failure_threshold(1)
+            .reset_timeout(Duration::from_secs(1));
+        let mut a = layer.layer(mock.clone());
+        let mut b = a.clone();
+
+        mock.set_failing(true);
+        assert!(send(&mut a).await.is_err());
+        assert_eq!(b.state(), CircuitBreakerState::Open);
+
+        tokio::time::advance(Duration::from_secs(1)).await;
+        mock.set_failing(false);
+
+        // The first request is the probe, and the second is rejected while it's in flight
+        let probe = a.ready().await.unwrap().call(());
+        assert_eq!(a.state(), CircuitBreakerState::HalfOpen);
+        let err = send(&mut b).await.unwrap_err();
+        assert!(err.has_kind(ErrorKind::ConnectionRefused));
+
+        probe.await.unwrap();
+        assert_eq!(layer.state(), CircuitBreakerState::Closed);
+        send(&mut b).await.unwrap();
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn dropped_probe_releases_slot() {
+        let mock = MockService::default();
+        let layer = CircuitBreakerLayer::new()
+            .failure_threshold(1)
+            .reset_timeout(Duration::from_secs(1));
+        let mut service = layer.layer(mock.clone());
+
+        mock.set_failing(true);
+        assert!(send(&mut service).await.is_err());
+        tokio::time::advance(Duration::from_secs(1)).await;
+        mock.set_failing(false);
+
+        // Drop the probe without polling it to completion
+        let probe = service.ready().await.unwrap().call(());
+        assert_eq!(service.state(), CircuitBreakerState::HalfOpen);
+        drop(probe);
+
+        // The next request is let through as a new probe
+        let calls = mock.calls();
+        send(&mut service).await.unwrap();
+        assert_eq!(mock.calls(), calls + 1);
+        assert_eq!(service.state(), CircuitBreakerState::Closed);
+    }
+}
@@ -1,13 +1,17 @@
 use crate::data::{RequestEnvelope, ResponseEnvelope};
-use crate::error::BoxError;
+use crate::error::{BoxError, Error};
 use crate::service::api::ApiService;
 use crate::transport::EventStream;
 
 use futures_util::TryFutureExt;
+use std::fmt;
 use std::future::Future;
 use std::marker::PhantomData;
 use std::pin::Pin;
+use std::sync::Arc;
 use std::task::{Context, Poll};
+#[cfg(any(feature = "tokio-tungstenite", feature = "async-tungstenite"))]
+use std::time::Duration;
 use tokio_tower::MakeTransport;
 use tower::Service;
 
@@ -15,14 +19,25 @@ use tower::Service;
 ///
 /// This wraps a [`MakeTransport`] (such as [`TungsteniteConnector`]), describing how to connect to
 /// a websocket sink/stream. This is used for as the inner service for the
-/// [`Reconnect`](tower::reconnect::Reconnect) middleware.
-#[derive(Clone, Debug)]
+/// [`Reconnect`](crate::service::Reconnect) middleware.
+#[derive(Clone)]
 pub struct MakeApiService<M, R> {
     maker: M,
     buffer_size: usize,
+    on_error: Option<Arc<dyn Fn(Error) + Send + Sync>>,
     _req: PhantomData<fn(R)>,
 }
 
+impl<M: fmt::Debug, R> fmt::Debug for MakeApiService<M, R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MakeApiService")
+            .field("maker", &self.maker)
+            .field("buffer_size", &self.buffer_size)
+            .field("on_error", &self.on_error.as_ref().map(|_| "Fn(Error)"))
+            .finish()
+    }
+}
+
 impl<M, R> MakeApiService<M, R>
 where
     M: MakeTransport<R, RequestEnvelope, Item = ResponseEnvelope>,
@@ -32,12 +47,26 @@ where
         Self {
             maker,
             buffer_size,
+            on_error: None,
             _req: PhantomData,
         }
     }
 }
 
 impl<M, R> MakeApiService<M, R> {
+    /// Sets a handler for transport errors that occur outside the request/response flow (e.g.,
+    /// receiving a message that exceeds the configured size limit). These errors are always
+    /// logged, even without a handler.
+    ///
+    /// This is called at most once per connection.
+    pub fn with_error_handler<F>(mut self, on_error: F) -> Self
+    where
+        F: Fn(Error) + Send + Sync + 'static,
+    {
+        self.on_error = Some(Arc::new(on_error));
+        self
+    }
+
     /// Consumes `self`, returning the inner service.
     pub fn into_inner(self) -> M {
         self.maker
@@ -62,32 +91,78 @@ where
 
     fn call(&mut self, request: R) -> Self::Future {
         let buffer_size = self.buffer_size;
+        let on_error = self.on_error.clone();
         Box::pin(
             self.maker
                 .make_transport(request)
-                .map_ok(move |transport| ApiService::new(transport, buffer_size)),
+                .map_ok(move |transport| match on_error {
+                    Some(on_error) => {
+                        ApiService::with_error_handler(transport, buffer_size, move |error| {
+                            tracing::error!(%error, "Transport error");
+                            on_error(error)
+                        })
+                    }
+                    None => ApiService::new(transport, buffer_size),
+                }),
         )
     }
 }
 
+#[cfg(any(feature = "tokio-tungstenite", feature = "async-tungstenite"))]
+fn with_keepalive<T, C>(
+    transport: crate::transport::ApiTransport<T, C>,
+    keepalive: Option<(Duration, Duration)>,
+) -> crate::transport::ApiTransport<T, C>
+where
+    T: futures_sink::Sink<C::Output> + futures_core::TryStream,
+    C: crate::codec::MessageCodec,
+{
+    match keepalive {
+        Some((interval, timeout)) => transport.with_keepalive(interval, timeout),
+        None => transport,
+    }
+}
+
 crate::cfg_feature! {
     #![feature = "tokio-tungstenite"]
 
     use crate::transport::TungsteniteApiTransport;
     use futures_util::FutureExt;
     use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+    use tokio_tungstenite::tungstenite::protocol::WebSocketConfig;
 
     /// A [`Service`] for creating new [`TungsteniteApiTransport`]s.
     ///
-    /// This is used by [`tower::reconnect::Reconnect`] (used in
+    /// This is used by [`Reconnect`](crate::service::Reconnect) (used in
     /// [`ClientBuilder`](crate::ClientBuilder)) for lazily connecting/reconnecting to websockets.
-    #[derive(Debug, Clone)]
-    pub struct TungsteniteConnector;
+    #[derive(Debug, Clone, Default)]
+    pub struct TungsteniteConnector {
+        config: WebSocketConfig,
+        keepalive: Option<(Duration, Duration)>,
+        #[cfg(feature = "proxy")]
+        proxy: Option<crate::service::HttpProxy>,
+        #[cfg(feature = "__tls")]
+        tls: Option<TlsConnector>,
+    }
+}
+
+crate::cfg_feature! {
+    #![feature = "__tls"]
+
+    /// A [`tokio_tungstenite::Connector`], which doesn't implement [`Debug`](fmt::Debug).
+    #[derive(Clone)]
+    pub(crate) struct TlsConnector(pub(crate) tokio_tungstenite::Connector);
+
+    impl fmt::Debug for TlsConnector {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("Connector")
+        }
+    }
 }
 
 crate::cfg_feature! {
     #![feature = "tokio-tungstenite"]
-    use crate::{Error, ErrorKind};
+    use crate::ErrorKind;
 
     impl<R> MakeApiService<TungsteniteConnector, R>
     where
@@ -95,7 +170,59 @@ crate::cfg_feature! {
     {
         /// Creates a new [`MakeApiService`] using [`tokio_tungstenite`] as the underlying transport.
         pub fn new_tungstenite(buffer_size: usize) -> Self {
-            MakeApiService::new(TungsteniteConnector, buffer_size)
+            MakeApiService::new(TungsteniteConnector::new(), buffer_size)
+        }
+    }
+
+    impl TungsteniteConnector {
+        /// Creates a new [`TungsteniteConnector`] using the default websocket config.
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Sets the websocket config (e.g., to change the max message size).
+        pub fn with_config(mut self, config: WebSocketConfig) -> Self {
+            self.config = config;
+            self
+        }
+
+        /// Sends websocket pings on created transports. See
+        /// [`ApiTransport::with_keepalive`](crate::transport::ApiTransport::with_keepalive).
+        pub fn with_keepalive(mut self, interval: Duration, timeout: Duration) -> Self {
+            self.keepalive = Some((interval, timeout));
+            self
+        }
+
+        /// Connects through an HTTP proxy, unless the host is excluded by the proxy's
+        /// [`with_no_proxy`](crate::service::HttpProxy::with_no_proxy) list.
+        ///
+        /// Only `ws://` URLs are supported through the proxy.
+        #[cfg(feature = "proxy")]
+        #[cfg_attr(docsrs, doc(cfg(feature = "proxy")))]
+        pub fn with_proxy(mut self, proxy: crate::service::HttpProxy) -> Self {
+            self.proxy = Some(proxy);
+            self
+        }
+
+        /// Uses a custom TLS connector for `wss://` URLs (e.g., to trust a self-signed
+        /// certificate). By default, the connector is created from the TLS feature that's enabled
+        /// in `tokio-tungstenite`.
+        ///
+        /// This requires one of the `native-tls`, `rustls-tls-native-roots`, or
+        /// `rustls-tls-webpki-roots` features. TLS isn't supported when connecting through an
+        /// HTTP proxy.
+        #[cfg(feature = "__tls")]
+        #[cfg_attr(
+            docsrs,
+            doc(cfg(any(
+                feature = "native-tls",
+                feature = "rustls-tls-native-roots",
+                feature = "rustls-tls-webpki-roots"
+            )))
+        )]
+        pub fn with_tls_connector(mut self, connector: tokio_tungstenite::Connector) -> Self {
+            self.tls = Some(TlsConnector(connector));
+            self
         }
     }
 
@@ -112,11 +239,253 @@ crate::cfg_feature! {
         }
 
         fn call(&mut self, request: R) -> Self::Future {
-            let transport = tokio_tungstenite::connect_async(request).map(|result| match result {
-                Ok((transport, _resp)) => Ok(TungsteniteApiTransport::new_tungstenite(transport)),
+            let keepalive = self.keepalive;
+
+            #[cfg(feature = "proxy")]
+            if let Some(proxy) = &self.proxy {
+                return connect_via_proxy(proxy, request, self.config, keepalive);
+            }
+
+            #[cfg(feature = "__tls")]
+            let transport = tokio_tungstenite::connect_async_tls_with_config(
+                request,
+                Some(self.config),
+                false,
+                self.tls.clone().map(|tls| tls.0),
+            );
+            #[cfg(not(feature = "__tls"))]
+            let transport = tokio_tungstenite::connect_async_with_config(
+                request,
+                Some(self.config),
+                false,
+            );
+
+            let transport = transport.map(move |result| match result {
+                Ok((transport, _resp)) => Ok(with_keepalive(
+                    TungsteniteApiTransport::new_tungstenite(transport),
+                    keepalive,
+                )),
                 Err(e) => Err(Error::new(ErrorKind::ConnectionRefused).with_source(e)),
             });
             Box::pin(transport)
         }
     }
 }
+
+#[cfg(feature = "proxy")]
+fn connect_via_proxy<R>(
+    proxy: &crate::service::HttpProxy,
+    request: R,
+    config: tokio_tungstenite::tungstenite::protocol::WebSocketConfig,
+    keepalive: Option<(Duration, Duration)>,
+) -> Pin<Box<dyn Future<Output = Result<crate::transport::TungsteniteApiTransport, Error>> + Send>>
+where
+    R: tokio_tungstenite::tungstenite::client::IntoClientRequest + Unpin + Send + 'static,
+{
+    use crate::transport::TungsteniteApiTransport;
+    use crate::ErrorKind;
+    use tokio_tungstenite::MaybeTlsStream;
+
+    let connection_error = |e: BoxError| Error::new(ErrorKind::ConnectionRefused).with_source(e);
+
+    let request = match request.into_client_request() {
+        Ok(request) => request,
+        Err(e) => return Box::pin(futures_util::future::ready(Err(connection_error(e.into())))),
+    };
+
+    let uri = request.uri();
+    let host = uri.host().unwrap_or_default().to_owned();
+
+    if proxy.is_bypassed(&host) {
+        return Box::pin(
+            tokio_tungstenite::connect_async_with_config(request, Some(config), false).map(
+                move |result| match result {
+                    Ok((transport, _resp)) => Ok(with_keepalive(
+                        TungsteniteApiTransport::new_tungstenite(transport),
+                        keepalive,
+                    )),
+                    Err(e) => Err(connection_error(e.into())),
+                },
+            ),
+        );
+    }
+
+    if uri.scheme_str() != Some("ws") {
+        return Box::pin(futures_util::future::ready(Err(connection_error(
+            "only ws:// URLs are supported when connecting through a proxy".into(),
+        ))));
+    }
+
+    let port = uri.port_u16().unwrap_or(80);
+    let proxy = proxy.clone();
+
+    Box::pin(async move {
+        let stream = proxy
+            .connect(&host, port)
+            .await
+            .map_err(|e| connection_error(e.into()))?;
+
+        match tokio_tungstenite::client_async_with_config(
+            request,
+            MaybeTlsStream::Plain(stream),
+            Some(config),
+        )
+        .await
+        {
+            Ok((transport, _resp)) => Ok(with_keepalive(
+                TungsteniteApiTransport::new_tungstenite(transport),
+                keepalive,
+            )),
+            Err(e) => Err(connection_error(e.into())),
+        }
+    })
+}
+
+crate::cfg_feature! {
+    #![feature = "async-tungstenite"]
+
+    use crate::transport::AsyncTungsteniteApiTransport;
+    use futures_util::io::{AsyncRead, AsyncWrite};
+    use std::io;
+
+    /// A [`Service`] for creating new [`AsyncTungsteniteApiTransport`]s, using the
+    /// runtime-agnostic [`async_tungstenite`] library.
+    ///
+    /// The connection is opened by the `connect` function, which is given a `host:port` address
+    /// and should return a connection (e.g., a TCP stream from `async_std` or `smol`). The
+    /// connection is responsible for TLS, if needed. The background task that reads from the
+    /// connection runs on the current Tokio runtime if there is one, or on `async-std` otherwise
+    /// (which requires the `async-std` feature).
+    ///
+    /// This is used by [`ClientBuilder::build_async_tungstenite`](crate::ClientBuilder::build_async_tungstenite).
+    #[derive(Clone)]
+    pub struct AsyncTungsteniteConnector<F> {
+        connect: F,
+        config: async_tungstenite::tungstenite::protocol::WebSocketConfig,
+        keepalive: Option<(Duration, Duration)>,
+    }
+
+    impl<F> std::fmt::Debug for AsyncTungsteniteConnector<F> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("AsyncTungsteniteConnector").finish_non_exhaustive()
+        }
+    }
+
+    impl<F> AsyncTungsteniteConnector<F> {
+        /// Creates a new [`AsyncTungsteniteConnector`] from a function that opens a connection.
+        pub fn new(connect: F) -> Self {
+            Self {
+                connect,
+                config: Default::default(),
+                keepalive: None,
+            }
+        }
+
+        /// Sets the websocket config (e.g., to change the max message size).
+        pub fn with_config(
+            mut self,
+            config: async_tungstenite::tungstenite::protocol::WebSocketConfig,
+        ) -> Self {
+            self.config = config;
+            self
+        }
+
+        /// Sends websocket pings on created transports. See
+        /// [`ApiTransport::with_keepalive`](crate::transport::ApiTransport::with_keepalive).
+        pub fn with_keepalive(mut self, interval: Duration, timeout: Duration) -> Self {
+            self.keepalive = Some((interval, timeout));
+            self
+        }
+    }
+
+    impl<F, Fut, S, R> Service<R> for AsyncTungsteniteConnector<F>
+    where
+        F: Fn(String) -> Fut,
+        Fut: Future<Output = io::Result<S>> + Send + 'static,
+        S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+        R: async_tungstenite::tungstenite::client::IntoClientRequest + Unpin + Send + 'static,
+    {
+        type Response = AsyncTungsteniteApiTransport<S>;
+        type Error = crate::Error;
+        type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, request: R) -> Self::Future {
+            use crate::ErrorKind;
+
+            let connection_error = |e: BoxError| Error::new(ErrorKind::ConnectionRefused).with_source(e);
+
+            let request = match request.into_client_request() {
+                Ok(request) => request,
+                Err(e) => return Box::pin(futures_util::future::ready(Err(connection_error(e.into())))),
+            };
+
+            let uri = request.uri();
+            let default_port = if uri.scheme_str() == Some("wss") { 443 } else { 80 };
+            let address = format!(
+                "{}:{}",
+                uri.host().unwrap_or_default(),
+                uri.port_u16().unwrap_or(default_port)
+            );
+            let connection = (self.connect)(address);
+            let config = self.config;
+            let keepalive = self.keepalive;
+
+            Box::pin(async move {
+                let stream = connection.await.map_err(|e| connection_error(e.into()))?;
+                match async_tungstenite::client_async_with_config(request, stream, Some(config)).await {
+                    Ok((transport, _resp)) => Ok(with_keepalive(
+                        AsyncTungsteniteApiTransport::new_tungstenite(transport),
+                        keepalive,
+                    )),
+                    Err(e) => Err(connection_error(e.into())),
+                }
+            })
+        }
+    }
+}
+
+crate::cfg_feature! {
+    #![feature = "wasm"]
+
+    use crate::transport::{WasmApiTransport, WasmWebSocket};
+    use futures_util::future::{self, Ready};
+
+    /// A [`Service`] for creating new [`WasmApiTransport`]s, using the browser's `WebSocket` API
+    /// via [`gloo_net`].
+    ///
+    /// This is the `wasm32-unknown-unknown` equivalent of [`TungsteniteConnector`].
+    #[derive(Debug, Clone)]
+    pub struct WasmConnector;
+
+    impl<R> MakeApiService<WasmConnector, R>
+    where
+        R: AsRef<str>,
+    {
+        /// Creates a new [`MakeApiService`] using the browser's `WebSocket` API as the underlying
+        /// transport.
+        pub fn new_wasm(buffer_size: usize) -> Self {
+            MakeApiService::new(WasmConnector, buffer_size)
+        }
+    }
+
+    impl<R> Service<R> for WasmConnector
+    where
+        R: AsRef<str>,
+    {
+        type Response = WasmApiTransport;
+        type Error = crate::Error;
+        type Future = Ready<Result<Self::Response, Self::Error>>;
+
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, request: R) -> Self::Future {
+            future::ready(WasmWebSocket::open(request.as_ref()).map(WasmApiTransport::new_wasm))
+        }
+    }
+}
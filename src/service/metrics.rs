@@ -0,0 +1,318 @@
+use crate::data::{RequestEnvelope, ResponseEnvelope};
+use crate::error::{Error, ErrorKind};
+
+use metrics::{counter, histogram};
+use pin_project_lite::pin_project;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tokio::time::Instant;
+use tower::{Layer, Service};
+
+const REQUESTS_SENT: &str = "vtubestudio_requests_sent_total";
+const RESPONSES_RECEIVED: &str = "vtubestudio_responses_received_total";
+const ERRORS: &str = "vtubestudio_errors_total";
+const RECONNECTS: &str = "vtubestudio_reconnects_total";
+const REQUEST_DURATION: &str = "vtubestudio_request_duration_seconds";
+
+/// A [`Layer`] that produces a [`Metrics`] service.
+///
+/// Services produced by this layer record the following metrics using the [`metrics`] crate
+/// facade, which can be exported using any compatible exporter:
+///
+/// * `vtubestudio_requests_sent_total` (counter, labeled by `message_type`)
+/// * `vtubestudio_responses_received_total` (counter, labeled by `message_type`), including
+///   responses containing an [`ApiError`](crate::data::ApiError)
+/// * `vtubestudio_errors_total` (counter, labeled by `message_type` and `kind`, the [`ErrorKind`]
+///   of the error)
+/// * `vtubestudio_request_duration_seconds` (histogram, labeled by `message_type`)
+///
+/// This is already included in services created by [`ClientBuilder`](crate::ClientBuilder), below
+/// the retry and authentication layers, so that retried requests and authentication requests are
+/// also counted. Clients created with [`build_connector`](crate::ClientBuilder::build_connector)
+/// also record reconnection attempts as `vtubestudio_reconnects_total` (counter).
+///
+/// [`metrics`]: https://docs.rs/metrics/0.24
+///
+/// # Example
+///
+/// ```
+/// use tower::ServiceBuilder;
+/// use vtubestudio::data::{RequestEnvelope, ResponseEnvelope};
+/// use vtubestudio::error::Error;
+/// use vtubestudio::service::MetricsLayer;
+///
+/// let service = ServiceBuilder::new()
+///     .layer(MetricsLayer::new())
+///     .service_fn(|req: RequestEnvelope| async move {
+///         // Send the request somewhere
+///         # Ok::<_, Error>(ResponseEnvelope::new(&vtubestudio::data::ApiStateResponse::default())?)
+///     });
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MetricsLayer {
+    _priv: (),
+}
+
+impl MetricsLayer {
+    /// Creates a new [`MetricsLayer`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<S> Layer<S> for MetricsLayer {
+    type Service = Metrics<S>;
+
+    fn layer(&self, service: S) -> Self::Service {
+        Metrics::new(service)
+    }
+}
+
+/// A [`Service`] that records metrics for each request.
+///
+/// See [`MetricsLayer`] for details.
+#[derive(Debug, Clone)]
+pub struct Metrics<S> {
+    service: S,
+}
+
+impl<S> Metrics<S> {
+    /// Creates a new [`Metrics`] service wrapping the given service.
+    pub fn new(service: S) -> Self {
+        Self { service }
+    }
+}
+
+impl<S> Service<RequestEnvelope> for Metrics<S>
+where
+    S: Service<RequestEnvelope, Response = ResponseEnvelope, Error = Error>,
+{
+    type Response = ResponseEnvelope;
+    type Error = Error;
+    type Future = ResponseFuture<S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: RequestEnvelope) -> Self::Future {
+        let message_type = req.message_type.as_str().to_owned();
+        counter!(REQUESTS_SENT, "message_type" => message_type.clone()).increment(1);
+
+        ResponseFuture {
+            future: self.service.call(req),
+            message_type,
+            start: Instant::now(),
+        }
+    }
+}
+
+pin_project! {
+    /// Response future for [`Metrics`].
+    pub struct ResponseFuture<F> {
+        #[pin]
+        future: F,
+        message_type: String,
+        start: Instant,
+    }
+}
+
+impl<F> fmt::Debug for ResponseFuture<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResponseFuture")
+            .field("message_type", &self.message_type)
+            .finish()
+    }
+}
+
+impl<F> Future for ResponseFuture<F>
+where
+    F: Future<Output = Result<ResponseEnvelope, Error>>,
+{
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let result = match this.future.poll(cx) {
+            Poll::Ready(result) => result,
+            Poll::Pending => return Poll::Pending,
+        };
+
+        let message_type = this.message_type.clone();
+        histogram!(REQUEST_DURATION, "message_type" => message_type.clone())
+            .record(this.start.elapsed().as_secs_f64());
+
+        let error_kind = match &result {
+            Ok(resp) => {
+                counter!(RESPONSES_RECEIVED, "message_type" => message_type.clone()).increment(1);
+                match &resp.data {
+                    Err(e) => Some(kind_label(Error::from(e.clone()).kind())),
+                    Ok(_) => None,
+                }
+            }
+            Err(e) => Some(kind_label(e.kind())),
+        };
+
+        if let Some(kind) = error_kind {
+            counter!(ERRORS, "message_type" => message_type, "kind" => kind).increment(1);
+        }
+
+        Poll::Ready(result)
+    }
+}
+
+fn kind_label(kind: &ErrorKind) -> String {
+    format!("{:?}", kind)
+}
+
+/// Records a reconnection attempt.
+pub(crate) fn record_reconnect() {
+    counter!(RECONNECTS).increment(1);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::{ApiError, ErrorId, StatisticsRequest, StatisticsResponse};
+
+    use metrics::{
+        Counter, CounterFn, Gauge, Histogram, HistogramFn, Key, KeyName, Metadata, Recorder,
+        SharedString, Unit,
+    };
+    use std::collections::BTreeMap;
+    use std::sync::{Arc, Mutex};
+    use tower::ServiceExt;
+
+    type Values = Arc<Mutex<BTreeMap<String, f64>>>;
+
+    // A minimal recorder that stores the sum of each metric, keyed by name and labels
+    #[derive(Debug, Default)]
+    struct TestRecorder {
+        values: Values,
+    }
+
+    struct Handle {
+        key: String,
+        values: Values,
+    }
+
+    impl Handle {
+        fn add(&self, value: f64) {
+            *self
+                .values
+                .lock()
+                .unwrap()
+                .entry(self.key.clone())
+                .or_default() += value;
+        }
+    }
+
+    impl CounterFn for Handle {
+        fn increment(&self, value: u64) {
+            self.add(value as f64);
+        }
+
+        fn absolute(&self, _: u64) {}
+    }
+
+    impl HistogramFn for Handle {
+        fn record(&self, _: f64) {
+            // Latencies aren't deterministic, so only count them
+            self.add(1.0);
+        }
+    }
+
+    impl TestRecorder {
+        fn handle(&self, key: &Key) -> Arc<Handle> {
+            let labels = key
+                .labels()
+                .map(|label| format!("{}={}", label.key(), label.value()))
+                .collect::<Vec<_>>();
+
+            Arc::new(Handle {
+                key: format!("{}{{{}}}", key.name(), labels.join(",")),
+                values: Arc::clone(&self.values),
+            })
+        }
+    }
+
+    impl Recorder for TestRecorder {
+        fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+        fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+        fn describe_histogram(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+        fn register_counter(&self, key: &Key, _: &Metadata<'_>) -> Counter {
+            Counter::from_arc(self.handle(key))
+        }
+
+        fn register_gauge(&self, _: &Key, _: &Metadata<'_>) -> Gauge {
+            Gauge::noop()
+        }
+
+        fn register_histogram(&self, key: &Key, _: &Metadata<'_>) -> Histogram {
+            Histogram::from_arc(self.handle(key))
+        }
+    }
+
+    #[tokio::test]
+    async fn record_metrics() -> Result<(), Error> {
+        let recorder = TestRecorder::default();
+        let _guard = metrics::set_default_local_recorder(&recorder);
+
+        let mut responses = vec![
+            Err(Error::new(ErrorKind::ConnectionDropped)),
+            Ok(ResponseEnvelope {
+                data: Err(ApiError {
+                    error_id: ErrorId::REQUEST_REQUIRES_AUTHENTICATION,
+                    message: String::new(),
+                }),
+                ..Default::default()
+            }),
+            Ok(ResponseEnvelope::new(&StatisticsResponse::default())?),
+        ];
+
+        let mut service = MetricsLayer::new().layer(tower::service_fn(move |_| {
+            let resp = responses.pop().unwrap();
+            async move { resp }
+        }));
+
+        for _ in 0..3 {
+            let req = RequestEnvelope::new(&StatisticsRequest {})?;
+            let _ = service.ready().await?.call(req).await;
+        }
+        record_reconnect();
+
+        let values = recorder.values.lock().unwrap().clone();
+        let expected = [
+            (
+                "vtubestudio_errors_total{message_type=StatisticsRequest,kind=Api}",
+                1.0,
+            ),
+            (
+                "vtubestudio_errors_total{message_type=StatisticsRequest,kind=ConnectionDropped}",
+                1.0,
+            ),
+            ("vtubestudio_reconnects_total{}", 1.0),
+            (
+                "vtubestudio_request_duration_seconds{message_type=StatisticsRequest}",
+                3.0,
+            ),
+            (
+                "vtubestudio_requests_sent_total{message_type=StatisticsRequest}",
+                3.0,
+            ),
+            (
+                "vtubestudio_responses_received_total{message_type=StatisticsRequest}",
+                2.0,
+            ),
+        ]
+        .iter()
+        .map(|(key, value)| (key.to_string(), *value))
+        .collect::<BTreeMap<_, _>>();
+        assert_eq!(values, expected);
+
+        Ok(())
+    }
+}
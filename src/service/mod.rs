@@ -1,17 +1,48 @@
 pub(crate) mod api;
 pub(crate) mod auth;
+pub(crate) mod circuit_breaker;
 pub(crate) mod maker;
+#[cfg(feature = "metrics")]
+pub(crate) mod metrics;
+pub(crate) mod multiplex;
+#[cfg(feature = "proxy")]
+pub(crate) mod proxy;
+pub(crate) mod rate_limit;
+pub(crate) mod reconnect;
 pub(crate) mod retry;
+#[cfg(feature = "tracing")]
+pub(crate) mod trace;
 
-use crate::data::{Request, RequestEnvelope, ResponseEnvelope};
+use crate::data::{
+    ApiStateRequest, EnumString, OpaqueValue, Request, RequestEnvelope, ResponseEnvelope,
+    ResponseMeta,
+};
 use crate::error::Error;
+use std::time::Duration;
+use tokio::time::Instant;
 use tower::util::BoxCloneService;
 use tower::{Service, ServiceExt};
 
-pub use crate::service::api::ApiService;
+pub use crate::service::api::{ApiService, IdTagger};
 pub use crate::service::auth::{Authentication, AuthenticationLayer, ResponseWithToken};
+pub use crate::service::circuit_breaker::{
+    CircuitBreaker, CircuitBreakerLayer, CircuitBreakerState,
+    ResponseFuture as CircuitBreakerFuture,
+};
 pub use crate::service::maker::MakeApiService;
-pub use crate::service::retry::RetryPolicy;
+pub use crate::service::rate_limit::{RateLimit, RateLimitLayer};
+pub use crate::service::reconnect::{Reconnect, ResponseFuture as ReconnectFuture};
+pub use crate::service::retry::{RetryDecision, RetryPolicy};
+
+crate::cfg_feature! {
+    #![feature = "metrics"]
+    pub use crate::service::metrics::{Metrics, MetricsLayer, ResponseFuture as MetricsFuture};
+}
+
+crate::cfg_feature! {
+    #![feature = "tracing"]
+    pub use crate::service::trace::{ResponseFuture as TracingFuture, Tracing, TracingLayer};
+}
 
 crate::cfg_feature! {
     #![feature = "tokio-tungstenite"]
@@ -19,6 +50,46 @@ crate::cfg_feature! {
     pub use crate::service::maker::TungsteniteConnector;
 }
 
+crate::cfg_feature! {
+    #![feature = "proxy"]
+    pub use crate::service::proxy::HttpProxy;
+}
+
+crate::cfg_feature! {
+    #![feature = "async-tungstenite"]
+    pub use crate::service::api::AsyncTungsteniteApiService;
+    pub use crate::service::maker::AsyncTungsteniteConnector;
+}
+
+crate::cfg_feature! {
+    #![feature = "wasm"]
+    pub use crate::service::maker::WasmConnector;
+}
+
+/// The layer used by [`ClientBuilder`](crate::ClientBuilder) to record metrics, which does nothing
+/// unless the `metrics` feature is enabled.
+#[cfg(feature = "metrics")]
+pub(crate) fn client_metrics_layer() -> MetricsLayer {
+    MetricsLayer::new()
+}
+
+#[cfg(not(feature = "metrics"))]
+pub(crate) fn client_metrics_layer() -> tower::layer::util::Identity {
+    tower::layer::util::Identity::new()
+}
+
+/// The layer used by [`ClientBuilder`](crate::ClientBuilder) to open a span per request, which does
+/// nothing unless the `tracing` feature is enabled.
+#[cfg(feature = "tracing")]
+pub(crate) fn client_tracing_layer() -> TracingLayer {
+    TracingLayer::new()
+}
+
+#[cfg(not(feature = "tracing"))]
+pub(crate) fn client_tracing_layer() -> tower::layer::util::Identity {
+    tower::layer::util::Identity::new()
+}
+
 /// A [`Clone`]able [`Service`] that is compatible with [`Client`](crate::client::Client).
 pub type BoxCloneApiService = BoxCloneService<RequestEnvelope, ResponseEnvelope, Error>;
 
@@ -54,3 +125,89 @@ where
 
     resp.parse::<Req::Response>()
 }
+
+/// Submit a request to the underlying service and parse the response, also returning the response
+/// metadata.
+///
+/// This is the same as [`Client::send_with_meta`](crate::Client::send_with_meta) but as a
+/// standalone function.
+pub async fn send_request_with_meta<S, Req: Request>(
+    service: &mut S,
+    data: &Req,
+) -> Result<(Req::Response, ResponseMeta), Error>
+where
+    S: Service<RequestEnvelope, Response = ResponseEnvelope>,
+    Error: From<S::Error>,
+{
+    let msg = RequestEnvelope::new(data)?;
+
+    let resp = service.ready().await?.call(msg).await?;
+
+    resp.parse_with_meta::<Req::Response>()
+}
+
+/// Submit a request with an arbitrary message type and data to the underlying service, returning
+/// the unparsed response.
+///
+/// This is the same as [`Client::send_raw`](crate::Client::send_raw) but as a standalone function.
+pub async fn send_raw_request<S>(
+    service: &mut S,
+    message_type: &str,
+    data: serde_json::Value,
+) -> Result<ResponseEnvelope, Error>
+where
+    S: Service<RequestEnvelope, Response = ResponseEnvelope>,
+    Error: From<S::Error>,
+{
+    let msg = RequestEnvelope {
+        message_type: EnumString::new_from_str(message_type.to_owned()),
+        data: OpaqueValue::new(&data)?,
+        ..RequestEnvelope::default()
+    };
+
+    Ok(service.ready().await?.call(msg).await?)
+}
+
+/// Send an [`ApiStateRequest`] to the underlying service and return the round-trip time.
+///
+/// This is the same as [`Client::ping`](crate::Client::ping) but as a standalone function.
+pub async fn send_ping<S>(service: &mut S) -> Result<Duration, Error>
+where
+    S: Service<RequestEnvelope, Response = ResponseEnvelope>,
+    Error: From<S::Error>,
+{
+    let start = Instant::now();
+    send_request(service, &ApiStateRequest {}).await?;
+    Ok(start.elapsed())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::{ApiStateResponse, ResponseEnvelope};
+    use crate::error::ErrorKind;
+
+    #[tokio::test(start_paused = true)]
+    async fn ping() -> Result<(), Error> {
+        let mut service = tower::service_fn(|req: RequestEnvelope| async move {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            let resp = ResponseEnvelope::new(&ApiStateResponse::default())?;
+            Ok::<_, Error>(resp.with_id(req.request_id.unwrap_or_default()))
+        });
+
+        let latency = send_ping(&mut service).await?;
+        assert_eq!(latency, Duration::from_millis(10));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn ping_disconnected() {
+        let mut service = tower::service_fn(|_: RequestEnvelope| async move {
+            Err::<ResponseEnvelope, _>(Error::new(ErrorKind::ConnectionDropped))
+        });
+
+        let err = send_ping(&mut service).await.unwrap_err();
+        assert!(err.has_kind(ErrorKind::ConnectionDropped));
+    }
+}
@@ -0,0 +1,225 @@
+use crate::error::{BoxError, Error, ErrorKind};
+
+use futures_channel::{mpsc, oneshot};
+use futures_core::{Stream, TryStream};
+use futures_sink::Sink;
+use pin_project_lite::pin_project;
+use std::collections::VecDeque;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+use tokio_tower::multiplex::TagStore;
+
+// How many requests to send before yielding, so a busy client doesn't starve other tasks
+const YIELD_EVERY: usize = 24;
+
+struct ClientRequest<Req, Resp> {
+    request: Req,
+    response: oneshot::Sender<Resp>,
+    span: tracing::Span,
+}
+
+/// Sends requests to a [`Dispatcher`] and waits for the matching responses.
+///
+/// This is equivalent to `tokio_tower::multiplex::Client`, except that the [`Dispatcher`] is
+/// returned to the caller instead of being spawned on a Tokio runtime.
+pub(crate) struct Client<Req, Resp> {
+    requests: mpsc::Sender<ClientRequest<Req, Resp>>,
+}
+
+impl<Req, Resp> fmt::Debug for Client<Req, Resp> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Client")
+            .field("closed", &self.requests.is_closed())
+            .finish()
+    }
+}
+
+impl<Req, Resp> Client<Req, Resp>
+where
+    Req: Send + 'static,
+    Resp: Send + 'static,
+{
+    /// Creates a new [`Client`] and the [`Dispatcher`] that drives the transport.
+    pub(crate) fn new<T>(transport: T) -> (Self, Dispatcher<T, Req>)
+    where
+        T: Sink<Req> + TryStream<Ok = Resp> + TagStore<Req, Resp>,
+    {
+        // Each sender has one guaranteed slot, so this only holds the request being handed off
+        let (tx, rx) = mpsc::channel(0);
+
+        let dispatcher = Dispatcher {
+            requests: rx,
+            pending: VecDeque::new(),
+            transport,
+            finish: false,
+            rx_only: false,
+        };
+
+        (Self { requests: tx }, dispatcher)
+    }
+
+    pub(crate) fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Error>> {
+        self.requests
+            .poll_ready(cx)
+            .map_err(|_| Error::new(ErrorKind::ConnectionDropped))
+    }
+
+    pub(crate) fn call(
+        &mut self,
+        request: Req,
+    ) -> impl Future<Output = Result<Resp, Error>> + Send + 'static {
+        let (tx, rx) = oneshot::channel();
+        let result = self.requests.start_send(ClientRequest {
+            request,
+            response: tx,
+            span: tracing::Span::current(),
+        });
+
+        async move {
+            match result {
+                Ok(()) => rx
+                    .await
+                    .map_err(|_| Error::new(ErrorKind::ConnectionDropped)),
+                Err(e) if e.is_disconnected() => Err(Error::new(ErrorKind::ConnectionDropped)),
+                Err(_) => Err(Error::new(ErrorKind::TransportFull)),
+            }
+        }
+    }
+}
+
+struct Pending<Tag, Resp> {
+    tag: Tag,
+    response: oneshot::Sender<Resp>,
+    span: tracing::Span,
+}
+
+pin_project! {
+    /// Sends requests from a [`Client`] through the transport, and forwards each response to the
+    /// request with the matching tag. Completes once every [`Client`] has been dropped and all
+    /// pending responses have been received, or when the transport fails.
+    pub(crate) struct Dispatcher<T, Req>
+    where
+        T: TryStream,
+        T: TagStore<Req, <T as TryStream>::Ok>,
+    {
+        requests: mpsc::Receiver<ClientRequest<Req, T::Ok>>,
+        pending: VecDeque<Pending<T::Tag, T::Ok>>,
+        #[pin]
+        transport: T,
+        // Whether all clients have been dropped
+        finish: bool,
+        // Whether the sink has been closed, and we're only waiting for responses
+        rx_only: bool,
+    }
+}
+
+impl<T, Req> Future for Dispatcher<T, Req>
+where
+    T: Sink<Req> + TryStream + TagStore<Req, <T as TryStream>::Ok>,
+    BoxError: From<<T as Sink<Req>>::Error> + From<<T as TryStream>::Error>,
+{
+    type Output = Result<(), Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut this = self.project();
+        let send_error = |e| Error::from(tokio_tower::Error::<T, Req>::BrokenTransportSend(e));
+
+        let mut sent = 0;
+        while !*this.finish {
+            // Responses still need to be polled below, even if the sink isn't ready
+            match this.transport.as_mut().poll_ready(cx) {
+                Poll::Ready(Ok(())) => {}
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(send_error(e))),
+                Poll::Pending => break,
+            }
+
+            match Pin::new(&mut *this.requests).poll_next(cx) {
+                Poll::Ready(Some(ClientRequest {
+                    mut request,
+                    response,
+                    span,
+                })) => {
+                    let tag = this.transport.as_mut().assign_tag(&mut request);
+                    span.in_scope(|| tracing::trace!("Sending request"));
+                    this.transport
+                        .as_mut()
+                        .start_send(request)
+                        .map_err(send_error)?;
+
+                    this.pending.push_back(Pending {
+                        tag,
+                        response,
+                        span,
+                    });
+
+                    sent += 1;
+                    if sent == YIELD_EVERY {
+                        cx.waker().wake_by_ref();
+                        break;
+                    }
+                }
+                Poll::Ready(None) => *this.finish = true,
+                Poll::Pending => break,
+            }
+        }
+
+        if !this.pending.is_empty() && !*this.rx_only {
+            if *this.finish {
+                // No more requests will be sent, so close the sink (which also flushes it)
+                if this
+                    .transport
+                    .as_mut()
+                    .poll_close(cx)
+                    .map_err(send_error)?
+                    .is_ready()
+                {
+                    *this.rx_only = true;
+                }
+            } else {
+                // Responses still need to be polled, even if flushing isn't done yet
+                let _ = this.transport.as_mut().poll_flush(cx).map_err(send_error)?;
+            }
+        }
+
+        while !this.pending.is_empty() {
+            let response = match ready!(this.transport.as_mut().try_poll_next(cx)) {
+                Some(Ok(response)) => response,
+                Some(Err(e)) => {
+                    return Poll::Ready(Err(Error::from(
+                        tokio_tower::Error::<T, Req>::BrokenTransportRecv(Some(e)),
+                    )))
+                }
+                None => return Poll::Ready(Err(Error::new(ErrorKind::ConnectionDropped))),
+            };
+
+            let tag = this.transport.as_mut().finish_tag(&response);
+            let index = match this.pending.iter().position(|pending| pending.tag == tag) {
+                Some(index) => index,
+                None => return Poll::Ready(Err(Error::new(ErrorKind::Desynchronized))),
+            };
+
+            // Responses usually arrive in order, so the pending request is likely near the front
+            let pending = this
+                .pending
+                .swap_remove_front(index)
+                .expect("index is in bounds");
+            pending
+                .span
+                .in_scope(|| tracing::trace!("Received response"));
+
+            // The caller may have stopped waiting for the response
+            let _ = pending.response.send(response);
+        }
+
+        if *this.finish && this.pending.is_empty() {
+            if !*this.rx_only {
+                ready!(this.transport.poll_close(cx)).map_err(send_error)?;
+            }
+            return Poll::Ready(Ok(()));
+        }
+
+        Poll::Pending
+    }
+}
@@ -0,0 +1,318 @@
+use crate::runtime::{self, Sleep};
+use std::convert::TryFrom;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tokio::time::Instant;
+use tower::{Layer, Service};
+
+/// A [`Layer`] that produces a [`RateLimit`] service.
+///
+/// Unlike [`tower::limit::RateLimitLayer`], services produced by this layer (and all of their
+/// clones) share a single token bucket, so the limit still applies when the service is cloned
+/// (e.g., by a [`Client`](crate::Client)), and requests are spread evenly across the period
+/// instead of being sent all at once at the start of each period.
+///
+/// [`tower::limit::RateLimitLayer`]: https://docs.rs/tower/0.4/tower/limit/struct.RateLimitLayer.html
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+/// use tower::ServiceBuilder;
+/// use vtubestudio::data::{RequestEnvelope, ResponseEnvelope};
+/// use vtubestudio::error::Error;
+/// use vtubestudio::service::RateLimitLayer;
+///
+/// let service = ServiceBuilder::new()
+///     // Allow up to 60 requests per second, with bursts of up to 10 requests
+///     .layer(RateLimitLayer::new(60, Duration::from_secs(1)).with_burst(10))
+///     .service_fn(|req: RequestEnvelope| async move {
+///         // Send the request somewhere
+///         # Ok::<_, Error>(ResponseEnvelope::new(&vtubestudio::data::ApiStateResponse::default())?)
+///     });
+/// ```
+#[derive(Debug, Clone)]
+pub struct RateLimitLayer {
+    interval: Duration,
+    burst: u64,
+}
+
+impl RateLimitLayer {
+    /// Creates a new [`RateLimitLayer`] that allows `requests` requests per `per` duration.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `requests` is zero.
+    pub fn new(requests: u64, per: Duration) -> Self {
+        assert!(requests > 0, "rate limit must allow at least one request");
+
+        let interval = per.as_nanos() / u128::from(requests);
+
+        Self {
+            interval: Duration::from_nanos(u64::try_from(interval).unwrap_or(u64::MAX)),
+            burst: 1,
+        }
+    }
+
+    /// The number of requests that can be sent immediately after a period of inactivity.
+    ///
+    /// The default value is 1, meaning requests are evenly spaced. Values less than 1 are treated
+    /// as 1.
+    pub fn with_burst(mut self, burst: u64) -> Self {
+        self.burst = burst.max(1);
+        self
+    }
+}
+
+impl<S> Layer<S> for RateLimitLayer {
+    type Service = RateLimit<S>;
+
+    fn layer(&self, service: S) -> Self::Service {
+        RateLimit::new(service, self.interval, self.burst)
+    }
+}
+
+/// A token bucket, tracked as the time at which the bucket will be full again.
+#[derive(Debug)]
+struct TokenBucket {
+    interval: Duration,
+    /// How far ahead of the current time `full_at` is allowed to be, while still having tokens.
+    tolerance: Duration,
+    full_at: Instant,
+}
+
+impl TokenBucket {
+    /// Takes a token, or returns the time at which the next token will be available.
+    fn try_acquire(&mut self, now: Instant) -> Result<(), Instant> {
+        let full_at = self.full_at.max(now);
+
+        match full_at.checked_sub(self.tolerance) {
+            Some(available_at) if available_at > now => Err(available_at),
+            _ => {
+                self.full_at = full_at + self.interval;
+                Ok(())
+            }
+        }
+    }
+}
+
+/// A [`Service`] that limits the rate of requests sent to the inner service, using a token bucket.
+///
+/// When no tokens are available, [`poll_ready`](Service::poll_ready) returns [`Poll::Pending`]
+/// until the next token is available, rather than queueing the request internally.
+///
+/// Cloned services share the same token bucket.
+pub struct RateLimit<S> {
+    inner: S,
+    bucket: Arc<Mutex<TokenBucket>>,
+    has_token: bool,
+    sleep: Option<Sleep>,
+}
+
+impl<S> RateLimit<S> {
+    /// Creates a new [`RateLimit`] service that allows one request per `interval`, with bursts of
+    /// up to `burst` requests.
+    pub fn new(inner: S, interval: Duration, burst: u64) -> Self {
+        let burst = burst.max(1);
+        let tolerance = interval
+            .checked_mul(u32::try_from(burst - 1).unwrap_or(u32::MAX))
+            .unwrap_or(Duration::MAX);
+
+        Self {
+            inner,
+            bucket: Arc::new(Mutex::new(TokenBucket {
+                interval,
+                tolerance,
+                full_at: Instant::now(),
+            })),
+            has_token: false,
+            sleep: None,
+        }
+    }
+
+    /// Consumes `self`, returning the inner service.
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, TokenBucket> {
+        self.bucket.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl<S: Clone> Clone for RateLimit<S> {
+    fn clone(&self) -> Self {
+        // Tokens and timers aren't shared between clones, only the bucket itself
+        Self {
+            inner: self.inner.clone(),
+            bucket: Arc::clone(&self.bucket),
+            has_token: false,
+            sleep: None,
+        }
+    }
+}
+
+impl<S: fmt::Debug> fmt::Debug for RateLimit<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RateLimit")
+            .field("inner", &self.inner)
+            .field("bucket", &self.bucket)
+            .field("has_token", &self.has_token)
+            .finish()
+    }
+}
+
+impl<S, Request> Service<Request> for RateLimit<S>
+where
+    S: Service<Request>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        while !self.has_token {
+            if let Some(sleep) = self.sleep.as_mut() {
+                if sleep.as_mut().poll(cx).is_pending() {
+                    return Poll::Pending;
+                }
+            }
+
+            let result = self.lock().try_acquire(Instant::now());
+            match result {
+                Ok(()) => {
+                    self.has_token = true;
+                    self.sleep = None;
+                }
+                Err(available_at) => {
+                    let delay = available_at.saturating_duration_since(Instant::now());
+                    self.sleep = Some(runtime::sleep(delay));
+                }
+            }
+        }
+
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request) -> Self::Future {
+        assert!(
+            self.has_token,
+            "RateLimit::call was called before poll_ready returned Ready"
+        );
+
+        self.has_token = false;
+        self.inner.call(req)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::future::poll_fn;
+    use tower::ServiceExt;
+
+    fn service(
+        requests: u64,
+        per: Duration,
+        burst: u64,
+    ) -> impl Service<usize, Response = usize, Error = ()> + Clone {
+        RateLimitLayer::new(requests, per)
+            .with_burst(burst)
+            .layer(tower::service_fn(futures_util::future::ok))
+    }
+
+    fn poll_ready_now<S: Service<usize>>(service: &mut S) -> Poll<Result<(), S::Error>> {
+        let waker = futures_util::task::noop_waker();
+        service.poll_ready(&mut Context::from_waker(&waker))
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn pending_when_empty() {
+        let mut svc = service(10, Duration::from_secs(1), 1);
+
+        assert!(poll_ready_now(&mut svc).is_ready());
+        assert_eq!(svc.call(1).await, Ok(1));
+
+        // The bucket is empty until the next token is available after 100ms
+        assert!(poll_ready_now(&mut svc).is_pending());
+        tokio::time::advance(Duration::from_millis(50)).await;
+        assert!(poll_ready_now(&mut svc).is_pending());
+        tokio::time::advance(Duration::from_millis(50)).await;
+        assert!(poll_ready_now(&mut svc).is_ready());
+
+        // Polling again without calling doesn't use up another token
+        assert!(poll_ready_now(&mut svc).is_ready());
+        assert_eq!(svc.call(2).await, Ok(2));
+        assert!(poll_ready_now(&mut svc).is_pending());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn wakes_when_token_available() {
+        let mut svc = service(10, Duration::from_secs(1), 1);
+        svc.ready().await.unwrap().call(1).await.unwrap();
+
+        let start = Instant::now();
+        poll_fn(|cx| svc.poll_ready(cx)).await.unwrap();
+        assert_eq!(start.elapsed(), Duration::from_millis(100));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn burst() {
+        let mut svc = service(10, Duration::from_secs(1), 3);
+
+        for i in 0..3 {
+            assert!(poll_ready_now(&mut svc).is_ready());
+            svc.call(i).await.unwrap();
+        }
+        assert!(poll_ready_now(&mut svc).is_pending());
+
+        // Tokens refill one at a time, up to the burst size
+        tokio::time::advance(Duration::from_millis(100)).await;
+        assert!(poll_ready_now(&mut svc).is_ready());
+        svc.call(3).await.unwrap();
+        assert!(poll_ready_now(&mut svc).is_pending());
+
+        tokio::time::advance(Duration::from_secs(10)).await;
+        for i in 0..3 {
+            assert!(poll_ready_now(&mut svc).is_ready());
+            svc.call(i).await.unwrap();
+        }
+        assert!(poll_ready_now(&mut svc).is_pending());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn clones_share_bucket() {
+        let mut a = service(10, Duration::from_secs(1), 2);
+        let mut b = a.clone();
+
+        assert!(poll_ready_now(&mut a).is_ready());
+        a.call(1).await.unwrap();
+        assert!(poll_ready_now(&mut b).is_ready());
+        b.call(2).await.unwrap();
+
+        assert!(poll_ready_now(&mut a).is_pending());
+        assert!(poll_ready_now(&mut b).is_pending());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn throughput_is_bounded() {
+        let mut svc = service(100, Duration::from_secs(1), 5);
+        let start = Instant::now();
+
+        for i in 0..205 {
+            svc.ready().await.unwrap().call(i).await.unwrap();
+        }
+
+        // The first 5 requests are sent immediately, and the rest are sent every 10ms
+        assert_eq!(start.elapsed(), Duration::from_secs(2));
+    }
+
+    #[test]
+    #[should_panic]
+    fn call_without_poll_ready() {
+        let mut svc = service(10, Duration::from_secs(1), 1);
+        drop(svc.call(1));
+    }
+}
@@ -0,0 +1,287 @@
+use crate::error::BoxError;
+
+use pin_project_lite::pin_project;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tower::Service;
+
+/// A [`Service`] that lazily connects using an inner "make service" (a [`Service`] that returns
+/// [`Service`]s, such as [`MakeApiService`](crate::service::MakeApiService)), and reconnects when
+/// the connected service fails.
+///
+/// This behaves like `tower::reconnect::Reconnect`, but doesn't require any particular async
+/// runtime.
+///
+/// If connecting fails, the error is returned from the next call, and the following
+/// [`poll_ready`](Service::poll_ready) tries to connect again.
+pub struct Reconnect<M, Target>
+where
+    M: Service<Target>,
+{
+    make_service: M,
+    state: State<M::Future, M::Response>,
+    target: Target,
+    error: Option<M::Error>,
+}
+
+#[derive(Debug)]
+enum State<F, S> {
+    Idle,
+    Connecting(F),
+    Connected(S),
+}
+
+impl<M, Target> Reconnect<M, Target>
+where
+    M: Service<Target>,
+{
+    /// Creates a new [`Reconnect`], which connects to `target` on the first request.
+    pub fn new(make_service: M, target: Target) -> Self {
+        Self {
+            make_service,
+            state: State::Idle,
+            target,
+            error: None,
+        }
+    }
+
+    /// Creates a new [`Reconnect`] with an existing connection.
+    pub fn with_connection(connection: M::Response, make_service: M, target: Target) -> Self {
+        Self {
+            make_service,
+            state: State::Connected(connection),
+            target,
+            error: None,
+        }
+    }
+}
+
+impl<M, Target, S, Request> Service<Request> for Reconnect<M, Target>
+where
+    M: Service<Target, Response = S>,
+    S: Service<Request>,
+    M::Future: Unpin,
+    BoxError: From<M::Error> + From<S::Error>,
+    Target: Clone,
+{
+    type Response = S::Response;
+    type Error = BoxError;
+    type Future = ResponseFuture<S::Future, M::Error>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        loop {
+            match &mut self.state {
+                State::Idle => {
+                    if let Err(e) = futures_util::ready!(self.make_service.poll_ready(cx)) {
+                        return Poll::Ready(Err(e.into()));
+                    }
+
+                    let future = self.make_service.call(self.target.clone());
+                    self.state = State::Connecting(future);
+                }
+                State::Connecting(future) => {
+                    match futures_util::ready!(Pin::new(future).poll(cx)) {
+                        Ok(service) => self.state = State::Connected(service),
+                        Err(e) => {
+                            tracing::trace!("Failed to connect");
+                            // Report the error from `call`, so the next `poll_ready` can try again
+                            self.state = State::Idle;
+                            self.error = Some(e);
+                            return Poll::Ready(Ok(()));
+                        }
+                    }
+                }
+                State::Connected(service) => match futures_util::ready!(service.poll_ready(cx)) {
+                    Ok(()) => return Poll::Ready(Ok(())),
+                    Err(_) => {
+                        tracing::trace!("Connection failed, reconnecting");
+                        self.state = State::Idle;
+                    }
+                },
+            }
+        }
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        if let Some(error) = self.error.take() {
+            return ResponseFuture {
+                inner: Inner::Error { error: Some(error) },
+            };
+        }
+
+        match &mut self.state {
+            State::Connected(service) => ResponseFuture {
+                inner: Inner::Future {
+                    future: service.call(request),
+                },
+            },
+            _ => panic!("Reconnect::call was called before poll_ready returned Ready"),
+        }
+    }
+}
+
+impl<M, Target> fmt::Debug for Reconnect<M, Target>
+where
+    M: Service<Target> + fmt::Debug,
+    M::Future: fmt::Debug,
+    M::Response: fmt::Debug,
+    Target: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Reconnect")
+            .field("make_service", &self.make_service)
+            .field("state", &self.state)
+            .field("target", &self.target)
+            .finish()
+    }
+}
+
+pin_project! {
+    /// Response future for [`Reconnect`], which resolves to the response or the failure to
+    /// connect.
+    #[derive(Debug)]
+    pub struct ResponseFuture<F, E> {
+        #[pin]
+        inner: Inner<F, E>,
+    }
+}
+
+pin_project! {
+    #[project = InnerProj]
+    #[derive(Debug)]
+    enum Inner<F, E> {
+        Future {
+            #[pin]
+            future: F,
+        },
+        Error {
+            error: Option<E>,
+        },
+    }
+}
+
+impl<F, T, E, ME> Future for ResponseFuture<F, ME>
+where
+    F: Future<Output = Result<T, E>>,
+    BoxError: From<E> + From<ME>,
+{
+    type Output = Result<T, BoxError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match self.project().inner.project() {
+            InnerProj::Future { future } => future.poll(cx).map_err(BoxError::from),
+            InnerProj::Error { error } => {
+                let error = error.take().expect("polled after completion");
+                Poll::Ready(Err(error.into()))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::{ApiStateRequest, ApiStateResponse, RequestEnvelope, ResponseEnvelope};
+    use crate::error::{Error, ErrorKind};
+    use futures_util::future::{self, Ready};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use tower::util::BoxService;
+    use tower::ServiceExt;
+
+    type Connection = BoxService<RequestEnvelope, ResponseEnvelope, Error>;
+
+    // Fails to connect on the second attempt, and each connection closes after one request
+    #[derive(Clone, Default)]
+    struct Connector {
+        attempts: Arc<AtomicUsize>,
+    }
+
+    impl Service<&'static str> for Connector {
+        type Response = Connection;
+        type Error = Error;
+        type Future = Ready<Result<Connection, Error>>;
+
+        fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, _: &'static str) -> Self::Future {
+            if self.attempts.fetch_add(1, Ordering::Relaxed) == 1 {
+                return future::ready(Err(Error::new(ErrorKind::ConnectionRefused)));
+            }
+
+            let connection = tower::service_fn(|_: RequestEnvelope| {
+                future::ready(
+                    ResponseEnvelope::new(&ApiStateResponse::default()).map_err(Error::from),
+                )
+            });
+
+            future::ready(Ok(BoxService::new(ReadyOnce::new(connection))))
+        }
+    }
+
+    // A connection that closes after a single request
+    struct ReadyOnce<S> {
+        service: S,
+        called: bool,
+    }
+
+    impl<S> ReadyOnce<S> {
+        fn new(service: S) -> Self {
+            Self {
+                service,
+                called: false,
+            }
+        }
+    }
+
+    impl<S> Service<RequestEnvelope> for ReadyOnce<S>
+    where
+        S: Service<RequestEnvelope, Error = Error>,
+    {
+        type Response = S::Response;
+        type Error = Error;
+        type Future = S::Future;
+
+        fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Error>> {
+            if self.called {
+                return Poll::Ready(Err(Error::new(ErrorKind::ConnectionDropped)));
+            }
+            self.service.poll_ready(cx)
+        }
+
+        fn call(&mut self, req: RequestEnvelope) -> Self::Future {
+            self.called = true;
+            self.service.call(req)
+        }
+    }
+
+    #[tokio::test]
+    async fn reconnect() -> Result<(), Error> {
+        let connector = Connector::default();
+        let mut service = Reconnect::new(connector.clone(), "ws://localhost:8001");
+        let request = || RequestEnvelope::new(&ApiStateRequest {});
+
+        // Connects lazily
+        assert_eq!(connector.attempts.load(Ordering::Relaxed), 0);
+        let resp = service.ready().await.map_err(Error::from_boxed)?;
+        resp.call(request()?).await.map_err(Error::from_boxed)?;
+        assert_eq!(connector.attempts.load(Ordering::Relaxed), 1);
+
+        // The connection closed, and reconnecting fails
+        let resp = service.ready().await.map_err(Error::from_boxed)?;
+        let err = Error::from_boxed(resp.call(request()?).await.unwrap_err());
+        assert!(err.has_kind(ErrorKind::ConnectionRefused));
+        assert_eq!(connector.attempts.load(Ordering::Relaxed), 2);
+
+        // The next attempt succeeds
+        let resp = service.ready().await.map_err(Error::from_boxed)?;
+        resp.call(request()?).await.map_err(Error::from_boxed)?;
+        assert_eq!(connector.attempts.load(Ordering::Relaxed), 3);
+
+        Ok(())
+    }
+}
@@ -1,7 +1,10 @@
 use crate::data::{RequestEnvelope, ResponseEnvelope};
 use crate::error::{Error, ErrorKind};
 
-use futures_util::future;
+use futures_util::future::{self, BoxFuture};
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
 use tower::retry::{Policy, Retry};
 use tower::Layer;
 use tracing::debug;
@@ -13,6 +16,58 @@ use tracing::debug;
 pub struct RetryPolicy {
     retry_on_disconnect: bool,
     retry_on_auth_error: bool,
+    predicate: Option<RetryPredicate>,
+    attempts: u32,
+}
+
+/// The result of a retry condition (see [`RetryPolicy::with_condition`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetryDecision {
+    /// Retry the request after the given delay.
+    Retry(Duration),
+    /// Return the error immediately.
+    DoNotRetry,
+}
+
+impl From<Option<Duration>> for RetryDecision {
+    fn from(delay: Option<Duration>) -> Self {
+        match delay {
+            Some(delay) => RetryDecision::Retry(delay),
+            None => RetryDecision::DoNotRetry,
+        }
+    }
+}
+
+type ConditionFn = dyn Fn(&Error, u32) -> RetryDecision + Send + Sync;
+
+/// User-provided callback for deciding whether to retry other errors.
+#[derive(Clone)]
+pub(crate) struct RetryPredicate(Arc<ConditionFn>);
+
+impl RetryPredicate {
+    pub(crate) fn new<F>(predicate: F) -> Self
+    where
+        F: Fn(&Error) -> Option<Duration> + Send + Sync + 'static,
+    {
+        Self::from_condition(move |error, _| predicate(error).into())
+    }
+
+    pub(crate) fn from_condition<F>(condition: F) -> Self
+    where
+        F: Fn(&Error, u32) -> RetryDecision + Send + Sync + 'static,
+    {
+        Self(Arc::new(condition))
+    }
+
+    fn decide(&self, error: &Error, attempt: u32) -> RetryDecision {
+        (self.0)(error, attempt)
+    }
+}
+
+impl fmt::Debug for RetryPredicate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("RetryPredicate").finish()
+    }
 }
 
 impl RetryPolicy {
@@ -21,6 +76,8 @@ impl RetryPolicy {
         RetryPolicy {
             retry_on_disconnect: true,
             retry_on_auth_error: true,
+            predicate: None,
+            attempts: 0,
         }
     }
 
@@ -35,6 +92,148 @@ impl RetryPolicy {
         self.retry_on_auth_error = value;
         self
     }
+
+    /// Decides whether to retry errors that aren't handled by
+    /// [`on_disconnect`](Self::on_disconnect) or [`on_auth_error`](Self::on_auth_error).
+    ///
+    /// The predicate is called with the error (including [`ApiError`](crate::data::ApiError)
+    /// responses, which can be inspected with [`Error::to_api_error`]), and should return
+    /// `Some(delay)` to retry the request after `delay`, or `None` to return the error
+    /// immediately.
+    ///
+    /// If the retried request fails again, the predicate is called again, so it should eventually
+    /// return `None` for errors that can't be fixed by retrying.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use vtubestudio::error::ErrorKind;
+    /// use vtubestudio::service::RetryPolicy;
+    ///
+    /// let policy = RetryPolicy::new().with_predicate(|error| {
+    ///     if error.has_kind(ErrorKind::Cooldown) {
+    ///         Some(Duration::from_millis(500))
+    ///     } else {
+    ///         None
+    ///     }
+    /// });
+    /// ```
+    pub fn with_predicate<F>(self, predicate: F) -> Self
+    where
+        F: Fn(&Error) -> Option<Duration> + Send + Sync + 'static,
+    {
+        self.with_retry_predicate(Some(RetryPredicate::new(predicate)))
+    }
+
+    /// Like [`with_predicate`](Self::with_predicate), but the condition is also given the number
+    /// of the attempt that failed (starting at `1` for the original request), which can be used to
+    /// implement backoff or to limit the number of retries.
+    ///
+    /// This replaces any condition set by [`with_predicate`](Self::with_predicate) or
+    /// [`on_error_kind`](Self::on_error_kind). The default condition never retries.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use vtubestudio::error::ErrorKind;
+    /// use vtubestudio::service::{RetryDecision, RetryPolicy};
+    ///
+    /// let policy = RetryPolicy::new().with_condition(|error, attempt| {
+    ///     if error.has_kind(ErrorKind::Cooldown) && attempt <= 3 {
+    ///         // Wait 100ms, 200ms, then 400ms
+    ///         RetryDecision::Retry(Duration::from_millis(100) * 2u32.pow(attempt - 1))
+    ///     } else {
+    ///         RetryDecision::DoNotRetry
+    ///     }
+    /// });
+    /// ```
+    pub fn with_condition<F>(self, condition: F) -> Self
+    where
+        F: Fn(&Error, u32) -> RetryDecision + Send + Sync + 'static,
+    {
+        self.with_retry_predicate(Some(RetryPredicate::from_condition(condition)))
+    }
+
+    /// Retries errors of the given [`ErrorKind`] after a fixed delay.
+    ///
+    /// Unlike [`with_condition`](Self::with_condition), this keeps the existing condition for
+    /// errors of other kinds, so it can be called multiple times. Errors of this kind are retried
+    /// until the request succeeds or fails with a different error.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use vtubestudio::error::ErrorKind;
+    /// use vtubestudio::service::RetryPolicy;
+    ///
+    /// let policy = RetryPolicy::new()
+    ///     .on_error_kind(ErrorKind::Cooldown, Duration::from_millis(500))
+    ///     .on_error_kind(ErrorKind::TransportFull, Duration::from_millis(50));
+    /// ```
+    pub fn on_error_kind(self, kind: ErrorKind, delay: Duration) -> Self {
+        let fallback = self.predicate.clone();
+        self.with_retry_predicate(Some(RetryPredicate::from_condition(
+            move |error, attempt| {
+                if error.has_kind_ref(&kind) {
+                    RetryDecision::Retry(delay)
+                } else {
+                    fallback
+                        .as_ref()
+                        .map_or(RetryDecision::DoNotRetry, |p| p.decide(error, attempt))
+                }
+            },
+        )))
+    }
+
+    pub(crate) fn with_retry_predicate(mut self, predicate: Option<RetryPredicate>) -> Self {
+        self.predicate = predicate;
+        self
+    }
+
+    fn retry_after_predicate(
+        &self,
+        req: &RequestEnvelope,
+        error: &Error,
+    ) -> Option<BoxFuture<'static, Self>> {
+        let attempt = self.attempts + 1;
+        let delay = match self.predicate.as_ref()?.decide(error, attempt) {
+            RetryDecision::Retry(delay) => delay,
+            RetryDecision::DoNotRetry => return None,
+        };
+
+        debug!(
+            message_type = req.message_type.as_str(),
+            ?delay,
+            attempt,
+            %error,
+            "Retrying request due to retry predicate"
+        );
+
+        let policy = self.next_attempt();
+        Some(Box::pin(async move {
+            if !delay.is_zero() {
+                crate::runtime::sleep(delay).await;
+            }
+            policy
+        }))
+    }
+}
+
+impl RetryPolicy {
+    fn next_attempt(&self) -> Self {
+        let mut policy = self.clone();
+        policy.attempts += 1;
+        policy
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl<S> Layer<S> for RetryPolicy {
@@ -47,41 +246,176 @@ impl<S> Layer<S> for RetryPolicy {
 }
 
 impl Policy<RequestEnvelope, ResponseEnvelope, Error> for RetryPolicy {
-    type Future = future::Ready<Self>;
+    type Future = BoxFuture<'static, Self>;
 
     fn retry(
         &self,
         req: &RequestEnvelope,
         result: Result<&ResponseEnvelope, &Error>,
     ) -> Option<Self::Future> {
-        Some(future::ready(match result {
+        Some(Box::pin(future::ready(match result {
             Ok(resp) if resp.is_unauthenticated_error() && self.retry_on_auth_error => {
-                self.clone().on_auth_error(false)
+                self.next_attempt().on_auth_error(false)
             }
 
+            Ok(resp) => match &resp.data {
+                Err(api_error) => {
+                    return self.retry_after_predicate(req, &Error::from(api_error.clone()));
+                }
+                Ok(_) => return None,
+            },
+
             Err(e) => {
                 if self.retry_on_auth_error && e.is_unauthenticated_error() {
                     debug!(
                         message_type = req.message_type.as_str(),
                         "Retrying request due to API auth error"
                     );
-                    self.clone().on_auth_error(false)
+                    self.next_attempt().on_auth_error(false)
                 } else if self.retry_on_disconnect && e.has_kind(ErrorKind::ConnectionDropped) {
                     debug!(
                         message_type = req.message_type.as_str(),
                         "Retrying request due to disconnection"
                     );
-                    self.clone().on_disconnect(false)
+                    self.next_attempt().on_disconnect(false)
                 } else {
-                    return None;
+                    return self.retry_after_predicate(req, e);
                 }
             }
-
-            _ => return None,
-        }))
+        })))
     }
 
     fn clone_request(&self, req: &RequestEnvelope) -> Option<RequestEnvelope> {
         Some(req.clone())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::{ApiError, ErrorId, HotkeyTriggerRequest, HotkeyTriggerResponse};
+    use crate::service::send_request;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tokio::time::Instant;
+    use tower::ServiceBuilder;
+
+    // Responds with the given error for the first `failures` requests, then succeeds.
+    async fn send_with_failures(
+        policy: RetryPolicy,
+        error_id: ErrorId,
+        failures: usize,
+    ) -> (Result<HotkeyTriggerResponse, Error>, usize) {
+        let attempts = Arc::new(AtomicUsize::new(0));
+
+        let mut service = ServiceBuilder::new().layer(policy).service_fn({
+            let attempts = Arc::clone(&attempts);
+            move |_: RequestEnvelope| {
+                let attempt = attempts.fetch_add(1, Ordering::Relaxed);
+                async move {
+                    if attempt < failures {
+                        Ok(ResponseEnvelope {
+                            data: Err(ApiError {
+                                error_id,
+                                message: String::new(),
+                            }),
+                            ..Default::default()
+                        })
+                    } else {
+                        ResponseEnvelope::new(&HotkeyTriggerResponse::default())
+                            .map_err(Error::from)
+                    }
+                }
+            }
+        });
+
+        let result = send_request(&mut service, &HotkeyTriggerRequest::default()).await;
+        (result, attempts.load(Ordering::Relaxed))
+    }
+
+    fn retry_cooldowns() -> RetryPolicy {
+        RetryPolicy::new().with_predicate(|error| {
+            assert!(error.is_api_error());
+            error
+                .has_kind(ErrorKind::Cooldown)
+                .then_some(Duration::from_millis(100))
+        })
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn predicate_retries_after_delay() {
+        let start = Instant::now();
+        let (result, attempts) =
+            send_with_failures(retry_cooldowns(), ErrorId::HOTKEY_COOLDOWN_NOT_OVER, 2).await;
+
+        assert!(result.is_ok());
+        assert_eq!(attempts, 3);
+        assert_eq!(start.elapsed(), Duration::from_millis(200));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn predicate_fails_immediately() {
+        let (result, attempts) =
+            send_with_failures(retry_cooldowns(), ErrorId::HOTKEY_ID_NOT_FOUND_IN_MODEL, 2).await;
+
+        assert!(result.unwrap_err().has_kind(ErrorKind::NotFound));
+        assert_eq!(attempts, 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn condition_receives_attempt() {
+        let seen = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let policy = RetryPolicy::new().with_condition({
+            let seen = Arc::clone(&seen);
+            move |error, attempt| {
+                seen.lock().unwrap().push(attempt);
+                if error.has_kind(ErrorKind::Cooldown) {
+                    RetryDecision::Retry(Duration::from_millis(100) * attempt)
+                } else {
+                    RetryDecision::DoNotRetry
+                }
+            }
+        });
+
+        let start = Instant::now();
+        let (result, attempts) =
+            send_with_failures(policy.clone(), ErrorId::HOTKEY_COOLDOWN_NOT_OVER, 2).await;
+        assert!(result.is_ok());
+        assert_eq!(attempts, 3);
+        assert_eq!(start.elapsed(), Duration::from_millis(300));
+        assert_eq!(*seen.lock().unwrap(), [1, 2]);
+
+        // Not found errors aren't retried
+        let (result, attempts) =
+            send_with_failures(policy, ErrorId::HOTKEY_ID_NOT_FOUND_IN_MODEL, 2).await;
+        assert!(result.unwrap_err().has_kind(ErrorKind::NotFound));
+        assert_eq!(attempts, 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn on_error_kind() {
+        let policy = RetryPolicy::new()
+            .on_error_kind(ErrorKind::Cooldown, Duration::from_millis(100))
+            .on_error_kind(ErrorKind::TransportFull, Duration::from_millis(10));
+
+        let start = Instant::now();
+        let (result, attempts) =
+            send_with_failures(policy.clone(), ErrorId::HOTKEY_COOLDOWN_NOT_OVER, 2).await;
+        assert!(result.is_ok());
+        assert_eq!(attempts, 3);
+        assert_eq!(start.elapsed(), Duration::from_millis(200));
+
+        let (result, attempts) =
+            send_with_failures(policy, ErrorId::HOTKEY_ID_NOT_FOUND_IN_MODEL, 2).await;
+        assert!(result.unwrap_err().has_kind(ErrorKind::NotFound));
+        assert_eq!(attempts, 1);
+    }
+
+    #[tokio::test]
+    async fn no_predicate() {
+        let (result, attempts) =
+            send_with_failures(RetryPolicy::new(), ErrorId::HOTKEY_COOLDOWN_NOT_OVER, 2).await;
+
+        assert!(result.unwrap_err().has_kind(ErrorKind::Cooldown));
+        assert_eq!(attempts, 1);
+    }
+}
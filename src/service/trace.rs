@@ -0,0 +1,349 @@
+use crate::data::{RequestEnvelope, ResponseEnvelope};
+use crate::error::Error;
+
+use pin_project_lite::pin_project;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tokio::time::Instant;
+use tower::{Layer, Service};
+use tracing::field::Empty;
+use tracing::instrument::{Instrument, Instrumented};
+use tracing::Span;
+
+/// A [`Layer`] that produces a [`Tracing`] service.
+///
+/// Each request is handled inside a `vts_request` span with `message_type` and `request_id`
+/// fields, so that logs from the underlying services (e.g., authentication retries and the
+/// multiplexed transport) can be grouped by request. If the request doesn't have an ID yet (since
+/// it's assigned later by the [`ApiService`]), the `request_id` field is recorded from the
+/// response instead.
+///
+/// Once the request completes, the span also records:
+///
+/// * `vts.request.duration_ms`: the time taken to receive a response, in milliseconds
+/// * `vts.response.status`: `"success"`, `"APIError"` (for [`ApiError`] responses), or `"error"`
+///   (for other errors, such as disconnections)
+/// * `vts.error_id`: the [`ErrorId`] of the [`ApiError`], if any
+///
+/// With the `opentelemetry` feature, the span also records `otel.kind` (always `"client"`) and
+/// `otel.status_code` (`"OK"` or `"ERROR"`). These are recognized by
+/// [`tracing-opentelemetry`](https://docs.rs/tracing-opentelemetry), if it's used by the
+/// application, to mark the span as a client span and failed requests as errors.
+///
+/// This is already included in services created by [`ClientBuilder`](crate::ClientBuilder) when the
+/// `tracing` feature is enabled.
+///
+/// [`ApiService`]: crate::service::ApiService
+/// [`ApiError`]: crate::data::ApiError
+/// [`ErrorId`]: crate::data::ErrorId
+///
+/// # Example
+///
+/// ```
+/// use tower::ServiceBuilder;
+/// use vtubestudio::data::{RequestEnvelope, ResponseEnvelope};
+/// use vtubestudio::error::Error;
+/// use vtubestudio::service::TracingLayer;
+///
+/// let service = ServiceBuilder::new()
+///     .layer(TracingLayer::new())
+///     .service_fn(|req: RequestEnvelope| async move {
+///         tracing::debug!("This event is inside the request's span");
+///         # Ok::<_, Error>(ResponseEnvelope::new(&vtubestudio::data::ApiStateResponse::default())?)
+///     });
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TracingLayer {
+    _priv: (),
+}
+
+impl TracingLayer {
+    /// Creates a new [`TracingLayer`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<S> Layer<S> for TracingLayer {
+    type Service = Tracing<S>;
+
+    fn layer(&self, service: S) -> Self::Service {
+        Tracing::new(service)
+    }
+}
+
+/// A [`Service`] that opens a [`tracing`] span for each [`RequestEnvelope`].
+///
+/// See [`TracingLayer`] for details.
+#[derive(Debug, Clone)]
+pub struct Tracing<S> {
+    service: S,
+}
+
+impl<S> Tracing<S> {
+    /// Creates a new [`Tracing`] service wrapping the given service.
+    pub fn new(service: S) -> Self {
+        Self { service }
+    }
+}
+
+impl<S> Service<RequestEnvelope> for Tracing<S>
+where
+    S: Service<RequestEnvelope, Response = ResponseEnvelope, Error = Error>,
+{
+    type Response = ResponseEnvelope;
+    type Error = Error;
+    type Future = ResponseFuture<S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: RequestEnvelope) -> Self::Future {
+        let span = tracing::debug_span!(
+            "vts_request",
+            message_type = req.message_type.as_str(),
+            request_id = Empty,
+            vts.request.duration_ms = Empty,
+            vts.response.status = Empty,
+            vts.error_id = Empty,
+            otel.kind = Empty,
+            otel.status_code = Empty,
+        );
+
+        let has_id = req.request_id.is_some();
+        if let Some(id) = &req.request_id {
+            span.record("request_id", id.as_str());
+        }
+
+        #[cfg(feature = "opentelemetry")]
+        span.record("otel.kind", "client");
+
+        // Calling the inner service inside the span lets services that spawn work elsewhere (such
+        // as `Buffer`) propagate the span
+        let future = span.in_scope(|| self.service.call(req));
+
+        ResponseFuture {
+            future: future.instrument(span.clone()),
+            span,
+            start: Instant::now(),
+            has_id,
+        }
+    }
+}
+
+pin_project! {
+    /// Response future for [`Tracing`].
+    pub struct ResponseFuture<F> {
+        #[pin]
+        future: Instrumented<F>,
+        span: Span,
+        start: Instant,
+        has_id: bool,
+    }
+}
+
+impl<F> fmt::Debug for ResponseFuture<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResponseFuture")
+            .field("span", &self.span)
+            .finish()
+    }
+}
+
+impl<F> Future for ResponseFuture<F>
+where
+    F: Future<Output = Result<ResponseEnvelope, Error>>,
+{
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let result = match this.future.poll(cx) {
+            Poll::Ready(result) => result,
+            Poll::Pending => return Poll::Pending,
+        };
+
+        let span = this.span;
+        span.record(
+            "vts.request.duration_ms",
+            this.start.elapsed().as_secs_f64() * 1000.0,
+        );
+
+        if let (false, Ok(resp)) = (*this.has_id, &result) {
+            span.record("request_id", resp.request_id.as_str());
+        }
+
+        let (status, api_error) = match &result {
+            Ok(resp) => match &resp.data {
+                Ok(_) => ("success", None),
+                Err(e) => ("APIError", Some(e)),
+            },
+            Err(e) => match e.to_api_error() {
+                Some(e) => ("APIError", Some(e)),
+                None => ("error", None),
+            },
+        };
+
+        span.record("vts.response.status", status);
+        if let Some(e) = api_error {
+            span.record("vts.error_id", e.error_id.as_i32());
+        }
+
+        #[cfg(feature = "opentelemetry")]
+        span.record(
+            "otel.status_code",
+            if status == "success" { "OK" } else { "ERROR" },
+        );
+
+        Poll::Ready(result)
+    }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use super::*;
+    use crate::data::{ApiStateRequest, ErrorId, StatisticsRequest, StatisticsResponse};
+    use crate::testing::MockServer;
+    use crate::Client;
+
+    use std::collections::HashMap;
+    use std::fmt;
+    use std::num::NonZeroU64;
+    use std::sync::{Arc, Mutex};
+    use tower::ServiceExt;
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata, Subscriber};
+    use tracing_core::span::Current;
+
+    #[derive(Debug, Default)]
+    struct Spans {
+        // Fields of each span, indexed by span ID - 1
+        fields: Vec<(&'static Metadata<'static>, HashMap<&'static str, String>)>,
+        stack: Vec<Id>,
+    }
+
+    // A minimal subscriber that records span fields
+    #[derive(Debug, Clone, Default)]
+    struct SpanRecorder(Arc<Mutex<Spans>>);
+
+    struct FieldVisitor<'a>(&'a mut HashMap<&'static str, String>);
+
+    impl Visit for FieldVisitor<'_> {
+        fn record_str(&mut self, field: &Field, value: &str) {
+            self.0.insert(field.name(), value.to_owned());
+        }
+
+        fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+            self.0.insert(field.name(), format!("{:?}", value));
+        }
+    }
+
+    impl SpanRecorder {
+        fn spans(&self, name: &str) -> Vec<HashMap<&'static str, String>> {
+            let spans = self.0.lock().unwrap();
+            spans
+                .fields
+                .iter()
+                .filter(|(metadata, _)| metadata.name() == name)
+                .map(|(_, fields)| fields.clone())
+                .collect()
+        }
+    }
+
+    impl Subscriber for SpanRecorder {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, span: &Attributes<'_>) -> Id {
+            let mut spans = self.0.lock().unwrap();
+            let mut fields = HashMap::new();
+            span.record(&mut FieldVisitor(&mut fields));
+            spans.fields.push((span.metadata(), fields));
+            Id::from_non_zero_u64(NonZeroU64::new(spans.fields.len() as u64).unwrap())
+        }
+
+        fn record(&self, span: &Id, values: &Record<'_>) {
+            let mut spans = self.0.lock().unwrap();
+            let (_, fields) = &mut spans.fields[span.into_u64() as usize - 1];
+            values.record(&mut FieldVisitor(fields));
+        }
+
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+        fn event(&self, _: &Event<'_>) {}
+
+        fn enter(&self, span: &Id) {
+            self.0.lock().unwrap().stack.push(span.clone());
+        }
+
+        fn exit(&self, _: &Id) {
+            self.0.lock().unwrap().stack.pop();
+        }
+
+        fn current_span(&self) -> Current {
+            let spans = self.0.lock().unwrap();
+            match spans.stack.last() {
+                Some(id) => Current::new(id.clone(), spans.fields[id.into_u64() as usize - 1].0),
+                None => Current::none(),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn request_spans() -> Result<(), Error> {
+        let recorder = SpanRecorder::default();
+        let _guard = tracing::subscriber::set_default(recorder.clone());
+
+        let server = MockServer::new();
+        server.respond::<StatisticsRequest>(&StatisticsResponse::default())?;
+        let (mut client, _events) = Client::builder().build_connector(server.connector());
+
+        client.send(&StatisticsRequest {}).await?;
+
+        let mut envelope = RequestEnvelope::new(&StatisticsRequest {})?;
+        envelope.request_id = Some("custom-id".into());
+        client.clone().into_service().oneshot(envelope).await?;
+
+        let error = client.send(&ApiStateRequest {}).await.unwrap_err();
+        assert!(error.is_api_error());
+
+        let spans = recorder.spans("vts_request");
+        assert_eq!(spans.len(), 3);
+        for span in &spans[..2] {
+            assert_eq!(span["message_type"], "StatisticsRequest");
+            assert_eq!(span["vts.response.status"], "success");
+            assert!(!span.contains_key("vts.error_id"));
+        }
+        assert_eq!(spans[0]["request_id"], "0");
+        assert_eq!(spans[1]["request_id"], "custom-id");
+
+        assert_eq!(spans[2]["request_id"], "1");
+        assert_eq!(spans[2]["message_type"], "APIStateRequest");
+        assert_eq!(spans[2]["vts.response.status"], "APIError");
+        assert_eq!(
+            spans[2]["vts.error_id"],
+            ErrorId::INTERNAL_SERVER_ERROR.as_i32().to_string()
+        );
+
+        for span in &spans {
+            assert!(span["vts.request.duration_ms"].parse::<f64>().is_ok());
+            assert_eq!(
+                span.get("otel.kind").map(String::as_str),
+                cfg!(feature = "opentelemetry").then_some("client")
+            );
+        }
+
+        #[cfg(feature = "opentelemetry")]
+        {
+            assert_eq!(spans[0]["otel.status_code"], "OK");
+            assert_eq!(spans[2]["otel.status_code"], "ERROR");
+        }
+
+        Ok(())
+    }
+}
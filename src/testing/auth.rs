@@ -0,0 +1,306 @@
+use crate::data::{
+    AuthenticationRequest, AuthenticationResponse, AuthenticationTokenRequest,
+    AuthenticationTokenResponse, ErrorId, ResponseEnvelope,
+};
+use crate::testing::{api_error, MockServer, RequestMatcher};
+
+/// Scripted responses to the VTube Studio authentication flow, for use with a [`MockServer`].
+///
+/// This responds to [`AuthenticationRequest`]s (accepting only the configured tokens) and
+/// [`AuthenticationTokenRequest`]s (returning the configured new token, or denying the request as
+/// if the user clicked "Deny" in the VTube Studio pop-up).
+///
+/// Combined with [`MockServer::service`] and [`MockServer::received_message_types`], this can be
+/// used to assert the exact sequence of requests sent by the
+/// [`Authentication`](crate::service::Authentication) middleware, e.g., to regression-test token
+/// persistence logic.
+///
+/// # Example
+///
+/// ```
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), vtubestudio::error::BoxError> {
+/// use tower::{Layer, Service, ServiceExt};
+/// use vtubestudio::data::{
+///     AuthenticationTokenRequest, RequestEnvelope, StatisticsRequest, StatisticsResponse,
+/// };
+/// use vtubestudio::service::AuthenticationLayer;
+/// use vtubestudio::testing::{MockAuthentication, MockServer};
+///
+/// let server = MockServer::new();
+/// server.respond::<StatisticsRequest>(&StatisticsResponse::default())?;
+///
+/// MockAuthentication::new()
+///     .with_new_token("new-token")
+///     .install(&server);
+///
+/// // Authenticate using a stored token that VTube Studio no longer accepts
+/// let mut service = AuthenticationLayer::new(AuthenticationTokenRequest {
+///     plugin_name: "Plugin name".into(),
+///     plugin_developer: "Developer name".into(),
+///     plugin_icon: None,
+/// })
+/// .with_token(Some("expired-token".into()))
+/// .layer(server.service());
+///
+/// let resp = service
+///     .ready()
+///     .await?
+///     .call(RequestEnvelope::new(&StatisticsRequest {})?)
+///     .await?;
+///
+/// assert_eq!(resp.new_token.as_deref(), Some("new-token"));
+/// assert_eq!(
+///     server.received_message_types(),
+///     [
+///         "AuthenticationRequest",
+///         "AuthenticationTokenRequest",
+///         "AuthenticationRequest",
+///         "StatisticsRequest",
+///     ]
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MockAuthentication {
+    valid_tokens: Vec<String>,
+    new_token: Option<String>,
+}
+
+impl MockAuthentication {
+    /// Creates a new [`MockAuthentication`] that rejects all tokens and denies all token requests.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Accepts `token` in [`AuthenticationRequest`]s. Can be called multiple times to accept
+    /// multiple tokens.
+    pub fn with_valid_token<S: Into<String>>(mut self, token: S) -> Self {
+        self.valid_tokens.push(token.into());
+        self
+    }
+
+    /// Responds to [`AuthenticationTokenRequest`]s with `token`, which is also accepted in
+    /// [`AuthenticationRequest`]s.
+    ///
+    /// If this isn't set, token requests fail with [`ErrorId::TOKEN_REQUEST_DENIED`].
+    pub fn with_new_token<S: Into<String>>(mut self, token: S) -> Self {
+        self.new_token = Some(token.into());
+        self
+    }
+
+    /// Registers handlers for the authentication requests on `server`, replacing any previously
+    /// registered handlers for those requests.
+    pub fn install(self, server: &MockServer) {
+        let new_token = self.new_token.clone();
+        server.respond_with(
+            RequestMatcher::message_type::<AuthenticationTokenRequest>(),
+            move |_| match &new_token {
+                Some(token) => ResponseEnvelope::new(&AuthenticationTokenResponse {
+                    authentication_token: token.clone(),
+                })
+                .expect("failed to serialize AuthenticationTokenResponse"),
+                None => api_error(
+                    ErrorId::TOKEN_REQUEST_DENIED,
+                    "User has denied API access for your plugin.".to_owned(),
+                ),
+            },
+        );
+
+        server.respond_with(
+            RequestMatcher::message_type::<AuthenticationRequest>(),
+            move |req| {
+                let authenticated = req
+                    .data
+                    .deserialize::<AuthenticationRequest>()
+                    .is_ok_and(|req| self.is_valid(&req.authentication_token));
+
+                let reason = if authenticated {
+                    "Token valid. The plugin is authenticated for the duration of this session."
+                } else {
+                    "Token invalid. The plugin is not authenticated."
+                };
+
+                ResponseEnvelope::new(&AuthenticationResponse {
+                    authenticated,
+                    reason: reason.to_owned(),
+                })
+                .expect("failed to serialize AuthenticationResponse")
+            },
+        );
+    }
+
+    fn is_valid(&self, token: &str) -> bool {
+        self.new_token.as_deref() == Some(token) || self.valid_tokens.iter().any(|t| t == token)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::{RequestEnvelope, StatisticsRequest, StatisticsResponse};
+    use crate::error::Error;
+    use crate::service::{AuthenticationLayer, ResponseWithToken};
+    use tower::{Layer, Service, ServiceExt};
+
+    async fn run(
+        auth: MockAuthentication,
+        stored_token: Option<&str>,
+    ) -> Result<(ResponseWithToken, Vec<String>), Error> {
+        let server = MockServer::new();
+        server.respond::<StatisticsRequest>(&StatisticsResponse::default())?;
+        auth.install(&server);
+
+        let mut service = AuthenticationLayer::new(AuthenticationTokenRequest {
+            plugin_name: "Plugin name".into(),
+            plugin_developer: "Developer name".into(),
+            plugin_icon: None,
+        })
+        .with_token(stored_token.map(String::from))
+        .layer(server.service());
+
+        let resp = service
+            .ready()
+            .await?
+            .call(RequestEnvelope::new(&StatisticsRequest {})?)
+            .await?;
+
+        assert!(server.unexpected().is_empty());
+        Ok((resp, server.received_message_types()))
+    }
+
+    #[tokio::test]
+    async fn valid_stored_token() -> Result<(), Error> {
+        let auth = MockAuthentication::new()
+            .with_valid_token("stored")
+            .with_new_token("new");
+        let (resp, requests) = run(auth, Some("stored")).await?;
+
+        assert_eq!(resp.new_token, None);
+        assert_eq!(requests, ["AuthenticationRequest", "StatisticsRequest"]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn invalid_stored_token() -> Result<(), Error> {
+        let auth = MockAuthentication::new().with_new_token("new");
+        let (resp, requests) = run(auth, Some("stored")).await?;
+
+        assert_eq!(resp.new_token.as_deref(), Some("new"));
+        assert_eq!(
+            requests,
+            [
+                "AuthenticationRequest",
+                "AuthenticationTokenRequest",
+                "AuthenticationRequest",
+                "StatisticsRequest",
+            ]
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn no_stored_token() -> Result<(), Error> {
+        let auth = MockAuthentication::new().with_new_token("new");
+        let (resp, requests) = run(auth, None).await?;
+
+        assert_eq!(resp.new_token.as_deref(), Some("new"));
+        assert_eq!(
+            requests,
+            [
+                "AuthenticationTokenRequest",
+                "AuthenticationRequest",
+                "StatisticsRequest",
+            ]
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn ensure_authenticated() -> Result<(), Error> {
+        use crate::data::{ApiStateRequest, ApiStateResponse};
+        use crate::Client;
+
+        let server = MockServer::new();
+        server.respond::<ApiStateRequest>(&ApiStateResponse {
+            current_session_authenticated: true,
+            ..Default::default()
+        })?;
+        MockAuthentication::new()
+            .with_valid_token("stored")
+            .install(&server);
+
+        let (mut client, _events) = Client::builder()
+            .authentication("Plugin name", "Developer name", None)
+            .auth_token(Some("stored".into()))
+            .build_connector(server.connector());
+
+        client.ensure_authenticated().await?;
+        assert!(client.connection_state().is_authenticated());
+
+        // Already authenticated, so no more requests are sent
+        client.ensure_authenticated().await?;
+        assert_eq!(
+            server.received_message_types(),
+            ["AuthenticationRequest", "APIStateRequest"]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn client_auth_token() -> Result<(), Error> {
+        use crate::data::{ApiStateRequest, ApiStateResponse};
+        use crate::Client;
+
+        let server = MockServer::new();
+        server.respond::<ApiStateRequest>(&ApiStateResponse::default())?;
+        MockAuthentication::new()
+            .with_new_token("new")
+            .install(&server);
+
+        let (mut client, _events) = Client::builder()
+            .authentication("Plugin name", "Developer name", None)
+            .auth_token(Some("stored".into()))
+            .build_connector(server.connector());
+
+        assert_eq!(client.auth_token().as_deref(), Some("stored"));
+
+        client.send(&ApiStateRequest {}).await?;
+        assert_eq!(client.auth_token().as_deref(), Some("new"));
+        assert_eq!(client.clone().auth_token().as_deref(), Some("new"));
+
+        let (client, _events) = Client::builder().build_connector(server.connector());
+        assert_eq!(client.auth_token(), None);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn ensure_authenticated_without_auth_info() -> Result<(), Error> {
+        use crate::data::{ApiStateRequest, ApiStateResponse};
+        use crate::Client;
+
+        let server = MockServer::new();
+        server.respond::<ApiStateRequest>(&ApiStateResponse::default())?;
+
+        let (mut client, _events) = Client::builder().build_connector(server.connector());
+
+        let err = client.ensure_authenticated().await.unwrap_err();
+        assert!(err.is_unauthenticated_error());
+        assert_eq!(server.received_message_types(), ["APIStateRequest"]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn token_request_denied() {
+        let err = run(MockAuthentication::new(), None).await.unwrap_err();
+
+        assert_eq!(
+            err.to_api_error().map(|e| e.error_id),
+            Some(ErrorId::TOKEN_REQUEST_DENIED)
+        );
+    }
+}
@@ -0,0 +1,665 @@
+use crate::data::{ErrorId, EventData, Request, RequestEnvelope, ResponseEnvelope};
+use crate::error::{Error, ErrorKind};
+use crate::testing::{api_error, RequestMatcher};
+
+use futures_core::Stream;
+use futures_sink::Sink;
+use futures_util::future::{self, Ready};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use tokio::sync::mpsc;
+use tower::Service;
+
+type Handler = Box<dyn Fn(&RequestEnvelope) -> ResponseEnvelope + Send + Sync>;
+
+/// An in-memory server that pretends to be VTube Studio, responding to requests using registered
+/// handlers. Unlike [`FakeVtsServer`](crate::testing::FakeVtsServer), this doesn't open any
+/// sockets, and responses aren't consumed after being used.
+///
+/// Each incoming request is passed to the most recently registered handler that matches it. If
+/// no handler matches, an [`ApiError`](crate::data::ApiError) is returned and the request is
+/// recorded as unexpected.
+///
+/// The server can be used with a [`Client`](crate::Client) in two ways:
+///
+/// * [`ClientBuilder::build_connector`](crate::ClientBuilder::build_connector) with
+///   [`connector`](Self::connector), which supports reconnecting and forwards events sent with
+///   [`send_event`](Self::send_event) to the [`ClientEventStream`](crate::ClientEventStream).
+/// * [`ClientBuilder::build_service`](crate::ClientBuilder::build_service) with an
+///   [`ApiService`](crate::service::ApiService) wrapping a single [`connect`](Self::connect)ion.
+///   Events are yielded by the [`EventStream`](crate::transport::EventStream) returned by
+///   [`ApiService::new`](crate::service::ApiService::new).
+///
+/// This can be cloned, and all clones refer to the same server.
+///
+/// # Example
+///
+/// This drives the authentication flow end-to-end:
+///
+/// ```
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), vtubestudio::error::BoxError> {
+/// use vtubestudio::data::{
+///     AuthenticationRequest, AuthenticationResponse, AuthenticationTokenRequest,
+///     AuthenticationTokenResponse, ResponseEnvelope, StatisticsRequest, StatisticsResponse,
+/// };
+/// use vtubestudio::testing::{MockServer, RequestMatcher};
+/// use vtubestudio::{Client, ClientEvent};
+///
+/// let server = MockServer::new();
+///
+/// server.respond::<AuthenticationTokenRequest>(&AuthenticationTokenResponse {
+///     authentication_token: "new-token".to_owned(),
+/// })?;
+///
+/// server.respond_with(
+///     RequestMatcher::message_type::<AuthenticationRequest>(),
+///     |req| {
+///         let req = req.data.deserialize::<AuthenticationRequest>().unwrap();
+///         let authenticated = req.authentication_token == "new-token";
+///         ResponseEnvelope::new(&AuthenticationResponse {
+///             authenticated,
+///             reason: String::new(),
+///         })
+///         .unwrap()
+///     },
+/// );
+///
+/// server.respond::<StatisticsRequest>(&StatisticsResponse {
+///     uptime: 1000,
+///     ..Default::default()
+/// })?;
+///
+/// let (mut client, mut events) = Client::builder()
+///     .authentication("Plugin name", "Developer name", None)
+///     .build_connector(server.connector());
+///
+/// let resp = client.send(&StatisticsRequest {}).await?;
+/// assert_eq!(resp.uptime, 1000);
+/// assert!(client.connection_state().is_authenticated());
+///
+/// while let Some(event) = events.next().await {
+///     if let ClientEvent::NewAuthToken(token) = event {
+///         assert_eq!(token, "new-token");
+///         break;
+///     }
+/// }
+///
+/// assert!(server.unexpected().is_empty());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Default)]
+pub struct MockServer {
+    state: Arc<Mutex<MockState>>,
+}
+
+#[derive(Default)]
+struct MockState {
+    handlers: Vec<(RequestMatcher, Handler)>,
+    received: Vec<RequestEnvelope>,
+    unexpected: Vec<RequestEnvelope>,
+    connections: HashMap<usize, mpsc::UnboundedSender<ResponseEnvelope>>,
+    next_connection_id: usize,
+}
+
+impl fmt::Debug for MockServer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = self.lock();
+        f.debug_struct("MockServer")
+            .field(
+                "handlers",
+                &state
+                    .handlers
+                    .iter()
+                    .map(|(matcher, _)| matcher)
+                    .collect::<Vec<_>>(),
+            )
+            .field("received", &state.received.len())
+            .field("unexpected", &state.unexpected.len())
+            .field("connections", &state.connections.len())
+            .finish()
+    }
+}
+
+impl MockServer {
+    /// Creates a new server with no handlers.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Responds to every request of type `Req` with the given data.
+    pub fn respond<Req>(&self, data: &Req::Response) -> Result<(), serde_json::Error>
+    where
+        Req: Request,
+        Req::Response: Serialize,
+    {
+        let response = ResponseEnvelope::new(data)?;
+        self.respond_with(RequestMatcher::message_type::<Req>(), move |_| {
+            response.clone()
+        });
+        Ok(())
+    }
+
+    /// Responds to every request matching `matcher` by calling `handler`.
+    ///
+    /// The request ID of the returned response is replaced with the ID of the request.
+    pub fn respond_with<F>(&self, matcher: RequestMatcher, handler: F)
+    where
+        F: Fn(&RequestEnvelope) -> ResponseEnvelope + Send + Sync + 'static,
+    {
+        self.lock().handlers.push((matcher, Box::new(handler)));
+    }
+
+    /// Sends an event to all connected clients.
+    ///
+    /// Returns the number of connected clients the event was sent to.
+    pub fn send_event<T>(&self, data: &T) -> Result<usize, serde_json::Error>
+    where
+        T: EventData + Serialize,
+    {
+        let event = ResponseEnvelope::new(data)?;
+        let state = self.lock();
+
+        Ok(state
+            .connections
+            .values()
+            .filter(|tx| tx.send(event.clone()).is_ok())
+            .count())
+    }
+
+    /// Closes all current connections. New connections can still be opened afterwards.
+    pub fn disconnect_all(&self) {
+        self.lock().connections.clear();
+    }
+
+    /// Returns the number of open connections.
+    pub fn connection_count(&self) -> usize {
+        self.lock().connections.len()
+    }
+
+    /// Returns all requests received so far, in order.
+    pub fn received(&self) -> Vec<RequestEnvelope> {
+        self.lock().received.clone()
+    }
+
+    /// Waits until at least `count` requests have been received, such as cleanup requests sent
+    /// from a spawned task.
+    ///
+    /// This yields to the runtime instead of using a timeout, so it also works while time is
+    /// paused (e.g., with `#[tokio::test(start_paused = true)]`).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the requests still haven't been received after yielding many times.
+    pub async fn wait_for_requests(&self, count: usize) {
+        for _ in 0..10_000 {
+            if self.lock().received.len() >= count {
+                return;
+            }
+            tokio::task::yield_now().await;
+        }
+        panic!("timed out waiting for {} requests", count);
+    }
+
+    /// Returns the message types of all requests received so far, in order.
+    ///
+    /// This is useful for asserting the exact sequence of requests sent by middleware, such as
+    /// the [`Authentication`](crate::service::Authentication) service.
+    pub fn received_message_types(&self) -> Vec<String> {
+        self.lock()
+            .received
+            .iter()
+            .map(|req| req.message_type.as_str().to_owned())
+            .collect()
+    }
+
+    /// Returns the requests that didn't match any handler.
+    pub fn unexpected(&self) -> Vec<RequestEnvelope> {
+        self.lock().unexpected.clone()
+    }
+
+    /// Opens a new in-memory connection to this server.
+    pub fn connect(&self) -> MockTransport {
+        let (tx, rx) = mpsc::unbounded_channel();
+
+        let mut state = self.lock();
+        let id = state.next_connection_id;
+        state.next_connection_id += 1;
+        state.connections.insert(id, tx);
+
+        MockTransport {
+            server: self.clone(),
+            id,
+            receiver: rx,
+        }
+    }
+
+    /// Returns a [`MakeTransport`](tokio_tower::MakeTransport) that opens a new connection to this
+    /// server each time it's called, for use with
+    /// [`ClientBuilder::build_connector`](crate::ClientBuilder::build_connector).
+    pub fn connector(&self) -> MockConnector {
+        MockConnector {
+            server: self.clone(),
+        }
+    }
+
+    /// Returns a [`Service`] that responds to requests directly, without going through a
+    /// transport. This can be wrapped by middleware (such as
+    /// [`AuthenticationLayer`](crate::service::AuthenticationLayer)) to test it in isolation.
+    pub fn service(&self) -> MockService {
+        MockService {
+            server: self.clone(),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, MockState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl MockState {
+    fn respond(&mut self, req: RequestEnvelope) -> ResponseEnvelope {
+        let request_id = req.request_id.clone().unwrap_or_default();
+
+        let response = match self
+            .handlers
+            .iter()
+            .rev()
+            .find(|(matcher, _)| matcher.matches(&req))
+        {
+            Some((_, handler)) => handler(&req),
+            None => {
+                let message = format!(
+                    "MockServer has no handler for {}",
+                    req.message_type.as_str()
+                );
+                self.unexpected.push(req.clone());
+                api_error(ErrorId::INTERNAL_SERVER_ERROR, message)
+            }
+        };
+
+        self.received.push(req);
+        response.with_id(request_id)
+    }
+}
+
+/// An in-memory connection to a [`MockServer`].
+///
+/// This is a sink/stream of [`RequestEnvelope`]s and [`ResponseEnvelope`]s, which can be used
+/// with [`ApiService::new`](crate::service::ApiService::new).
+pub struct MockTransport {
+    server: MockServer,
+    id: usize,
+    receiver: mpsc::UnboundedReceiver<ResponseEnvelope>,
+}
+
+impl fmt::Debug for MockTransport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MockTransport")
+            .field("id", &self.id)
+            .finish()
+    }
+}
+
+impl Drop for MockTransport {
+    fn drop(&mut self) {
+        self.server.lock().connections.remove(&self.id);
+    }
+}
+
+impl Sink<RequestEnvelope> for MockTransport {
+    type Error = Error;
+
+    fn poll_ready(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn start_send(self: Pin<&mut Self>, item: RequestEnvelope) -> Result<(), Self::Error> {
+        let mut state = self.server.lock();
+
+        if !state.connections.contains_key(&self.id) {
+            return Err(Error::new(ErrorKind::ConnectionDropped));
+        }
+
+        let response = state.respond(item);
+        if let Some(tx) = state.connections.get(&self.id) {
+            let _ = tx.send(response);
+        }
+
+        Ok(())
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.server.lock().connections.remove(&self.id);
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl Stream for MockTransport {
+    type Item = Result<ResponseEnvelope, Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.receiver.poll_recv(cx).map(|item| item.map(Ok))
+    }
+}
+
+/// A [`MakeTransport`](tokio_tower::MakeTransport) that opens connections to a [`MockServer`].
+///
+/// The request (typically the URL from [`ClientBuilder`](crate::ClientBuilder)) is ignored.
+#[derive(Debug, Clone)]
+pub struct MockConnector {
+    server: MockServer,
+}
+
+impl<R> Service<R> for MockConnector {
+    type Response = MockTransport;
+    type Error = Error;
+    type Future = Ready<Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, _request: R) -> Self::Future {
+        future::ready(Ok(self.server.connect()))
+    }
+}
+
+/// A [`Service`] that sends requests directly to a [`MockServer`]. Created by
+/// [`MockServer::service`].
+///
+/// Unlike a [`MockTransport`], this never disconnects, and responses are returned immediately.
+#[derive(Debug, Clone)]
+pub struct MockService {
+    server: MockServer,
+}
+
+impl Service<RequestEnvelope> for MockService {
+    type Response = ResponseEnvelope;
+    type Error = Error;
+    type Future = Ready<Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: RequestEnvelope) -> Self::Future {
+        future::ready(Ok(self.server.lock().respond(req)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::{
+        Event, StatisticsRequest, StatisticsResponse, TestEvent, VtsFolderInfoRequest,
+    };
+    use crate::service::ApiService;
+    use crate::{Client, ClientEvent};
+    use futures_util::StreamExt;
+
+    #[tokio::test]
+    async fn responses_and_events() -> Result<(), Error> {
+        let server = MockServer::new();
+        server.respond::<StatisticsRequest>(&StatisticsResponse {
+            uptime: 1,
+            ..Default::default()
+        })?;
+
+        let (mut client, mut events) = Client::builder().build_connector(server.connector());
+
+        assert_eq!(client.send(&StatisticsRequest {}).await?.uptime, 1);
+
+        // More recently registered handlers take priority
+        server.respond::<StatisticsRequest>(&StatisticsResponse {
+            uptime: 2,
+            ..Default::default()
+        })?;
+        assert_eq!(client.send(&StatisticsRequest {}).await?.uptime, 2);
+
+        let err = client.send(&VtsFolderInfoRequest {}).await.unwrap_err();
+        assert!(err.is_api_error());
+        assert_eq!(server.unexpected().len(), 1);
+        assert_eq!(server.received().len(), 3);
+
+        let event = TestEvent {
+            your_test_message: "hello".into(),
+            counter: 5,
+        };
+        assert_eq!(server.send_event(&event)?, 1);
+
+        loop {
+            match events.next().await {
+                Some(ClientEvent::Api(Event::Test(received))) => {
+                    assert_eq!(received, event);
+                    break;
+                }
+                Some(_) => continue,
+                None => panic!("event stream ended"),
+            }
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn reconnect_after_disconnect() -> Result<(), Error> {
+        let server = MockServer::new();
+        server.respond::<StatisticsRequest>(&StatisticsResponse::default())?;
+
+        let (mut client, mut events) = Client::builder().build_connector(server.connector());
+
+        client.send(&StatisticsRequest {}).await?;
+        assert_eq!(server.connection_count(), 1);
+
+        server.disconnect_all();
+        loop {
+            match events.next().await {
+                Some(ClientEvent::Disconnected) => break,
+                Some(_) => continue,
+                None => panic!("event stream ended"),
+            }
+        }
+
+        client.send(&StatisticsRequest {}).await?;
+        assert_eq!(server.connection_count(), 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn reconnecting_events() -> Result<(), Error> {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let server = MockServer::new();
+        server.respond::<StatisticsRequest>(&StatisticsResponse::default())?;
+
+        // Fails the first two connection attempts
+        let attempts = Arc::new(AtomicUsize::new(0));
+        let connector = tower::service_fn({
+            let server = server.clone();
+            move |_: String| {
+                let result = if attempts.fetch_add(1, Ordering::Relaxed) < 2 {
+                    Err(Error::new(ErrorKind::ConnectionRefused))
+                } else {
+                    Ok(server.connect())
+                };
+                future::ready(result)
+            }
+        });
+
+        let (mut client, mut events) = Client::builder().build_connector(connector);
+        for _ in 0..2 {
+            let err = client.send(&StatisticsRequest {}).await.unwrap_err();
+            assert!(err.has_kind(ErrorKind::ConnectionRefused), "{:?}", err);
+        }
+        client.send(&StatisticsRequest {}).await?;
+
+        server.disconnect_all();
+        loop {
+            match events.next().await {
+                Some(ClientEvent::Disconnected) => break,
+                Some(_) => continue,
+                None => panic!("event stream ended"),
+            }
+        }
+        client.send(&StatisticsRequest {}).await?;
+
+        let mut received = Vec::new();
+        while let Ok(Some(event)) =
+            tokio::time::timeout(std::time::Duration::from_millis(100), events.next()).await
+        {
+            match event {
+                ClientEvent::Reconnecting { attempt } => received.push(Some(attempt)),
+                ClientEvent::Connected => received.push(None),
+                _ => {}
+            }
+        }
+
+        // The initial attempt doesn't count as a reconnect
+        assert_eq!(received, [Some(1), Some(2), None, Some(1), None]);
+
+        Ok(())
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn give_up_reconnecting() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::time::Duration;
+
+        let attempts = Arc::new(AtomicUsize::new(0));
+        let connector = tower::service_fn({
+            let attempts = Arc::clone(&attempts);
+            move |_: String| {
+                attempts.fetch_add(1, Ordering::Relaxed);
+                future::ready(Err::<MockTransport, _>(Error::new(
+                    ErrorKind::ConnectionRefused,
+                )))
+            }
+        });
+
+        let (mut client, mut events) = Client::builder()
+            .max_reconnect_attempts(2)
+            .reconnect_delay(Duration::from_secs(1))
+            .build_connector(connector);
+
+        let start = tokio::time::Instant::now();
+        for _ in 0..5 {
+            let err = client.send(&StatisticsRequest {}).await.unwrap_err();
+            assert!(err.has_kind(ErrorKind::ConnectionRefused), "{:?}", err);
+        }
+
+        // The initial attempt and 2 reconnects, each reconnect after a delay
+        assert_eq!(attempts.load(Ordering::Relaxed), 3);
+        assert!(start.elapsed() >= Duration::from_secs(2));
+
+        let mut received = Vec::new();
+        while let Ok(Some(event)) = tokio::time::timeout(Duration::from_secs(1), events.next()).await
+        {
+            match event {
+                ClientEvent::Reconnecting { attempt } => received.push(Some(attempt)),
+                ClientEvent::GaveUp => received.push(None),
+                _ => {}
+            }
+        }
+        assert_eq!(received, [Some(1), Some(2), None]);
+    }
+
+    #[tokio::test]
+    async fn connection_callbacks() -> Result<(), Error> {
+        let server = MockServer::new();
+        server.respond::<StatisticsRequest>(&StatisticsResponse::default())?;
+        server.respond::<VtsFolderInfoRequest>(&Default::default())?;
+
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let wait_for_log = |len: usize| {
+            let log = Arc::clone(&log);
+            async move {
+                for _ in 0..10_000 {
+                    if log.lock().unwrap().len() >= len {
+                        return;
+                    }
+                    tokio::task::yield_now().await;
+                }
+                panic!("timed out waiting for {} callbacks", len);
+            }
+        };
+
+        let (mut client, _events) = Client::builder()
+            .on_connect({
+                let log = Arc::clone(&log);
+                move |mut client| {
+                    let log = Arc::clone(&log);
+                    async move {
+                        // The client can be used from within the callback
+                        client.send(&VtsFolderInfoRequest {}).await?;
+                        log.lock().unwrap().push("connect");
+                        Ok(())
+                    }
+                }
+            })
+            .on_disconnect({
+                let log = Arc::clone(&log);
+                move |_client| {
+                    let log = Arc::clone(&log);
+                    async move {
+                        log.lock().unwrap().push("disconnect");
+                        Err(Error::new(ErrorKind::Other))
+                    }
+                }
+            })
+            .build_connector(server.connector());
+
+        client.send(&StatisticsRequest {}).await?;
+        wait_for_log(1).await;
+
+        server.disconnect_all();
+        wait_for_log(2).await;
+
+        // Errors from callbacks don't affect the client
+        client.send(&StatisticsRequest {}).await?;
+        wait_for_log(3).await;
+
+        assert_eq!(*log.lock().unwrap(), ["connect", "disconnect", "connect"]);
+        assert_eq!(
+            server.received_message_types(),
+            [
+                "StatisticsRequest",
+                "VTSFolderInfoRequest",
+                "StatisticsRequest",
+                "VTSFolderInfoRequest",
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn build_service() -> Result<(), Error> {
+        let server = MockServer::new();
+        server.respond::<StatisticsRequest>(&StatisticsResponse {
+            uptime: 1,
+            ..Default::default()
+        })?;
+
+        let (service, mut events) = ApiService::new(server.connect(), 16);
+        let (mut client, _) = Client::builder().build_service(service);
+
+        assert_eq!(client.send(&StatisticsRequest {}).await?.uptime, 1);
+
+        server.send_event(&TestEvent::default())?;
+        let event = events.next().await.unwrap()?;
+        assert!(matches!(event, Event::Test(_)));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,771 @@
+mod auth;
+mod mock;
+mod replay;
+
+pub use crate::testing::auth::MockAuthentication;
+pub use crate::testing::mock::{MockConnector, MockServer, MockService, MockTransport};
+pub use crate::testing::replay::{RecordedTransport, Recording, ReplayTransport};
+
+use crate::data::{ApiError, ErrorId, EventData, Request, RequestEnvelope, ResponseEnvelope};
+
+use futures_util::{SinkExt, StreamExt};
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::fmt;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+use tokio_tungstenite::tungstenite::Message;
+
+/// Describes which incoming requests a response applies to. Used in [`FakeVtsServer`] and
+/// [`MockServer`].
+pub struct RequestMatcher {
+    description: String,
+    predicate: Box<dyn Fn(&RequestEnvelope) -> bool + Send + Sync>,
+}
+
+impl fmt::Debug for RequestMatcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RequestMatcher")
+            .field("description", &self.description)
+            .finish()
+    }
+}
+
+impl RequestMatcher {
+    /// Matches any request.
+    pub fn any() -> Self {
+        Self::predicate("any request", |_| true)
+    }
+
+    /// Matches requests with the same message type as `Req`, regardless of the data.
+    pub fn message_type<Req: Request>() -> Self {
+        Self::predicate(Req::MESSAGE_TYPE.as_str().to_owned(), |req| {
+            req.message_type == Req::MESSAGE_TYPE
+        })
+    }
+
+    /// Matches requests with the same message type and data as `data`.
+    ///
+    /// Data is compared as JSON values, so field order doesn't matter.
+    pub fn request<Req: Request>(data: &Req) -> Result<Self, serde_json::Error> {
+        let expected = serde_json::to_value(data)?;
+        let description = format!("{} {}", Req::MESSAGE_TYPE, expected);
+
+        Ok(Self::predicate(description, move |req| {
+            req.message_type == Req::MESSAGE_TYPE
+                && req
+                    .data
+                    .deserialize::<serde_json::Value>()
+                    .is_ok_and(|value| value == expected)
+        }))
+    }
+
+    /// Matches requests using an arbitrary predicate.
+    pub fn predicate<S, F>(description: S, predicate: F) -> Self
+    where
+        S: Into<String>,
+        F: Fn(&RequestEnvelope) -> bool + Send + Sync + 'static,
+    {
+        Self {
+            description: description.into(),
+            predicate: Box::new(predicate),
+        }
+    }
+
+    /// Returns `true` if the request matches.
+    pub fn matches(&self, req: &RequestEnvelope) -> bool {
+        (self.predicate)(req)
+    }
+}
+
+/// A websocket server that pretends to be VTube Studio, responding to requests according to a
+/// script. Intended for testing [`Client`](crate::Client)s end-to-end without running VTube
+/// Studio.
+///
+/// Each incoming request is compared to the next [`RequestMatcher`] in the script. If it matches,
+/// the corresponding [`ResponseEnvelope`] is returned (with the request ID replaced). Otherwise, an
+/// [`ApiError`] is returned and the request is recorded as unexpected.
+///
+/// The server stops when this value is dropped.
+///
+/// # Example
+///
+#[cfg_attr(feature = "testing", doc = "```")]
+#[cfg_attr(not(feature = "testing"), doc = "```ignore")]
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), vtubestudio::error::BoxError> {
+/// use vtubestudio::data::{ResponseEnvelope, StatisticsRequest, StatisticsResponse};
+/// use vtubestudio::testing::{FakeVtsServer, RequestMatcher};
+/// use vtubestudio::Client;
+///
+/// let server = FakeVtsServer::start(vec![(
+///     RequestMatcher::message_type::<StatisticsRequest>(),
+///     ResponseEnvelope::new(&StatisticsResponse {
+///         uptime: 1000,
+///         ..Default::default()
+///     })?,
+/// )])
+/// .await?;
+///
+/// let (mut client, _) = Client::builder().url(server.url()).build_tungstenite();
+///
+/// let resp = client.send(&StatisticsRequest {}).await?;
+/// assert_eq!(resp.uptime, 1000);
+///
+/// server.assert_all_matched();
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct FakeVtsServer {
+    addr: SocketAddr,
+    state: Arc<Mutex<ServerState>>,
+    outgoing: broadcast::Sender<ServerMessage>,
+    task: JoinHandle<()>,
+}
+
+#[derive(Debug)]
+struct ServerState {
+    script: VecDeque<(RequestMatcher, ResponseEnvelope)>,
+    unexpected: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+enum ServerMessage {
+    Text(String),
+    Close,
+}
+
+impl FakeVtsServer {
+    /// Starts a server listening on a random local port.
+    pub async fn start(
+        script: Vec<(RequestMatcher, ResponseEnvelope)>,
+    ) -> Result<Self, std::io::Error> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let addr = listener.local_addr()?;
+
+        let state = Arc::new(Mutex::new(ServerState {
+            script: script.into(),
+            unexpected: Vec::new(),
+        }));
+
+        let (outgoing, _) = broadcast::channel(128);
+
+        let task = tokio::spawn({
+            let state = Arc::clone(&state);
+            let outgoing = outgoing.clone();
+            async move {
+                while let Ok((stream, _)) = listener.accept().await {
+                    tokio::spawn(handle_connection(
+                        stream,
+                        Arc::clone(&state),
+                        outgoing.subscribe(),
+                    ));
+                }
+            }
+        });
+
+        Ok(Self {
+            addr,
+            state,
+            outgoing,
+            task,
+        })
+    }
+
+    /// The websocket URL of this server (e.g., `ws://127.0.0.1:12345`), suitable for passing to
+    /// [`ClientBuilder::url`](crate::ClientBuilder::url).
+    pub fn url(&self) -> String {
+        format!("ws://{}", self.addr)
+    }
+
+    /// The local address of this server.
+    pub fn local_addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Appends a request/response pair to the end of the script.
+    pub fn push(&self, matcher: RequestMatcher, response: ResponseEnvelope) {
+        self.lock().script.push_back((matcher, response));
+    }
+
+    /// Sends an event to all connected clients.
+    ///
+    /// Returns the number of connected clients the event was sent to.
+    pub fn send_event<T>(&self, data: &T) -> Result<usize, serde_json::Error>
+    where
+        T: EventData + Serialize,
+    {
+        let text = serde_json::to_string(&ResponseEnvelope::new(data)?)?;
+        Ok(self.outgoing.send(ServerMessage::Text(text)).unwrap_or(0))
+    }
+
+    /// Closes all current connections. The server will still accept new connections.
+    pub fn disconnect_all(&self) {
+        let _ = self.outgoing.send(ServerMessage::Close);
+    }
+
+    /// Returns the number of scripted responses that haven't been matched yet.
+    pub fn remaining(&self) -> usize {
+        self.lock().script.len()
+    }
+
+    /// Panics if any scripted requests were not received, or if any unexpected requests were
+    /// received.
+    #[track_caller]
+    pub fn assert_all_matched(&self) {
+        let state = self.lock();
+
+        assert!(
+            state.unexpected.is_empty(),
+            "FakeVtsServer received unexpected requests: {:#?}",
+            state.unexpected
+        );
+
+        assert!(
+            state.script.is_empty(),
+            "FakeVtsServer did not receive expected requests: {:#?}",
+            state
+                .script
+                .iter()
+                .map(|(matcher, _)| &matcher.description)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, ServerState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Drop for FakeVtsServer {
+    fn drop(&mut self) {
+        self.task.abort();
+        self.disconnect_all();
+    }
+}
+
+impl ServerState {
+    fn respond(&mut self, text: &str) -> ResponseEnvelope {
+        let req = match serde_json::from_str::<RequestEnvelope>(text) {
+            Ok(req) => req,
+            Err(e) => {
+                self.unexpected.push(text.to_owned());
+                return api_error(ErrorId::JSON_INVALID, e.to_string());
+            }
+        };
+
+        let request_id = req.request_id.clone().unwrap_or_default();
+
+        match self.script.front() {
+            Some((matcher, _)) if matcher.matches(&req) => {
+                let (_, resp) = self.script.pop_front().expect("script should be non-empty");
+                resp.with_id(request_id)
+            }
+            expected => {
+                let message = match expected {
+                    Some((matcher, _)) => format!(
+                        "FakeVtsServer expected {}, received {}",
+                        matcher.description, text
+                    ),
+                    None => format!("FakeVtsServer received unscripted request {}", text),
+                };
+
+                self.unexpected.push(text.to_owned());
+                api_error(ErrorId::INTERNAL_SERVER_ERROR, message).with_id(request_id)
+            }
+        }
+    }
+}
+
+fn api_error(error_id: ErrorId, message: String) -> ResponseEnvelope {
+    ResponseEnvelope {
+        data: Err(ApiError { error_id, message }),
+        ..Default::default()
+    }
+}
+
+async fn handle_connection(
+    stream: TcpStream,
+    state: Arc<Mutex<ServerState>>,
+    mut outgoing: broadcast::Receiver<ServerMessage>,
+) {
+    let ws = match tokio_tungstenite::accept_async(stream).await {
+        Ok(ws) => ws,
+        Err(error) => {
+            tracing::warn!(%error, "FakeVtsServer failed to accept websocket connection");
+            return;
+        }
+    };
+
+    let (mut sink, mut stream) = ws.split();
+
+    loop {
+        let reply = tokio::select! {
+            msg = stream.next() => match msg {
+                Some(Ok(Message::Text(text))) => {
+                    let resp = state
+                        .lock()
+                        .unwrap_or_else(|e| e.into_inner())
+                        .respond(&text);
+
+                    match serde_json::to_string(&resp) {
+                        Ok(text) => text,
+                        Err(_) => continue,
+                    }
+                }
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => continue,
+            },
+            msg = outgoing.recv() => match msg {
+                Ok(ServerMessage::Text(text)) => text,
+                Ok(ServerMessage::Close) | Err(broadcast::error::RecvError::Closed) => break,
+                Err(broadcast::error::RecvError::Lagged(_)) => continue,
+            },
+        };
+
+        if sink.send(Message::Text(reply)).await.is_err() {
+            break;
+        }
+    }
+
+    let _ = sink.close().await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::{
+        ApiStateRequest, ApiStateResponse, EventSubscriptionRequest, EventSubscriptionResponse,
+        StatisticsRequest, StatisticsResponse, TestEvent, TestEventConfig,
+    };
+    use crate::{Client, ClientEvent, Error};
+
+    #[tokio::test]
+    async fn scripted_responses_and_events() -> Result<(), Error> {
+        let subscribe = EventSubscriptionRequest::subscribe(&TestEventConfig {
+            test_message_for_event: "hello".into(),
+        })?;
+
+        let server = FakeVtsServer::start(vec![
+            (
+                RequestMatcher::request(&subscribe)?,
+                ResponseEnvelope::new(&EventSubscriptionResponse::default())?,
+            ),
+            (
+                RequestMatcher::message_type::<StatisticsRequest>(),
+                ResponseEnvelope::new(&StatisticsResponse {
+                    uptime: 1234,
+                    ..Default::default()
+                })?,
+            ),
+        ])
+        .await
+        .unwrap();
+
+        let (mut client, mut events) = Client::builder().url(server.url()).build_tungstenite();
+
+        client.send(&subscribe).await?;
+        assert_eq!(client.send(&StatisticsRequest {}).await?.uptime, 1234);
+
+        let event = TestEvent {
+            your_test_message: "hello".into(),
+            counter: 5,
+        };
+        assert_eq!(server.send_event(&event)?, 1);
+
+        loop {
+            match events.next().await {
+                Some(ClientEvent::Api(crate::data::Event::Test(received))) => {
+                    assert_eq!(received, event);
+                    break;
+                }
+                Some(_) => continue,
+                None => panic!("event stream ended"),
+            }
+        }
+
+        server.assert_all_matched();
+
+        Ok(())
+    }
+
+    #[cfg(feature = "async-tungstenite")]
+    #[tokio::test]
+    async fn async_tungstenite_client() -> Result<(), Error> {
+        use async_tungstenite::tokio::TokioAdapter;
+        use tokio::net::TcpStream;
+
+        let server = FakeVtsServer::start(vec![(
+            RequestMatcher::message_type::<StatisticsRequest>(),
+            ResponseEnvelope::new(&StatisticsResponse {
+                uptime: 1234,
+                ..Default::default()
+            })?,
+        )])
+        .await
+        .unwrap();
+
+        let (mut client, _events) = Client::builder()
+            .url(server.url())
+            .build_async_tungstenite(|addr| async move {
+                TcpStream::connect(addr).await.map(TokioAdapter::new)
+            });
+
+        assert_eq!(client.send(&StatisticsRequest {}).await?.uptime, 1234);
+        server.assert_all_matched();
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn max_message_size() -> Result<(), Error> {
+        use crate::data::{CurrentModelRequest, CurrentModelResponse};
+        use crate::ErrorKind;
+
+        let server = FakeVtsServer::start(vec![(
+            RequestMatcher::message_type::<CurrentModelRequest>(),
+            ResponseEnvelope::new(&CurrentModelResponse {
+                model_name: "a".repeat(4096),
+                ..Default::default()
+            })?,
+        )])
+        .await
+        .unwrap();
+
+        let (mut client, mut events) = Client::builder()
+            .url(server.url())
+            .max_message_size(Some(1024))
+            .retry_on_disconnect(false)
+            .build_tungstenite();
+
+        let err = client.send(&CurrentModelRequest {}).await.unwrap_err();
+        assert!(err.has_kind(ErrorKind::ConnectionDropped), "{:?}", err);
+
+        loop {
+            match events.next().await {
+                Some(ClientEvent::Error(e)) => {
+                    assert!(e.has_kind(ErrorKind::MessageTooLarge), "{:?}", e);
+                    break;
+                }
+                Some(_) => continue,
+                None => panic!("event stream ended"),
+            }
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn keepalive_timeout() -> Result<(), Error> {
+        use crate::ErrorKind;
+        use std::time::Duration;
+
+        // A server that completes the handshake, but never reads (or responds to pings)
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        let server = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+            tokio::time::sleep(Duration::from_secs(60)).await;
+            drop(ws);
+        });
+
+        let (mut client, mut events) = Client::builder()
+            .url(url)
+            .keepalive(Duration::from_millis(50))
+            .keepalive_timeout(Duration::from_millis(100))
+            .retry_on_disconnect(false)
+            .build_tungstenite();
+
+        let err = tokio::time::timeout(Duration::from_secs(5), client.send(&StatisticsRequest {}))
+            .await
+            .expect("keepalive timeout was not detected")
+            .unwrap_err();
+        assert!(err.has_kind(ErrorKind::ConnectionDropped), "{:?}", err);
+
+        // The initial `Disconnected` event is sent on startup
+        assert!(matches!(events.next().await, Some(ClientEvent::Disconnected)));
+        assert!(matches!(events.next().await, Some(ClientEvent::Connected)));
+        assert!(matches!(events.next().await, Some(ClientEvent::Disconnected)));
+
+        server.abort();
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn typed_event_channels() -> Result<(), Error> {
+        use crate::data::ModelLoadedEvent;
+        use tokio::sync::broadcast::error::TryRecvError;
+
+        let server = FakeVtsServer::start(vec![(
+            RequestMatcher::message_type::<StatisticsRequest>(),
+            ResponseEnvelope::new(&StatisticsResponse::default())?,
+        )])
+        .await
+        .unwrap();
+
+        let (mut client, _events) = Client::builder().url(server.url()).build_tungstenite();
+
+        let mut first = client.event_channel::<TestEvent>();
+        let mut second = client.clone().event_channel::<TestEvent>();
+        let mut model_loaded = client.event_channel::<ModelLoadedEvent>();
+
+        client.send(&StatisticsRequest {}).await?;
+
+        let event = TestEvent {
+            your_test_message: "hello".into(),
+            counter: 5,
+        };
+        assert_eq!(server.send_event(&event)?, 1);
+
+        assert_eq!(first.recv().await.unwrap(), event);
+        assert_eq!(second.recv().await.unwrap(), event);
+        assert!(matches!(model_loaded.try_recv(), Err(TryRecvError::Empty)));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn client_close() -> Result<(), Error> {
+        use crate::client::ConnectionState;
+
+        let server = FakeVtsServer::start(vec![
+            (
+                RequestMatcher::message_type::<StatisticsRequest>(),
+                ResponseEnvelope::new(&StatisticsResponse::default())?,
+            ),
+            (
+                RequestMatcher::message_type::<StatisticsRequest>(),
+                ResponseEnvelope::new(&StatisticsResponse::default())?,
+            ),
+        ])
+        .await
+        .unwrap();
+
+        let (mut client, mut events) = Client::builder().url(server.url()).build_tungstenite();
+
+        client.send(&StatisticsRequest {}).await?;
+        assert_eq!(client.connection_state(), ConnectionState::Connected);
+
+        client.close().await;
+        assert_eq!(client.connection_state(), ConnectionState::Disconnected);
+
+        let mut received = Vec::new();
+        while let Ok(Some(event)) =
+            tokio::time::timeout(std::time::Duration::from_millis(100), events.next()).await
+        {
+            received.push(event);
+        }
+
+        assert!(matches!(
+            received.as_slice(),
+            [
+                ClientEvent::Disconnected,
+                ClientEvent::Connected,
+                ClientEvent::Disconnected
+            ]
+        ));
+
+        // Sending another request reconnects
+        client.send(&StatisticsRequest {}).await?;
+        server.assert_all_matched();
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn connection_state() -> Result<(), Error> {
+        use crate::client::ConnectionState;
+        use crate::data::{AuthenticationRequest, AuthenticationResponse};
+
+        let server = FakeVtsServer::start(vec![
+            (
+                RequestMatcher::message_type::<AuthenticationRequest>(),
+                ResponseEnvelope::new(&AuthenticationResponse {
+                    authenticated: true,
+                    reason: String::new(),
+                })?,
+            ),
+            (
+                RequestMatcher::message_type::<StatisticsRequest>(),
+                ResponseEnvelope::new(&StatisticsResponse::default())?,
+            ),
+        ])
+        .await
+        .unwrap();
+
+        let (mut client, _events) = Client::builder()
+            .url(server.url())
+            .authentication("Plugin name", "Developer name", None)
+            .auth_token(Some("token".into()))
+            .build_tungstenite();
+
+        assert_eq!(client.connection_state(), ConnectionState::Disconnected);
+
+        client.send(&StatisticsRequest {}).await?;
+        assert_eq!(client.connection_state(), ConnectionState::Authenticated);
+
+        client.close().await;
+        assert_eq!(client.connection_state(), ConnectionState::Disconnected);
+
+        server.assert_all_matched();
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn unexpected_request() -> Result<(), Error> {
+        let server = FakeVtsServer::start(vec![(
+            RequestMatcher::message_type::<ApiStateRequest>(),
+            ResponseEnvelope::new(&ApiStateResponse::default())?,
+        )])
+        .await
+        .unwrap();
+
+        let (mut client, _) = Client::builder().url(server.url()).build_tungstenite();
+
+        let err = client.send(&StatisticsRequest {}).await.unwrap_err();
+        assert!(err.is_api_error());
+        assert_eq!(server.remaining(), 1);
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            server.assert_all_matched()
+        }));
+        assert!(result.is_err());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn permissions() -> Result<(), Error> {
+        use crate::data::{Permission, PermissionRequest, PermissionResponse, PermissionStatus};
+
+        let not_granted = PermissionResponse {
+            grant_success: false,
+            requested_permission: None,
+            permissions: vec![PermissionStatus {
+                name: Permission::LoadCustomImagesAsItems.into(),
+                granted: false,
+            }],
+        };
+        let granted = PermissionResponse {
+            grant_success: true,
+            requested_permission: Some(Permission::LoadCustomImagesAsItems.into()),
+            permissions: vec![PermissionStatus {
+                name: Permission::LoadCustomImagesAsItems.into(),
+                granted: true,
+            }],
+        };
+
+        let server = FakeVtsServer::start(vec![
+            (
+                RequestMatcher::request(&PermissionRequest {
+                    requested_permission: None,
+                })?,
+                ResponseEnvelope::new(&not_granted)?,
+            ),
+            (
+                RequestMatcher::request(&PermissionRequest {
+                    requested_permission: Some(Permission::LoadCustomImagesAsItems.into()),
+                })?,
+                ResponseEnvelope::new(&granted)?,
+            ),
+            (
+                RequestMatcher::message_type::<PermissionRequest>(),
+                ResponseEnvelope::new(&PermissionResponse {
+                    permissions: Vec::new(),
+                    ..granted
+                })?,
+            ),
+        ])
+        .await
+        .unwrap();
+
+        let (mut client, _) = Client::builder().url(server.url()).build_tungstenite();
+
+        assert!(
+            !client
+                .has_permission(Permission::LoadCustomImagesAsItems)
+                .await?
+        );
+        assert!(
+            client
+                .request_permission(Permission::LoadCustomImagesAsItems)
+                .await?
+        );
+        // Permissions missing from the list are treated as not granted
+        assert!(
+            !client
+                .request_permission(Permission::LoadCustomImagesAsItems)
+                .await?
+        );
+
+        server.assert_all_matched();
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn trigger_hotkey_by_name() -> Result<(), Error> {
+        use crate::data::{
+            Hotkey, HotkeyTriggerRequest, HotkeyTriggerResponse, HotkeysInCurrentModelRequest,
+            HotkeysInCurrentModelResponse,
+        };
+        use crate::ErrorKind;
+
+        let hotkeys = ResponseEnvelope::new(&HotkeysInCurrentModelResponse {
+            available_hotkeys: vec![
+                Hotkey {
+                    name: "Eyes Cry".into(),
+                    hotkey_id: "abc".into(),
+                    ..Default::default()
+                },
+                Hotkey {
+                    name: "Wave".into(),
+                    hotkey_id: "def".into(),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        })?;
+
+        let server = FakeVtsServer::start(vec![
+            (
+                RequestMatcher::message_type::<HotkeysInCurrentModelRequest>(),
+                hotkeys.clone(),
+            ),
+            (
+                RequestMatcher::request(&HotkeyTriggerRequest {
+                    hotkey_id: "def".into(),
+                    item_instance_id: None,
+                })?,
+                ResponseEnvelope::new(&HotkeyTriggerResponse {
+                    hotkey_id: "def".into(),
+                })?,
+            ),
+            (
+                RequestMatcher::message_type::<HotkeysInCurrentModelRequest>(),
+                hotkeys,
+            ),
+        ])
+        .await
+        .unwrap();
+
+        let (mut client, _) = Client::builder().url(server.url()).build_tungstenite();
+
+        let resp = client.trigger_hotkey_by_name("Wave").await?;
+        assert_eq!(resp.hotkey_id.as_str(), "def");
+
+        let err = client.trigger_hotkey_by_name("Missing").await.unwrap_err();
+        assert!(err.has_kind(ErrorKind::HotkeyNotFound));
+        assert!(!err.is_api_error());
+
+        server.assert_all_matched();
+
+        Ok(())
+    }
+}
@@ -0,0 +1,409 @@
+use crate::data::{ErrorId, RequestEnvelope, ResponseEnvelope};
+use crate::error::{Error, ErrorKind};
+use crate::testing::api_error;
+
+use futures_core::{Stream, TryStream};
+use futures_sink::Sink;
+use pin_project_lite::pin_project;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tokio::sync::mpsc;
+
+/// A list of request/response pairs, as recorded by [`RecordedTransport`] and replayed by
+/// [`ReplayTransport`].
+///
+/// This can be serialized with `serde_json` (e.g., to save a session to disk).
+pub type Recording = Vec<(RequestEnvelope, ResponseEnvelope)>;
+
+pin_project! {
+    /// A transport that records all request/response pairs passing through an inner transport.
+    ///
+    /// Responses are paired with requests by request ID. Events (and any other responses that
+    /// don't correspond to a sent request) are passed through without being recorded.
+    ///
+    /// The resulting [`Recording`] can be serialized to JSON and replayed later with a
+    /// [`ReplayTransport`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), vtubestudio::error::BoxError> {
+    /// use futures_util::{SinkExt, StreamExt};
+    /// use vtubestudio::data::{RequestEnvelope, StatisticsRequest, StatisticsResponse};
+    /// use vtubestudio::testing::{MockServer, RecordedTransport};
+    ///
+    /// let server = MockServer::new();
+    /// server.respond::<StatisticsRequest>(&StatisticsResponse::default())?;
+    ///
+    /// let mut transport = RecordedTransport::new(server.connect());
+    /// transport
+    ///     .send(RequestEnvelope::new(&StatisticsRequest {})?.with_id(Some("1".into())))
+    ///     .await?;
+    /// transport.next().await.unwrap()?;
+    ///
+    /// let recording = transport.into_recording();
+    /// assert_eq!(recording.len(), 1);
+    ///
+    /// let json = serde_json::to_string_pretty(&recording)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[derive(Debug)]
+    pub struct RecordedTransport<T> {
+        #[pin]
+        transport: T,
+        pending: Vec<RequestEnvelope>,
+        recording: Recording,
+    }
+}
+
+impl<T> RecordedTransport<T> {
+    /// Creates a new [`RecordedTransport`] wrapping `transport`.
+    pub fn new(transport: T) -> Self {
+        Self {
+            transport,
+            pending: Vec::new(),
+            recording: Vec::new(),
+        }
+    }
+
+    /// Returns the request/response pairs recorded so far.
+    pub fn recording(&self) -> &[(RequestEnvelope, ResponseEnvelope)] {
+        &self.recording
+    }
+
+    /// Consumes `self`, returning the recorded request/response pairs.
+    ///
+    /// Requests that haven't received a response yet are not included.
+    pub fn into_recording(self) -> Recording {
+        self.recording
+    }
+}
+
+impl<T> Sink<RequestEnvelope> for RecordedTransport<T>
+where
+    T: Sink<RequestEnvelope>,
+{
+    type Error = T::Error;
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.project().transport.poll_ready(cx)
+    }
+
+    fn start_send(self: Pin<&mut Self>, item: RequestEnvelope) -> Result<(), Self::Error> {
+        let this = self.project();
+        this.transport.start_send(item.clone())?;
+        this.pending.push(item);
+        Ok(())
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.project().transport.poll_flush(cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.project().transport.poll_close(cx)
+    }
+}
+
+impl<T> Stream for RecordedTransport<T>
+where
+    T: TryStream<Ok = ResponseEnvelope>,
+{
+    type Item = Result<ResponseEnvelope, T::Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        let item = futures_util::ready!(this.transport.try_poll_next(cx));
+
+        if let Some(Ok(resp)) = &item {
+            let index = this
+                .pending
+                .iter()
+                .position(|req| req.request_id.as_ref() == Some(&resp.request_id));
+
+            if let Some(index) = index {
+                let req = this.pending.remove(index);
+                this.recording.push((req, resp.clone()));
+            }
+        }
+
+        Poll::Ready(item)
+    }
+}
+
+/// A transport that responds to requests using a [`Recording`] (e.g., one captured by
+/// [`RecordedTransport`]), without connecting to VTube Studio.
+///
+/// Each request is answered with the response from the first unused recorded pair with the same
+/// message type, so a session that sends the same requests in the same order is replayed exactly.
+/// Once all recorded pairs of a message type have been used, the most recently used one is
+/// repeated. Requests with a message type that doesn't appear in the recording receive an
+/// [`ApiError`](crate::data::ApiError) response.
+///
+/// The request ID of each response is replaced with the ID of the request.
+///
+/// # Example
+///
+/// ```
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), vtubestudio::error::BoxError> {
+/// use vtubestudio::service::ApiService;
+/// use vtubestudio::testing::{Recording, ReplayTransport};
+/// use vtubestudio::data::StatisticsRequest;
+/// use vtubestudio::Client;
+///
+/// let json = r#"[[
+///     {
+///         "apiName": "VTubeStudioPublicAPI",
+///         "apiVersion": "1.0",
+///         "requestID": "1",
+///         "messageType": "StatisticsRequest",
+///         "data": {}
+///     },
+///     {
+///         "apiName": "VTubeStudioPublicAPI",
+///         "apiVersion": "1.0",
+///         "timestamp": 1625405710728,
+///         "requestID": "1",
+///         "messageType": "StatisticsResponse",
+///         "data": {
+///             "uptime": 1000,
+///             "framerate": 60,
+///             "vTubeStudioVersion": "1.28.0",
+///             "allowedPlugins": 1,
+///             "connectedPlugins": 1,
+///             "startedWithSteam": true,
+///             "windowWidth": 1920,
+///             "windowHeight": 1080,
+///             "windowIsFullscreen": false
+///         }
+///     }
+/// ]]"#;
+///
+/// let recording: Recording = serde_json::from_str(json)?;
+/// let (service, _events) = ApiService::new(ReplayTransport::new(recording), 16);
+/// let (mut client, _) = Client::builder().build_service(service);
+///
+/// assert_eq!(client.send(&StatisticsRequest {}).await?.uptime, 1000);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ReplayTransport {
+    recording: Vec<ReplayEntry>,
+    sender: Option<mpsc::UnboundedSender<ResponseEnvelope>>,
+    receiver: mpsc::UnboundedReceiver<ResponseEnvelope>,
+}
+
+#[derive(Debug)]
+struct ReplayEntry {
+    request: RequestEnvelope,
+    response: ResponseEnvelope,
+    used: bool,
+}
+
+impl ReplayTransport {
+    /// Creates a new [`ReplayTransport`] that responds using the given request/response pairs.
+    pub fn new(recording: Recording) -> Self {
+        let (sender, receiver) = mpsc::unbounded_channel();
+
+        Self {
+            recording: recording
+                .into_iter()
+                .map(|(request, response)| ReplayEntry {
+                    request,
+                    response,
+                    used: false,
+                })
+                .collect(),
+            sender: Some(sender),
+            receiver,
+        }
+    }
+
+    /// Returns the number of recorded pairs that haven't been replayed yet.
+    pub fn remaining(&self) -> usize {
+        self.recording.iter().filter(|entry| !entry.used).count()
+    }
+
+    fn respond(&mut self, req: &RequestEnvelope) -> ResponseEnvelope {
+        let request_id = req.request_id.clone().unwrap_or_default();
+        let matches_type = |entry: &&mut ReplayEntry| entry.request.message_type == req.message_type;
+
+        let response = match self
+            .recording
+            .iter_mut()
+            .filter(matches_type)
+            .find(|entry| !entry.used)
+        {
+            Some(entry) => {
+                entry.used = true;
+                entry.response.clone()
+            }
+            None => match self.recording.iter_mut().rev().find(matches_type) {
+                Some(entry) => entry.response.clone(),
+                None => api_error(
+                    ErrorId::INTERNAL_SERVER_ERROR,
+                    format!(
+                        "ReplayTransport has no recorded response for {}",
+                        req.message_type.as_str()
+                    ),
+                ),
+            },
+        };
+
+        response.with_id(request_id)
+    }
+}
+
+impl Sink<RequestEnvelope> for ReplayTransport {
+    type Error = Error;
+
+    fn poll_ready(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn start_send(mut self: Pin<&mut Self>, item: RequestEnvelope) -> Result<(), Self::Error> {
+        let response = self.respond(&item);
+
+        match &self.sender {
+            Some(tx) if tx.send(response).is_ok() => Ok(()),
+            _ => Err(Error::new(ErrorKind::ConnectionDropped)),
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.sender = None;
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl Stream for ReplayTransport {
+    type Item = Result<ResponseEnvelope, Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.receiver.poll_recv(cx).map(|item| item.map(Ok))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::{
+        StatisticsRequest, StatisticsResponse, VtsFolderInfoRequest, VtsFolderInfoResponse,
+    };
+    use crate::service::ApiService;
+    use crate::testing::MockServer;
+    use crate::Client;
+    use futures_util::{SinkExt, StreamExt};
+
+    async fn record() -> Result<Recording, Error> {
+        let server = MockServer::new();
+        server.respond::<StatisticsRequest>(&StatisticsResponse {
+            uptime: 1,
+            ..Default::default()
+        })?;
+        server.respond::<VtsFolderInfoRequest>(&VtsFolderInfoResponse {
+            models: "Live2DModels".into(),
+            ..Default::default()
+        })?;
+
+        let mut transport = RecordedTransport::new(server.connect());
+        let requests = vec![
+            RequestEnvelope::new(&StatisticsRequest {})?,
+            RequestEnvelope::new(&VtsFolderInfoRequest {})?,
+            RequestEnvelope::new(&StatisticsRequest {})?,
+        ];
+
+        for (i, req) in requests.into_iter().enumerate() {
+            transport.send(req.with_id(Some(i.to_string().into()))).await?;
+            transport.next().await.unwrap()?;
+        }
+
+        // Events aren't recorded
+        server.send_event(&crate::data::TestEvent::default())?;
+        transport.next().await.unwrap()?;
+
+        assert_eq!(transport.recording().len(), 3);
+        Ok(transport.into_recording())
+    }
+
+    async fn replay(recording: Recording) -> Result<(i64, String, i64), Error> {
+        let (service, _events) = ApiService::new(ReplayTransport::new(recording), 16);
+        let (mut client, _) = Client::builder().build_service(service);
+
+        Ok((
+            client.send(&StatisticsRequest {}).await?.uptime,
+            client.send(&VtsFolderInfoRequest {}).await?.models,
+            client.send(&StatisticsRequest {}).await?.uptime,
+        ))
+    }
+
+    #[tokio::test]
+    async fn record_and_replay() -> Result<(), Error> {
+        let recording = record().await?;
+        assert_eq!(
+            recording
+                .iter()
+                .map(|(req, resp)| (req.message_type.as_str(), resp.message_type().as_str()))
+                .collect::<Vec<_>>(),
+            [
+                ("StatisticsRequest", "StatisticsResponse"),
+                ("VTSFolderInfoRequest", "VTSFolderInfoResponse"),
+                ("StatisticsRequest", "StatisticsResponse"),
+            ]
+        );
+
+        // Round-trip through JSON, as if the recording was saved to disk
+        let json = serde_json::to_string(&recording)?;
+        let first = replay(serde_json::from_str(&json)?).await?;
+        let second = replay(serde_json::from_str(&json)?).await?;
+
+        assert_eq!(first, (1, "Live2DModels".to_owned(), 1));
+        assert_eq!(first, second);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn replay_fallbacks() -> Result<(), Error> {
+        let recording = vec![(
+            RequestEnvelope::new(&StatisticsRequest {})?,
+            ResponseEnvelope::new(&StatisticsResponse {
+                uptime: 5,
+                ..Default::default()
+            })?,
+        )];
+
+        let mut transport = ReplayTransport::new(recording);
+        assert_eq!(transport.remaining(), 1);
+
+        // Recorded responses are reused once exhausted
+        for id in ["a", "b"].iter() {
+            transport
+                .send(RequestEnvelope::new(&StatisticsRequest {})?.with_id(Some((*id).into())))
+                .await?;
+            let resp = transport.next().await.unwrap()?;
+            assert_eq!(resp.request_id.as_str(), *id);
+            assert_eq!(resp.parse::<StatisticsResponse>()?.uptime, 5);
+        }
+        assert_eq!(transport.remaining(), 0);
+
+        // Unknown request types receive an API error
+        transport
+            .send(RequestEnvelope::new(&VtsFolderInfoRequest {})?)
+            .await?;
+        assert!(transport.next().await.unwrap()?.is_api_error());
+
+        transport.close().await?;
+        assert!(transport.next().await.is_none());
+
+        Ok(())
+    }
+}
@@ -1,29 +1,51 @@
 use crate::codec::MessageCodec;
 use crate::data::{RequestEnvelope, ResponseEnvelope};
 use crate::error::BoxError;
+use crate::transport::keepalive::Keepalive;
 
 use futures_core::{Stream, TryStream};
 use futures_sink::Sink;
 use pin_project_lite::pin_project;
 use std::pin::Pin;
 use std::task::{Context, Poll};
+use std::time::Duration;
 
 crate::cfg_feature! {
-    #![feature = "tokio-tungstenite"]
+    #![any(feature = "tokio-tungstenite", feature = "async-tungstenite")]
+    #[cfg(feature = "tokio-tungstenite")]
     use tokio_tungstenite::tungstenite;
+    #[cfg(all(feature = "async-tungstenite", not(feature = "tokio-tungstenite")))]
+    use async_tungstenite::tungstenite;
     use crate::codec::TungsteniteCodec;
 
     impl<T> ApiTransport<T, TungsteniteCodec>
     where
         T: Sink<tungstenite::Message> + TryStream,
     {
-        /// Creates a new [`ApiTransport`] for sending/receiving [`tokio_tungstenite`] messages.
+        /// Creates a new [`ApiTransport`] for sending/receiving `tungstenite` messages (as used by
+        /// `tokio_tungstenite` and `async_tungstenite`).
         pub fn new_tungstenite(transport: T) -> Self {
             ApiTransport::new(transport, TungsteniteCodec)
         }
     }
 }
 
+crate::cfg_feature! {
+    #![feature = "wasm"]
+    use gloo_net::websocket::Message;
+    use crate::codec::WasmCodec;
+
+    impl<T> ApiTransport<T, WasmCodec>
+    where
+        T: Sink<Message> + TryStream,
+    {
+        /// Creates a new [`ApiTransport`] for sending/receiving [`gloo_net`] websocket messages.
+        pub fn new_wasm(transport: T) -> Self {
+            ApiTransport::new(transport, WasmCodec)
+        }
+    }
+}
+
 pin_project! {
     /// A transport that uses a [`MessageCodec`] to implement:
     ///
@@ -38,7 +60,9 @@ pin_project! {
     pub struct ApiTransport<T, C> {
         #[pin]
         transport: T,
-        codec: C
+        codec: C,
+        keepalive: Option<Keepalive>,
+        closed: bool,
     }
 }
 
@@ -49,7 +73,27 @@ where
 {
     /// Creates a new [`ApiTransport`].
     pub fn new(transport: T, codec: C) -> Self {
-        Self { transport, codec }
+        Self {
+            transport,
+            codec,
+            keepalive: None,
+            closed: false,
+        }
+    }
+
+    /// Sends a websocket ping every `interval` while the stream is being polled. If no pong is
+    /// received within `timeout` of a ping being sent, the stream yields an error with
+    /// [`ErrorKind::ConnectionDropped`](crate::error::ErrorKind::ConnectionDropped) and then ends,
+    /// so that the connection can be replaced.
+    ///
+    /// This has no effect if the codec doesn't support pings (see [`MessageCodec::ping`]).
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a Tokio runtime, or if `interval` is zero.
+    pub fn with_keepalive(mut self, interval: Duration, timeout: Duration) -> Self {
+        self.keepalive = C::ping().map(|_| Keepalive::new(interval, timeout));
+        self
     }
 }
 
@@ -105,8 +149,9 @@ where
 
 impl<T, C> Stream for ApiTransport<T, C>
 where
-    T: TryStream<Ok = C::Input>,
-    T::Error: Into<BoxError>,
+    T: TryStream<Ok = C::Input> + Sink<C::Output>,
+    <T as TryStream>::Error: Into<BoxError>,
+    BoxError: From<<T as Sink<C::Output>>::Error>,
     C: MessageCodec,
     C::Error: Into<BoxError>,
 {
@@ -115,9 +160,45 @@ where
     fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         let mut this = self.project();
 
+        if *this.closed {
+            return Poll::Ready(None);
+        }
+
+        if let Some(keepalive) = this.keepalive.as_mut() {
+            if keepalive.poll_ping_due(cx) {
+                if let Poll::Ready(result) = this.transport.as_mut().poll_ready(cx) {
+                    result?;
+                    if let Some(ping) = C::ping() {
+                        this.transport.as_mut().start_send(ping)?;
+                    }
+                    keepalive.ping_sent();
+                }
+            }
+
+            if keepalive.flush_due() {
+                if let Poll::Ready(result) = this.transport.as_mut().poll_flush(cx) {
+                    result?;
+                    keepalive.flushed();
+                }
+            }
+
+            // Checked after sending pings, so that the timer for a new ping is registered
+            if let Err(e) = keepalive.poll_timeout(cx) {
+                tracing::warn!("Closing connection after keepalive timeout");
+                *this.closed = true;
+                return Poll::Ready(Some(Err(e.into())));
+            }
+        }
+
         Poll::Ready(loop {
             match futures_util::ready!(this.transport.as_mut().try_poll_next(cx)) {
                 Some(Ok(msg)) => {
+                    if C::is_pong(&msg) {
+                        if let Some(keepalive) = this.keepalive.as_mut() {
+                            keepalive.pong_received();
+                        }
+                    }
+
                     if let Some(s) = C::decode(msg).map_err(Into::into)? {
                         tracing::debug!(message = s.as_str(), "Received message");
                         break Some(serde_json::from_str(&s).map_err(Into::into));
@@ -129,3 +210,102 @@ where
         })
     }
 }
+
+#[cfg(all(test, feature = "tokio-tungstenite"))]
+mod tests {
+    use super::*;
+    use crate::error::{Error, ErrorKind};
+
+    use futures_util::StreamExt;
+    use std::convert::Infallible;
+    use std::sync::{Arc, Mutex};
+    use tokio::sync::mpsc;
+    use tokio_tungstenite::tungstenite::Message;
+
+    // A websocket transport that records sent messages, and optionally responds to pings
+    struct PingTransport {
+        sent: Arc<Mutex<Vec<Message>>>,
+        reply_to_pings: bool,
+        sender: mpsc::UnboundedSender<Message>,
+        receiver: mpsc::UnboundedReceiver<Message>,
+    }
+
+    impl PingTransport {
+        fn new(reply_to_pings: bool) -> (Self, Arc<Mutex<Vec<Message>>>) {
+            let (sender, receiver) = mpsc::unbounded_channel();
+            let sent = Arc::default();
+            let transport = Self {
+                sent: Arc::clone(&sent),
+                reply_to_pings,
+                sender,
+                receiver,
+            };
+            (transport, sent)
+        }
+    }
+
+    impl Sink<Message> for PingTransport {
+        type Error = Infallible;
+
+        fn poll_ready(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Result<(), Infallible>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn start_send(self: Pin<&mut Self>, item: Message) -> Result<(), Infallible> {
+            if let (Message::Ping(data), true) = (&item, self.reply_to_pings) {
+                let _ = self.sender.send(Message::Pong(data.clone()));
+            }
+            self.sent.lock().unwrap().push(item);
+            Ok(())
+        }
+
+        fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Result<(), Infallible>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn poll_close(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Result<(), Infallible>> {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    impl Stream for PingTransport {
+        type Item = Result<Message, Infallible>;
+
+        fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+            self.receiver.poll_recv(cx).map(|msg| msg.map(Ok))
+        }
+    }
+
+    fn ping_count(sent: &Mutex<Vec<Message>>) -> usize {
+        let sent = sent.lock().unwrap();
+        sent.iter().filter(|msg| msg.is_ping()).count()
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn keepalive_timeout() {
+        let (transport, sent) = PingTransport::new(false);
+        let mut transport = ApiTransport::new_tungstenite(transport)
+            .with_keepalive(Duration::from_secs(5), Duration::from_secs(2));
+
+        let start = tokio::time::Instant::now();
+        let err = transport.next().await.unwrap().unwrap_err();
+        let err = Error::from_boxed(err);
+        assert!(err.has_kind(ErrorKind::ConnectionDropped), "{:?}", err);
+        assert_eq!(start.elapsed(), Duration::from_secs(7));
+        assert_eq!(ping_count(&sent), 1);
+
+        // The stream ends after the timeout
+        assert!(transport.next().await.is_none());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn keepalive_pong() {
+        let (transport, sent) = PingTransport::new(true);
+        let mut transport = ApiTransport::new_tungstenite(transport)
+            .with_keepalive(Duration::from_secs(5), Duration::from_secs(2));
+
+        let result = tokio::time::timeout(Duration::from_secs(60), transport.next()).await;
+        assert!(result.is_err(), "stream yielded {:?}", result);
+        assert_eq!(ping_count(&sent), 12);
+    }
+}
@@ -1,25 +1,33 @@
-use crate::data::{RequestEnvelope, ResponseEnvelope};
+use crate::data::{RequestEnvelope, RequestId, ResponseEnvelope};
 
+use futures_channel::mpsc;
 use futures_core::{Stream, TryStream};
 use futures_sink::Sink;
 use futures_util::stream::{IntoStream, SplitSink};
-use futures_util::{StreamExt, TryStreamExt};
+use futures_util::{SinkExt, StreamExt, TryStreamExt};
 use pin_project_lite::pin_project;
+use std::collections::HashMap;
 use std::pin::Pin;
-use std::task::{Context, Poll};
-use tokio::sync::mpsc;
+use std::task::{ready, Context, Poll};
 
 pin_project! {
     /// API transport that buffers elements of the stream.
     ///
     /// This is used to ensure that the underlying transport continues to be polled even if we're
     /// not awaiting paired API responses (e.g., receiving events).
+    ///
+    /// This also keeps track of the request IDs that are awaiting a response. Responses with any
+    /// other request ID (e.g., a late response to a request from a previous connection, or an
+    /// unsolicited message that isn't an event) are logged and dropped, since the multiplexer
+    /// would otherwise fail with [`ErrorKind::Desynchronized`](crate::error::ErrorKind) and close
+    /// the connection.
     #[derive(Debug)]
     pub(crate) struct BufferedApiTransport<T> where T: TryStream {
         #[pin]
         sink: SplitSink<IntoStream<T>, RequestEnvelope>,
         #[pin]
         stream: mpsc::Receiver<Result<ResponseEnvelope, T::Error>>,
+        pending: HashMap<RequestId, usize>,
     }
 }
 
@@ -32,9 +40,9 @@ where
     pub fn new(transport: T, buffer_size: usize) -> Self {
         let (resp_sink, mut resp_stream) = transport.into_stream().split();
 
-        let (buffered_sender, buffered_receiver) = mpsc::channel(buffer_size);
+        let (mut buffered_sender, buffered_receiver) = mpsc::channel(buffer_size);
 
-        tokio::spawn(async move {
+        crate::runtime::spawn(async move {
             while let Some(item) = resp_stream.next().await {
                 if buffered_sender.send(item).await.is_err() {
                     tracing::warn!("Dropping message due to buffer being full");
@@ -47,6 +55,7 @@ where
         Self {
             sink: resp_sink,
             stream: buffered_receiver,
+            pending: HashMap::new(),
         }
     }
 }
@@ -62,6 +71,15 @@ where
     }
 
     fn start_send(mut self: Pin<&mut Self>, item: RequestEnvelope) -> Result<(), Self::Error> {
+        if let Some(id) = &item.request_id {
+            *self
+                .as_mut()
+                .project()
+                .pending
+                .entry(id.clone())
+                .or_default() += 1;
+        }
+
         self.as_mut().project().sink.start_send(item)
     }
 
@@ -81,6 +99,117 @@ where
     type Item = Result<ResponseEnvelope, T::Error>;
 
     fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
-        self.project().stream.poll_recv(cx)
+        let mut this = self.project();
+
+        loop {
+            let resp = match ready!(this.stream.as_mut().poll_next(cx)) {
+                Some(Ok(resp)) => resp,
+                other => return Poll::Ready(other),
+            };
+
+            match this.pending.get_mut(&resp.request_id) {
+                Some(count) => {
+                    *count -= 1;
+                    if *count == 0 {
+                        this.pending.remove(&resp.request_id);
+                    }
+                    return Poll::Ready(Some(Ok(resp)));
+                }
+                None => tracing::warn!(
+                    request_id = %resp.request_id,
+                    message_type = resp.message_type().as_str(),
+                    "Dropping response with unknown request ID"
+                ),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::data::{
+        RequestEnvelope, RequestId, ResponseEnvelope, StatisticsRequest, StatisticsResponse,
+    };
+    use crate::error::Error;
+    use crate::service::ApiService;
+
+    use futures_core::Stream;
+    use futures_sink::Sink;
+    use std::convert::Infallible;
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+    use tokio::sync::mpsc;
+    use tower::{Service, ServiceExt};
+
+    // A transport that forwards sent requests to a channel, and receives responses from another
+    struct ChannelTransport {
+        requests: mpsc::UnboundedSender<RequestEnvelope>,
+        responses: mpsc::UnboundedReceiver<ResponseEnvelope>,
+    }
+
+    impl Sink<RequestEnvelope> for ChannelTransport {
+        type Error = Infallible;
+
+        fn poll_ready(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Result<(), Infallible>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn start_send(self: Pin<&mut Self>, item: RequestEnvelope) -> Result<(), Infallible> {
+            let _ = self.requests.send(item);
+            Ok(())
+        }
+
+        fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Result<(), Infallible>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn poll_close(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Result<(), Infallible>> {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    impl Stream for ChannelTransport {
+        type Item = Result<ResponseEnvelope, Infallible>;
+
+        fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+            self.responses.poll_recv(cx).map(|resp| resp.map(Ok))
+        }
+    }
+
+    #[tokio::test]
+    async fn unknown_request_id() -> Result<(), Error> {
+        let (req_tx, mut req_rx) = mpsc::unbounded_channel();
+        let (resp_tx, resp_rx) = mpsc::unbounded_channel();
+        let transport = ChannelTransport {
+            requests: req_tx,
+            responses: resp_rx,
+        };
+        let (mut service, _events) = ApiService::new(transport, 8);
+
+        let resp = StatisticsResponse {
+            uptime: 1234,
+            ..Default::default()
+        };
+
+        for _ in 0..2 {
+            let req = RequestEnvelope::new(&StatisticsRequest {})?;
+            let call = service.ready().await?.call(req);
+            let sent = req_rx.recv().await.unwrap();
+            let request_id = sent.request_id.unwrap();
+
+            // A response to a request that was never sent is dropped, instead of closing the
+            // connection
+            let unknown = ResponseEnvelope::new(&resp)?.with_id(RequestId::from("unknown"));
+            resp_tx.send(unknown).unwrap();
+            resp_tx
+                .send(ResponseEnvelope::new(&resp)?.with_id(request_id.clone()))
+                .unwrap();
+
+            let received = call.await?;
+            assert_eq!(received.request_id, request_id);
+            assert_eq!(received.parse::<StatisticsResponse>()?, resp);
+        }
+
+        Ok(())
     }
 }
@@ -0,0 +1,146 @@
+use crate::data::RequestEnvelope;
+
+use futures_core::{Stream, TryStream};
+use futures_sink::Sink;
+use futures_util::FutureExt;
+use pin_project_lite::pin_project;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tokio::sync::watch;
+use tokio_tower::MakeTransport;
+use tower::Service;
+
+type CloseSignal = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+/// Creates a future that resolves once the close generation is incremented past its current value.
+fn close_signal(mut receiver: watch::Receiver<u64>) -> CloseSignal {
+    let generation = *receiver.borrow_and_update();
+    Box::pin(async move {
+        let _ = receiver.wait_for(|current| *current > generation).await;
+    })
+}
+
+/// A [`Service`] wrapping a [`MakeTransport`], where each created
+/// transport can be closed by incrementing the value of a [`watch`] channel.
+#[derive(Debug, Clone)]
+pub(crate) struct CloseableConnector<M> {
+    maker: M,
+    close_rx: watch::Receiver<u64>,
+}
+
+impl<M> CloseableConnector<M> {
+    pub fn new(maker: M, close_rx: watch::Receiver<u64>) -> Self {
+        Self { maker, close_rx }
+    }
+}
+
+impl<M, R> Service<R> for CloseableConnector<M>
+where
+    M: MakeTransport<R, RequestEnvelope>,
+    M::Future: Send + 'static,
+{
+    type Response = CloseableTransport<M::Transport>;
+    type Error = M::MakeError;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.maker.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: R) -> Self::Future {
+        // Subscribe before connecting, so that a close requested mid-connection still applies
+        let signal = close_signal(self.close_rx.clone());
+        Box::pin(self.maker.make_transport(request).map(move |result| {
+            result.map(|transport| CloseableTransport {
+                transport,
+                signal,
+                closing: false,
+                closed: false,
+            })
+        }))
+    }
+}
+
+pin_project! {
+    /// A transport that starts closing its sink when signaled, and continues to yield items from
+    /// its stream until the remote end finishes the close handshake.
+    pub(crate) struct CloseableTransport<T> {
+        #[pin]
+        transport: T,
+        signal: CloseSignal,
+        closing: bool,
+        closed: bool,
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for CloseableTransport<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CloseableTransport")
+            .field("transport", &self.transport)
+            .field("closing", &self.closing)
+            .field("closed", &self.closed)
+            .finish()
+    }
+}
+
+impl<T, I> Sink<I> for CloseableTransport<T>
+where
+    T: Sink<I>,
+{
+    type Error = T::Error;
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.project().transport.poll_ready(cx)
+    }
+
+    fn start_send(self: Pin<&mut Self>, item: I) -> Result<(), Self::Error> {
+        self.project().transport.start_send(item)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.project().transport.poll_flush(cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.project().transport.poll_close(cx)
+    }
+}
+
+impl<T> CloseableTransport<T>
+where
+    T: Sink<RequestEnvelope>,
+{
+    fn poll_close_signal(self: Pin<&mut Self>, cx: &mut Context<'_>) {
+        let mut this = self.project();
+
+        if !*this.closing && this.signal.as_mut().poll(cx).is_ready() {
+            *this.closing = true;
+        }
+
+        if *this.closing && !*this.closed {
+            match this.transport.as_mut().poll_close(cx) {
+                Poll::Ready(result) => {
+                    if result.is_err() {
+                        tracing::warn!("Failed to close transport");
+                    }
+                    *this.closed = true;
+                }
+                Poll::Pending => {}
+            }
+        }
+    }
+}
+
+impl<T> Stream for CloseableTransport<T>
+where
+    T: TryStream + Sink<RequestEnvelope>,
+{
+    type Item = Result<T::Ok, <T as TryStream>::Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.as_mut().poll_close_signal(cx);
+        self.project().transport.try_poll_next(cx)
+    }
+}
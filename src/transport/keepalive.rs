@@ -0,0 +1,110 @@
+use crate::error::{Error, ErrorKind};
+use crate::runtime::{self, Sleep};
+
+use std::fmt;
+use std::task::Context;
+use std::time::Duration;
+
+/// Tracks when websocket pings should be sent, and whether the corresponding pongs arrived in time.
+pub(crate) struct Keepalive {
+    interval: Duration,
+    timeout: Duration,
+    /// When the next ping is due.
+    next_ping: Sleep,
+    /// Set while waiting for a pong.
+    deadline: Option<Sleep>,
+    /// Whether a ping is due but hasn't been sent yet.
+    ping_due: bool,
+    /// Whether a ping was sent but hasn't been flushed yet.
+    flush_due: bool,
+}
+
+impl Keepalive {
+    /// Creates a new [`Keepalive`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `interval` is zero.
+    pub(crate) fn new(interval: Duration, timeout: Duration) -> Self {
+        assert!(
+            interval > Duration::ZERO,
+            "keepalive interval must be non-zero"
+        );
+
+        Self {
+            interval,
+            timeout,
+            next_ping: runtime::sleep(interval),
+            deadline: None,
+            ping_due: false,
+            flush_due: false,
+        }
+    }
+
+    /// Returns an error if the pong timeout has elapsed.
+    pub(crate) fn poll_timeout(&mut self, cx: &mut Context<'_>) -> Result<(), Error> {
+        let timed_out = match &mut self.deadline {
+            Some(deadline) => deadline.as_mut().poll(cx).is_ready(),
+            None => false,
+        };
+
+        if timed_out {
+            self.deadline = None;
+            return Err(Error::new(ErrorKind::ConnectionDropped)
+                .with_source("no pong received before keepalive timeout"));
+        }
+
+        Ok(())
+    }
+
+    /// Returns `true` if a ping should be sent now.
+    pub(crate) fn poll_ping_due(&mut self, cx: &mut Context<'_>) -> bool {
+        // If pings are late, the next one is scheduled a full interval after this one
+        while self.next_ping.as_mut().poll(cx).is_ready() {
+            self.ping_due = true;
+            self.next_ping = runtime::sleep(self.interval);
+        }
+        self.ping_due
+    }
+
+    /// Marks a ping as sent (but not yet flushed), starting the pong timeout if it isn't already
+    /// running.
+    pub(crate) fn ping_sent(&mut self) {
+        self.ping_due = false;
+        self.flush_due = true;
+        if self.deadline.is_none() {
+            self.deadline = Some(runtime::sleep(self.timeout));
+        }
+    }
+
+    /// Whether a sent ping still needs to be flushed.
+    pub(crate) fn flush_due(&self) -> bool {
+        self.flush_due
+    }
+
+    pub(crate) fn flushed(&mut self) {
+        self.flush_due = false;
+    }
+
+    /// Stops the pong timeout.
+    pub(crate) fn pong_received(&mut self) {
+        self.deadline = None;
+    }
+}
+
+impl fmt::Debug for Keepalive {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Keepalive")
+            .field("interval", &self.interval)
+            .field("timeout", &self.timeout)
+            .field("awaiting_pong", &self.deadline.is_some())
+            .finish()
+    }
+}
+
+impl Clone for Keepalive {
+    /// Creates a [`Keepalive`] with the same settings, but a fresh state.
+    fn clone(&self) -> Self {
+        Self::new(self.interval, self.timeout)
+    }
+}
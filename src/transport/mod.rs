@@ -1,6 +1,10 @@
 pub(crate) mod api;
 pub(crate) mod buffered;
+pub(crate) mod closeable;
 pub(crate) mod event;
+pub(crate) mod keepalive;
+#[cfg(feature = "wasm")]
+pub(crate) mod wasm;
 
 pub use crate::transport::api::ApiTransport;
 pub use crate::transport::event::EventStream;
@@ -17,3 +21,24 @@ crate::cfg_feature! {
     /// Type alias for an [`ApiTransport`] that handles [`tokio_tungstenite`] messages.
     pub type TungsteniteApiTransport = ApiTransport<TungsteniteTransport, TungsteniteCodec>;
 }
+
+crate::cfg_feature! {
+    #![feature = "async-tungstenite"]
+
+    /// Type alias for an [`async_tungstenite`] sink/stream over a connection of type `S`.
+    pub type AsyncTungsteniteTransport<S> = async_tungstenite::WebSocketStream<S>;
+
+    /// Type alias for an [`ApiTransport`] that handles [`async_tungstenite`] messages.
+    pub type AsyncTungsteniteApiTransport<S> =
+        ApiTransport<AsyncTungsteniteTransport<S>, crate::codec::TungsteniteCodec>;
+}
+
+crate::cfg_feature! {
+    #![feature = "wasm"]
+    use crate::codec::WasmCodec;
+
+    pub use crate::transport::wasm::WasmWebSocket;
+
+    /// Type alias for an [`ApiTransport`] that handles [`gloo_net`] websocket messages.
+    pub type WasmApiTransport = ApiTransport<WasmWebSocket, WasmCodec>;
+}
@@ -0,0 +1,93 @@
+use crate::error::{Error, ErrorKind};
+
+use futures_core::Stream;
+use futures_sink::Sink;
+use gloo_net::websocket::futures::WebSocket;
+use gloo_net::websocket::Message;
+use send_wrapper::SendWrapper;
+use std::fmt;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+#[cfg(all(target_arch = "wasm32", target_feature = "atomics"))]
+compile_error!(
+    "the `wasm` feature doesn't support multi-threaded wasm (`target_feature = \"atomics\"`)"
+);
+
+/// A browser [`WebSocket`] sink/stream, backed by [`gloo_net`].
+///
+/// Browser websockets can't be sent across threads, so this is wrapped in a [`SendWrapper`] to
+/// satisfy the `Send` bounds on [`Client`](crate::Client). On `wasm32` the [`Client`]'s
+/// background tasks are spawned on the same thread with `wasm_bindgen_futures::spawn_local`, so
+/// the websocket never leaves the thread that created it. Multi-threaded wasm builds (with the
+/// `atomics` target feature) aren't supported.
+///
+/// Errors are converted to [`Error`]s, since the underlying errors can contain JS values that
+/// aren't `Send`.
+pub struct WasmWebSocket {
+    inner: SendWrapper<Pin<Box<WebSocket>>>,
+}
+
+impl fmt::Debug for WasmWebSocket {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WasmWebSocket").finish_non_exhaustive()
+    }
+}
+
+impl WasmWebSocket {
+    /// Opens a new websocket connection to the given URL.
+    pub fn open(url: &str) -> Result<Self, Error> {
+        match WebSocket::open(url) {
+            Ok(ws) => Ok(Self::from(ws)),
+            Err(e) => Err(Error::new(ErrorKind::ConnectionRefused).with_source(e.to_string())),
+        }
+    }
+
+    fn inner(self: Pin<&mut Self>) -> Pin<&mut WebSocket> {
+        self.get_mut().inner.as_mut()
+    }
+}
+
+impl From<WebSocket> for WasmWebSocket {
+    fn from(ws: WebSocket) -> Self {
+        Self {
+            inner: SendWrapper::new(Box::pin(ws)),
+        }
+    }
+}
+
+fn write_error<E: fmt::Display>(e: E) -> Error {
+    Error::new(ErrorKind::Write).with_source(e.to_string())
+}
+
+impl Sink<Message> for WasmWebSocket {
+    type Error = Error;
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner().poll_ready(cx).map_err(write_error)
+    }
+
+    fn start_send(self: Pin<&mut Self>, item: Message) -> Result<(), Self::Error> {
+        self.inner().start_send(item).map_err(write_error)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner().poll_flush(cx).map_err(write_error)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner().poll_close(cx).map_err(write_error)
+    }
+}
+
+impl Stream for WasmWebSocket {
+    type Item = Result<Message, Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.inner().poll_next(cx).map(|item| {
+            item.map(|result| {
+                result.map_err(|e| Error::new(ErrorKind::Read).with_source(e.to_string()))
+            })
+        })
+    }
+}